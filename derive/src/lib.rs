@@ -14,7 +14,12 @@
 //! - **Unit Enums**: Generates string enum schemas with all variant names
 //! - **Complex Enums**: Generates `oneOf` schemas for mixed enum types
 //! - **Struct Variant Enums**: Generates object schemas with named properties
-//! - **Tuple Variant Enums**: Generates array schemas with typed elements
+//! - **Tuple Variant Enums**: Generates fixed-length array schemas that type each
+//!   element with `prefixItems`
+//! - **Strict Structs**: `#[schema(deny_unknown_fields)]` rejects undeclared properties
+//! - **Doc Comments**: `///` comments on a type or struct field become its `description`
+//! - **Internally Tagged Enums**: Enums marked `#[serde(tag = "...")]` generate `oneOf`
+//!   object schemas whose tag property is pinned to the variant name with `const`
 //!
 //! ### Examples
 //!
@@ -76,11 +81,36 @@ use syn::{DeriveInput, parse_macro_input};
 use derive_util::{EnumVisitor, StructVisitor};
 
 /// Derive the JsonSchema trait for structs and enums.
-#[proc_macro_derive(JsonSchema, attributes())]
+///
+/// Structs marked `#[schema(deny_unknown_fields)]` generate object schemas with
+/// `"additionalProperties": false`, mirroring serde's attribute of the same name.
+#[proc_macro_derive(JsonSchema, attributes(schema))]
 pub fn derive_json_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
+    let description = doc_comment(&input.attrs).map(|doc| {
+        quote! {
+            result["description"] = #doc.into();
+        }
+    });
+    let deny_unknown_fields = schema_flag(&input.attrs, "deny_unknown_fields").then(|| {
+        quote! {
+            result["additionalProperties"] = false.into();
+        }
+    });
     let ty_name = input.ident;
-    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    // Every type parameter must itself have a schema for the fields that use it.
+    let mut generics = input.generics.clone();
+    let type_params: Vec<syn::Ident> = generics
+        .type_params()
+        .map(|param| param.ident.clone())
+        .collect();
+    let where_clause = generics.make_where_clause();
+    for param in type_params {
+        where_clause
+            .predicates
+            .push(syn::parse_quote!(#param: stigmergy::JsonSchema));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let schema_gen = match input.data {
         syn::Data::Struct(ref ds) => {
@@ -94,12 +124,22 @@ pub fn derive_json_schema(input: proc_macro::TokenStream) -> proc_macro::TokenSt
                 #required
                 result["type"] = "object".into();
                 result["properties"] = properties;
+                #deny_unknown_fields
+                #description
                 result
             }
         }
         syn::Data::Enum(ref de) => {
-            let mut jsv = JsonSchemaEnumVisitor::new();
-            jsv.visit_enum(&ty_name, de)
+            let mut jsv = JsonSchemaEnumVisitor::new(serde_tag(&input.attrs));
+            let schema = jsv.visit_enum(&ty_name, de);
+            match description {
+                Some(description) => quote! {
+                    let mut result = #schema;
+                    #description
+                    result
+                },
+                None => schema,
+            }
         }
         syn::Data::Union(_) => {
             panic!("unions are not supported");
@@ -116,6 +156,61 @@ pub fn derive_json_schema(input: proc_macro::TokenStream) -> proc_macro::TokenSt
     generated.into()
 }
 
+/// Joins the `#[doc = "..."]` attributes that `///` comments expand to.
+///
+/// Returns `None` when there is no documentation.
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::NameValue(syn::MetaNameValue {
+                lit: syn::Lit::Str(doc),
+                ..
+            })) => Some(doc.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    if doc.is_empty() { None } else { Some(doc) }
+}
+
+/// Returns true if a `#[schema(...)]` attribute lists `flag`.
+fn schema_flag(attrs: &[syn::Attribute], flag: &str) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("schema"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(syn::Meta::List(list)) => Some(list.nested),
+            _ => None,
+        })
+        .flatten()
+        .any(|nested| matches!(nested, syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident(flag)))
+}
+
+/// Returns the `tag` of a `#[serde(tag = "...")]` attribute, if present.
+fn serde_tag(attrs: &[syn::Attribute]) -> Option<String> {
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("serde")) {
+        if let Ok(syn::Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested.iter() {
+                if let syn::NestedMeta::Meta(syn::Meta::NameValue(nv)) = nested
+                    && nv.path.is_ident("tag")
+                    && let syn::Lit::Str(tag) = &nv.lit
+                {
+                    return Some(tag.value());
+                }
+            }
+        }
+    }
+    None
+}
+
 ///////////////////////////////////////// JsonSchemaStructVisitor ////////////////////////////////////////
 
 struct JsonSchemaStructVisitor;
@@ -140,9 +235,15 @@ impl StructVisitor for JsonSchemaStructVisitor {
                     field_ident.clone()
                 };
                 let field_type = field.ty.clone();
+                let description = doc_comment(&field.attrs).map(|doc| {
+                    quote! {
+                        properties[#field_ident]["description"] = #doc.into();
+                    }
+                });
                 result = quote! {
                     #result
                     properties[#field_ident] = <#field_type as stigmergy::JsonSchema>::json_schema();
+                    #description
                 };
                 required = quote! {
                     #required
@@ -159,18 +260,42 @@ impl StructVisitor for JsonSchemaStructVisitor {
 ///////////////////////////////////////// JsonSchemaEnumVisitor ////////////////////////////////////////
 
 struct JsonSchemaEnumVisitor {
+    /// The serde tag property for internally tagged enums.
+    tag: Option<String>,
     unit_variants: Vec<String>,
     complex_variants: Vec<TokenStream>,
 }
 
 impl JsonSchemaEnumVisitor {
-    fn new() -> Self {
+    fn new(tag: Option<String>) -> Self {
         Self {
+            tag,
             unit_variants: Vec::new(),
             complex_variants: Vec::new(),
         }
     }
 
+    /// Generates the object schema for a variant of an internally tagged enum.
+    ///
+    /// The tag property is required and must equal the variant name; `fields` adds the
+    /// variant's own properties and required names.
+    fn tagged_variant_schema(tag: &str, variant_name: &str, fields: TokenStream) -> TokenStream {
+        quote! {
+            {
+                let mut properties = serde_json::json!({
+                    #tag: {"type": "string", "const": #variant_name}
+                });
+                let mut required: Vec<serde_json::Value> = vec![#tag.into()];
+                #fields
+                serde_json::json!({
+                    "type": "object",
+                    "properties": properties,
+                    "required": required
+                })
+            }
+        }
+    }
+
     fn generate_final_schema(
         unit_variants: &[String],
         complex_variants: &[TokenStream],
@@ -241,6 +366,11 @@ impl EnumVisitor for JsonSchemaEnumVisitor {
         variant: &syn::Variant,
     ) -> Self::VariantOutput {
         let variant_name = variant.ident.to_string();
+        if let Some(tag) = &self.tag {
+            let variant_schema = Self::tagged_variant_schema(tag, &variant_name, quote! {});
+            self.complex_variants.push(variant_schema.clone());
+            return variant_schema;
+        }
         self.unit_variants.push(variant_name);
 
         // Return empty token stream since we accumulate in self.unit_variants
@@ -275,6 +405,19 @@ impl EnumVisitor for JsonSchemaEnumVisitor {
             }
         }
 
+        if let Some(tag) = &self.tag {
+            let variant_schema = Self::tagged_variant_schema(
+                tag,
+                &variant_name,
+                quote! {
+                    #properties
+                    #required
+                },
+            );
+            self.complex_variants.push(variant_schema.clone());
+            return variant_schema;
+        }
+
         let variant_schema = quote! {
             {
                 let mut result = serde_json::json!({});
@@ -308,6 +451,32 @@ impl EnumVisitor for JsonSchemaEnumVisitor {
     ) -> Self::VariantOutput {
         let variant_name = variant.ident.to_string();
 
+        if let Some(tag) = &self.tag {
+            // serde only allows newtype variants wrapping a struct or map here, so the
+            // tag is added to the inner object schema.
+            if fields.unnamed.len() != 1 {
+                panic!("tuple variants cannot be internally tagged");
+            }
+            let field_type = &fields.unnamed[0].ty;
+            let variant_schema = quote! {
+                {
+                    let mut result = <#field_type as stigmergy::JsonSchema>::json_schema();
+                    result["properties"][#tag] = serde_json::json!({
+                        "type": "string",
+                        "const": #variant_name
+                    });
+                    if let Some(serde_json::Value::Array(required)) = result.get_mut("required") {
+                        required.insert(0, #tag.into());
+                    } else {
+                        result["required"] = serde_json::json!([#tag]);
+                    }
+                    result
+                }
+            };
+            self.complex_variants.push(variant_schema.clone());
+            return variant_schema;
+        }
+
         // Generate items for tuple variant
         let mut items = quote! {};
 
@@ -319,22 +488,35 @@ impl EnumVisitor for JsonSchemaEnumVisitor {
             };
         }
 
+        // A single field is matched by `items`; longer tuples type each position with
+        // `prefixItems` and pin the length.
+        let array_schema = if fields.unnamed.len() == 1 {
+            quote! {
+                serde_json::json!({
+                    "type": "array",
+                    "items": items.into_iter().next().unwrap()
+                })
+            }
+        } else {
+            let arity = fields.unnamed.len();
+            quote! {
+                serde_json::json!({
+                    "type": "array",
+                    "prefixItems": items,
+                    "minItems": #arity,
+                    "maxItems": #arity
+                })
+            }
+        };
+
         let variant_schema = quote! {
             {
                 let mut items: Vec<serde_json::Value> = vec![];
                 #items
-                let items_schema = if items.len() == 1 {
-                    items.into_iter().next().unwrap()
-                } else {
-                    serde_json::Value::Array(items)
-                };
                 serde_json::json!({
                     "type": "object",
                     "properties": {
-                        #variant_name: {
-                            "type": "array",
-                            "items": items_schema
-                        }
+                        #variant_name: #array_schema
                     },
                     "required": [#variant_name]
                 })
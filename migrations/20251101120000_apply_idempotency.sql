@@ -0,0 +1,15 @@
+-- This migration adds the apply_idempotency table used to deduplicate retried
+-- batch apply requests.
+
+-- The `apply_idempotency` table records the response computed for each
+-- `Idempotency-Key` supplied to the apply endpoint.
+CREATE TABLE apply_idempotency (
+    -- The client-supplied idempotency key.
+    idempotency_key VARCHAR(255) PRIMARY KEY,
+    -- The request that was applied under this key, stored as JSONB.
+    request JSONB NOT NULL,
+    -- The response that was returned for the request, stored as JSONB.
+    response JSONB NOT NULL,
+    -- The timestamp when the key was first used.
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,5 @@
+-- This migration adds optional human-readable names to invariants.
+
+-- The `name` column lets operators refer to an invariant by a memorable alias
+-- instead of its opaque identifier. Names are unique when present.
+ALTER TABLE invariants ADD COLUMN name VARCHAR(255) UNIQUE;
@@ -0,0 +1,10 @@
+-- This migration records whether each edge is directed.
+
+-- Existing edges were all created as directed edges.
+ALTER TABLE edges ADD COLUMN directed BOOLEAN NOT NULL DEFAULT TRUE;
+
+-- An undirected edge between two entities may exist only once per label,
+-- regardless of which endpoint was given as the source.
+CREATE UNIQUE INDEX idx_edges_undirected_unique
+    ON edges (LEAST(src_entity, dst_entity), GREATEST(src_entity, dst_entity), label_entity)
+    WHERE NOT directed;
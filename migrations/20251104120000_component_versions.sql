@@ -0,0 +1,5 @@
+-- This migration adds a version counter to component instances.
+
+-- The version starts at 1 and increases by one on every write, so clients can
+-- detect that a component changed between their read and their write.
+ALTER TABLE component_instances ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
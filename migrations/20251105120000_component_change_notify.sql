@@ -0,0 +1,36 @@
+-- This migration publishes component instance changes on a notification channel.
+
+-- Every committed insert, update or delete of a component instance sends a JSON
+-- payload on the `component_changes` channel. The entity is encoded the same way
+-- the API serializes it. Notification payloads are limited to 8000 bytes, so the
+-- new data is included only when it is small; listeners fetch it otherwise.
+CREATE FUNCTION notify_component_change() RETURNS TRIGGER AS $$
+DECLARE
+    row_data component_instances;
+    payload JSONB;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        row_data := OLD;
+    ELSE
+        row_data := NEW;
+    END IF;
+    payload := jsonb_build_object(
+        'entity', rtrim(translate(encode(row_data.entity_id, 'base64'), '+/', '-_'), '='),
+        'component', row_data.component_name,
+        'kind', CASE TG_OP
+            WHEN 'INSERT' THEN 'created'
+            WHEN 'UPDATE' THEN 'updated'
+            ELSE 'deleted'
+        END
+    );
+    IF TG_OP <> 'DELETE' AND octet_length(row_data.data::TEXT) < 7000 THEN
+        payload := payload || jsonb_build_object('data', row_data.data);
+    END IF;
+    PERFORM pg_notify('component_changes', payload::TEXT);
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER component_instances_notify
+    AFTER INSERT OR UPDATE OR DELETE ON component_instances
+    FOR EACH ROW EXECUTE FUNCTION notify_component_change();
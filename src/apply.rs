@@ -1,17 +1,144 @@
 //! Batch apply operations endpoint.
 //!
 //! This module provides a transactional batch operation endpoint that allows
-//! multiple create/update/delete operations to be applied atomically.
+//! multiple create/update/delete operations on entities, components, component
+//! definitions, invariants, and edges to be applied atomically.  Batches may also
+//! read components, observing the writes of earlier operations in the same batch,
+//! which supports read-modify-write flows.
 
 use axum::Router;
 use axum::extract::State;
-use axum::http::StatusCode;
+use axum::extract::rejection::JsonRejection;
+use axum::http::{HeaderMap, StatusCode};
 use axum::response::Json;
 use axum::routing::post;
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+
 use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+
+use crate::{
+    BidParser, Component, ComponentDefinition, ComponentNamePolicy, DataStoreError, Edge, Entity,
+    InvariantID, InvariantViolation, ValidationError,
+};
+
+/// A reference to an entity from within an apply batch.
+///
+/// References serialize as strings: either an entity identifier, or `@name` to refer to an
+/// entity bound earlier in the same batch by `Operation::CreateEntity { local_ref, .. }`.
+/// Local references are resolved when the referencing operation executes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityRef {
+    /// A concrete entity.
+    Entity(Entity),
+    /// A batch-local name bound by an earlier `CreateEntity` operation.
+    Local(String),
+}
+
+impl EntityRef {
+    /// Resolves this reference against the entities bound so far in the batch.
+    fn resolve(&self, bindings: &HashMap<String, Entity>) -> Result<Entity, String> {
+        match self {
+            EntityRef::Entity(entity) => Ok(*entity),
+            EntityRef::Local(name) => bindings
+                .get(name)
+                .copied()
+                .ok_or_else(|| format!("unbound local reference: @{}", name)),
+        }
+    }
+}
+
+impl std::fmt::Display for EntityRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityRef::Entity(entity) => write!(f, "{}", entity),
+            EntityRef::Local(name) => write!(f, "@{}", name),
+        }
+    }
+}
+
+impl From<Entity> for EntityRef {
+    fn from(entity: Entity) -> Self {
+        EntityRef::Entity(entity)
+    }
+}
+
+impl Serialize for EntityRef {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            EntityRef::Entity(entity) => entity.serialize(serializer),
+            EntityRef::Local(name) => serializer.serialize_str(&format!("@{}", name)),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for EntityRef {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        if let Some(name) = s.strip_prefix('@') {
+            if name.is_empty() {
+                return Err(serde::de::Error::custom(
+                    "local entity reference must have a name after '@'",
+                ));
+            }
+            Ok(EntityRef::Local(name.to_string()))
+        } else {
+            Entity::from_str(&s)
+                .map(EntityRef::Entity)
+                .map_err(|e| serde::de::Error::custom(format!("invalid entity {:?}: {}", s, e)))
+        }
+    }
+}
+
+/// An edge named from within an apply batch, whose endpoints may be local references.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EdgeRef {
+    /// The source entity of the edge.
+    pub src: EntityRef,
+    /// The destination entity of the edge.
+    pub dst: EntityRef,
+    /// The label entity of the edge.
+    pub label: EntityRef,
+    /// Whether the edge points from src to dst (true) or joins them symmetrically (false).
+    #[serde(default = "crate::edge::default_directed")]
+    pub directed: bool,
+}
+
+impl EdgeRef {
+    /// Resolves each endpoint against the entities bound so far in the batch.
+    fn resolve(&self, bindings: &HashMap<String, Entity>) -> Result<Edge, String> {
+        Ok(Edge {
+            src: self.src.resolve(bindings)?,
+            dst: self.dst.resolve(bindings)?,
+            label: self.label.resolve(bindings)?,
+            directed: self.directed,
+        })
+    }
+
+    /// Returns the endpoints in `src`, `dst`, `label` order.
+    fn endpoints(&self) -> [&EntityRef; 3] {
+        [&self.src, &self.dst, &self.label]
+    }
+}
 
-use crate::{Component, ComponentDefinition, Entity, InvariantID};
+impl From<Edge> for EdgeRef {
+    fn from(edge: Edge) -> Self {
+        EdgeRef {
+            src: edge.src.into(),
+            dst: edge.dst.into(),
+            label: edge.label.into(),
+            directed: edge.directed,
+        }
+    }
+}
 
 /// A batch operation that can be applied to the system.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,25 +149,37 @@ pub enum Operation {
         /// Optional entity ID. If None, a random entity will be generated.
         #[serde(skip_serializing_if = "Option::is_none")]
         entity: Option<Entity>,
+        /// Optional batch-local name for the entity.
+        ///
+        /// Later operations in the same batch may refer to the entity as `@name`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        local_ref: Option<String>,
     },
     /// Deletes an entity and all its components.
     DeleteEntity {
         /// Entity to delete.
-        entity: Entity,
+        entity: EntityRef,
     },
     /// Creates or updates a component on an entity.
     UpsertComponent {
         /// Entity to attach component to.
-        entity: Entity,
+        entity: EntityRef,
         /// Component type.
         component: Component,
         /// Component data.
         data: Value,
     },
+    /// Reads a component from an entity within the batch's transaction.
+    GetComponent {
+        /// Entity to read the component from.
+        entity: EntityRef,
+        /// Component type to read.
+        component: Component,
+    },
     /// Deletes a component from an entity.
     DeleteComponent {
         /// Entity to delete component from.
-        entity: Entity,
+        entity: EntityRef,
         /// Component type to delete.
         component: Component,
     },
@@ -48,6 +187,10 @@ pub enum Operation {
     UpsertComponentDefinition {
         /// Component definition to create or update.
         definition: ComponentDefinition,
+        /// If true, fail the operation when stored instances of the component do not
+        /// satisfy the new schema.
+        #[serde(default)]
+        check_existing: bool,
     },
     /// Deletes a component definition.
     DeleteComponentDefinition {
@@ -67,6 +210,54 @@ pub enum Operation {
         /// Invariant ID to delete.
         invariant_id: InvariantID,
     },
+    /// Creates an edge if it doesn't exist.
+    UpsertEdge {
+        /// Edge to create.
+        edge: EdgeRef,
+    },
+    /// Deletes an edge.
+    DeleteEdge {
+        /// Edge to delete.
+        edge: EdgeRef,
+    },
+}
+
+impl Operation {
+    /// Returns the operation's `type` tag, such as `upsert_component`.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Operation::CreateEntity { .. } => "create_entity",
+            Operation::DeleteEntity { .. } => "delete_entity",
+            Operation::UpsertComponent { .. } => "upsert_component",
+            Operation::GetComponent { .. } => "get_component",
+            Operation::DeleteComponent { .. } => "delete_component",
+            Operation::UpsertComponentDefinition { .. } => "upsert_component_definition",
+            Operation::DeleteComponentDefinition { .. } => "delete_component_definition",
+            Operation::UpsertInvariant { .. } => "upsert_invariant",
+            Operation::DeleteInvariant { .. } => "delete_invariant",
+            Operation::UpsertEdge { .. } => "upsert_edge",
+            Operation::DeleteEdge { .. } => "delete_edge",
+        }
+    }
+
+    /// Returns the entity the operation acts on, if it names a single entity.
+    pub fn entity(&self) -> Option<EntityRef> {
+        match self {
+            Operation::CreateEntity {
+                entity: Some(entity),
+                ..
+            } => Some(EntityRef::Entity(*entity)),
+            Operation::CreateEntity {
+                local_ref: Some(name),
+                ..
+            } => Some(EntityRef::Local(name.clone())),
+            Operation::DeleteEntity { entity }
+            | Operation::UpsertComponent { entity, .. }
+            | Operation::GetComponent { entity, .. }
+            | Operation::DeleteComponent { entity, .. } => Some(entity.clone()),
+            _ => None,
+        }
+    }
 }
 
 /// Request containing a batch of operations to apply.
@@ -74,6 +265,40 @@ pub enum Operation {
 pub struct ApplyRequest {
     /// Operations to apply in order.
     pub operations: Vec<Operation>,
+    /// If true (the default), any failed operation rolls back the entire batch.
+    ///
+    /// If false, each operation runs inside its own savepoint: failed operations are rolled
+    /// back individually and the remaining operations are committed.
+    #[serde(default = "default_atomic")]
+    pub atomic: bool,
+    /// If true, every registered invariant is evaluated after the operations are applied
+    /// and the whole batch is rolled back if any entity violates one.
+    #[serde(default)]
+    pub enforce_invariants: bool,
+    /// If true, only the checks that need no database access are run and nothing is
+    /// applied; see [`validate_operations`].
+    #[serde(default)]
+    pub validate_only: bool,
+}
+
+fn default_atomic() -> bool {
+    true
+}
+
+fn default_changed() -> bool {
+    true
+}
+
+impl ApplyRequest {
+    /// Creates an atomic request for the given operations.
+    pub fn new(operations: Vec<Operation>) -> Self {
+        Self {
+            operations,
+            atomic: true,
+            enforce_invariants: false,
+            validate_only: false,
+        }
+    }
 }
 
 /// Result of a single operation.
@@ -93,6 +318,9 @@ pub enum OperationResult {
         entity: Entity,
         /// True if entity was deleted, false if it didn't exist.
         deleted: bool,
+        /// Number of edges that referenced the entity and were deleted with it.
+        #[serde(default)]
+        edges_removed: u64,
     },
     /// Component upsert result.
     UpsertComponent {
@@ -102,6 +330,18 @@ pub enum OperationResult {
         component: Component,
         /// True if component was created, false if it was updated.
         created: bool,
+        /// False if the component already held identical data and nothing was written.
+        #[serde(default = "default_changed")]
+        changed: bool,
+    },
+    /// Component read result.
+    GetComponent {
+        /// The entity the component was read from.
+        entity: Entity,
+        /// The component type.
+        component: Component,
+        /// The component data, or None if the entity has no such component.
+        data: Option<Value>,
     },
     /// Component deletion result.
     DeleteComponent {
@@ -142,6 +382,20 @@ pub enum OperationResult {
         /// True if invariant was deleted, false if it didn't exist.
         deleted: bool,
     },
+    /// Edge upsert result.
+    UpsertEdge {
+        /// The edge.
+        edge: Edge,
+        /// True if edge was created, false if it already existed.
+        created: bool,
+    },
+    /// Edge deletion result.
+    DeleteEdge {
+        /// The edge.
+        edge: Edge,
+        /// True if edge was deleted, false if it didn't exist.
+        deleted: bool,
+    },
     /// Operation error.
     Error {
         /// Index of the operation that failed.
@@ -149,6 +403,25 @@ pub enum OperationResult {
         /// Error message.
         error: String,
     },
+    /// Component data failed schema validation.
+    ValidationFailed {
+        /// Index of the operation that failed.
+        operation_index: usize,
+        /// JSON Pointer to the offending location in the data; empty for the root.
+        path: String,
+        /// What was wrong at that location.
+        message: String,
+    },
+}
+
+impl OperationResult {
+    /// Returns true if the operation failed.
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            OperationResult::Error { .. } | OperationResult::ValidationFailed { .. }
+        )
+    }
 }
 
 /// Response from applying a batch of operations.
@@ -158,78 +431,557 @@ pub struct ApplyResponse {
     pub results: Vec<OperationResult>,
     /// True if transaction was committed, false if rolled back due to errors.
     pub committed: bool,
+    /// Invariant violations that caused the transaction to be rolled back.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<InvariantViolation>,
+}
+
+/// Header carrying the client-supplied idempotency key for apply requests.
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Longest idempotency key accepted, matching the width of the column it is stored in.
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Reads the idempotency key from `headers`, if present.
+///
+/// Keys must be 1 to [`MAX_IDEMPOTENCY_KEY_LEN`] bytes of visible ASCII; anything else is
+/// rejected with `400 Bad Request` before a transaction is opened.
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<&str>, (StatusCode, String)> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let bytes = value.as_bytes();
+    if bytes.is_empty() || !bytes.iter().all(u8::is_ascii_graphic) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "invalid idempotency key: must be non-empty visible ASCII".to_string(),
+        ));
+    }
+    if bytes.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "invalid idempotency key: {} bytes exceeds the limit of {}",
+                bytes.len(),
+                MAX_IDEMPOTENCY_KEY_LEN
+            ),
+        ));
+    }
+    // All bytes are ASCII, so this cannot fail
+    Ok(value.to_str().ok())
+}
+
+/// Default maximum number of operations accepted in a single apply request.
+pub const DEFAULT_MAX_APPLY_OPERATIONS: usize = 10_000;
+
+/// Router state for the apply endpoint.
+#[derive(Clone)]
+struct ApplyState {
+    pool: sqlx::PgPool,
+    max_operations: usize,
+    policy: ComponentNamePolicy,
 }
 
 /// Applies a batch of operations transactionally.
 ///
+/// Requests with more than the configured maximum number of operations are
+/// rejected with `413 Payload Too Large` before a transaction is opened.
+///
+/// If the request carries an `Idempotency-Key` header, the response of the
+/// first committed request with that key is recorded in the same transaction
+/// and returned verbatim for any retry, without re-executing the operations.
+/// Reusing a key with a different request is rejected with
+/// `422 Unprocessable Entity`, and a key that is not 1 to
+/// [`MAX_IDEMPOTENCY_KEY_LEN`] bytes of visible ASCII with `400 Bad Request`.
+///
 /// All operations are executed even if an early operation fails. This is
 /// intentional to provide complete error feedback in a single request.
-/// The transaction will rollback if any operation fails, ensuring atomicity.
+/// For atomic requests, the transaction will rollback if any operation fails,
+/// ensuring atomicity. For non-atomic requests, each operation is wrapped in a
+/// savepoint so that only the failed operations are rolled back and the rest
+/// are committed.
+///
+/// If the request sets `enforce_invariants`, all registered invariants are
+/// evaluated after the operations run. Any violation rolls back the entire
+/// batch, even for non-atomic requests, and is reported in the response.
+///
+/// If the request sets `validate_only`, the batch is checked with
+/// [`validate_operations`] and the errors it finds are returned with
+/// `committed: false`. No transaction is opened and the idempotency key is
+/// ignored.
 ///
 /// Design rationale: Executing all operations allows clients to see all
 /// validation errors and issues in one round-trip, rather than discovering
 /// them incrementally. The performance overhead is minimal since all operations
 /// occur within the same transaction.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "apply",
+        skip_all,
+        fields(
+            operations = tracing::field::Empty,
+            atomic = tracing::field::Empty,
+            validate_only = tracing::field::Empty,
+            committed = tracing::field::Empty
+        )
+    )
+)]
 async fn apply_operations(
-    State(pool): State<sqlx::PgPool>,
-    Json(request): Json<ApplyRequest>,
+    State(state): State<ApplyState>,
+    headers: HeaderMap,
+    request: Result<Json<ApplyRequest>, JsonRejection>,
 ) -> Result<Json<ApplyResponse>, (StatusCode, String)> {
-    let mut tx = pool.begin().await.map_err(|e| {
+    // Malformed operations, such as an invalid entity or component name, are client errors
+    // whose message names the offending operation and why it was rejected.
+    let Json(request) = request.map_err(|err| (StatusCode::BAD_REQUEST, err.body_text()))?;
+    #[cfg(feature = "tracing")]
+    {
+        let span = tracing::Span::current();
+        span.record("operations", request.operations.len());
+        span.record("atomic", request.atomic);
+        span.record("validate_only", request.validate_only);
+    }
+    if request.operations.len() > state.max_operations {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "too many operations: {} exceeds the limit of {}",
+                request.operations.len(),
+                state.max_operations
+            ),
+        ));
+    }
+
+    let idempotency_key = idempotency_key(&headers)?;
+
+    if request.validate_only {
+        return Ok(Json(ApplyResponse {
+            results: validate_operations(&request.operations),
+            committed: false,
+            violations: Vec::new(),
+        }));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             format!("failed to begin transaction: {}", e),
         )
     })?;
 
+    let request_value = serde_json::to_value(&request).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("failed to serialize request: {}", e),
+        )
+    })?;
+
+    if let Some(key) = idempotency_key {
+        let stored = crate::sql::idempotency::get(&mut tx, key)
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("failed to check idempotency key: {}", e),
+                )
+            })?;
+        if let Some((stored_request, stored_response)) = stored {
+            if stored_request != request_value {
+                return Err((
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "idempotency key reused with a different request".to_string(),
+                ));
+            }
+            let response = serde_json::from_value(stored_response).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("failed to deserialize stored response: {}", e),
+                )
+            })?;
+            return Ok(Json(response));
+        }
+    }
+
     let mut results = Vec::new();
+    let mut bindings = HashMap::new();
 
     for (idx, operation) in request.operations.iter().enumerate() {
-        let result = match operation {
-            Operation::CreateEntity { entity } => {
-                let entity = entity.unwrap_or_else(|| {
-                    Entity::random_url_safe().expect("failed to generate random entity")
-                });
-
-                match crate::sql::entity::create_idempotent(&mut tx, &entity).await {
-                    Ok(created) => OperationResult::CreateEntity { entity, created },
-                    Err(e) => OperationResult::Error {
-                        operation_index: idx,
-                        error: format!("failed to create entity: {}", e),
-                    },
-                }
+        let result = if request.atomic {
+            execute_operation(&mut tx, &mut bindings, idx, operation, state.policy).await
+        } else {
+            savepoint(&mut tx, "SAVEPOINT apply_operation").await?;
+            let result =
+                execute_operation(&mut tx, &mut bindings, idx, operation, state.policy).await;
+            if result.is_error() {
+                savepoint(&mut tx, "ROLLBACK TO SAVEPOINT apply_operation").await?;
             }
-            Operation::DeleteEntity { entity } => {
-                match crate::sql::entity::delete(&mut tx, entity).await {
-                    Ok(deleted) => OperationResult::DeleteEntity {
-                        entity: *entity,
-                        deleted,
-                    },
-                    Err(e) => OperationResult::Error {
-                        operation_index: idx,
-                        error: format!("failed to delete entity: {}", e),
-                    },
+            savepoint(&mut tx, "RELEASE SAVEPOINT apply_operation").await?;
+            result
+        };
+        results.push(result);
+    }
+
+    let has_errors = results.iter().any(OperationResult::is_error);
+
+    let violations = if request.enforce_invariants && !(request.atomic && has_errors) {
+        crate::invariant::evaluate_invariants(&mut tx)
+            .await
+            .map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("failed to evaluate invariants: {}", e),
+                )
+            })?
+    } else {
+        Vec::new()
+    };
+
+    let committed = if (request.atomic && has_errors) || !violations.is_empty() {
+        tx.rollback().await.map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to rollback transaction: {}", e),
+            )
+        })?;
+        false
+    } else {
+        if let Some(key) = idempotency_key {
+            let response = ApplyResponse {
+                results: results.clone(),
+                committed: true,
+                violations: Vec::new(),
+            };
+            let response_value = serde_json::to_value(&response).map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("failed to serialize response: {}", e),
+                )
+            })?;
+            match crate::sql::idempotency::create(&mut tx, key, &request_value, &response_value)
+                .await
+            {
+                Ok(()) => {}
+                Err(DataStoreError::AlreadyExists) => {
+                    return Err((
+                        StatusCode::CONFLICT,
+                        "idempotency key used by a concurrent request".to_string(),
+                    ));
+                }
+                Err(e) => {
+                    return Err((
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("failed to record idempotency key: {}", e),
+                    ));
                 }
             }
+        }
+        tx.commit().await.map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to commit transaction: {}", e),
+            )
+        })?;
+        true
+    };
+
+    #[cfg(feature = "tracing")]
+    {
+        tracing::Span::current().record("committed", committed);
+        tracing::info!(
+            committed,
+            errors = results.iter().filter(|result| result.is_error()).count(),
+            violations = violations.len(),
+            "applied operations"
+        );
+    }
+
+    Ok(Json(ApplyResponse {
+        results,
+        committed,
+        violations,
+    }))
+}
+
+/// Executes a savepoint management statement within the transaction.
+async fn savepoint(
+    tx: &mut Transaction<'_, Postgres>,
+    statement: &'static str,
+) -> Result<(), (StatusCode, String)> {
+    sqlx::query(statement)
+        .execute(&mut **tx)
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("failed to execute {}: {}", statement, e),
+            )
+        })
+}
+
+/// Validates every stored instance of the definition's component against its schema.
+///
+/// Returns an error message listing each offending entity if any instance fails.
+async fn check_existing_instances(
+    tx: &mut Transaction<'_, Postgres>,
+    definition: &ComponentDefinition,
+) -> Result<(), String> {
+    let instances = crate::sql::component::list_for_component(tx, &definition.component)
+        .await
+        .map_err(|e| format!("failed to list existing component instances: {}", e))?;
+
+    let violations: Vec<String> = instances
+        .iter()
+        .filter_map(|(entity, data)| {
+            definition
+                .validate_component_data(data)
+                .err()
+                .map(|e| format!("{} ({})", entity, e))
+        })
+        .collect();
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(format!(
+            "existing component data violates new schema: {}",
+            violations.join(", ")
+        ))
+    }
+}
+
+/// Checks a batch for the errors that can be found without a database.
+///
+/// Operations are checked in order, tracking the batch-local names and component
+/// definitions that earlier operations establish. The checks are:
+///
+/// - local references must be bound by an earlier `CreateEntity`, and bound only once;
+/// - component definition schemas must be well-formed;
+/// - invariant assertions must parse;
+/// - component data must satisfy any definition upserted earlier in the batch.
+///
+/// Returns an `OperationResult::Error` or `OperationResult::ValidationFailed` for each
+/// failing operation, so an empty result means the batch passed. Passing does not
+/// guarantee the batch will apply, as components defined only in the database are not
+/// checked.
+pub fn validate_operations(operations: &[Operation]) -> Vec<OperationResult> {
+    fn unbound(bound: &HashSet<&str>, entity: &EntityRef) -> Option<String> {
+        match entity {
+            EntityRef::Local(name) if !bound.contains(name.as_str()) => {
+                Some(format!("unbound local reference: @{}", name))
+            }
+            _ => None,
+        }
+    }
+
+    let mut bound: HashSet<&str> = HashSet::new();
+    let mut definitions: HashMap<&Component, &ComponentDefinition> = HashMap::new();
+    let mut results = Vec::new();
+    for (idx, operation) in operations.iter().enumerate() {
+        let error = |error: String| OperationResult::Error {
+            operation_index: idx,
+            error,
+        };
+        let failure = match operation {
+            Operation::CreateEntity {
+                local_ref: Some(name),
+                ..
+            } => (!bound.insert(name.as_str()))
+                .then(|| error(format!("local reference already bound: @{}", name))),
+            Operation::CreateEntity {
+                local_ref: None, ..
+            } => None,
+            Operation::DeleteEntity { entity }
+            | Operation::GetComponent { entity, .. }
+            | Operation::DeleteComponent { entity, .. } => unbound(&bound, entity).map(error),
             Operation::UpsertComponent {
                 entity,
                 component,
                 data,
-            } => match crate::sql::component_definition::get(&mut tx, component).await {
+            } => unbound(&bound, entity).map(error).or_else(|| {
+                let definition = definitions.get(component)?;
+                let e = definition.validate_component_data(data).err()?;
+                Some(validation_failed(idx, e))
+            }),
+            Operation::UpsertComponentDefinition { definition, .. } => {
+                match definition.validate_schema() {
+                    Ok(()) => {
+                        definitions.insert(&definition.component, definition);
+                        None
+                    }
+                    Err(e) => Some(error(format!(
+                        "component definition schema validation failed: {}",
+                        e
+                    ))),
+                }
+            }
+            Operation::DeleteComponentDefinition { component } => {
+                definitions.remove(component);
+                None
+            }
+            Operation::UpsertInvariant { asserts, .. } => BidParser::parse_expression(asserts)
+                .err()
+                .map(|e| error(format!("invalid assertion: {}", e))),
+            Operation::UpsertEdge { edge } | Operation::DeleteEdge { edge } => edge
+                .endpoints()
+                .into_iter()
+                .find_map(|entity| unbound(&bound, entity))
+                .map(error),
+            Operation::DeleteInvariant { .. } => None,
+        };
+        results.extend(failure);
+    }
+    results
+}
+
+/// Reports component data that failed its schema as `OperationResult::ValidationFailed`.
+fn validation_failed(idx: usize, e: ValidationError) -> OperationResult {
+    match DataStoreError::from(e) {
+        DataStoreError::ValidationFailed { path, message } => OperationResult::ValidationFailed {
+            operation_index: idx,
+            path,
+            message,
+        },
+        e => OperationResult::Error {
+            operation_index: idx,
+            error: format!("component data validation failed: {}", e),
+        },
+    }
+}
+
+/// Executes a single operation within the transaction, reporting failures as
+/// `OperationResult::Error` for the operation at `idx`.
+///
+/// `bindings` holds the batch-local entity names bound so far; successful
+/// `CreateEntity` operations with a `local_ref` add to it.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "apply::operation",
+        skip_all,
+        fields(
+            index = idx,
+            operation = operation.kind(),
+            entity = operation.entity().map(tracing::field::display),
+            outcome = tracing::field::Empty
+        )
+    )
+)]
+async fn execute_operation(
+    tx: &mut Transaction<'_, Postgres>,
+    bindings: &mut HashMap<String, Entity>,
+    idx: usize,
+    operation: &Operation,
+    policy: ComponentNamePolicy,
+) -> OperationResult {
+    let result = run_operation(tx, bindings, idx, operation, policy).await;
+    #[cfg(feature = "tracing")]
+    tracing::Span::current().record("outcome", if result.is_error() { "error" } else { "ok" });
+    result
+}
+
+/// Performs the work of [`execute_operation`].
+///
+/// Component names are resolved to their definition's stored name under `policy` before
+/// they are used.
+async fn run_operation(
+    tx: &mut Transaction<'_, Postgres>,
+    bindings: &mut HashMap<String, Entity>,
+    idx: usize,
+    operation: &Operation,
+    policy: ComponentNamePolicy,
+) -> OperationResult {
+    match operation {
+        Operation::CreateEntity { entity, local_ref } => {
+            if let Some(name) = local_ref
+                && bindings.contains_key(name)
+            {
+                return OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("local reference already bound: @{}", name),
+                };
+            }
+
+            let entity = entity.unwrap_or_else(|| {
+                Entity::random_url_safe().expect("failed to generate random entity")
+            });
+
+            match crate::sql::entity::create_idempotent(tx, &entity).await {
+                Ok(created) => {
+                    if let Some(name) = local_ref {
+                        bindings.insert(name.clone(), entity);
+                    }
+                    OperationResult::CreateEntity { entity, created }
+                }
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to create entity: {}", e),
+                },
+            }
+        }
+        Operation::DeleteEntity { entity } => {
+            let entity = match entity.resolve(bindings) {
+                Ok(entity) => entity,
+                Err(error) => {
+                    return OperationResult::Error {
+                        operation_index: idx,
+                        error,
+                    };
+                }
+            };
+            let edges_removed = match crate::sql::edge::delete_all_for_entity(tx, &entity).await {
+                Ok(edges_removed) => edges_removed,
+                Err(e) => {
+                    return OperationResult::Error {
+                        operation_index: idx,
+                        error: format!("failed to delete edges for entity: {}", e),
+                    };
+                }
+            };
+            match crate::sql::entity::delete(tx, &entity).await {
+                Ok(deleted) => OperationResult::DeleteEntity {
+                    entity,
+                    deleted,
+                    edges_removed,
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to delete entity: {}", e),
+                },
+            }
+        }
+        Operation::UpsertComponent {
+            entity,
+            component,
+            data,
+        } => {
+            let entity = match entity.resolve(bindings) {
+                Ok(entity) => entity,
+                Err(error) => {
+                    return OperationResult::Error {
+                        operation_index: idx,
+                        error,
+                    };
+                }
+            };
+            let component = &match resolve_component(tx, idx, component, policy).await {
+                Ok(component) => component,
+                Err(result) => return result,
+            };
+            match crate::sql::component_definition::get(tx, component).await {
                 Ok(Some(def_record)) => {
                     if let Err(e) = def_record.definition.validate_component_data(data) {
-                        OperationResult::Error {
-                            operation_index: idx,
-                            error: format!("component data validation failed: {}", e),
-                        }
+                        validation_failed(idx, e)
                     } else {
-                        match crate::sql::component::upsert(&mut tx, entity, component, data).await
-                        {
-                            Ok(created) => OperationResult::UpsertComponent {
-                                entity: *entity,
+                        match crate::sql::component::upsert(tx, &entity, component, data).await {
+                            Ok(outcome) => OperationResult::UpsertComponent {
+                                entity,
                                 component: component.clone(),
-                                created,
+                                created: outcome.created(),
+                                changed: outcome.changed(),
                             },
-                            Err(crate::DataStoreError::NotFound) => OperationResult::Error {
+                            Err(DataStoreError::NotFound) => OperationResult::Error {
                                 operation_index: idx,
                                 error: "entity not found".to_string(),
                             },
@@ -248,136 +1000,248 @@ async fn apply_operations(
                     operation_index: idx,
                     error: format!("failed to retrieve component definition: {}", e),
                 },
-            },
-            Operation::DeleteComponent { entity, component } => {
-                match crate::sql::component::delete(&mut tx, entity, component).await {
-                    Ok(deleted) => OperationResult::DeleteComponent {
-                        entity: *entity,
-                        component: component.clone(),
-                        deleted,
-                    },
-                    Err(e) => OperationResult::Error {
-                        operation_index: idx,
-                        error: format!("failed to delete component: {}", e),
-                    },
-                }
             }
-            Operation::UpsertComponentDefinition { definition } => {
-                if let Err(e) = definition.validate_schema() {
-                    OperationResult::Error {
+        }
+        Operation::GetComponent { entity, component } => {
+            let entity = match entity.resolve(bindings) {
+                Ok(entity) => entity,
+                Err(error) => {
+                    return OperationResult::Error {
                         operation_index: idx,
-                        error: format!("component definition schema validation failed: {}", e),
-                    }
-                } else {
-                    match crate::sql::component_definition::get(&mut tx, &definition.component)
-                        .await
-                    {
-                        Ok(Some(_)) => {
-                            match crate::sql::component_definition::update(&mut tx, definition)
-                                .await
-                            {
-                                Ok(_) => OperationResult::UpsertComponentDefinition {
-                                    component: definition.component.clone(),
-                                    created: false,
-                                },
-                                Err(e) => OperationResult::Error {
-                                    operation_index: idx,
-                                    error: format!("failed to update component definition: {}", e),
-                                },
-                            }
-                        }
-                        Ok(None) => {
-                            match crate::sql::component_definition::create(&mut tx, definition)
-                                .await
-                            {
-                                Ok(_) => OperationResult::UpsertComponentDefinition {
-                                    component: definition.component.clone(),
-                                    created: true,
-                                },
-                                Err(e) => OperationResult::Error {
-                                    operation_index: idx,
-                                    error: format!("failed to create component definition: {}", e),
-                                },
-                            }
-                        }
-                        Err(e) => OperationResult::Error {
-                            operation_index: idx,
-                            error: format!("failed to check component definition: {}", e),
-                        },
-                    }
+                        error,
+                    };
                 }
+            };
+            let component = &match resolve_component(tx, idx, component, policy).await {
+                Ok(component) => component,
+                Err(result) => return result,
+            };
+            match crate::sql::component::get(tx, &entity, component).await {
+                Ok(data) => OperationResult::GetComponent {
+                    entity,
+                    component: component.clone(),
+                    data,
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to get component: {}", e),
+                },
             }
-            Operation::DeleteComponentDefinition { component } => {
-                match crate::sql::component_definition::delete(&mut tx, component).await {
-                    Ok(deleted) => OperationResult::DeleteComponentDefinition {
-                        component: component.clone(),
-                        deleted,
-                    },
-                    Err(e) => OperationResult::Error {
+        }
+        Operation::DeleteComponent { entity, component } => {
+            let entity = match entity.resolve(bindings) {
+                Ok(entity) => entity,
+                Err(error) => {
+                    return OperationResult::Error {
                         operation_index: idx,
-                        error: format!("failed to delete component definition: {}", e),
-                    },
+                        error,
+                    };
                 }
+            };
+            let component = &match resolve_component(tx, idx, component, policy).await {
+                Ok(component) => component,
+                Err(result) => return result,
+            };
+            match crate::sql::component::delete(tx, &entity, component).await {
+                Ok(deleted) => OperationResult::DeleteComponent {
+                    entity,
+                    component: component.clone(),
+                    deleted,
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to delete component: {}", e),
+                },
             }
-            Operation::UpsertInvariant {
-                invariant_id,
-                asserts,
-            } => {
-                let invariant_id = invariant_id.unwrap_or_else(|| {
-                    InvariantID::random_url_safe().expect("failed to generate random invariant")
-                });
-
-                match crate::sql::invariants::upsert(&mut tx, &invariant_id, asserts).await {
-                    Ok(created) => OperationResult::UpsertInvariant {
-                        invariant_id,
-                        asserts: asserts.clone(),
-                        created,
-                    },
-                    Err(e) => OperationResult::Error {
+        }
+        Operation::UpsertComponentDefinition {
+            definition,
+            check_existing,
+        } => {
+            match resolve_component(tx, idx, &definition.component, policy).await {
+                Ok(stored) if stored != definition.component => {
+                    return OperationResult::Error {
                         operation_index: idx,
-                        error: format!("failed to upsert invariant: {}", e),
-                    },
+                        error: format!(
+                            "component definition '{}' conflicts with existing '{}' that differs only in case",
+                            definition.component.as_str(),
+                            stored.as_str()
+                        ),
+                    };
                 }
+                Ok(_) => {}
+                Err(result) => return result,
             }
-            Operation::DeleteInvariant { invariant_id } => {
-                match crate::sql::invariants::delete(&mut tx, invariant_id).await {
-                    Ok(deleted) => OperationResult::DeleteInvariant {
-                        invariant_id: *invariant_id,
-                        deleted,
+            if let Err(e) = definition.validate_schema() {
+                OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("component definition schema validation failed: {}", e),
+                }
+            } else if *check_existing
+                && let Err(error) = check_existing_instances(tx, definition).await
+            {
+                OperationResult::Error {
+                    operation_index: idx,
+                    error,
+                }
+            } else {
+                match crate::sql::component_definition::get(tx, &definition.component).await {
+                    Ok(Some(_)) => {
+                        match crate::sql::component_definition::update(tx, definition).await {
+                            Ok(_) => OperationResult::UpsertComponentDefinition {
+                                component: definition.component.clone(),
+                                created: false,
+                            },
+                            Err(e) => OperationResult::Error {
+                                operation_index: idx,
+                                error: format!("failed to update component definition: {}", e),
+                            },
+                        }
+                    }
+                    Ok(None) => {
+                        match crate::sql::component_definition::create_with_policy(
+                            tx, definition, policy,
+                        )
+                        .await
+                        {
+                            Ok(_) => OperationResult::UpsertComponentDefinition {
+                                component: definition.component.clone(),
+                                created: true,
+                            },
+                            Err(e) => OperationResult::Error {
+                                operation_index: idx,
+                                error: format!("failed to create component definition: {}", e),
+                            },
+                        }
+                    }
+                    Err(e) => OperationResult::Error {
+                        operation_index: idx,
+                        error: format!("failed to check component definition: {}", e),
+                    },
+                }
+            }
+        }
+        Operation::DeleteComponentDefinition { component } => {
+            let component = &match resolve_component(tx, idx, component, policy).await {
+                Ok(component) => component,
+                Err(result) => return result,
+            };
+            match crate::sql::component_definition::delete(tx, component).await {
+                Ok(deleted) => OperationResult::DeleteComponentDefinition {
+                    component: component.clone(),
+                    deleted,
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to delete component definition: {}", e),
+                },
+            }
+        }
+        Operation::UpsertInvariant {
+            invariant_id,
+            asserts,
+        } => {
+            let invariant_id = invariant_id.unwrap_or_else(|| {
+                InvariantID::random_url_safe().expect("failed to generate random invariant")
+            });
+
+            match crate::sql::invariants::upsert(tx, &invariant_id, asserts).await {
+                Ok(created) => OperationResult::UpsertInvariant {
+                    invariant_id,
+                    asserts: asserts.clone(),
+                    created,
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to upsert invariant: {}", e),
+                },
+            }
+        }
+        Operation::DeleteInvariant { invariant_id } => {
+            match crate::sql::invariants::delete(tx, invariant_id).await {
+                Ok(deleted) => OperationResult::DeleteInvariant {
+                    invariant_id: *invariant_id,
+                    deleted,
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to delete invariant: {}", e),
+                },
+            }
+        }
+        Operation::UpsertEdge { edge } => {
+            let edge = &match edge.resolve(bindings) {
+                Ok(edge) => edge,
+                Err(error) => {
+                    return OperationResult::Error {
+                        operation_index: idx,
+                        error,
+                    };
+                }
+            };
+            match crate::sql::edge::get(tx, &edge.src, &edge.dst, &edge.label).await {
+                Ok(_) => OperationResult::UpsertEdge {
+                    edge: edge.clone(),
+                    created: false,
+                },
+                Err(DataStoreError::NotFound) => match crate::sql::edge::create(tx, edge).await {
+                    Ok(()) => OperationResult::UpsertEdge {
+                        edge: edge.clone(),
+                        created: true,
                     },
                     Err(e) => OperationResult::Error {
                         operation_index: idx,
-                        error: format!("failed to delete invariant: {}", e),
+                        error: format!("failed to create edge: {}", e),
                     },
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to check edge: {}", e),
+                },
+            }
+        }
+        Operation::DeleteEdge { edge } => {
+            let edge = match edge.resolve(bindings) {
+                Ok(edge) => edge,
+                Err(error) => {
+                    return OperationResult::Error {
+                        operation_index: idx,
+                        error,
+                    };
                 }
+            };
+            match crate::sql::edge::delete(tx, &edge.src, &edge.dst, &edge.label).await {
+                Ok(()) => OperationResult::DeleteEdge {
+                    edge: edge.clone(),
+                    deleted: true,
+                },
+                Err(DataStoreError::NotFound) => OperationResult::DeleteEdge {
+                    edge: edge.clone(),
+                    deleted: false,
+                },
+                Err(e) => OperationResult::Error {
+                    operation_index: idx,
+                    error: format!("failed to delete edge: {}", e),
+                },
             }
-        };
-        results.push(result);
+        }
     }
+}
 
-    let has_errors = results
-        .iter()
-        .any(|r| matches!(r, OperationResult::Error { .. }));
-
-    let committed = if has_errors {
-        tx.rollback().await.map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("failed to rollback transaction: {}", e),
-            )
-        })?;
-        false
-    } else {
-        tx.commit().await.map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("failed to commit transaction: {}", e),
-            )
-        })?;
-        true
-    };
-
-    Ok(Json(ApplyResponse { results, committed }))
+/// Resolves `component` to its definition's stored name under `policy`, reporting a
+/// failed lookup as an error for the operation at `idx`.
+async fn resolve_component(
+    tx: &mut Transaction<'_, Postgres>,
+    idx: usize,
+    component: &Component,
+    policy: ComponentNamePolicy,
+) -> Result<Component, OperationResult> {
+    crate::sql::component_definition::resolve_name(tx, component, policy)
+        .await
+        .map_err(|e| OperationResult::Error {
+            operation_index: idx,
+            error: format!("failed to retrieve component definition: {}", e),
+        })
 }
 
 /// Creates the apply router with batch operation endpoint.
@@ -392,9 +1256,71 @@ async fn apply_operations(
 /// # }
 /// ```
 pub fn create_apply_router(pool: sqlx::PgPool) -> Router {
+    create_apply_router_with_limit(pool, DEFAULT_MAX_APPLY_OPERATIONS)
+}
+
+/// Creates the apply router, rejecting batches with more than `max_operations`
+/// operations.
+///
+/// # Example
+///
+/// ```no_run
+/// # use stigmergy::create_apply_router_with_limit;
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool) {
+/// let router = create_apply_router_with_limit(pool, 1_000);
+/// # }
+/// ```
+pub fn create_apply_router_with_limit(pool: sqlx::PgPool, max_operations: usize) -> Router {
+    create_apply_router_with_limit_and_policy(pool, max_operations, ComponentNamePolicy::default())
+}
+
+/// Creates the apply router, resolving component names according to `policy`.
+///
+/// Under [`ComponentNamePolicy::CaseInsensitive`], component and definition operations
+/// address the definition whose name matches regardless of case, and upserting a
+/// definition that differs only in case from an existing one fails as a conflict.
+///
+/// # Example
+///
+/// ```no_run
+/// # use stigmergy::{ComponentNamePolicy, create_apply_router_with_policy};
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool) {
+/// let router = create_apply_router_with_policy(pool, ComponentNamePolicy::CaseInsensitive);
+/// # }
+/// ```
+pub fn create_apply_router_with_policy(pool: sqlx::PgPool, policy: ComponentNamePolicy) -> Router {
+    create_apply_router_with_limit_and_policy(pool, DEFAULT_MAX_APPLY_OPERATIONS, policy)
+}
+
+/// Creates the apply router with both an operation limit and a component name policy.
+///
+/// # Example
+///
+/// ```no_run
+/// # use stigmergy::{ComponentNamePolicy, create_apply_router_with_limit_and_policy};
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool) {
+/// let router = create_apply_router_with_limit_and_policy(
+///     pool,
+///     1_000,
+///     ComponentNamePolicy::CaseInsensitive,
+/// );
+/// # }
+/// ```
+pub fn create_apply_router_with_limit_and_policy(
+    pool: sqlx::PgPool,
+    max_operations: usize,
+    policy: ComponentNamePolicy,
+) -> Router {
     Router::new()
         .route("/apply", post(apply_operations))
-        .with_state(pool)
+        .with_state(ApplyState {
+            pool,
+            max_operations,
+            policy,
+        })
 }
 
 #[cfg(test)]
@@ -454,17 +1380,26 @@ mod tests {
         let component = Component::new("TestComponent").unwrap();
 
         let ops = vec![
-            Operation::CreateEntity { entity: None },
+            Operation::CreateEntity {
+                entity: None,
+                local_ref: None,
+            },
             Operation::CreateEntity {
                 entity: Some(entity),
+                local_ref: Some("player".to_string()),
+            },
+            Operation::DeleteEntity {
+                entity: entity.into(),
             },
-            Operation::DeleteEntity { entity },
             Operation::UpsertComponent {
-                entity,
+                entity: EntityRef::Local("player".to_string()),
                 component: component.clone(),
                 data: serde_json::json!({"value": 42}),
             },
-            Operation::DeleteComponent { entity, component },
+            Operation::DeleteComponent {
+                entity: entity.into(),
+                component,
+            },
         ];
 
         let json = serde_json::to_string_pretty(&ops).unwrap();
@@ -487,11 +1422,13 @@ mod tests {
             OperationResult::DeleteEntity {
                 entity,
                 deleted: false,
+                edges_removed: 0,
             },
             OperationResult::UpsertComponent {
                 entity,
                 component: component.clone(),
                 created: true,
+                changed: true,
             },
             OperationResult::DeleteComponent {
                 entity,
@@ -507,6 +1444,7 @@ mod tests {
         let response = ApplyResponse {
             results,
             committed: false,
+            violations: vec![],
         };
 
         let json = serde_json::to_string_pretty(&response).unwrap();
@@ -517,70 +1455,204 @@ mod tests {
         assert!(!deserialized.committed);
     }
 
+    /// A span's name, its parent span's name, and its `operation` field.
+    #[cfg(feature = "tracing")]
+    type RecordedSpan = (String, Option<String>, Option<String>);
+
+    /// Records every span created while installed as the default subscriber.
+    #[cfg(feature = "tracing")]
+    #[derive(Clone, Default)]
+    struct SpanRecorder {
+        spans: std::sync::Arc<std::sync::Mutex<Vec<RecordedSpan>>>,
+        stack: std::sync::Arc<std::sync::Mutex<Vec<u64>>>,
+    }
+
+    #[cfg(feature = "tracing")]
+    impl tracing::Subscriber for SpanRecorder {
+        // Parallel tests install their own dispatchers and rebuild the shared callsite
+        // interest cache; asking every time keeps this recorder from being skipped.
+        fn register_callsite(
+            &self,
+            _: &'static tracing::Metadata<'static>,
+        ) -> tracing::subscriber::Interest {
+            tracing::subscriber::Interest::sometimes()
+        }
+
+        fn enabled(&self, _: &tracing::Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, attrs: &tracing::span::Attributes<'_>) -> tracing::span::Id {
+            struct Operation(Option<String>);
+            impl tracing::field::Visit for Operation {
+                fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+                    if field.name() == "operation" {
+                        self.0 = Some(value.to_string());
+                    }
+                }
+                fn record_debug(&mut self, _: &tracing::field::Field, _: &dyn std::fmt::Debug) {}
+            }
+            let mut operation = Operation(None);
+            attrs.record(&mut operation);
+
+            let mut spans = self.spans.lock().unwrap();
+            let parent = self
+                .stack
+                .lock()
+                .unwrap()
+                .last()
+                .map(|id| spans[*id as usize - 1].0.clone());
+            spans.push((attrs.metadata().name().to_string(), parent, operation.0));
+            tracing::span::Id::from_u64(spans.len() as u64)
+        }
+
+        fn record(&self, _: &tracing::span::Id, _: &tracing::span::Record<'_>) {}
+
+        fn record_follows_from(&self, _: &tracing::span::Id, _: &tracing::span::Id) {}
+
+        fn event(&self, _: &tracing::Event<'_>) {}
+
+        fn enter(&self, span: &tracing::span::Id) {
+            self.stack.lock().unwrap().push(span.into_u64());
+        }
+
+        fn exit(&self, _: &tracing::span::Id) {
+            self.stack.lock().unwrap().pop();
+        }
+    }
+
+    #[cfg(feature = "tracing")]
     #[tokio::test]
-    async fn empty_operations() {
+    async fn apply_records_spans_per_operation() {
         let pool = crate::sql::tests::setup_test_db().await;
-        let router = create_apply_router(pool.clone());
-        let server = TestServer::new(router).unwrap();
-
-        let response = server.post("/apply").json(&json!({"operations": []})).await;
+        let server = TestServer::new(create_apply_router(pool.clone())).unwrap();
+        let recorder = SpanRecorder::default();
 
-        response.assert_status_ok();
-        let apply_response: ApplyResponse = response.json();
-        println!("empty_operations response: {:?}", apply_response);
+        let entity = Entity::new([5u8; 32]);
+        let request = ApplyRequest::new(vec![
+            Operation::CreateEntity {
+                entity: Some(entity),
+                local_ref: None,
+            },
+            Operation::DeleteEntity {
+                entity: entity.into(),
+            },
+        ]);
+        {
+            use tracing::instrument::WithSubscriber;
+            server
+                .post("/apply")
+                .json(&request)
+                .into_future()
+                .with_subscriber(recorder.clone())
+                .await
+                .assert_status_ok();
+        }
 
+        let spans = recorder.spans.lock().unwrap().clone();
+        println!("apply_records_spans_per_operation response: {:?}", spans);
+        let apply = "apply".to_string();
+        assert_eq!(spans.iter().filter(|(name, ..)| *name == apply).count(), 1);
+        let operations: Vec<_> = spans
+            .iter()
+            .filter(|(name, ..)| name == "apply::operation")
+            .map(|(_, parent, operation)| (parent.clone(), operation.clone()))
+            .collect();
         assert_eq!(
-            apply_response,
-            ApplyResponse {
-                results: vec![],
-                committed: true
-            }
+            operations,
+            vec![
+                (Some(apply.clone()), Some("create_entity".to_string())),
+                (Some(apply.clone()), Some("delete_entity".to_string())),
+            ]
         );
+        assert!(spans.iter().any(|(name, parent, _)| {
+            name == "sql::entity::create_idempotent"
+                && parent.as_deref() == Some("apply::operation")
+        }));
     }
 
     #[tokio::test]
-    async fn create_entity_with_explicit_id() {
+    async fn validate_only_reports_errors_without_sql() {
         let pool = crate::sql::tests::setup_test_db().await;
         let router = create_apply_router(pool.clone());
         let server = TestServer::new(router).unwrap();
+        // Any SQL issued from here on fails, so a successful response shows none was.
+        pool.close().await;
+
+        let point = Component::new("Point").unwrap();
+        let request = ApplyRequest {
+            validate_only: true,
+            ..ApplyRequest::new(vec![
+                Operation::UpsertComponentDefinition {
+                    definition: ComponentDefinition::new(
+                        Component::new("Broken").unwrap(),
+                        json!({"type": "no-such-type"}),
+                    ),
+                    check_existing: false,
+                },
+                Operation::CreateEntity {
+                    entity: None,
+                    local_ref: Some("player".to_string()),
+                },
+                Operation::UpsertComponentDefinition {
+                    definition: ComponentDefinition::new(
+                        point.clone(),
+                        json!({"type": "object", "properties": {"x": {"type": "integer"}}}),
+                    ),
+                    check_existing: false,
+                },
+                Operation::UpsertComponent {
+                    entity: EntityRef::Local("player".to_string()),
+                    component: point.clone(),
+                    data: json!({"x": "left"}),
+                },
+                Operation::DeleteComponent {
+                    entity: EntityRef::Local("enemy".to_string()),
+                    component: point,
+                },
+                Operation::UpsertInvariant {
+                    invariant_id: None,
+                    asserts: "Point.x >=".to_string(),
+                },
+            ])
+        };
 
-        let entity = unique_entity("create_explicit");
-
-        let response = server
-            .post("/apply")
-            .json(&json!({
-                "operations": [
-                    {"type": "create_entity", "entity": entity}
-                ]
-            }))
-            .await;
-
+        let response = server.post("/apply").json(&request).await;
         response.assert_status_ok();
         let apply_response: ApplyResponse = response.json();
         println!(
-            "create_entity_with_explicit_id response: {:?}",
+            "validate_only_reports_errors_without_sql response: {:?}",
             apply_response
         );
 
-        assert_eq!(
-            apply_response,
-            ApplyResponse {
-                results: vec![OperationResult::CreateEntity {
-                    entity,
-                    created: true
-                }],
-                committed: true
-            }
-        );
-
-        let mut tx = pool.begin().await.unwrap();
-        let record = crate::sql::entity::get(&mut tx, &entity).await.unwrap();
-        tx.commit().await.unwrap();
-        assert!(record.is_some());
+        assert!(!apply_response.committed);
+        let indices: Vec<usize> = apply_response
+            .results
+            .iter()
+            .filter_map(|result| match result {
+                OperationResult::Error {
+                    operation_index, ..
+                }
+                | OperationResult::ValidationFailed {
+                    operation_index, ..
+                } => Some(*operation_index),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(indices, vec![0, 3, 4, 5]);
+        assert!(matches!(
+            &apply_response.results[0],
+            OperationResult::Error { error, .. }
+                if error.starts_with("component definition schema validation failed")
+        ));
+        assert!(matches!(
+            &apply_response.results[1],
+            OperationResult::ValidationFailed { path, .. } if path == "/x"
+        ));
     }
 
     #[tokio::test]
-    async fn create_entity_with_generated_id() {
+    async fn malformed_operation_is_bad_request() {
         let pool = crate::sql::tests::setup_test_db().await;
         let router = create_apply_router(pool.clone());
         let server = TestServer::new(router).unwrap();
@@ -588,72 +1660,406 @@ mod tests {
         let response = server
             .post("/apply")
             .json(&json!({
-                "operations": [
-                    {"type": "create_entity"}
-                ]
+                "operations": [{"type": "delete_entity", "entity": "not-an-entity"}]
             }))
             .await;
-
-        response.assert_status_ok();
-        let apply_response: ApplyResponse = response.json();
         println!(
-            "create_entity_with_generated_id response: {:?}",
-            apply_response
+            "malformed_operation_is_bad_request response: {}",
+            response.text()
+        );
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let text = response.text();
+        assert!(text.contains("operations[0]"), "{}", text);
+        assert!(
+            text.contains(r#"invalid entity "not-an-entity""#),
+            "{}",
+            text
         );
 
-        assert!(apply_response.committed);
-        assert_eq!(apply_response.results.len(), 1);
-        match &apply_response.results[0] {
-            OperationResult::CreateEntity { entity, created } => {
-                assert!(*created);
-                let mut tx = pool.begin().await.unwrap();
-                let record = crate::sql::entity::get(&mut tx, entity).await.unwrap();
-                tx.commit().await.unwrap();
-                assert!(record.is_some());
-            }
-            _ => panic!("Expected CreateEntity result"),
-        }
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [{
+                    "type": "delete_component",
+                    "entity": Entity::new([1u8; 32]),
+                    "component": "9Lives"
+                }]
+            }))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        assert!(
+            response
+                .text()
+                .contains(r#"invalid component name "9Lives""#)
+        );
     }
 
     #[tokio::test]
-    async fn create_entity_idempotent() {
+    async fn empty_operations() {
         let pool = crate::sql::tests::setup_test_db().await;
         let router = create_apply_router(pool.clone());
         let server = TestServer::new(router).unwrap();
 
-        let entity = unique_entity("create_idempotent");
-        create_test_entity(&pool, &entity).await;
-
-        let response = server
-            .post("/apply")
-            .json(&json!({
-                "operations": [
-                    {"type": "create_entity", "entity": entity}
-                ]
-            }))
-            .await;
+        let response = server.post("/apply").json(&json!({"operations": []})).await;
 
         response.assert_status_ok();
         let apply_response: ApplyResponse = response.json();
-        println!("create_entity_idempotent response: {:?}", apply_response);
+        println!("empty_operations response: {:?}", apply_response);
 
         assert_eq!(
             apply_response,
             ApplyResponse {
-                results: vec![OperationResult::CreateEntity {
-                    entity,
-                    created: false
-                }],
-                committed: true
+                results: vec![],
+                committed: true,
+                violations: vec![],
             }
         );
     }
 
     #[tokio::test]
-    async fn delete_existing_entity() {
+    async fn too_many_operations_rejected() {
         let pool = crate::sql::tests::setup_test_db().await;
-        let router = create_apply_router(pool.clone());
-        let server = TestServer::new(router).unwrap();
+        let state = ApplyState {
+            pool: pool.clone(),
+            max_operations: DEFAULT_MAX_APPLY_OPERATIONS,
+            policy: ComponentNamePolicy::default(),
+        };
+        let operations = (0..DEFAULT_MAX_APPLY_OPERATIONS + 1)
+            .map(|_| Operation::CreateEntity {
+                entity: None,
+                local_ref: None,
+            })
+            .collect();
+
+        let result = apply_operations(
+            State(state),
+            HeaderMap::new(),
+            Ok(Json(ApplyRequest::new(operations))),
+        )
+        .await;
+
+        assert!(result.is_err());
+        let (status, message) = result.unwrap_err();
+        println!("too_many_operations_rejected response: {:?}", message);
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(message.contains("10001"));
+        assert!(message.contains("10000"));
+    }
+
+    #[tokio::test]
+    async fn operations_at_limit_accepted() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router_with_limit(pool.clone(), 2);
+        let server = TestServer::new(router).unwrap();
+
+        let operations = vec![
+            Operation::CreateEntity {
+                entity: None,
+                local_ref: None,
+            },
+            Operation::CreateEntity {
+                entity: None,
+                local_ref: None,
+            },
+        ];
+        let response = server
+            .post("/apply")
+            .json(&ApplyRequest::new(operations))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "operations_at_limit_accepted response: {:?}",
+            apply_response
+        );
+        assert!(apply_response.committed);
+        assert_eq!(apply_response.results.len(), 2);
+    }
+
+    fn idempotency_headers(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(IDEMPOTENCY_KEY_HEADER, key.parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_replays_response() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let state = ApplyState {
+            pool: pool.clone(),
+            max_operations: DEFAULT_MAX_APPLY_OPERATIONS,
+            policy: ComponentNamePolicy::default(),
+        };
+        let entity = unique_entity("idempotency_replay");
+        let request = ApplyRequest::new(vec![
+            Operation::CreateEntity {
+                entity: Some(entity),
+                local_ref: None,
+            },
+            Operation::CreateEntity {
+                entity: None,
+                local_ref: None,
+            },
+        ]);
+
+        let entity_count = async || {
+            let mut tx = pool.begin().await.unwrap();
+            crate::sql::entity::count(&mut tx).await.unwrap()
+        };
+
+        let Json(first) = apply_operations(
+            State(state.clone()),
+            idempotency_headers("idempotency_replay"),
+            Ok(Json(request.clone())),
+        )
+        .await
+        .unwrap();
+        assert!(first.committed);
+        assert_eq!(entity_count().await, 2);
+
+        let Json(second) = apply_operations(
+            State(state.clone()),
+            idempotency_headers("idempotency_replay"),
+            Ok(Json(request.clone())),
+        )
+        .await
+        .unwrap();
+        println!("idempotency_key_replays_response response: {:?}", second);
+
+        // The replay returns the recorded response without creating anything.
+        assert_eq!(first, second);
+        assert_eq!(entity_count().await, 2);
+
+        // Without the key the batch runs again and creates another random entity.
+        let Json(third) = apply_operations(State(state), HeaderMap::new(), Ok(Json(request)))
+            .await
+            .unwrap();
+        assert!(third.committed);
+        assert_eq!(entity_count().await, 3);
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_reused_with_different_request() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let state = ApplyState {
+            pool: pool.clone(),
+            max_operations: DEFAULT_MAX_APPLY_OPERATIONS,
+            policy: ComponentNamePolicy::default(),
+        };
+        let first = ApplyRequest::new(vec![Operation::CreateEntity {
+            entity: Some(unique_entity("idempotency_reuse_a")),
+            local_ref: None,
+        }]);
+        let second = ApplyRequest::new(vec![Operation::CreateEntity {
+            entity: Some(unique_entity("idempotency_reuse_b")),
+            local_ref: None,
+        }]);
+
+        let Json(response) = apply_operations(
+            State(state.clone()),
+            idempotency_headers("idempotency_reuse"),
+            Ok(Json(first)),
+        )
+        .await
+        .unwrap();
+        assert!(response.committed);
+        let result = apply_operations(
+            State(state),
+            idempotency_headers("idempotency_reuse"),
+            Ok(Json(second)),
+        )
+        .await;
+
+        assert!(result.is_err());
+        let (status, _message) = result.unwrap_err();
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    #[tokio::test]
+    async fn idempotency_key_must_be_short_visible_ascii() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let state = ApplyState {
+            pool: pool.clone(),
+            max_operations: DEFAULT_MAX_APPLY_OPERATIONS,
+            policy: ComponentNamePolicy::default(),
+        };
+        let request = ApplyRequest::new(vec![Operation::CreateEntity {
+            entity: None,
+            local_ref: None,
+        }]);
+
+        let too_long = "k".repeat(MAX_IDEMPOTENCY_KEY_LEN + 1);
+        for key in [too_long.as_str(), "has space", "tab\tkey", ""] {
+            let result = apply_operations(
+                State(state.clone()),
+                idempotency_headers(key),
+                Ok(Json(request.clone())),
+            )
+            .await;
+            let (status, message) = result.unwrap_err();
+            println!(
+                "idempotency_key_must_be_short_visible_ascii response: {:?}",
+                message
+            );
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+
+        let mut non_ascii = HeaderMap::new();
+        non_ascii.insert(
+            IDEMPOTENCY_KEY_HEADER,
+            axum::http::HeaderValue::from_bytes("clé".as_bytes()).unwrap(),
+        );
+        let (status, _message) =
+            apply_operations(State(state.clone()), non_ascii, Ok(Json(request.clone())))
+                .await
+                .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let mut validate_only = request.clone();
+        validate_only.validate_only = true;
+        let (status, _message) = apply_operations(
+            State(state.clone()),
+            idempotency_headers("has space"),
+            Ok(Json(validate_only)),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let longest = format!(
+            "{}{}",
+            unique_entity("idempotency_longest"),
+            "k".repeat(MAX_IDEMPOTENCY_KEY_LEN)
+        );
+        let Json(response) = apply_operations(
+            State(state),
+            idempotency_headers(&longest[..MAX_IDEMPOTENCY_KEY_LEN]),
+            Ok(Json(request)),
+        )
+        .await
+        .unwrap();
+        assert!(response.committed);
+    }
+
+    #[tokio::test]
+    async fn create_entity_with_explicit_id() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("create_explicit");
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "create_entity", "entity": entity}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "create_entity_with_explicit_id response: {:?}",
+            apply_response
+        );
+
+        assert_eq!(
+            apply_response,
+            ApplyResponse {
+                results: vec![OperationResult::CreateEntity {
+                    entity,
+                    created: true
+                }],
+                committed: true,
+                violations: vec![],
+            }
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        let record = crate::sql::entity::get(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+        assert!(record.is_some());
+    }
+
+    #[tokio::test]
+    async fn create_entity_with_generated_id() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "create_entity"}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "create_entity_with_generated_id response: {:?}",
+            apply_response
+        );
+
+        assert!(apply_response.committed);
+        assert_eq!(apply_response.results.len(), 1);
+        match &apply_response.results[0] {
+            OperationResult::CreateEntity { entity, created } => {
+                assert!(*created);
+                let mut tx = pool.begin().await.unwrap();
+                let record = crate::sql::entity::get(&mut tx, entity).await.unwrap();
+                tx.commit().await.unwrap();
+                assert!(record.is_some());
+            }
+            _ => panic!("Expected CreateEntity result"),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_entity_idempotent() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("create_idempotent");
+        create_test_entity(&pool, &entity).await;
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "create_entity", "entity": entity}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!("create_entity_idempotent response: {:?}", apply_response);
+
+        assert_eq!(
+            apply_response,
+            ApplyResponse {
+                results: vec![OperationResult::CreateEntity {
+                    entity,
+                    created: false
+                }],
+                committed: true,
+                violations: vec![],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn delete_existing_entity() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
 
         let entity = unique_entity("delete_existing");
         create_test_entity(&pool, &entity).await;
@@ -676,9 +2082,11 @@ mod tests {
             ApplyResponse {
                 results: vec![OperationResult::DeleteEntity {
                     entity,
-                    deleted: true
+                    deleted: true,
+                    edges_removed: 0,
                 }],
-                committed: true
+                committed: true,
+                violations: vec![],
             }
         );
 
@@ -714,9 +2122,11 @@ mod tests {
             ApplyResponse {
                 results: vec![OperationResult::DeleteEntity {
                     entity,
-                    deleted: false
+                    deleted: false,
+                    edges_removed: 0,
                 }],
-                committed: true
+                committed: true,
+                violations: vec![],
             }
         );
     }
@@ -762,9 +2172,11 @@ mod tests {
                 results: vec![OperationResult::UpsertComponent {
                     entity,
                     component: component.clone(),
-                    created: true
+                    created: true,
+                    changed: true,
                 }],
-                committed: true
+                committed: true,
+                violations: vec![],
             }
         );
 
@@ -776,6 +2188,61 @@ mod tests {
         assert_eq!(retrieved, Some(data));
     }
 
+    #[tokio::test]
+    async fn get_component_reads_earlier_writes() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("get_component_reads");
+        let component = Component::new("Health").unwrap();
+        let schema = simple_object_schema(&[("hp", "number")]);
+
+        create_test_entity(&pool, &entity).await;
+        setup_component_definition(&pool, &component, schema).await;
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "get_component", "entity": entity, "component": component},
+                    {
+                        "type": "upsert_component",
+                        "entity": entity,
+                        "component": component,
+                        "data": {"hp": 7}
+                    },
+                    {"type": "get_component", "entity": entity, "component": component}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "get_component_reads_earlier_writes response: {:?}",
+            apply_response
+        );
+
+        assert!(apply_response.committed);
+        assert_eq!(
+            apply_response.results[0],
+            OperationResult::GetComponent {
+                entity,
+                component: component.clone(),
+                data: None,
+            }
+        );
+        assert_eq!(
+            apply_response.results[2],
+            OperationResult::GetComponent {
+                entity,
+                component,
+                data: Some(json!({"hp": 7})),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn upsert_component_updates_existing() {
         let pool = crate::sql::tests::setup_test_db().await;
@@ -826,10 +2293,12 @@ mod tests {
                 entity: result_entity,
                 component: result_component,
                 created,
+                changed,
             } => {
                 assert_eq!(*result_entity, entity);
                 assert_eq!(*result_component, component);
                 assert!(!created);
+                assert!(changed);
             }
             r => panic!("Expected UpsertComponent with created=false, got: {:?}", r),
         }
@@ -843,18 +2312,25 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn upsert_component_entity_not_found() {
+    async fn upsert_component_identical_data_reports_unchanged() {
         let pool = crate::sql::tests::setup_test_db().await;
         let router = create_apply_router(pool.clone());
         let server = TestServer::new(router).unwrap();
 
-        let entity = unique_entity("upsert_no_entity");
-        let component = Component::new("Invalid").unwrap();
-        let schema = simple_object_schema(&[("value", "number")]);
-        let data = json!({"value": 42});
+        let entity = unique_entity("upsert_unchanged");
+        let component = Component::new("Score").unwrap();
+        let schema = simple_object_schema(&[("points", "number")]);
+        let data = json!({"points": 100});
 
+        create_test_entity(&pool, &entity).await;
         setup_component_definition(&pool, &component, schema).await;
 
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::component::create(&mut tx, &entity, &component, &data)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
         let response = server
             .post("/apply")
             .json(&json!({
@@ -872,21 +2348,67 @@ mod tests {
         response.assert_status_ok();
         let apply_response: ApplyResponse = response.json();
         println!(
-            "upsert_component_entity_not_found response: {:?}",
+            "upsert_component_identical_data_reports_unchanged response: {:?}",
             apply_response
         );
 
-        assert!(!apply_response.committed);
-        assert_eq!(apply_response.results.len(), 1);
+        assert!(apply_response.committed);
         match &apply_response.results[0] {
-            OperationResult::Error {
-                operation_index,
-                error,
+            OperationResult::UpsertComponent {
+                created, changed, ..
             } => {
-                assert_eq!(*operation_index, 0);
-                assert_eq!(*error, "entity not found");
+                assert!(!created);
+                assert!(!changed);
             }
-            _ => panic!("Expected Error result"),
+            r => panic!("Expected UpsertComponent result, got: {:?}", r),
+        }
+    }
+
+    #[tokio::test]
+    async fn upsert_component_entity_not_found() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("upsert_no_entity");
+        let component = Component::new("Invalid").unwrap();
+        let schema = simple_object_schema(&[("value", "number")]);
+        let data = json!({"value": 42});
+
+        setup_component_definition(&pool, &component, schema).await;
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {
+                        "type": "upsert_component",
+                        "entity": entity,
+                        "component": component,
+                        "data": data
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "upsert_component_entity_not_found response: {:?}",
+            apply_response
+        );
+
+        assert!(!apply_response.committed);
+        assert_eq!(apply_response.results.len(), 1);
+        match &apply_response.results[0] {
+            OperationResult::Error {
+                operation_index,
+                error,
+            } => {
+                assert_eq!(*operation_index, 0);
+                assert_eq!(*error, "entity not found");
+            }
+            _ => panic!("Expected Error result"),
         }
     }
 
@@ -984,17 +2506,64 @@ mod tests {
         assert!(!apply_response.committed);
         assert_eq!(apply_response.results.len(), 1);
         match &apply_response.results[0] {
-            OperationResult::Error {
+            OperationResult::ValidationFailed {
                 operation_index,
-                error,
+                path,
+                message,
             } => {
                 assert_eq!(*operation_index, 0);
-                assert!(error.contains("component data validation failed"));
+                assert_eq!(path, "");
+                assert!(message.contains("required_number"));
             }
-            _ => panic!("Expected Error result"),
+            _ => panic!("Expected ValidationFailed result"),
         }
     }
 
+    #[tokio::test]
+    async fn upsert_component_type_mismatch_reports_path() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("upsert_type_mismatch");
+        let component = Component::new("Score").unwrap();
+        let schema = simple_object_schema(&[("points", "number")]);
+
+        create_test_entity(&pool, &entity).await;
+        setup_component_definition(&pool, &component, schema).await;
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {
+                        "type": "upsert_component",
+                        "entity": entity,
+                        "component": component,
+                        "data": {"points": "lots"}
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "upsert_component_type_mismatch_reports_path response: {:?}",
+            apply_response
+        );
+
+        assert!(!apply_response.committed);
+        assert_eq!(
+            apply_response.results[0],
+            OperationResult::ValidationFailed {
+                operation_index: 0,
+                path: "/points".to_string(),
+                message: "Type mismatch: expected number, got string".to_string(),
+            }
+        );
+    }
+
     #[tokio::test]
     async fn delete_existing_component() {
         let pool = crate::sql::tests::setup_test_db().await;
@@ -1019,59 +2588,445 @@ mod tests {
             .post("/apply")
             .json(&json!({
                 "operations": [
-                    {
-                        "type": "delete_component",
-                        "entity": entity,
-                        "component": component
-                    }
+                    {
+                        "type": "delete_component",
+                        "entity": entity,
+                        "component": component
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!("delete_existing_component response: {:?}", apply_response);
+
+        assert_eq!(
+            apply_response,
+            ApplyResponse {
+                results: vec![OperationResult::DeleteComponent {
+                    entity,
+                    component: component.clone(),
+                    deleted: true
+                }],
+                committed: true,
+                violations: vec![],
+            }
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        let retrieved = crate::sql::component::get(&mut tx, &entity, &component)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        assert!(retrieved.is_none());
+    }
+
+    #[tokio::test]
+    async fn delete_nonexistent_component() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("delete_comp_nonexistent");
+        let component = Component::new("NotThere").unwrap();
+
+        create_test_entity(&pool, &entity).await;
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {
+                        "type": "delete_component",
+                        "entity": entity,
+                        "component": component
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "delete_nonexistent_component response: {:?}",
+            apply_response
+        );
+
+        assert_eq!(
+            apply_response,
+            ApplyResponse {
+                results: vec![OperationResult::DeleteComponent {
+                    entity,
+                    component,
+                    deleted: false
+                }],
+                committed: true,
+                violations: vec![],
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_all_success_commits() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity1 = unique_entity("batch_success_1");
+        let entity2 = unique_entity("batch_success_2");
+        let component = Component::new("Position").unwrap();
+        let schema = simple_object_schema(&[("x", "number"), ("y", "number")]);
+        let data = json!({"x": 1.0, "y": 2.0});
+
+        setup_component_definition(&pool, &component, schema).await;
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "create_entity", "entity": entity1},
+                    {"type": "create_entity", "entity": entity2},
+                    {
+                        "type": "upsert_component",
+                        "entity": entity1,
+                        "component": component,
+                        "data": data
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!("batch_all_success_commits response: {:?}", apply_response);
+
+        assert!(apply_response.committed);
+        assert_eq!(apply_response.results.len(), 3);
+
+        let mut tx = pool.begin().await.unwrap();
+        assert!(
+            crate::sql::entity::get(&mut tx, &entity1)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            crate::sql::entity::get(&mut tx, &entity2)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            crate::sql::component::get(&mut tx, &entity1, &component)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        tx.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn batch_with_error_rolls_back() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("batch_rollback");
+        let component = Component::new("Invalid").unwrap();
+        let data = json!({"value": 42});
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "create_entity", "entity": entity},
+                    {
+                        "type": "upsert_component",
+                        "entity": entity,
+                        "component": component,
+                        "data": data
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!("batch_with_error_rolls_back response: {:?}", apply_response);
+
+        assert!(!apply_response.committed);
+        assert_eq!(apply_response.results.len(), 2);
+        match &apply_response.results[0] {
+            OperationResult::CreateEntity { created, .. } => assert!(*created),
+            _ => panic!("Expected CreateEntity result"),
+        }
+        match &apply_response.results[1] {
+            OperationResult::Error {
+                operation_index, ..
+            } => {
+                assert_eq!(*operation_index, 1);
+            }
+            _ => panic!("Expected Error result"),
+        }
+
+        let mut tx = pool.begin().await.unwrap();
+        let record = crate::sql::entity::get(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+        assert!(record.is_none());
+    }
+
+    #[tokio::test]
+    async fn non_atomic_batch_commits_successful_operations() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity1 = unique_entity("non_atomic_1");
+        let entity2 = unique_entity("non_atomic_2");
+        let component = Component::new("Undefined").unwrap();
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "atomic": false,
+                "operations": [
+                    {"type": "create_entity", "entity": entity1},
+                    {
+                        "type": "upsert_component",
+                        "entity": entity1,
+                        "component": component,
+                        "data": {"value": 42}
+                    },
+                    {"type": "create_entity", "entity": entity2}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "non_atomic_batch_commits_successful_operations response: {:?}",
+            apply_response
+        );
+
+        assert!(apply_response.committed);
+        assert_eq!(apply_response.results.len(), 3);
+        match &apply_response.results[1] {
+            OperationResult::Error {
+                operation_index, ..
+            } => assert_eq!(*operation_index, 1),
+            r => panic!("Expected Error result, got: {:?}", r),
+        }
+
+        let mut tx = pool.begin().await.unwrap();
+        assert!(
+            crate::sql::entity::get(&mut tx, &entity1)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            crate::sql::entity::get(&mut tx, &entity2)
+                .await
+                .unwrap()
+                .is_some()
+        );
+        tx.commit().await.unwrap();
+    }
+
+    #[test]
+    fn entity_ref_serde() {
+        let entity = Entity::new([1u8; 32]);
+
+        let concrete: EntityRef = serde_json::from_value(json!(entity)).unwrap();
+        assert_eq!(concrete, EntityRef::Entity(entity));
+        assert_eq!(serde_json::to_value(&concrete).unwrap(), json!(entity));
+
+        let local: EntityRef = serde_json::from_value(json!("@player")).unwrap();
+        assert_eq!(local, EntityRef::Local("player".to_string()));
+        assert_eq!(serde_json::to_value(&local).unwrap(), json!("@player"));
+
+        assert!(serde_json::from_value::<EntityRef>(json!("@")).is_err());
+        assert!(serde_json::from_value::<EntityRef>(json!("not-an-entity")).is_err());
+    }
+
+    #[tokio::test]
+    async fn local_ref_binds_generated_entity() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let component = Component::new("LocalRefHealth").unwrap();
+        let schema = simple_object_schema(&[("hp", "number")]);
+        setup_component_definition(&pool, &component, schema).await;
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "create_entity", "local_ref": "hero"},
+                    {
+                        "type": "upsert_component",
+                        "entity": "@hero",
+                        "component": component,
+                        "data": {"hp": 10}
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "local_ref_binds_generated_entity response: {:?}",
+            apply_response
+        );
+
+        assert!(apply_response.committed);
+        let created = match &apply_response.results[0] {
+            OperationResult::CreateEntity { entity, .. } => *entity,
+            r => panic!("Expected CreateEntity result, got: {:?}", r),
+        };
+        match &apply_response.results[1] {
+            OperationResult::UpsertComponent { entity, .. } => assert_eq!(*entity, created),
+            r => panic!("Expected UpsertComponent result, got: {:?}", r),
+        }
+
+        let mut tx = pool.begin().await.unwrap();
+        let retrieved = crate::sql::component::get(&mut tx, &created, &component)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(retrieved, Some(json!({"hp": 10})));
+    }
+
+    #[tokio::test]
+    async fn unbound_local_ref_fails() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "delete_entity", "entity": "@missing"}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!("unbound_local_ref_fails response: {:?}", apply_response);
+
+        assert!(!apply_response.committed);
+        assert_eq!(
+            apply_response.results,
+            vec![OperationResult::Error {
+                operation_index: 0,
+                error: "unbound local reference: @missing".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn batch_creates_entities_and_edge() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let src = unique_entity("edge_batch_src");
+        let dst = unique_entity("edge_batch_dst");
+        let label = unique_entity("edge_batch_label");
+        let edge = Edge {
+            src,
+            dst,
+            label,
+            directed: true,
+        };
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "create_entity", "entity": src},
+                    {"type": "create_entity", "entity": dst},
+                    {"type": "create_entity", "entity": label},
+                    {"type": "upsert_edge", "edge": edge},
+                    {"type": "upsert_edge", "edge": edge}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "batch_creates_entities_and_edge response: {:?}",
+            apply_response
+        );
+
+        assert!(apply_response.committed);
+        assert_eq!(
+            apply_response.results[3],
+            OperationResult::UpsertEdge {
+                edge: edge.clone(),
+                created: true
+            }
+        );
+        assert_eq!(
+            apply_response.results[4],
+            OperationResult::UpsertEdge {
+                edge: edge.clone(),
+                created: false
+            }
+        );
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "delete_edge", "edge": edge},
+                    {"type": "delete_edge", "edge": edge}
                 ]
             }))
             .await;
 
         response.assert_status_ok();
         let apply_response: ApplyResponse = response.json();
-        println!("delete_existing_component response: {:?}", apply_response);
-
+        assert!(apply_response.committed);
         assert_eq!(
-            apply_response,
-            ApplyResponse {
-                results: vec![OperationResult::DeleteComponent {
-                    entity,
-                    component: component.clone(),
+            apply_response.results,
+            vec![
+                OperationResult::DeleteEdge {
+                    edge: edge.clone(),
                     deleted: true
-                }],
-                committed: true
-            }
+                },
+                OperationResult::DeleteEdge {
+                    edge,
+                    deleted: false
+                },
+            ]
         );
-
-        let mut tx = pool.begin().await.unwrap();
-        let retrieved = crate::sql::component::get(&mut tx, &entity, &component)
-            .await
-            .unwrap();
-        tx.commit().await.unwrap();
-        assert!(retrieved.is_none());
     }
 
     #[tokio::test]
-    async fn delete_nonexistent_component() {
+    async fn edge_operations_resolve_local_refs() {
         let pool = crate::sql::tests::setup_test_db().await;
         let router = create_apply_router(pool.clone());
         let server = TestServer::new(router).unwrap();
 
-        let entity = unique_entity("delete_comp_nonexistent");
-        let component = Component::new("NotThere").unwrap();
-
-        create_test_entity(&pool, &entity).await;
-
+        let label = unique_entity("edge_local_label");
+        let edge = json!({"src": "@a", "dst": "@b", "label": label});
         let response = server
             .post("/apply")
             .json(&json!({
                 "operations": [
-                    {
-                        "type": "delete_component",
-                        "entity": entity,
-                        "component": component
-                    }
+                    {"type": "create_entity", "local_ref": "a"},
+                    {"type": "create_entity", "local_ref": "b"},
+                    {"type": "create_entity", "entity": label},
+                    {"type": "upsert_edge", "edge": edge},
+                    {"type": "delete_edge", "edge": edge}
                 ]
             }))
             .await;
@@ -1079,102 +3034,130 @@ mod tests {
         response.assert_status_ok();
         let apply_response: ApplyResponse = response.json();
         println!(
-            "delete_nonexistent_component response: {:?}",
+            "edge_operations_resolve_local_refs response: {:?}",
             apply_response
         );
-
+        assert!(apply_response.committed);
+        let created = |idx: usize| match apply_response.results[idx] {
+            OperationResult::CreateEntity { entity, .. } => entity,
+            ref other => panic!("expected create_entity result, got {:?}", other),
+        };
+        let resolved = Edge {
+            src: created(0),
+            dst: created(1),
+            label,
+            directed: true,
+        };
         assert_eq!(
-            apply_response,
-            ApplyResponse {
-                results: vec![OperationResult::DeleteComponent {
-                    entity,
-                    component,
-                    deleted: false
-                }],
-                committed: true
+            apply_response.results[3],
+            OperationResult::UpsertEdge {
+                edge: resolved.clone(),
+                created: true
+            }
+        );
+        assert_eq!(
+            apply_response.results[4],
+            OperationResult::DeleteEdge {
+                edge: resolved,
+                deleted: true
             }
         );
+
+        let unbound = vec![Operation::UpsertEdge {
+            edge: EdgeRef {
+                src: EntityRef::Local("missing".to_string()),
+                dst: label.into(),
+                label: label.into(),
+                directed: true,
+            },
+        }];
+        assert!(matches!(
+            &validate_operations(&unbound)[..],
+            [OperationResult::Error { error, .. }] if error.contains("@missing")
+        ));
     }
 
     #[tokio::test]
-    async fn batch_all_success_commits() {
+    async fn upsert_edge_missing_entity_rolls_back() {
         let pool = crate::sql::tests::setup_test_db().await;
         let router = create_apply_router(pool.clone());
         let server = TestServer::new(router).unwrap();
 
-        let entity1 = unique_entity("batch_success_1");
-        let entity2 = unique_entity("batch_success_2");
-        let component = Component::new("Position").unwrap();
-        let schema = simple_object_schema(&[("x", "number"), ("y", "number")]);
-        let data = json!({"x": 1.0, "y": 2.0});
-
-        setup_component_definition(&pool, &component, schema).await;
+        let src = unique_entity("edge_missing_src");
+        let edge = Edge {
+            src,
+            dst: unique_entity("edge_missing_dst"),
+            label: unique_entity("edge_missing_lbl"),
+            directed: true,
+        };
 
         let response = server
             .post("/apply")
             .json(&json!({
                 "operations": [
-                    {"type": "create_entity", "entity": entity1},
-                    {"type": "create_entity", "entity": entity2},
-                    {
-                        "type": "upsert_component",
-                        "entity": entity1,
-                        "component": component,
-                        "data": data
-                    }
+                    {"type": "create_entity", "entity": src},
+                    {"type": "upsert_edge", "edge": edge}
                 ]
             }))
             .await;
 
         response.assert_status_ok();
         let apply_response: ApplyResponse = response.json();
-        println!("batch_all_success_commits response: {:?}", apply_response);
-
-        assert!(apply_response.committed);
-        assert_eq!(apply_response.results.len(), 3);
-
-        let mut tx = pool.begin().await.unwrap();
-        assert!(
-            crate::sql::entity::get(&mut tx, &entity1)
-                .await
-                .unwrap()
-                .is_some()
-        );
-        assert!(
-            crate::sql::entity::get(&mut tx, &entity2)
-                .await
-                .unwrap()
-                .is_some()
-        );
-        assert!(
-            crate::sql::component::get(&mut tx, &entity1, &component)
-                .await
-                .unwrap()
-                .is_some()
+        println!(
+            "upsert_edge_missing_entity_rolls_back response: {:?}",
+            apply_response
         );
-        tx.commit().await.unwrap();
+
+        assert!(!apply_response.committed);
+        assert!(matches!(
+            apply_response.results[1],
+            OperationResult::Error {
+                operation_index: 1,
+                ..
+            }
+        ));
     }
 
     #[tokio::test]
-    async fn batch_with_error_rolls_back() {
+    async fn upsert_definition_check_existing_rejects_invalid_data() {
         let pool = crate::sql::tests::setup_test_db().await;
         let router = create_apply_router(pool.clone());
         let server = TestServer::new(router).unwrap();
 
-        let entity = unique_entity("batch_rollback");
-        let component = Component::new("Invalid").unwrap();
-        let data = json!({"value": 42});
+        let entity = unique_entity("check_existing");
+        let component = Component::new("Position").unwrap();
+        create_test_entity(&pool, &entity).await;
+        setup_component_definition(
+            &pool,
+            &component,
+            simple_object_schema(&[("x", "number"), ("y", "number")]),
+        )
+        .await;
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::component::create(&mut tx, &entity, &component, &json!({"x": 1, "y": 2}))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let tightened = json!({
+            "type": "object",
+            "properties": {
+                "x": {"type": "number"},
+                "y": {"type": "number"},
+                "z": {"type": "number"}
+            },
+            "required": ["x", "y", "z"]
+        });
 
         let response = server
             .post("/apply")
             .json(&json!({
                 "operations": [
-                    {"type": "create_entity", "entity": entity},
                     {
-                        "type": "upsert_component",
-                        "entity": entity,
-                        "component": component,
-                        "data": data
+                        "type": "upsert_component_definition",
+                        "definition": {"component": component, "schema": tightened},
+                        "check_existing": true
                     }
                 ]
             }))
@@ -1182,27 +3165,42 @@ mod tests {
 
         response.assert_status_ok();
         let apply_response: ApplyResponse = response.json();
-        println!("batch_with_error_rolls_back response: {:?}", apply_response);
+        println!(
+            "upsert_definition_check_existing_rejects_invalid_data response: {:?}",
+            apply_response
+        );
 
         assert!(!apply_response.committed);
-        assert_eq!(apply_response.results.len(), 2);
         match &apply_response.results[0] {
-            OperationResult::CreateEntity { created, .. } => assert!(*created),
-            _ => panic!("Expected CreateEntity result"),
-        }
-        match &apply_response.results[1] {
-            OperationResult::Error {
-                operation_index, ..
-            } => {
-                assert_eq!(*operation_index, 1);
+            OperationResult::Error { error, .. } => {
+                assert!(error.contains("existing component data violates new schema"));
+                assert!(error.contains(&entity.to_string()));
             }
-            _ => panic!("Expected Error result"),
+            r => panic!("Expected Error result, got: {:?}", r),
         }
 
-        let mut tx = pool.begin().await.unwrap();
-        let record = crate::sql::entity::get(&mut tx, &entity).await.unwrap();
-        tx.commit().await.unwrap();
-        assert!(record.is_none());
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {
+                        "type": "upsert_component_definition",
+                        "definition": {"component": component, "schema": tightened}
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        assert!(apply_response.committed);
+    }
+
+    #[test]
+    fn apply_request_defaults_to_atomic() {
+        let request: ApplyRequest = serde_json::from_value(json!({"operations": []})).unwrap();
+        assert!(request.atomic);
+        assert!(ApplyRequest::new(vec![]).atomic);
     }
 
     #[tokio::test]
@@ -1305,6 +3303,7 @@ mod tests {
                 entity,
                 component,
                 created: true,
+                ..
             } => {
                 assert_eq!(*entity, entity1);
                 assert_eq!(*component, comp1);
@@ -1344,6 +3343,7 @@ mod tests {
             OperationResult::DeleteEntity {
                 entity,
                 deleted: false,
+                ..
             } => assert_eq!(*entity, entity3),
             r => panic!(
                 "Expected DeleteEntity result with deleted=false, got: {:?}",
@@ -1396,4 +3396,134 @@ mod tests {
             }
         }
     }
+
+    #[tokio::test]
+    async fn enforce_invariants_rolls_back_violations() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("enforce_invariants");
+        let component = Component::new("score").unwrap();
+        let invariant_id = InvariantID::new([9u8; 32]);
+
+        create_test_entity(&pool, &entity).await;
+        setup_component_definition(&pool, &component, json!({"type": "integer"})).await;
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::invariants::create(&mut tx, &invariant_id, "score >= 0")
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "enforce_invariants": true,
+                "operations": [
+                    {
+                        "type": "upsert_component",
+                        "entity": entity,
+                        "component": component,
+                        "data": -5
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!(
+            "enforce_invariants_rolls_back_violations response: {:?}",
+            apply_response
+        );
+
+        assert!(!apply_response.committed);
+        assert_eq!(apply_response.violations.len(), 1);
+        assert_eq!(apply_response.violations[0].invariant_id, invariant_id);
+        assert_eq!(apply_response.violations[0].entity, entity);
+
+        let mut tx = pool.begin().await.unwrap();
+        let retrieved = crate::sql::component::get(&mut tx, &entity, &component)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(retrieved, None);
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "enforce_invariants": true,
+                "operations": [
+                    {
+                        "type": "upsert_component",
+                        "entity": entity,
+                        "component": component,
+                        "data": 3
+                    }
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        assert!(apply_response.committed);
+        assert!(apply_response.violations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn delete_entity_removes_edges() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = create_apply_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let entity = unique_entity("delete_edges_target");
+        let other = unique_entity("delete_edges_other");
+        let label = unique_entity("delete_edges_label");
+        create_test_entity(&pool, &entity).await;
+        create_test_entity(&pool, &other).await;
+        create_test_entity(&pool, &label).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        for (src, dst, label) in [
+            (entity, other, label),
+            (other, entity, label),
+            (other, label, entity),
+        ] {
+            let edge = Edge {
+                src,
+                dst,
+                label,
+                directed: true,
+            };
+            crate::sql::edge::create(&mut tx, &edge).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let response = server
+            .post("/apply")
+            .json(&json!({
+                "operations": [
+                    {"type": "delete_entity", "entity": entity}
+                ]
+            }))
+            .await;
+
+        response.assert_status_ok();
+        let apply_response: ApplyResponse = response.json();
+        println!("delete_entity_removes_edges response: {:?}", apply_response);
+
+        assert_eq!(
+            apply_response.results,
+            vec![OperationResult::DeleteEntity {
+                entity,
+                deleted: true,
+                edges_removed: 3,
+            }]
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        let edges = crate::sql::edge::list_all(&mut tx).await.unwrap();
+        tx.commit().await.unwrap();
+        assert!(edges.is_empty());
+    }
 }
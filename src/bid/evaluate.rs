@@ -1,10 +1,18 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
 use regex::Regex;
 use serde_json::Value;
 
-use crate::bid::{Bid, BinaryOperator, Expression, UnaryOperator};
+use crate::bid::{Bid, BinaryOperator, Expression, Position, UnaryOperator};
 
 /// A trait for resolving keys into JSON entities.
 /// This allows the evaluation engine to work with normalized or relational data structures.
+///
+/// The `*` operator hands its operand, which must be a string or number, to the resolver.
+/// The resolved value replaces the key, so member access such as `(*user_ref).active`
+/// indexes into the referenced entity. A key that resolves to `None` is reported as
+/// [`EvaluationError::DerefKeyNotFound`].
 pub trait EntityResolver {
     /// Resolves a key into a JSON Value.
     /// Returns Ok(None) if the key is not found.
@@ -12,28 +20,70 @@ pub trait EntityResolver {
     fn resolve(&self, key: &Value) -> Result<Option<Value>, EvaluationError>;
 }
 
+/// The data an expression is evaluated against.
+///
+/// Variables are looked up in [`EvaluationContext::data`]; the `*` dereference operator
+/// asks [`EvaluationContext::resolve`] for the entity behind a key.
+pub trait EvaluationContext {
+    /// The JSON document that variable paths are resolved in.
+    fn data(&self) -> &Value;
+
+    /// Resolves a key into a JSON Value, as [`EntityResolver::resolve`] does.
+    fn resolve(&self, key: &Value) -> Result<Option<Value>, EvaluationError>;
+}
+
+/// An [`EvaluationContext`] pairing a JSON document with an [`EntityResolver`].
+pub struct DataContext<'a> {
+    data: &'a Value,
+    resolver: &'a dyn EntityResolver,
+}
+
+impl<'a> DataContext<'a> {
+    /// Creates a context that reads variables from `data` and dereferences through `resolver`.
+    pub fn new(data: &'a Value, resolver: &'a dyn EntityResolver) -> Self {
+        Self { data, resolver }
+    }
+}
+
+impl EvaluationContext for DataContext<'_> {
+    fn data(&self) -> &Value {
+        self.data
+    }
+
+    fn resolve(&self, key: &Value) -> Result<Option<Value>, EvaluationError> {
+        self.resolver.resolve(key)
+    }
+}
+
 /// Errors that can occur during bid evaluation
 #[derive(Debug, Clone)]
 pub enum EvaluationError {
     /// Variable path not found in the JSON data
-    VariableNotFound {
+    UndefinedVariable {
         /// The variable path that was not found
         path: Vec<String>,
     },
-    /// Type mismatch during operation
+    /// An operand had the wrong type for its operation
     TypeMismatch {
-        /// Description of the type mismatch
-        message: String,
+        /// What the operation accepts
+        expected: String,
+        /// The type that was supplied
+        found: String,
+        /// Source position of the operation
+        position: Position,
+    },
+    /// Division or modulo by zero
+    DivisionByZero {
+        /// Source position of the operator
+        position: Position,
     },
-    /// Division by zero
-    DivisionByZero,
     /// Invalid operation
     InvalidOperation {
         /// Description of the invalid operation
         message: String,
     },
     /// Regex compilation error
-    RegexError {
+    InvalidRegex {
         /// The regex pattern that failed to compile
         pattern: String,
         /// The regex error message
@@ -49,19 +99,27 @@ pub enum EvaluationError {
 impl std::fmt::Display for EvaluationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            EvaluationError::VariableNotFound { path } => {
-                write!(f, "Variable not found: {}", path.join("."))
+            EvaluationError::UndefinedVariable { path } => {
+                write!(f, "Undefined variable: {}", path.join("."))
             }
-            EvaluationError::TypeMismatch { message } => {
-                write!(f, "Type mismatch: {}", message)
+            EvaluationError::TypeMismatch {
+                expected,
+                found,
+                position,
+            } => {
+                write!(
+                    f,
+                    "Type mismatch at {}: expected {}, found {}",
+                    position, expected, found
+                )
             }
-            EvaluationError::DivisionByZero => {
-                write!(f, "Division by zero")
+            EvaluationError::DivisionByZero { position } => {
+                write!(f, "Division by zero at {}", position)
             }
             EvaluationError::InvalidOperation { message } => {
                 write!(f, "Invalid operation: {}", message)
             }
-            EvaluationError::RegexError { pattern, error } => {
+            EvaluationError::InvalidRegex { pattern, error } => {
                 write!(f, "Regex error for pattern {pattern:?}: {error}")
             }
             EvaluationError::DerefKeyNotFound { key } => {
@@ -73,6 +131,30 @@ impl std::fmt::Display for EvaluationError {
 
 impl std::error::Error for EvaluationError {}
 
+impl EvaluationError {
+    /// Builds a type mismatch whose position is filled in by [`EvaluationError::at`].
+    fn type_mismatch(expected: impl Into<String>, found: impl Into<String>) -> Self {
+        EvaluationError::TypeMismatch {
+            expected: expected.into(),
+            found: found.into(),
+            position: Position::start(),
+        }
+    }
+
+    /// Attributes a positioned error to the operation at `position`.
+    ///
+    /// Operator helpers work on values and do not know where their operation appears in
+    /// the source, so the expression evaluator stamps the position on their errors.
+    fn at(mut self, at: Position) -> Self {
+        if let EvaluationError::TypeMismatch { position, .. }
+        | EvaluationError::DivisionByZero { position } = &mut self
+        {
+            *position = at;
+        }
+        self
+    }
+}
+
 impl Bid {
     /// Evaluate the bid against the given JSON data
     /// Returns Some(bid_value) if the condition is met, None otherwise
@@ -81,11 +163,12 @@ impl Bid {
         data: &Value,
         resolver: &impl EntityResolver,
     ) -> Result<Option<Value>, EvaluationError> {
-        let condition_result = evaluate_expression(&self.on_condition, data, resolver)?;
+        let ctx = DataContext::new(data, resolver);
+        let condition_result = evaluate_expression(&self.on_condition, &ctx)?;
 
         // Check if condition evaluates to true
         if is_truthy(&condition_result) {
-            let bid_result = evaluate_expression(&self.bid_value, data, resolver)?;
+            let bid_result = evaluate_expression(&self.bid_value, &ctx)?;
             Ok(Some(bid_result))
         } else {
             Ok(None)
@@ -93,14 +176,52 @@ impl Bid {
     }
 }
 
-/// Evaluate an expression against the given JSON data
+impl Expression {
+    /// Evaluate the expression against the given context
+    ///
+    /// # Errors
+    /// * [`EvaluationError::UndefinedVariable`] - A variable path is missing from the data
+    /// * [`EvaluationError::TypeMismatch`] - An operand has the wrong type for its operator
+    /// * [`EvaluationError::DivisionByZero`] - The right operand of `/` or `%` is zero
+    /// * [`EvaluationError::InvalidRegex`] - The pattern of a `~=` match does not compile
+    /// * [`EvaluationError::DerefKeyNotFound`] - A `*` dereference names no entity
+    ///
+    /// Positioned errors carry the source position of the failing operator.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use stigmergy::{BidParser, DataContext, EntityResolver, EvaluationError};
+    /// use serde_json::{Value, json};
+    ///
+    /// struct NoEntities;
+    /// impl EntityResolver for NoEntities {
+    ///     fn resolve(&self, _key: &Value) -> Result<Option<Value>, EvaluationError> {
+    ///         Ok(None)
+    ///     }
+    /// }
+    ///
+    /// let expression = BidParser::parse_expression("price * 2").unwrap();
+    /// let data = json!({"price": 21});
+    /// let value = expression.evaluate(&DataContext::new(&data, &NoEntities)).unwrap();
+    /// assert_eq!(value, json!(42.0));
+    /// ```
+    pub fn evaluate(&self, ctx: &dyn EvaluationContext) -> Result<Value, EvaluationError> {
+        evaluate_expression(self, ctx)
+    }
+
+    /// Evaluate the expression and report whether its result is truthy
+    pub fn evaluate_truthy(&self, ctx: &dyn EvaluationContext) -> Result<bool, EvaluationError> {
+        Ok(is_truthy(&evaluate_expression(self, ctx)?))
+    }
+}
+
+/// Evaluate an expression against the given context
 fn evaluate_expression(
     expr: &Expression,
-    data: &Value,
-    resolver: &impl EntityResolver,
+    ctx: &dyn EvaluationContext,
 ) -> Result<Value, EvaluationError> {
     match expr {
-        Expression::Variable { path, .. } => resolve_variable_path(data, path),
+        Expression::Variable { path, .. } => resolve_variable_path(ctx.data(), path),
         Expression::StringLiteral { value, .. } => Ok(Value::String(value.clone())),
         Expression::IntegerLiteral { value, .. } => {
             Ok(Value::Number(serde_json::Number::from(*value)))
@@ -119,23 +240,34 @@ fn evaluate_expression(
             left,
             operator,
             right,
-            ..
+            position,
         } => {
-            let left_val = evaluate_expression(left, data, resolver)?;
-            let right_val = evaluate_expression(right, data, resolver)?;
-            evaluate_binary_operation(&left_val, operator, &right_val)
+            let left_val = evaluate_expression(left, ctx)?;
+            // Logical operators short-circuit: the right operand is only evaluated when
+            // the left one does not already decide the result.
+            match operator {
+                BinaryOperator::LogicalAnd if !is_truthy(&left_val) => return Ok(left_val),
+                BinaryOperator::LogicalOr if is_truthy(&left_val) => return Ok(left_val),
+                _ => {}
+            }
+            let right_val = evaluate_expression(right, ctx)?;
+            evaluate_binary_operation(&left_val, operator, &right_val).map_err(|e| e.at(*position))
         }
         Expression::UnaryOperation {
-            operator, operand, ..
+            operator,
+            operand,
+            position,
         } => {
-            let operand_val = evaluate_expression(operand, data, resolver)?;
-            evaluate_unary_operation(operator, &operand_val, resolver)
+            let operand_val = evaluate_expression(operand, ctx)?;
+            evaluate_unary_operation(operator, &operand_val, ctx).map_err(|e| e.at(*position))
         }
         Expression::MemberAccess {
-            object, property, ..
+            object,
+            property,
+            position,
         } => {
-            let object_val = evaluate_expression(object, data, resolver)?;
-            resolve_member_access(&object_val, property)
+            let object_val = evaluate_expression(object, ctx)?;
+            resolve_member_access(&object_val, property).map_err(|e| e.at(*position))
         }
     }
 }
@@ -149,12 +281,12 @@ fn resolve_variable_path(data: &Value, path: &[String]) -> Result<Value, Evaluat
             Value::Object(map) => {
                 current = map
                     .get(segment)
-                    .ok_or_else(|| EvaluationError::VariableNotFound {
+                    .ok_or_else(|| EvaluationError::UndefinedVariable {
                         path: path.to_vec(),
                     })?;
             }
             _ => {
-                return Err(EvaluationError::VariableNotFound {
+                return Err(EvaluationError::UndefinedVariable {
                     path: path.to_vec(),
                 });
             }
@@ -169,17 +301,14 @@ fn resolve_member_access(object: &Value, property: &str) -> Result<Value, Evalua
     match object {
         Value::Object(map) => map
             .get(property)
-            .ok_or_else(|| EvaluationError::VariableNotFound {
+            .ok_or_else(|| EvaluationError::UndefinedVariable {
                 path: vec![property.to_string()],
             })
             .cloned(),
-        _ => Err(EvaluationError::TypeMismatch {
-            message: format!(
-                "Cannot access property '{}' on {}",
-                property,
-                type_name(object)
-            ),
-        }),
+        _ => Err(EvaluationError::type_mismatch(
+            format!("object with property '{}'", property),
+            type_name(object),
+        )),
     }
 }
 
@@ -231,7 +360,7 @@ fn evaluate_binary_operation(
 fn evaluate_unary_operation(
     operator: &UnaryOperator,
     operand: &Value,
-    resolver: &impl EntityResolver,
+    ctx: &dyn EvaluationContext,
 ) -> Result<Value, EvaluationError> {
     match operator {
         UnaryOperator::Negate => negate_value(operand),
@@ -239,16 +368,14 @@ fn evaluate_unary_operation(
         UnaryOperator::Dereference => {
             // Ensure the operand is a valid key type (String or Number)
             if !operand.is_string() && !operand.is_number() {
-                return Err(EvaluationError::TypeMismatch {
-                    message: format!(
-                        "Dereference operator '*' requires a string or number key, but found {}",
-                        type_name(operand)
-                    ),
-                });
+                return Err(EvaluationError::type_mismatch(
+                    "string or number key",
+                    type_name(operand),
+                ));
             }
 
-            // Resolve the key using the resolver
-            match resolver.resolve(operand)? {
+            // Resolve the key using the context
+            match ctx.resolve(operand)? {
                 Some(entity) => Ok(entity),
                 None => Err(EvaluationError::DerefKeyNotFound {
                     key: operand.clone(),
@@ -276,33 +403,53 @@ fn values_equal(left: &Value, right: &Value) -> bool {
 }
 
 /// Compare two JSON values numerically
+///
+/// Two integers compare exactly; when either side is a float, both are compared as
+/// floats. Any other pairing, such as a string against a number, is a type mismatch.
+/// Negative and positive zero compare equal, and NaN is an invalid operation.
 fn compare_values<F>(left: &Value, right: &Value, compare: F) -> Result<Value, EvaluationError>
 where
     F: Fn(i8) -> bool,
 {
-    let left_num = extract_number(left)?;
-    let right_num = extract_number(right)?;
+    let (Value::Number(l), Value::Number(r)) = (left, right) else {
+        return Err(EvaluationError::type_mismatch(
+            "two numbers to compare",
+            format!("{} and {}", type_name(left), type_name(right)),
+        ));
+    };
 
-    let result = if left_num < right_num {
-        -1
-    } else if left_num > right_num {
-        1
-    } else {
-        0
+    let ordering = match (integer_value(l), integer_value(r)) {
+        (Some(l), Some(r)) => l.cmp(&r),
+        _ => {
+            let l_val = extract_number(left)?;
+            let r_val = extract_number(right)?;
+            // partial_cmp treats -0.0 and 0.0 as equal and refuses to order NaN
+            l_val
+                .partial_cmp(&r_val)
+                .ok_or_else(|| EvaluationError::InvalidOperation {
+                    message: format!("cannot compare {} and {}", l_val, r_val),
+                })?
+        }
     };
 
-    Ok(Value::Bool(compare(result)))
+    Ok(Value::Bool(compare(ordering as i8)))
+}
+
+/// Returns a JSON number as an exact integer, if it is one
+fn integer_value(number: &serde_json::Number) -> Option<i128> {
+    number
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| number.as_u64().map(i128::from))
 }
 
 /// Extract a numeric value from a JSON value
 fn extract_number(value: &Value) -> Result<f64, EvaluationError> {
     match value {
-        Value::Number(n) => n.as_f64().ok_or_else(|| EvaluationError::TypeMismatch {
-            message: "Invalid number".to_string(),
-        }),
-        _ => Err(EvaluationError::TypeMismatch {
-            message: format!("Expected number, found {}", type_name(value)),
-        }),
+        Value::Number(n) => n
+            .as_f64()
+            .ok_or_else(|| EvaluationError::type_mismatch("finite number", n.to_string())),
+        _ => Err(EvaluationError::type_mismatch("number", type_name(value))),
     }
 }
 
@@ -310,9 +457,7 @@ fn extract_number(value: &Value) -> Result<f64, EvaluationError> {
 fn is_integer(value: &Value) -> Result<bool, EvaluationError> {
     match value {
         Value::Number(n) => Ok(n.is_i64() || n.is_u64()),
-        _ => Err(EvaluationError::TypeMismatch {
-            message: format!("Expected number, found {}", type_name(value)),
-        }),
+        _ => Err(EvaluationError::type_mismatch("number", type_name(value))),
     }
 }
 
@@ -324,7 +469,9 @@ fn are_both_integers(left: &Value, right: &Value) -> Result<bool, EvaluationErro
 /// Check for division by zero
 fn check_division_by_zero(divisor: f64) -> Result<(), EvaluationError> {
     if divisor == 0.0 {
-        Err(EvaluationError::DivisionByZero)
+        Err(EvaluationError::DivisionByZero {
+            position: Position::start(),
+        })
     } else {
         Ok(())
     }
@@ -414,12 +561,12 @@ where
 fn add_values(left: &Value, right: &Value) -> Result<Value, EvaluationError> {
     match (left, right) {
         (Value::Number(l), Value::Number(r)) => {
-            let l_val = l.as_f64().ok_or_else(|| EvaluationError::TypeMismatch {
-                message: "Invalid left number in addition".to_string(),
-            })?;
-            let r_val = r.as_f64().ok_or_else(|| EvaluationError::TypeMismatch {
-                message: "Invalid right number in addition".to_string(),
-            })?;
+            let l_val = l
+                .as_f64()
+                .ok_or_else(|| EvaluationError::type_mismatch("finite number", l.to_string()))?;
+            let r_val = r
+                .as_f64()
+                .ok_or_else(|| EvaluationError::type_mismatch("finite number", r.to_string()))?;
             let result = l_val + r_val;
             if let Some(num) = serde_json::Number::from_f64(result) {
                 Ok(Value::Number(num))
@@ -430,9 +577,10 @@ fn add_values(left: &Value, right: &Value) -> Result<Value, EvaluationError> {
             }
         }
         (Value::String(l), Value::String(r)) => Ok(Value::String(format!("{}{}", l, r))),
-        _ => Err(EvaluationError::TypeMismatch {
-            message: format!("Cannot add {} and {}", type_name(left), type_name(right)),
-        }),
+        _ => Err(EvaluationError::type_mismatch(
+            "two numbers or two strings",
+            format!("{} and {}", type_name(left), type_name(right)),
+        )),
     }
 }
 
@@ -517,38 +665,55 @@ fn regex_match_values(left: &Value, right: &Value) -> Result<Value, EvaluationEr
     let haystack = match left {
         Value::String(s) => s,
         _ => {
-            return Err(EvaluationError::TypeMismatch {
-                message: format!(
-                    "Regex match left operand must be a string, found {}",
-                    type_name(left)
-                ),
-            });
+            return Err(EvaluationError::type_mismatch(
+                "string to match",
+                type_name(left),
+            ));
         }
     };
 
     let pattern = match right {
         Value::String(s) => s,
         _ => {
-            return Err(EvaluationError::TypeMismatch {
-                message: format!(
-                    "Regex match right operand must be a string, found {}",
-                    type_name(right)
-                ),
-            });
+            return Err(EvaluationError::type_mismatch(
+                "string pattern",
+                type_name(right),
+            ));
         }
     };
 
-    let regex = match Regex::new(pattern) {
-        Ok(r) => r,
-        Err(e) => {
-            return Err(EvaluationError::RegexError {
-                pattern: pattern.clone(),
+    with_cached_regex(pattern, |regex| Value::Bool(regex.is_match(haystack)))
+}
+
+/// The most compiled patterns [`with_cached_regex`] keeps per thread.
+///
+/// Patterns may come from evaluated data rather than bid literals, so the cache is
+/// cleared rather than allowed to grow without bound.
+const REGEX_CACHE_CAPACITY: usize = 256;
+
+thread_local! {
+    /// Compiled `~=` patterns, keyed by pattern string.
+    static REGEX_CACHE: RefCell<HashMap<String, Regex>> = RefCell::new(HashMap::new());
+}
+
+/// Runs `f` with the compiled form of `pattern`, compiling it only on first use.
+///
+/// Invalid patterns are reported as [`EvaluationError::InvalidRegex`] and never cached.
+fn with_cached_regex<T>(pattern: &str, f: impl FnOnce(&Regex) -> T) -> Result<T, EvaluationError> {
+    REGEX_CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if !cache.contains_key(pattern) {
+            let regex = Regex::new(pattern).map_err(|e| EvaluationError::InvalidRegex {
+                pattern: pattern.to_string(),
                 error: e.to_string(),
-            });
+            })?;
+            if cache.len() >= REGEX_CACHE_CAPACITY {
+                cache.clear();
+            }
+            cache.insert(pattern.to_string(), regex);
         }
-    };
-
-    Ok(Value::Bool(regex.is_match(haystack)))
+        Ok(f(&cache[pattern]))
+    })
 }
 
 #[cfg(test)]
@@ -596,6 +761,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn standalone_expression_evaluation() {
+        let expression = BidParser::parse_expression("score >= 0").unwrap();
+        let resolver = EmptyEntityResolver;
+
+        let positive = json!({"score": 3});
+        let negative = json!({"score": -5});
+        let empty = json!({});
+        assert!(
+            expression
+                .evaluate_truthy(&DataContext::new(&positive, &resolver))
+                .unwrap()
+        );
+        assert!(
+            !expression
+                .evaluate_truthy(&DataContext::new(&negative, &resolver))
+                .unwrap()
+        );
+        assert!(matches!(
+            expression.evaluate(&DataContext::new(&empty, &resolver)),
+            Err(EvaluationError::UndefinedVariable { .. })
+        ));
+    }
+
     #[test]
     fn simple_condition_evaluation() {
         let bid = BidParser::parse("ON user.active BID user.score").unwrap();
@@ -755,6 +944,24 @@ mod tests {
         assert!(matches!(result, Err(EvaluationError::TypeMismatch { .. })));
     }
 
+    #[test]
+    fn regex_match_reuses_compiled_pattern() {
+        let pattern = "^cached-[0-9]+$";
+        let bid = BidParser::parse(&format!(r#"ON text ~= "{}" BID 1"#, pattern)).unwrap();
+        let resolver = EmptyEntityResolver;
+        REGEX_CACHE.with(|cache| cache.borrow_mut().clear());
+
+        for i in 0..10_000 {
+            let data = json!({"text": format!("cached-{}", i)});
+            assert_eq!(bid.evaluate(&data, &resolver).unwrap(), Some(json!(1)));
+        }
+
+        let cached: Vec<String> =
+            REGEX_CACHE.with(|cache| cache.borrow().keys().cloned().collect());
+        println!("regex_match_reuses_compiled_pattern response: {:?}", cached);
+        assert_eq!(cached, vec![pattern.to_string()]);
+    }
+
     #[test]
     fn regex_match_invalid_pattern() {
         let bid = BidParser::parse(r#"ON text ~= "[invalid" BID result"#).unwrap();
@@ -765,7 +972,10 @@ mod tests {
         let resolver = EmptyEntityResolver;
 
         let result = bid.evaluate(&data, &resolver);
-        assert!(matches!(result, Err(EvaluationError::RegexError { .. })));
+        assert!(matches!(result, Err(EvaluationError::InvalidRegex { .. })));
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("[invalid"));
+        assert!(REGEX_CACHE.with(|cache| !cache.borrow().contains_key("[invalid")));
     }
 
     #[test]
@@ -814,7 +1024,7 @@ mod tests {
         let result = bid.evaluate(&data, &resolver);
         assert!(matches!(
             result,
-            Err(EvaluationError::VariableNotFound { .. })
+            Err(EvaluationError::UndefinedVariable { .. })
         ));
     }
 
@@ -835,7 +1045,138 @@ mod tests {
         let resolver = EmptyEntityResolver;
 
         let result = bid.evaluate(&data, &resolver);
-        assert!(matches!(result, Err(EvaluationError::DivisionByZero)));
+        assert!(matches!(
+            result,
+            Err(EvaluationError::DivisionByZero { .. })
+        ));
+    }
+
+    #[test]
+    fn logical_operators_short_circuit() {
+        /// Counts how often variables are looked up.
+        struct CountingContext {
+            data: Value,
+            lookups: std::cell::Cell<usize>,
+        }
+
+        impl EvaluationContext for CountingContext {
+            fn data(&self) -> &Value {
+                self.lookups.set(self.lookups.get() + 1);
+                &self.data
+            }
+
+            fn resolve(&self, _key: &Value) -> Result<Option<Value>, EvaluationError> {
+                Ok(None)
+            }
+        }
+
+        let ctx = CountingContext {
+            data: json!({}),
+            lookups: std::cell::Cell::new(0),
+        };
+        let evaluate = |source: &str| BidParser::parse_expression(source).unwrap().evaluate(&ctx);
+
+        let result = evaluate("false && missing_var");
+        println!("logical_operators_short_circuit response: {:?}", result);
+        assert_eq!(result.unwrap(), json!(false));
+        assert_eq!(evaluate("true || missing_var").unwrap(), json!(true));
+        assert_eq!(ctx.lookups.get(), 0);
+
+        assert!(matches!(
+            evaluate("true && missing_var"),
+            Err(EvaluationError::UndefinedVariable { .. })
+        ));
+        assert_eq!(ctx.lookups.get(), 1);
+    }
+
+    #[test]
+    fn comparison_coercion() {
+        let resolver = EmptyEntityResolver;
+        let data = json!({"big": 9007199254740993u64, "price": 5});
+        let evaluate = |source: &str| {
+            BidParser::parse_expression(source)
+                .unwrap()
+                .evaluate(&DataContext::new(&data, &resolver))
+        };
+
+        assert_eq!(evaluate("price < 5.5").unwrap(), json!(true));
+        assert_eq!(evaluate("5.0 >= price").unwrap(), json!(true));
+        assert_eq!(evaluate("big > 9007199254740992").unwrap(), json!(true));
+
+        let result = evaluate("\"a\" < 5");
+        println!("comparison_coercion response: {:?}", result);
+        match result {
+            Err(EvaluationError::TypeMismatch {
+                found, position, ..
+            }) => {
+                assert_eq!(found, "string and number");
+                assert_eq!(position, Position::new(1, 5));
+            }
+            other => panic!("expected TypeMismatch, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn comparison_of_signed_zeros_and_nan() {
+        let resolver = EmptyEntityResolver;
+        let data = json!({"zero": 0.0, "negative_zero": -0.0});
+        let evaluate = |source: &str| {
+            BidParser::parse_expression(source)
+                .unwrap()
+                .evaluate(&DataContext::new(&data, &resolver))
+        };
+
+        assert_eq!(evaluate("negative_zero < zero").unwrap(), json!(false));
+        assert_eq!(evaluate("negative_zero >= zero").unwrap(), json!(true));
+        assert_eq!(evaluate("-0.0 <= 0.0").unwrap(), json!(true));
+        assert_eq!(evaluate("zero > -0.0").unwrap(), json!(false));
+
+        // serde_json stores NaN as null, which is refused rather than ordered
+        let nan = Value::from(f64::NAN);
+        let result = compare_values(&nan, &json!(1.0), |cmp| cmp < 0);
+        println!("comparison_of_signed_zeros_and_nan response: {:?}", result);
+        assert!(matches!(result, Err(EvaluationError::TypeMismatch { .. })));
+    }
+
+    #[test]
+    fn evaluation_errors_carry_operator_position() {
+        let expression = BidParser::parse_expression("a / 0").unwrap();
+        let data = json!({"a": 4});
+        let resolver = EmptyEntityResolver;
+
+        let result = expression.evaluate(&DataContext::new(&data, &resolver));
+        println!(
+            "evaluation_errors_carry_operator_position response: {:?}",
+            result
+        );
+        match result {
+            Err(EvaluationError::DivisionByZero { position }) => {
+                assert_eq!(position, Position::new(1, 3));
+            }
+            other => panic!("expected DivisionByZero, got {:?}", other),
+        }
+
+        let expression = BidParser::parse_expression("1 + \"a\"").unwrap();
+        match expression.evaluate(&DataContext::new(&data, &resolver)) {
+            Err(EvaluationError::TypeMismatch {
+                expected,
+                found,
+                position,
+            }) => {
+                assert_eq!(expected, "two numbers or two strings");
+                assert_eq!(found, "number and string");
+                assert_eq!(position, Position::new(1, 3));
+            }
+            other => panic!("expected TypeMismatch, got {:?}", other),
+        }
+
+        let expression = BidParser::parse_expression("missing.path > 1").unwrap();
+        match expression.evaluate(&DataContext::new(&data, &resolver)) {
+            Err(EvaluationError::UndefinedVariable { path }) => {
+                assert_eq!(path, vec!["missing", "path"]);
+            }
+            other => panic!("expected UndefinedVariable, got {:?}", other),
+        }
     }
 
     #[test]
@@ -1022,6 +1363,20 @@ mod tests {
         assert_eq!(result, Some(json!(95)));
     }
 
+    #[test]
+    fn dereference_entity_reference() {
+        let entity = "entity:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        let expression = BidParser::parse_expression("(*user_ref).active").unwrap();
+        let data = json!({"user_ref": entity});
+
+        let mut resolver = MockEntityResolver::new();
+        resolver.insert(entity, json!({"active": true}));
+
+        let result = expression.evaluate(&DataContext::new(&data, &resolver));
+        println!("dereference_entity_reference response: {:?}", result);
+        assert_eq!(result.unwrap(), json!(true));
+    }
+
     #[test]
     fn dereference_key_not_found() {
         let bid = BidParser::parse("ON *key_var BID 100").unwrap();
@@ -18,6 +18,7 @@
 //! - **Comparison**: `==`, `!=`, `<`, `<=`, `>`, `>=`
 //! - **Logical**: `&&`, `||`, `!`
 //! - **Grouping**: Parentheses for precedence
+//! - **Comments**: `#` outside a string literal comments out the rest of the line
 //!
 //! ## Examples
 //!
@@ -33,7 +34,7 @@ use std::fmt;
 
 mod evaluate;
 
-pub use evaluate::{EntityResolver, EvaluationError};
+pub use evaluate::{DataContext, EntityResolver, EvaluationContext, EvaluationError};
 
 /// Position information for error reporting
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -145,6 +146,66 @@ impl Expression {
             | Expression::MemberAccess { position, .. } => *position,
         }
     }
+
+    /// Compares two expressions for equality, ignoring source positions.
+    pub fn structurally_eq(&self, other: &Expression) -> bool {
+        self.normalize() == other.normalize()
+    }
+
+    /// Returns a copy of this expression with every position reset to [`Position::start`].
+    ///
+    /// Two expressions that differ only in where they were parsed from normalize to equal
+    /// values, so `BidParser::parse(&bid.to_string())` can be compared against `bid`.
+    pub fn normalize(&self) -> Expression {
+        let position = Position::start();
+        match self {
+            Expression::Variable { path, .. } => Expression::Variable {
+                path: path.clone(),
+                position,
+            },
+            Expression::StringLiteral { value, .. } => Expression::StringLiteral {
+                value: value.clone(),
+                position,
+            },
+            Expression::IntegerLiteral { value, .. } => Expression::IntegerLiteral {
+                value: *value,
+                position,
+            },
+            Expression::FloatLiteral { value, .. } => Expression::FloatLiteral {
+                value: *value,
+                position,
+            },
+            Expression::BooleanLiteral { value, .. } => Expression::BooleanLiteral {
+                value: *value,
+                position,
+            },
+            Expression::BinaryOperation {
+                left,
+                operator,
+                right,
+                ..
+            } => Expression::BinaryOperation {
+                left: Box::new(left.normalize()),
+                operator: operator.clone(),
+                right: Box::new(right.normalize()),
+                position,
+            },
+            Expression::UnaryOperation {
+                operator, operand, ..
+            } => Expression::UnaryOperation {
+                operator: operator.clone(),
+                operand: Box::new(operand.normalize()),
+                position,
+            },
+            Expression::MemberAccess {
+                object, property, ..
+            } => Expression::MemberAccess {
+                object: Box::new(object.normalize()),
+                property: property.clone(),
+                position,
+            },
+        }
+    }
 }
 
 /// Binary operators with precedence information
@@ -242,7 +303,9 @@ pub enum UnaryOperator {
     Negate,
     /// Logical NOT
     LogicalNot,
-    /// Pointer dereference
+    /// Pointer dereference: `*key` replaces a string or number key with the entity data
+    /// returned by the evaluation context's resolver, so `(*key).prop` reads a property
+    /// of the referenced entity
     Dereference,
 }
 
@@ -258,6 +321,8 @@ impl fmt::Display for UnaryOperator {
 }
 
 /// A complete bid expression with condition and value
+///
+/// To store a bid without its source positions, see [`bid_ast`].
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct Bid {
     /// The condition expression after ON
@@ -266,19 +331,232 @@ pub struct Bid {
     pub bid_value: Expression,
 }
 
+impl Bid {
+    /// Compares two bids for equality, ignoring source positions.
+    pub fn structurally_eq(&self, other: &Bid) -> bool {
+        self.on_condition.structurally_eq(&other.on_condition)
+            && self.bid_value.structurally_eq(&other.bid_value)
+    }
+
+    /// Returns a copy of this bid with every position reset to [`Position::start`].
+    ///
+    /// For any bid produced by [`BidParser::parse`], the [`Display`](fmt::Display) form
+    /// parses back to a bid that normalizes equal to this one.
+    pub fn normalize(&self) -> Bid {
+        Bid {
+            on_condition: self.on_condition.normalize(),
+            bid_value: self.bid_value.normalize(),
+        }
+    }
+}
+
 impl fmt::Display for Bid {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "ON {} BID {}", self.on_condition, self.bid_value)
     }
 }
 
+/// Serde functions that store a [`Bid`] as its AST without source positions.
+///
+/// The derived serde form of [`Bid`] keeps every [`Position`]. Use this module with
+/// `#[serde(with = "stigmergy::bid_ast")]` to store a bid's AST without them, so it
+/// reloads without reparsing its text. A reloaded bid has every position set to
+/// [`Position::start`] and equals the [`normalize`](Bid::normalize)d original.
+pub mod bid_ast {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{Bid, BinaryOperator, Expression, Position, UnaryOperator};
+
+    /// Mirrors [`Expression`] without the positions.
+    #[derive(Serialize, Deserialize)]
+    enum Ast {
+        Variable {
+            path: Vec<String>,
+        },
+        StringLiteral {
+            value: String,
+        },
+        IntegerLiteral {
+            value: i64,
+        },
+        FloatLiteral {
+            value: f64,
+        },
+        BooleanLiteral {
+            value: bool,
+        },
+        BinaryOperation {
+            left: Box<Ast>,
+            operator: BinaryOperator,
+            right: Box<Ast>,
+        },
+        UnaryOperation {
+            operator: UnaryOperator,
+            operand: Box<Ast>,
+        },
+        MemberAccess {
+            object: Box<Ast>,
+            property: String,
+        },
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct BidAst {
+        on_condition: Ast,
+        bid_value: Ast,
+    }
+
+    impl From<&Expression> for Ast {
+        fn from(expression: &Expression) -> Self {
+            match expression {
+                Expression::Variable { path, .. } => Ast::Variable { path: path.clone() },
+                Expression::StringLiteral { value, .. } => Ast::StringLiteral {
+                    value: value.clone(),
+                },
+                Expression::IntegerLiteral { value, .. } => Ast::IntegerLiteral { value: *value },
+                Expression::FloatLiteral { value, .. } => Ast::FloatLiteral { value: *value },
+                Expression::BooleanLiteral { value, .. } => Ast::BooleanLiteral { value: *value },
+                Expression::BinaryOperation {
+                    left,
+                    operator,
+                    right,
+                    ..
+                } => Ast::BinaryOperation {
+                    left: Box::new(left.as_ref().into()),
+                    operator: operator.clone(),
+                    right: Box::new(right.as_ref().into()),
+                },
+                Expression::UnaryOperation {
+                    operator, operand, ..
+                } => Ast::UnaryOperation {
+                    operator: operator.clone(),
+                    operand: Box::new(operand.as_ref().into()),
+                },
+                Expression::MemberAccess {
+                    object, property, ..
+                } => Ast::MemberAccess {
+                    object: Box::new(object.as_ref().into()),
+                    property: property.clone(),
+                },
+            }
+        }
+    }
+
+    impl From<Ast> for Expression {
+        fn from(ast: Ast) -> Self {
+            let position = Position::start();
+            match ast {
+                Ast::Variable { path } => Expression::Variable { path, position },
+                Ast::StringLiteral { value } => Expression::StringLiteral { value, position },
+                Ast::IntegerLiteral { value } => Expression::IntegerLiteral { value, position },
+                Ast::FloatLiteral { value } => Expression::FloatLiteral { value, position },
+                Ast::BooleanLiteral { value } => Expression::BooleanLiteral { value, position },
+                Ast::BinaryOperation {
+                    left,
+                    operator,
+                    right,
+                } => Expression::BinaryOperation {
+                    left: Box::new((*left).into()),
+                    operator,
+                    right: Box::new((*right).into()),
+                    position,
+                },
+                Ast::UnaryOperation { operator, operand } => Expression::UnaryOperation {
+                    operator,
+                    operand: Box::new((*operand).into()),
+                    position,
+                },
+                Ast::MemberAccess { object, property } => Expression::MemberAccess {
+                    object: Box::new((*object).into()),
+                    property,
+                    position,
+                },
+            }
+        }
+    }
+
+    /// Serializes `bid` as its AST, omitting source positions.
+    pub fn serialize<S>(bid: &Bid, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        BidAst {
+            on_condition: (&bid.on_condition).into(),
+            bid_value: (&bid.bid_value).into(),
+        }
+        .serialize(serializer)
+    }
+
+    /// Deserializes a bid AST written by [`serialize`], with every position at the start.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Bid, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let ast = BidAst::deserialize(deserializer)?;
+        Ok(Bid {
+            on_condition: ast.on_condition.into(),
+            bid_value: ast.bid_value.into(),
+        })
+    }
+}
+
+/// Binding strength of unary operators, tighter than any binary operator.
+const UNARY_PRECEDENCE: u8 = 8;
+
+/// Binding strength of literals, variables and member access.
+const PRIMARY_PRECEDENCE: u8 = 9;
+
+impl Expression {
+    /// How tightly this expression binds when printed without parentheses.
+    fn display_precedence(&self) -> u8 {
+        match self {
+            Expression::BinaryOperation { operator, .. } => operator.precedence(),
+            Expression::UnaryOperation { .. } => UNARY_PRECEDENCE,
+            _ => PRIMARY_PRECEDENCE,
+        }
+    }
+
+    /// Writes this expression, parenthesized only if it binds looser than `min_precedence`.
+    ///
+    /// Emitting only the parentheses precedence requires keeps long `&&`/`+` chains flat, so
+    /// their printed form stays within the parser's nesting limit and reloads cleanly.
+    fn fmt_operand(&self, f: &mut fmt::Formatter<'_>, min_precedence: u8) -> fmt::Result {
+        if self.display_precedence() < min_precedence {
+            write!(f, "({})", self)
+        } else {
+            write!(f, "{}", self)
+        }
+    }
+}
+
 impl fmt::Display for Expression {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Expression::Variable { path, .. } => write!(f, "{}", path.join(".")),
-            Expression::StringLiteral { value, .. } => write!(f, "\"{}\"", value),
+            Expression::StringLiteral { value, .. } => {
+                write!(f, "\"")?;
+                for ch in value.chars() {
+                    match ch {
+                        '"' => write!(f, "\\\"")?,
+                        '\\' => write!(f, "\\\\")?,
+                        '\n' => write!(f, "\\n")?,
+                        '\t' => write!(f, "\\t")?,
+                        '\r' => write!(f, "\\r")?,
+                        _ => write!(f, "{}", ch)?,
+                    }
+                }
+                write!(f, "\"")
+            }
             Expression::IntegerLiteral { value, .. } => write!(f, "{}", value),
-            Expression::FloatLiteral { value, .. } => write!(f, "{}", value),
+            Expression::FloatLiteral { value, .. } => {
+                // Keep the decimal point so the literal lexes back as a float.
+                let text = value.to_string();
+                if value.is_finite() && !text.contains('.') {
+                    write!(f, "{}.0", text)
+                } else {
+                    write!(f, "{}", text)
+                }
+            }
             Expression::BooleanLiteral { value, .. } => write!(f, "{}", value),
             Expression::BinaryOperation {
                 left,
@@ -286,17 +564,30 @@ impl fmt::Display for Expression {
                 right,
                 ..
             } => {
-                write!(f, "({} {} {})", left, operator, right)
+                let precedence = operator.precedence();
+                let (left_min, right_min) = if operator.is_right_associative() {
+                    (precedence + 1, precedence)
+                } else {
+                    (precedence, precedence + 1)
+                };
+                left.fmt_operand(f, left_min)?;
+                write!(f, " {} ", operator)?;
+                right.fmt_operand(f, right_min)
             }
             Expression::UnaryOperation {
                 operator, operand, ..
             } => {
-                write!(f, "{}({})", operator, operand)
+                write!(f, "{}", operator)?;
+                operand.fmt_operand(f, UNARY_PRECEDENCE)
             }
             Expression::MemberAccess {
                 object, property, ..
             } => {
-                write!(f, "({}).{}", object, property)
+                if matches!(**object, Expression::MemberAccess { .. }) {
+                    write!(f, "{}.{}", object, property)
+                } else {
+                    write!(f, "({}).{}", object, property)
+                }
             }
         }
     }
@@ -405,6 +696,11 @@ pub enum BidParseError {
         /// Where the error occurred
         position: Position,
     },
+    /// Expression nesting exceeds the parser's maximum depth
+    NestingTooDeep {
+        /// Where the nesting limit was exceeded
+        position: Position,
+    },
 }
 
 impl fmt::Display for BidParseError {
@@ -452,6 +748,26 @@ impl fmt::Display for BidParseError {
                     character, position
                 )
             }
+            BidParseError::NestingTooDeep { position } => {
+                write!(f, "Expression nested too deeply at {}", position)
+            }
+        }
+    }
+}
+
+impl BidParseError {
+    /// Get the position at which the error was detected
+    pub fn position(&self) -> Position {
+        match self {
+            BidParseError::UnexpectedToken { position, .. }
+            | BidParseError::InvalidNumber { position, .. }
+            | BidParseError::UnterminatedString { position }
+            | BidParseError::InvalidCharacter { position, .. }
+            | BidParseError::MissingOnKeyword { position }
+            | BidParseError::MissingBidKeyword { position }
+            | BidParseError::EmptyExpression { position }
+            | BidParseError::InvalidEscapeSequence { position, .. }
+            | BidParseError::NestingTooDeep { position } => *position,
         }
     }
 }
@@ -527,6 +843,13 @@ impl Handle<UserError> for BidParseError {
                 format!("Invalid escape sequence '\\{}' at {}", character, position),
                 Some("Valid escape sequences are: \\n \\t \\r \\\\ \\\"".to_string()),
             ),
+            BidParseError::NestingTooDeep { position } => (
+                format!("Expression nested too deeply at {}", position),
+                Some(format!(
+                    "Expressions may nest at most {} levels of parentheses and operators",
+                    BidParser::DEFAULT_MAX_DEPTH
+                )),
+            ),
         };
 
         Some(UserError {
@@ -540,12 +863,60 @@ impl Handle<UserError> for BidParseError {
 pub struct BidParser;
 
 impl BidParser {
+    /// The nesting depth allowed by [`BidParser::parse`] and [`BidParser::parse_expression`]
+    ///
+    /// Each parenthesised sub-expression, prefix operator and right-hand side of `^`
+    /// counts as one level.  Deeper inputs fail with [`BidParseError::NestingTooDeep`]
+    /// rather than overflowing the stack.
+    pub const DEFAULT_MAX_DEPTH: usize = 64;
+
     /// Parse a bid expression from a string
     pub fn parse(input: &str) -> Result<Bid, BidParseError> {
+        Self::parse_with_max_depth(input, Self::DEFAULT_MAX_DEPTH)
+    }
+
+    /// Parse a bid expression from a string, allowing at most `max_depth` levels of nesting
+    pub fn parse_with_max_depth(input: &str, max_depth: usize) -> Result<Bid, BidParseError> {
         let mut lexer = Lexer::new(input);
-        let mut parser = Parser::new(&mut lexer)?;
+        let mut parser = Parser::new(&mut lexer, max_depth)?;
         parser.parse_bid()
     }
+
+    /// Parse a newline-separated list of bids
+    ///
+    /// Each line may start with a `-` bullet.  Blank lines and lines starting with `#`
+    /// are skipped.  On failure, returns the zero-based index of the offending line
+    /// within `input` along with the error from parsing it.
+    pub fn parse_many(input: &str) -> Result<Vec<Bid>, (usize, BidParseError)> {
+        let mut bids = Vec::new();
+        for (index, line) in input.lines().enumerate() {
+            let line = line.trim();
+            if line.starts_with('#') {
+                continue;
+            }
+            let line = line.strip_prefix('-').unwrap_or(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            bids.push(Self::parse(line).map_err(|err| (index, err))?);
+        }
+        Ok(bids)
+    }
+
+    /// Parse a standalone expression, such as an invariant assertion, from a string
+    pub fn parse_expression(input: &str) -> Result<Expression, BidParseError> {
+        Self::parse_expression_with_max_depth(input, Self::DEFAULT_MAX_DEPTH)
+    }
+
+    /// Parse a standalone expression, allowing at most `max_depth` levels of nesting
+    pub fn parse_expression_with_max_depth(
+        input: &str,
+        max_depth: usize,
+    ) -> Result<Expression, BidParseError> {
+        let mut lexer = Lexer::new(input);
+        let mut parser = Parser::new(&mut lexer, max_depth)?;
+        parser.parse_standalone_expression()
+    }
 }
 
 /// Lexer for tokenizing input
@@ -593,18 +964,23 @@ impl Lexer {
         }
     }
 
-    fn skip_whitespace(&mut self) {
+    /// Skips whitespace and `#` comments, which run to the end of the line.
+    fn skip_whitespace_and_comments(&mut self) {
+        let mut in_comment = false;
         while let Some(ch) = self.current_char() {
-            if ch.is_whitespace() {
-                self.advance();
-            } else {
+            if ch == '\n' {
+                in_comment = false;
+            } else if ch == '#' {
+                in_comment = true;
+            } else if !in_comment && !ch.is_whitespace() {
                 break;
             }
+            self.advance();
         }
     }
 
     fn next_token(&mut self) -> Result<Token, BidParseError> {
-        self.skip_whitespace();
+        self.skip_whitespace_and_comments();
 
         let position = self.current_position();
 
@@ -922,14 +1298,20 @@ impl Lexer {
 struct Parser<'a> {
     lexer: &'a mut Lexer,
     current_token: Token,
+    /// Current nesting depth
+    depth: usize,
+    /// Maximum nesting depth before parsing fails
+    max_depth: usize,
 }
 
 impl<'a> Parser<'a> {
-    fn new(lexer: &'a mut Lexer) -> Result<Self, BidParseError> {
+    fn new(lexer: &'a mut Lexer, max_depth: usize) -> Result<Self, BidParseError> {
         let current_token = lexer.next_token()?;
         Ok(Self {
             lexer,
             current_token,
+            depth: 0,
+            max_depth,
         })
     }
 
@@ -938,6 +1320,21 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
+    /// Runs `parse` one nesting level deeper, failing if that exceeds `max_depth`
+    fn nested<T>(
+        &mut self,
+        position: Position,
+        parse: impl FnOnce(&mut Self) -> Result<T, BidParseError>,
+    ) -> Result<T, BidParseError> {
+        if self.depth >= self.max_depth {
+            return Err(BidParseError::NestingTooDeep { position });
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
     fn parse_bid(&mut self) -> Result<Bid, BidParseError> {
         // Expect ON keyword
         if !matches!(self.current_token.token_type, TokenType::On) {
@@ -976,6 +1373,21 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_standalone_expression(&mut self) -> Result<Expression, BidParseError> {
+        let expression = self.parse_expression()?;
+
+        // Should be at end of input
+        if !matches!(self.current_token.token_type, TokenType::EndOfInput) {
+            return Err(BidParseError::UnexpectedToken {
+                found: format!("{:?}", self.current_token.token_type),
+                expected: "end of input".to_string(),
+                position: self.current_token.position,
+            });
+        }
+
+        Ok(expression)
+    }
+
     fn parse_expression(&mut self) -> Result<Expression, BidParseError> {
         self.parse_logical_or()
     }
@@ -1101,7 +1513,7 @@ impl<'a> Parser<'a> {
             let position = self.current_token.position;
             self.advance()?;
             // Right-associative
-            let right = self.parse_power()?;
+            let right = self.nested(position, |parser| parser.parse_power())?;
             Ok(Expression::BinaryOperation {
                 left: Box::new(left),
                 operator: BinaryOperator::Power,
@@ -1118,7 +1530,7 @@ impl<'a> Parser<'a> {
             TokenType::Minus => {
                 let position = self.current_token.position;
                 self.advance()?;
-                let operand = self.parse_unary()?;
+                let operand = self.nested(position, |parser| parser.parse_unary())?;
                 Ok(Expression::UnaryOperation {
                     operator: UnaryOperator::Negate,
                     operand: Box::new(operand),
@@ -1128,7 +1540,7 @@ impl<'a> Parser<'a> {
             TokenType::LogicalNot => {
                 let position = self.current_token.position;
                 self.advance()?;
-                let operand = self.parse_unary()?;
+                let operand = self.nested(position, |parser| parser.parse_unary())?;
                 Ok(Expression::UnaryOperation {
                     operator: UnaryOperator::LogicalNot,
                     operand: Box::new(operand),
@@ -1138,7 +1550,7 @@ impl<'a> Parser<'a> {
             TokenType::Multiply => {
                 let position = self.current_token.position;
                 self.advance()?;
-                let operand = self.parse_unary()?;
+                let operand = self.nested(position, |parser| parser.parse_unary())?;
                 Ok(Expression::UnaryOperation {
                     operator: UnaryOperator::Dereference,
                     operand: Box::new(operand),
@@ -1198,8 +1610,9 @@ impl<'a> Parser<'a> {
                 Ok(Expression::BooleanLiteral { value, position })
             }
             TokenType::LeftParen => {
+                let position = self.current_token.position;
                 self.advance()?;
-                let mut expr = self.parse_expression()?;
+                let mut expr = self.nested(position, |parser| parser.parse_expression())?;
                 if matches!(self.current_token.token_type, TokenType::RightParen) {
                     self.advance()?;
 
@@ -1260,6 +1673,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_standalone_expression() {
+        let result = BidParser::parse_expression("score >= 0").unwrap();
+        assert!(matches!(
+            result,
+            Expression::BinaryOperation {
+                operator: BinaryOperator::GreaterThanOrEqual,
+                ..
+            }
+        ));
+
+        assert!(BidParser::parse_expression("score >= 0 BID 1").is_err());
+        assert!(BidParser::parse_expression("").is_err());
+    }
+
     #[test]
     fn parse_arithmetic_expression() {
         let result = BidParser::parse("ON price > 100 BID price * 0.9").unwrap();
@@ -1583,7 +2011,7 @@ mod tests {
         };
 
         let display = format!("{}", expr);
-        assert_eq!(display, "(user.score * 1.5)");
+        assert_eq!(display, "user.score * 1.5");
     }
 
     #[test]
@@ -1603,6 +2031,159 @@ mod tests {
         assert_eq!(display, "ON true BID 100");
     }
 
+    #[test]
+    fn display_escapes_strings_and_keeps_float_points() {
+        let bid = BidParser::parse(r#"ON name == "say \"hi\"\n" BID 2.0 * 1.5"#).unwrap();
+        let display = bid.to_string();
+        println!(
+            "display_escapes_strings_and_keeps_float_points response: {}",
+            display
+        );
+        assert_eq!(display, r#"ON name == "say \"hi\"\n" BID 2.0 * 1.5"#);
+        assert_eq!(
+            BidParser::parse(&display).unwrap().normalize(),
+            bid.normalize()
+        );
+    }
+
+    #[test]
+    fn bid_serde_round_trips_ast() {
+        let bid = BidParser::parse(
+            r#"ON (*user.profile).active && name ~= "^a.*" BID -score * 1.5 ^ 2 % 7"#,
+        )
+        .unwrap();
+        #[derive(serde::Serialize, serde::Deserialize)]
+        struct Stored {
+            #[serde(with = "bid_ast")]
+            bid: Bid,
+        }
+
+        let json = serde_json::to_value(Stored { bid: bid.clone() }).unwrap();
+        println!("bid_serde_round_trips_ast response: {}", json);
+        assert!(!json.to_string().contains("position"));
+
+        let restored: Stored = serde_json::from_value(json).unwrap();
+        assert_eq!(restored.bid, bid.normalize());
+        assert!(restored.bid.structurally_eq(&bid));
+
+        // The derived form keeps positions, so it round-trips exactly
+        let derived = serde_json::to_value(&bid).unwrap();
+        assert!(derived.to_string().contains("position"));
+        assert_eq!(serde_json::from_value::<Bid>(derived).unwrap(), bid);
+    }
+
+    #[test]
+    fn comments_are_skipped() {
+        let bid = BidParser::parse("ON true BID 1 # always bid 1").unwrap();
+        println!("comments_are_skipped response: {:?}", bid);
+        assert!(bid.structurally_eq(&BidParser::parse("ON true BID 1").unwrap()));
+
+        let bid =
+            BidParser::parse("# leading\nON name == \"#1\" # hash in a string\nBID 2").unwrap();
+        assert!(matches!(
+            bid.on_condition,
+            Expression::BinaryOperation { ref right, .. }
+                if matches!(**right, Expression::StringLiteral { ref value, .. } if value == "#1")
+        ));
+        assert!(matches!(
+            bid.bid_value,
+            Expression::IntegerLiteral { value: 2, .. }
+        ));
+    }
+
+    #[test]
+    fn structurally_eq_ignores_positions() {
+        let bid = BidParser::parse("ON user.active && score > 1 BID score * 2").unwrap();
+        let shifted =
+            BidParser::parse("ON\n    user.active && score > 1\nBID   score * 2").unwrap();
+        println!("structurally_eq_ignores_positions response: {:?}", shifted);
+        assert_ne!(bid, shifted);
+        assert!(bid.structurally_eq(&shifted));
+        assert!(bid.on_condition.structurally_eq(&shifted.on_condition));
+
+        let different = BidParser::parse("ON user.active && score > 1 BID score * 3").unwrap();
+        assert!(!bid.structurally_eq(&different));
+    }
+
+    fn arbitrary_expression() -> impl proptest::strategy::Strategy<Value = Expression> {
+        use proptest::prelude::*;
+
+        let position = Position::start();
+        let identifier = "[a-z_][a-z0-9_]{0,6}".prop_filter("keyword", |identifier| {
+            identifier != "true" && identifier != "false"
+        });
+        let leaf = prop_oneof![
+            proptest::collection::vec(identifier.clone(), 1..4)
+                .prop_map(move |path| Expression::Variable { path, position }),
+            "[ -~\n\t\r]{0,8}".prop_map(move |value| Expression::StringLiteral { value, position }),
+            (0..=i64::MAX).prop_map(move |value| Expression::IntegerLiteral { value, position }),
+            (0.0..1e20f64).prop_map(move |value| Expression::FloatLiteral { value, position }),
+            any::<bool>().prop_map(move |value| Expression::BooleanLiteral { value, position }),
+        ];
+        let binary_operator = prop_oneof![
+            Just(BinaryOperator::Add),
+            Just(BinaryOperator::Subtract),
+            Just(BinaryOperator::Multiply),
+            Just(BinaryOperator::Divide),
+            Just(BinaryOperator::Modulo),
+            Just(BinaryOperator::Power),
+            Just(BinaryOperator::Equal),
+            Just(BinaryOperator::NotEqual),
+            Just(BinaryOperator::LessThan),
+            Just(BinaryOperator::LessThanOrEqual),
+            Just(BinaryOperator::GreaterThan),
+            Just(BinaryOperator::GreaterThanOrEqual),
+            Just(BinaryOperator::LogicalAnd),
+            Just(BinaryOperator::LogicalOr),
+            Just(BinaryOperator::RegexMatch),
+        ];
+        let unary_operator = prop_oneof![
+            Just(UnaryOperator::Negate),
+            Just(UnaryOperator::LogicalNot),
+            Just(UnaryOperator::Dereference),
+        ];
+        leaf.prop_recursive(4, 32, 2, move |inner| {
+            prop_oneof![
+                (inner.clone(), binary_operator.clone(), inner.clone()).prop_map(
+                    move |(left, operator, right)| Expression::BinaryOperation {
+                        left: Box::new(left),
+                        operator,
+                        right: Box::new(right),
+                        position,
+                    }
+                ),
+                (unary_operator.clone(), inner.clone()).prop_map(move |(operator, operand)| {
+                    Expression::UnaryOperation {
+                        operator,
+                        operand: Box::new(operand),
+                        position,
+                    }
+                }),
+                (inner, identifier.clone()).prop_map(move |(object, property)| {
+                    Expression::MemberAccess {
+                        object: Box::new(object),
+                        property,
+                        position,
+                    }
+                }),
+            ]
+        })
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn display_round_trips_through_parse(
+            on_condition in arbitrary_expression(),
+            bid_value in arbitrary_expression(),
+        ) {
+            let bid = Bid { on_condition, bid_value };
+            let display = bid.to_string();
+            let parsed = BidParser::parse(&display).unwrap();
+            proptest::prop_assert_eq!(parsed.normalize(), bid.normalize());
+            proptest::prop_assert_eq!(parsed.to_string(), display);
+        }
+    }
+
     #[test]
     fn position_tracking() {
         let result = BidParser::parse("ON\n  user.active\nBID\n  user.score");
@@ -2043,6 +2624,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_many_reports_failing_line() {
+        let bids =
+            BidParser::parse_many("- ON a BID 1\n\n  - ON b BID b - 1\n# comment\nON c BID 3\n")
+                .unwrap();
+        assert_eq!(bids.len(), 3);
+        assert_eq!(bids[1], BidParser::parse("ON b BID b - 1").unwrap());
+
+        let result = BidParser::parse_many("- ON a BID 1\n- ON b 2\n- ON c BID 3");
+        println!("parse_many_reports_failing_line response: {:?}", result);
+        assert!(matches!(
+            result,
+            Err((1, BidParseError::MissingBidKeyword { .. }))
+        ));
+    }
+
+    #[test]
+    fn excessive_nesting_is_rejected() {
+        let depth = 100_000;
+        let input = format!("ON true BID {}x{}", "(".repeat(depth), ")".repeat(depth));
+        let result = BidParser::parse(&input);
+        println!("excessive_nesting_is_rejected response: {:?}", result);
+        assert!(matches!(
+            result,
+            Err(BidParseError::NestingTooDeep { position }) if position == Position::new(1, 77)
+        ));
+
+        let negations = format!("{}x", "-".repeat(depth));
+        assert!(matches!(
+            BidParser::parse_expression(&negations),
+            Err(BidParseError::NestingTooDeep { .. })
+        ));
+        let powers = vec!["x"; depth].join(" ^ ");
+        assert!(matches!(
+            BidParser::parse_expression(&powers),
+            Err(BidParseError::NestingTooDeep { .. })
+        ));
+
+        assert!(BidParser::parse_expression_with_max_depth("((x))", 2).is_ok());
+        assert!(matches!(
+            BidParser::parse_expression_with_max_depth("(((x)))", 2),
+            Err(BidParseError::NestingTooDeep { position }) if position == Position::new(1, 3)
+        ));
+    }
+
     // Additional comprehensive edge case tests
 
     #[test]
@@ -2601,8 +3227,20 @@ mod tests {
     fn dereference_operator_display() {
         let result = BidParser::parse("ON *key BID 42").unwrap();
         let display = format!("{}", result.on_condition);
-        assert!(display.contains("*("));
-        assert!(display.contains("key"));
+        assert_eq!(display, "*key");
+        assert_eq!(
+            BidParser::parse_expression(&display).unwrap().normalize(),
+            result.on_condition.normalize()
+        );
+
+        // The operand keeps its parentheses only when it binds looser than the operator
+        let result = BidParser::parse("ON *(a + b) BID 42").unwrap();
+        let display = format!("{}", result.on_condition);
+        assert_eq!(display, "*(a + b)");
+        assert_eq!(
+            BidParser::parse_expression(&display).unwrap().normalize(),
+            result.on_condition.normalize()
+        );
     }
 
     #[test]
@@ -4,9 +4,10 @@ use arrrg_derive::CommandLine;
 use stigmergy::{
     cli_utils::{self, OutputFormat},
     commands::{
-        handle_apply_command, handle_component_command, handle_componentdefinition_command,
-        handle_config_command, handle_edge_command, handle_entity_command,
-        handle_invariant_command, handle_system_command,
+        handle_apply_command, handle_bid_command, handle_component_command,
+        handle_componentdefinition_command, handle_config_command, handle_edge_command,
+        handle_entity_command, handle_invariant_command, handle_migrate_command,
+        handle_system_command,
     },
     http_utils,
 };
@@ -17,7 +18,7 @@ struct Options {
     base_url: String,
     #[arrrg(
         optional,
-        "Output format for get/list commands: json or yaml (default: json)"
+        "Output format for get/list commands: json, yaml, table or plain (default: json)"
     )]
     output: OutputFormat,
 }
@@ -26,10 +27,13 @@ const USAGE: &str = r#"Usage: stigctl [options] <command> [args...]
 
 Options:
   --base-url <url>     Base URL of the Stigmergy API server (default: http://localhost:8080)
-  --output <format>    Output format for get/list commands: json or yaml (default: json)
+  --output <format>    Output format for get/list commands: json, yaml, table or plain
+                       (default: json)
 
 Commands:
-  apply <directory>                            Apply configuration from directory
+  apply <directory|request.json|->             Apply configuration from directory, or a
+                                               JSON ApplyRequest from a file or stdin
+  bid eval --expr <bid> --context <ctx.json>   Evaluate a bid against a JSON context
   config get                                   Get the current configuration
   config set <file.json|file.yaml>            Set configuration from file
   entity create                                Create a new entity
@@ -37,6 +41,7 @@ Commands:
   entity delete <entity-id>                    Delete an entity
   system create <config-json>                 Create a system from config
   system create-from-md <file.md>             Create a system from markdown file
+  system validate <file.md>                   Check a system markdown file locally
   system list                                  List all systems
   system get <system-id>                       Get a system by ID
   system update <system-id> <config-json>     Update a system
@@ -46,6 +51,8 @@ Commands:
   componentdefinition get <id>                 Get a component definition by ID
   componentdefinition update <id> <schema>     Update a component definition
   componentdefinition delete <id>              Delete a component definition
+  componentdefinition export [bundle.json]     Export all component definitions
+  componentdefinition import <bundle.json>     Import component definitions from a bundle
   component create <entity> <component> <data> Create a component instance for an entity
   component list <entity-id>                   List all component instances for an entity
   component get <entity-id> <comp-id>          Get a component instance by ID for an entity
@@ -62,7 +69,8 @@ Commands:
   invariant list                               List all invariants
   invariant get <invariant-id>                 Get an invariant by ID
   invariant update <invariant-id> <expression> Update an invariant
-  invariant delete <invariant-id>              Delete an invariant"#;
+  invariant delete <invariant-id>              Delete an invariant
+  migrate [--database-url <url>]               Apply database migrations (default: $DATABASE_URL)"#;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -84,6 +92,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "apply" => {
             handle_apply_command(&free[1..], &client).await;
         }
+        "bid" => {
+            handle_bid_command(&free[1..], &client, options.output).await;
+        }
         "config" => {
             handle_config_command(&free[1..], &client, options.output).await;
         }
@@ -105,9 +116,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         "edge" => {
             handle_edge_command(&free[1..], &client, options.output).await;
         }
+        "migrate" => {
+            handle_migrate_command(&free[1..]).await;
+        }
         _ => {
             cli_utils::exit_with_error(&format!(
-                "Unknown command '{}'. Available commands: apply, config, entity, system, componentdefinition, component, invariant, edge",
+                "Unknown command '{}'. Available commands: apply, bid, config, entity, system, componentdefinition, component, invariant, edge, migrate",
                 free[0]
             ));
         }
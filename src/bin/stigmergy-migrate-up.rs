@@ -43,7 +43,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Running migrations...");
 
     // Run migrations embedded at compile time
-    sqlx::migrate!("./migrations").run(&pool).await?;
+    stigmergy::sql::run_migrations(&pool).await?;
 
     println!("Migrations completed successfully!");
 
@@ -1,13 +1,17 @@
 use arrrg::CommandLine;
 use arrrg_derive::CommandLine;
 use axum::Router;
+use std::time::Duration;
 use tokio::net::TcpListener;
 use tokio::signal;
 
+use stigmergy::sql::{self, DbConfig};
 use stigmergy::{
-    create_apply_router, create_component_definition_router, create_component_instance_router,
-    create_config_router, create_edge_router, create_entity_router, create_invariant_router,
-    create_system_router, load_latest_config,
+    ComponentNamePolicy, create_apply_router_with_policy,
+    create_component_definition_router_with_policy, create_component_instance_router_with_policy,
+    create_config_router, create_edge_router, create_entity_router_with_policy,
+    create_health_router, create_invariant_router, create_system_router, load_latest_config,
+    log_requests,
 };
 
 #[derive(CommandLine, Default, PartialEq, Eq)]
@@ -18,6 +22,16 @@ struct Args {
     host: Option<String>,
     #[arrrg(optional, "Port to bind the HTTP server")]
     port: Option<u16>,
+    #[arrrg(optional, "Maximum number of database connections")]
+    max_connections: Option<u32>,
+    #[arrrg(optional, "Minimum number of idle database connections")]
+    min_connections: Option<u32>,
+    #[arrrg(optional, "Seconds to wait for a database connection")]
+    acquire_timeout: Option<u64>,
+    #[arrrg(optional, "Seconds before an idle database connection is closed")]
+    idle_timeout: Option<u64>,
+    #[arrrg(flag, "Treat component definition names case-insensitively")]
+    case_insensitive_components: bool,
     #[arrrg(flag, "Enable verbose logging")]
     verbose: bool,
 }
@@ -28,10 +42,16 @@ USAGE:
     stigmergyd [OPTIONS]
 
 OPTIONS:
-    --database-url <URL> PostgreSQL database URL [env: DATABASE_URL]
-    --host <HOST>        Host to bind the HTTP server [default: 127.0.0.1]
-    --port <PORT>        Port to bind the HTTP server [default: 8080]
-    --verbose            Enable verbose logging
+    --database-url <URL>     PostgreSQL database URL [env: DATABASE_URL]
+    --host <HOST>            Host to bind the HTTP server [default: 127.0.0.1]
+    --port <PORT>            Port to bind the HTTP server [default: 8080]
+    --max-connections <N>    Maximum database connections [default: 10]
+    --min-connections <N>    Minimum idle database connections [default: 0]
+    --acquire-timeout <SECS> Seconds to wait for a database connection [default: 30]
+    --idle-timeout <SECS>    Seconds before an idle connection is closed [default: 600]
+    --case-insensitive-components
+                             Treat component definition names case-insensitively
+    --verbose                Enable verbose logging
 
 DESCRIPTION:
     Runs the Stigmergy daemon with entity and component management
@@ -99,22 +119,25 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     if config.verbose {
         println!("Stigmergy daemon starting with configuration:");
-        println!("  Database URL: {}", config.database_url);
+        println!("  Database URL: {}", config.db.database_url);
         println!("  Bind address: {}:{}", config.host, config.port);
     }
 
     // Connect to PostgreSQL
-    let pool = sqlx::PgPool::connect(&config.database_url)
+    let pool = sql::connect(&config.db)
         .await
         .map_err(|e| format!("Failed to connect to database: {}", e))?;
 
     if config.verbose {
-        println!("Connected to PostgreSQL database");
+        println!(
+            "Connected to PostgreSQL database (pool: {} open, {} max)",
+            pool.size(),
+            pool.options().get_max_connections()
+        );
     }
 
     // Run migrations
-    sqlx::migrate!("./migrations")
-        .run(&pool)
+    stigmergy::sql::run_migrations(&pool)
         .await
         .map_err(|e| format!("Failed to run migrations: {}", e))?;
 
@@ -135,14 +158,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Create routers
-    let entity_router = create_entity_router(pool.clone());
-    let component_definition_router = create_component_definition_router(pool.clone());
-    let component_router = create_component_instance_router(pool.clone());
+    let entity_router = create_entity_router_with_policy(pool.clone(), config.component_names);
+    let component_definition_router =
+        create_component_definition_router_with_policy(pool.clone(), config.component_names);
+    let component_router =
+        create_component_instance_router_with_policy(pool.clone(), config.component_names);
     let system_router = create_system_router(pool.clone());
     let invariant_router = create_invariant_router(pool.clone());
-    let apply_router = create_apply_router(pool.clone());
+    let apply_router = create_apply_router_with_policy(pool.clone(), config.component_names);
     let config_router = create_config_router(pool.clone());
     let edge_router = create_edge_router(pool.clone());
+    let health_router = create_health_router(pool.clone());
 
     let app = Router::new()
         .nest("/api/v1", entity_router)
@@ -152,7 +178,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .nest("/api/v1", invariant_router)
         .nest("/api/v1", apply_router)
         .nest("/api/v1", config_router)
-        .nest("/api/v1", edge_router);
+        .nest("/api/v1", edge_router)
+        .merge(health_router)
+        .layer(axum::middleware::from_fn(log_requests));
 
     // Bind to address
     let addr = format!("{}:{}", config.host, config.port);
@@ -162,7 +190,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     println!("🚀 Stigmergy daemon started successfully!");
     println!("📡 Server listening on: http://{}", addr);
-    println!("💾 Database: {}", config.database_url);
+    println!("💾 Database: {}", config.db.database_url);
     println!("🔄 Ready to accept API requests");
 
     if config.verbose {
@@ -195,7 +223,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
             if config.verbose {
                 println!("📊 Final statistics:");
-                println!("   Database: {}", config.database_url);
+                println!("   Database: {}", config.db.database_url);
                 println!("   Shutdown completed successfully");
             }
 
@@ -207,9 +235,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 }
 
 struct ServerConfig {
-    database_url: String,
+    db: DbConfig,
     host: String,
     port: u16,
+    component_names: ComponentNamePolicy,
     verbose: bool,
 }
 
@@ -220,10 +249,29 @@ impl ServerConfig {
             .or_else(|| std::env::var("DATABASE_URL").ok())
             .unwrap_or_else(|| "postgres://localhost/stigmergy".to_string());
 
+        let mut db = DbConfig::new(database_url);
+        if let Some(max_connections) = args.max_connections {
+            db.max_connections = max_connections;
+        }
+        if let Some(min_connections) = args.min_connections {
+            db.min_connections = min_connections;
+        }
+        if let Some(acquire_timeout) = args.acquire_timeout {
+            db.acquire_timeout = Duration::from_secs(acquire_timeout);
+        }
+        if let Some(idle_timeout) = args.idle_timeout {
+            db.idle_timeout = Some(Duration::from_secs(idle_timeout));
+        }
+
         Self {
-            database_url,
+            db,
             host: args.host.unwrap_or_else(|| "127.0.0.1".to_string()),
             port: args.port.unwrap_or(8080),
+            component_names: if args.case_insensitive_components {
+                ComponentNamePolicy::CaseInsensitive
+            } else {
+                ComponentNamePolicy::CaseSensitive
+            },
             verbose: args.verbose,
         }
     }
@@ -279,4 +327,8 @@ fn print_api_endpoints() {
     println!("  Batch Operations:");
     println!("    POST   /api/v1/apply           Apply batch of operations transactionally");
     println!();
+    println!("  Probes:");
+    println!("    GET    /health                 Liveness check");
+    println!("    GET    /ready                  Readiness check (queries the database)");
+    println!();
 }
@@ -6,7 +6,7 @@
 //! ## Key Features
 //!
 //! - **Error Handling**: Standardized error reporting with optional usage information
-//! - **JSON Output**: Formatted JSON serialization for CLI responses
+//! - **Formatted Output**: JSON, YAML, aligned tables or plain tab-separated lines
 //! - **Program Termination**: Clean exit functions with appropriate error codes
 //!
 //! ## Usage Examples
@@ -36,6 +36,11 @@ pub enum OutputFormat {
     Json,
     /// YAML format.
     Yaml,
+    /// Aligned columns with a header row, for people reading lists.
+    Table,
+    /// Plain text; records without a dedicated rendering print one per line with
+    /// tab-separated fields.
+    Plain,
 }
 
 impl fmt::Display for OutputFormat {
@@ -43,6 +48,8 @@ impl fmt::Display for OutputFormat {
         match self {
             OutputFormat::Json => f.write_str("json"),
             OutputFormat::Yaml => f.write_str("yaml"),
+            OutputFormat::Table => f.write_str("table"),
+            OutputFormat::Plain => f.write_str("plain"),
         }
     }
 }
@@ -54,8 +61,10 @@ impl FromStr for OutputFormat {
         match s.to_lowercase().as_str() {
             "json" => Ok(OutputFormat::Json),
             "yaml" | "yml" => Ok(OutputFormat::Yaml),
+            "table" => Ok(OutputFormat::Table),
+            "plain" => Ok(OutputFormat::Plain),
             _ => Err(format!(
-                "Unknown output format: {}. Use 'json' or 'yaml'",
+                "Unknown output format: {}. Use 'json', 'yaml', 'table' or 'plain'",
                 s
             )),
         }
@@ -129,7 +138,7 @@ pub fn print_success(message: &str) {
 ///
 /// # Arguments
 /// * `value` - Any serializable value to output
-/// * `format` - Output format (JSON or YAML)
+/// * `format` - Output format (JSON, YAML, table or plain)
 ///
 /// # Returns
 /// * `Ok(())` - Output was successfully printed
@@ -162,10 +171,117 @@ where
                     .map_err(|e| format!("Failed to serialize YAML: {}", e))?
             );
         }
+        OutputFormat::Table => {
+            let value = serde_json::to_value(value)
+                .map_err(|e| format!("Failed to serialize table: {}", e))?;
+            print!("{}", render_table(&value));
+        }
+        OutputFormat::Plain => {
+            let value = serde_json::to_value(value)
+                .map_err(|e| format!("Failed to serialize plain output: {}", e))?;
+            print!("{}", render_plain(&value));
+        }
     }
     Ok(())
 }
 
+/// Renders a single value as a table cell or plain field.
+///
+/// Strings are printed without quotes; arrays and objects are printed as compact JSON.
+fn render_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Splits a value into a header row and data rows.
+///
+/// An array of objects yields one row per element with a column per key, in the
+/// order keys are first seen. An object yields key/value rows. Anything else
+/// yields a single `value` column.
+fn tabulate(value: &serde_json::Value) -> (Vec<String>, Vec<Vec<String>>) {
+    match value {
+        serde_json::Value::Array(items) if items.iter().all(|item| item.is_object()) => {
+            let mut columns: Vec<String> = Vec::new();
+            for item in items {
+                for key in item
+                    .as_object()
+                    .into_iter()
+                    .flat_map(|object| object.keys())
+                {
+                    if !columns.contains(key) {
+                        columns.push(key.clone());
+                    }
+                }
+            }
+            let rows = items
+                .iter()
+                .map(|item| {
+                    columns
+                        .iter()
+                        .map(|column| item.get(column).map(render_cell).unwrap_or_default())
+                        .collect()
+                })
+                .collect();
+            (columns, rows)
+        }
+        serde_json::Value::Array(items) => (
+            vec!["value".to_string()],
+            items.iter().map(|item| vec![render_cell(item)]).collect(),
+        ),
+        serde_json::Value::Object(object) => (
+            vec!["field".to_string(), "value".to_string()],
+            object
+                .iter()
+                .map(|(key, value)| vec![key.clone(), render_cell(value)])
+                .collect(),
+        ),
+        other => (vec!["value".to_string()], vec![vec![render_cell(other)]]),
+    }
+}
+
+/// Renders a value as left-aligned columns under an upper-case header.
+fn render_table(value: &serde_json::Value) -> String {
+    let (columns, rows) = tabulate(value);
+    let header: Vec<String> = columns.iter().map(|c| c.to_uppercase()).collect();
+    let mut widths: Vec<usize> = header.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Renders a value as tab-separated lines without a header.
+fn render_plain(value: &serde_json::Value) -> String {
+    let (_, rows) = match value {
+        // A lone object reads better as one record than as key/value pairs.
+        serde_json::Value::Object(_) => tabulate(&serde_json::Value::Array(vec![value.clone()])),
+        other => tabulate(other),
+    };
+    let mut output = String::new();
+    for row in rows {
+        output.push_str(&row.join("\t"));
+        output.push('\n');
+    }
+    output
+}
+
 /// Serializes a value to pretty-printed JSON and outputs to stdout.
 ///
 /// This function provides consistent JSON formatting across CLI tools,
@@ -202,7 +318,7 @@ where
 ///
 /// # Arguments
 /// * `value` - Any serializable value to output
-/// * `format` - Output format (JSON or YAML)
+/// * `format` - Output format (JSON, YAML, table or plain)
 /// * `context` - Context description for error messages (e.g., "response", "entity")
 ///
 /// # Examples
@@ -248,3 +364,42 @@ where
         exit_with_error(&format!("Failed to format {} JSON: {}", context, e));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn output_format_parses_all_formats() {
+        for format in [
+            OutputFormat::Json,
+            OutputFormat::Yaml,
+            OutputFormat::Table,
+            OutputFormat::Plain,
+        ] {
+            assert_eq!(format.to_string().parse::<OutputFormat>(), Ok(format));
+        }
+        assert!("xml".parse::<OutputFormat>().is_err());
+    }
+
+    #[test]
+    fn table_aligns_columns() {
+        let value = json!([
+            {"count": 3, "name": "Position"},
+            {"count": 12, "extra": {"a": 1}, "name": "Hp"},
+        ]);
+        assert_eq!(
+            render_table(&value),
+            "COUNT  NAME      EXTRA\n\
+             3      Position\n\
+             12     Hp        {\"a\":1}\n"
+        );
+    }
+
+    #[test]
+    fn plain_prints_bare_values() {
+        assert_eq!(render_plain(&json!(["a", "b"])), "a\nb\n");
+        assert_eq!(render_plain(&json!({"dst": "y", "src": "x"})), "y\tx\n");
+    }
+}
@@ -3,6 +3,10 @@
 //! This module handles the `stigctl apply` command for applying configurations
 //! from directories containing entity, component, system, and invariant definitions.
 //!
+//! The command also accepts a JSON `ApplyRequest` directly, read from a file or from
+//! stdin when the argument is `-`, in which case the status of every operation is
+//! printed along with the summary.
+//!
 //! ## Supported Formats
 //!
 //! - **YAML files**: Multi-document YAML files with `.yaml` or `.yml` extensions
@@ -133,6 +137,23 @@ pub async fn handle_apply_command(args: &[String], client: &StigmergyClient) {
     let directory = &args[0];
     let path = Path::new(directory);
 
+    if directory == "-" || path.is_file() {
+        let content = if directory == "-" {
+            std::io::read_to_string(std::io::stdin())
+        } else {
+            fs::read_to_string(path)
+        }
+        .unwrap_or_else(|e| {
+            cli_utils::exit_with_error(&format!("Failed to read {}: {}", directory, e))
+        });
+        match submit_apply_request(&content, client).await {
+            Ok(response) if response.committed => {}
+            Ok(_) => cli_utils::exit_with_error("Transaction rolled back due to errors"),
+            Err(e) => cli_utils::exit_with_error(&format!("Failed to apply request: {}", e)),
+        }
+        return;
+    }
+
     if !path.exists() {
         cli_utils::exit_with_error(&format!("Directory '{}' does not exist", directory));
     }
@@ -153,10 +174,13 @@ pub async fn handle_apply_command(args: &[String], client: &StigmergyClient) {
 
 /// Get usage information for the apply command
 fn get_apply_usage() -> &'static str {
-    r#"Usage: stigctl apply <directory>
+    r#"Usage: stigctl apply <directory | request.json | ->
 
 Applies configuration from a directory containing entity, component, system, and invariant definitions.
 
+Given a file, or - for stdin, submits the JSON ApplyRequest it contains and prints the
+status of each operation.  Exits nonzero if the batch was not committed.
+
 Directory structure:
   <directory>/
     entity/               # Entity definitions (YAML)
@@ -171,7 +195,141 @@ Supported file formats:
 
 Examples:
   stigctl apply foo/
-  stigctl apply ./config/"#
+  stigctl apply ./config/
+  cat batch.json | stigctl apply -"#
+}
+
+/// Submits a JSON `ApplyRequest` and prints the status of each operation.
+async fn submit_apply_request(
+    content: &str,
+    client: &StigmergyClient,
+) -> Result<ApplyResponse, ApplyError> {
+    let request: ApplyRequest = serde_json::from_str(content)?;
+    println!("Applying {} operations...", request.operations.len());
+
+    let response: ApplyResponse = client
+        .post("apply", &request)
+        .await
+        .map_err(ApplyError::HttpError)?;
+
+    for (index, result) in response.results.iter().enumerate() {
+        let marker = if result.is_error() { "✗" } else { "✓" };
+        println!("  {} {}: {}", marker, index + 1, describe_result(result));
+    }
+    print_apply_results(&response);
+    Ok(response)
+}
+
+/// Describes the outcome of a single operation in one line.
+fn describe_result(result: &OperationResult) -> String {
+    fn outcome(flag: bool, yes: &str, no: &str) -> String {
+        if flag {
+            yes.to_string()
+        } else {
+            no.to_string()
+        }
+    }
+    match result {
+        OperationResult::CreateEntity { entity, created } => format!(
+            "entity {} {}",
+            entity,
+            outcome(*created, "created", "already existed")
+        ),
+        OperationResult::DeleteEntity {
+            entity,
+            deleted,
+            edges_removed,
+        } => format!(
+            "entity {} {} ({} edges removed)",
+            entity,
+            outcome(*deleted, "deleted", "did not exist"),
+            edges_removed
+        ),
+        OperationResult::UpsertComponent {
+            entity,
+            component,
+            created,
+            changed,
+        } => format!(
+            "component {} on {} {}",
+            component.as_str(),
+            entity,
+            if *created {
+                "created"
+            } else if *changed {
+                "updated"
+            } else {
+                "unchanged"
+            }
+        ),
+        OperationResult::GetComponent {
+            entity,
+            component,
+            data,
+        } => match data {
+            Some(data) => format!("component {} on {} is {}", component.as_str(), entity, data),
+            None => format!(
+                "component {} on {} does not exist",
+                component.as_str(),
+                entity
+            ),
+        },
+        OperationResult::DeleteComponent {
+            entity,
+            component,
+            deleted,
+        } => format!(
+            "component {} on {} {}",
+            component.as_str(),
+            entity,
+            outcome(*deleted, "deleted", "did not exist")
+        ),
+        OperationResult::UpsertComponentDefinition { component, created } => format!(
+            "component definition {} {}",
+            component.as_str(),
+            outcome(*created, "created", "updated")
+        ),
+        OperationResult::DeleteComponentDefinition { component, deleted } => format!(
+            "component definition {} {}",
+            component.as_str(),
+            outcome(*deleted, "deleted", "did not exist")
+        ),
+        OperationResult::UpsertInvariant {
+            invariant_id,
+            created,
+            ..
+        } => format!(
+            "invariant {} {}",
+            invariant_id,
+            outcome(*created, "created", "updated")
+        ),
+        OperationResult::DeleteInvariant {
+            invariant_id,
+            deleted,
+        } => format!(
+            "invariant {} {}",
+            invariant_id,
+            outcome(*deleted, "deleted", "did not exist")
+        ),
+        OperationResult::UpsertEdge { edge, created } => format!(
+            "edge {} -[{}]-> {} {}",
+            edge.src,
+            edge.label,
+            edge.dst,
+            outcome(*created, "created", "already existed")
+        ),
+        OperationResult::DeleteEdge { edge, deleted } => format!(
+            "edge {} -[{}]-> {} {}",
+            edge.src,
+            edge.label,
+            edge.dst,
+            outcome(*deleted, "deleted", "did not exist")
+        ),
+        OperationResult::Error { error, .. } => format!("error: {}", error),
+        OperationResult::ValidationFailed { path, message, .. } => {
+            format!("validation failed at '{}': {}", path, message)
+        }
+    }
 }
 
 /// Process a directory and all its subdirectories
@@ -225,7 +383,7 @@ async fn process_directory(path: &Path, client: &StigmergyClient) -> Result<(),
 
     println!("Applying {} operations...", operations.len());
 
-    let request = ApplyRequest { operations };
+    let request = ApplyRequest::new(operations);
     let response: ApplyResponse = client
         .post("apply", &request)
         .await
@@ -258,6 +416,7 @@ fn process_entity_directory(
                 if let Ok(entity) = Entity::from_str(&entity_str) {
                     operations.push(Operation::CreateEntity {
                         entity: Some(entity),
+                        local_ref: None,
                     });
                 } else {
                     return Err(ApplyError::ParseError(format!(
@@ -279,6 +438,7 @@ fn process_entity_directory(
                     if let Ok(entity) = Entity::from_str(entity_str) {
                         operations.push(Operation::CreateEntity {
                             entity: Some(entity),
+                            local_ref: None,
                         });
                     } else {
                         return Err(ApplyError::ParseError(format!(
@@ -293,6 +453,7 @@ fn process_entity_directory(
                             if let Ok(entity) = Entity::from_str(entity_str) {
                                 operations.push(Operation::CreateEntity {
                                     entity: Some(entity),
+                                    local_ref: None,
                                 });
                             } else {
                                 return Err(ApplyError::ParseError(format!(
@@ -303,7 +464,10 @@ fn process_entity_directory(
                         }
                     } else {
                         // Generate random entity
-                        operations.push(Operation::CreateEntity { entity: None });
+                        operations.push(Operation::CreateEntity {
+                            entity: None,
+                            local_ref: None,
+                        });
                     }
                 }
             }
@@ -334,7 +498,10 @@ fn process_component_definition_directory(
 
                 let definition = ComponentDefinition::new(component, def_yaml.schema);
 
-                operations.push(Operation::UpsertComponentDefinition { definition });
+                operations.push(Operation::UpsertComponentDefinition {
+                    definition,
+                    check_existing: false,
+                });
             }
         } else {
             for doc in serde_yml::Deserializer::from_str(&content) {
@@ -348,7 +515,10 @@ fn process_component_definition_directory(
 
                 let definition = ComponentDefinition::new(component, def_yaml.schema);
 
-                operations.push(Operation::UpsertComponentDefinition { definition });
+                operations.push(Operation::UpsertComponentDefinition {
+                    definition,
+                    check_existing: false,
+                });
             }
         }
     }
@@ -377,7 +547,7 @@ fn process_component_directory(
                 })?;
 
                 operations.push(Operation::UpsertComponent {
-                    entity: comp_instance.entity,
+                    entity: comp_instance.entity.into(),
                     component,
                     data: comp_instance.data,
                 });
@@ -394,7 +564,7 @@ fn process_component_directory(
                 })?;
 
                 operations.push(Operation::UpsertComponent {
-                    entity: comp_instance.entity,
+                    entity: comp_instance.entity.into(),
                     component,
                     data: comp_instance.data,
                 });
@@ -550,6 +720,7 @@ fn print_apply_results(response: &ApplyResponse) {
     let mut created_component_definitions = 0;
     let mut updated_component_definitions = 0;
     let mut updated_components = 0;
+    let mut unchanged_components = 0;
     let mut created_components = 0;
     let mut created_invariants = 0;
     let mut updated_invariants = 0;
@@ -557,10 +728,8 @@ fn print_apply_results(response: &ApplyResponse) {
 
     for result in &response.results {
         match result {
-            OperationResult::CreateEntity { created, .. } => {
-                if *created {
-                    created_entities += 1;
-                }
+            OperationResult::CreateEntity { created: true, .. } => {
+                created_entities += 1;
             }
             OperationResult::UpsertComponentDefinition { created, .. } => {
                 if *created {
@@ -569,11 +738,15 @@ fn print_apply_results(response: &ApplyResponse) {
                     updated_component_definitions += 1;
                 }
             }
-            OperationResult::UpsertComponent { created, .. } => {
+            OperationResult::UpsertComponent {
+                created, changed, ..
+            } => {
                 if *created {
                     created_components += 1;
-                } else {
+                } else if *changed {
                     updated_components += 1;
+                } else {
+                    unchanged_components += 1;
                 }
             }
             OperationResult::UpsertInvariant { created, .. } => {
@@ -583,7 +756,7 @@ fn print_apply_results(response: &ApplyResponse) {
                     updated_invariants += 1;
                 }
             }
-            OperationResult::Error { .. } => {
+            OperationResult::Error { .. } | OperationResult::ValidationFailed { .. } => {
                 errors += 1;
             }
             _ => {}
@@ -612,6 +785,9 @@ fn print_apply_results(response: &ApplyResponse) {
     if updated_components > 0 {
         println!("  ✓ Updated {} components", updated_components);
     }
+    if unchanged_components > 0 {
+        println!("  ✓ {} components already up to date", unchanged_components);
+    }
     if created_invariants > 0 {
         println!("  ✓ Created {} invariants", created_invariants);
     }
@@ -622,19 +798,95 @@ fn print_apply_results(response: &ApplyResponse) {
         println!("  ✗ {} errors occurred", errors);
 
         for result in response.results.iter() {
-            if let OperationResult::Error {
-                operation_index,
-                error,
-            } = result
-            {
-                println!("    Operation {}: {}", operation_index + 1, error);
+            match result {
+                OperationResult::Error {
+                    operation_index,
+                    error,
+                } => {
+                    println!("    Operation {}: {}", operation_index + 1, error);
+                }
+                OperationResult::ValidationFailed {
+                    operation_index,
+                    path,
+                    message,
+                } => {
+                    println!(
+                        "    Operation {}: validation failed at '{}': {}",
+                        operation_index + 1,
+                        path,
+                        message
+                    );
+                }
+                _ => {}
             }
         }
     }
 
+    if !response.violations.is_empty() {
+        println!("  ✗ {} invariant violations", response.violations.len());
+
+        for violation in response.violations.iter() {
+            println!(
+                "    {} on {}: {}",
+                violation.invariant_id, violation.entity, violation.reason
+            );
+        }
+    }
+
     if response.committed {
         println!("  ✓ Transaction committed successfully");
     } else {
         println!("  ✗ Transaction rolled back");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apply::Operation;
+
+    #[test]
+    fn apply_request_json_and_result_descriptions() {
+        let request: ApplyRequest = serde_json::from_str(
+            r#"{"operations": [{"type": "create_entity"}, {"type": "delete_component_definition", "component": "Health"}]}"#,
+        )
+        .unwrap();
+        assert!(request.atomic);
+        assert!(matches!(
+            request.operations[0],
+            Operation::CreateEntity { entity: None, .. }
+        ));
+
+        let entity = Entity::new([7u8; 32]);
+        let described = [
+            OperationResult::CreateEntity {
+                entity,
+                created: true,
+            },
+            OperationResult::UpsertComponent {
+                entity,
+                component: Component::new("Health").unwrap(),
+                created: false,
+                changed: false,
+            },
+            OperationResult::ValidationFailed {
+                operation_index: 2,
+                path: "/hp".to_string(),
+                message: "expected integer".to_string(),
+            },
+        ]
+        .iter()
+        .map(describe_result)
+        .collect::<Vec<_>>();
+        println!(
+            "apply_request_json_and_result_descriptions response: {:?}",
+            described
+        );
+        assert_eq!(described[0], format!("entity {} created", entity));
+        assert_eq!(
+            described[1],
+            format!("component Health on {} unchanged", entity)
+        );
+        assert_eq!(described[2], "validation failed at '/hp': expected integer");
+    }
+}
@@ -0,0 +1,167 @@
+//! # Bid Command Handler
+//!
+//! This module handles bid-related CLI commands. Bids are evaluated locally, without
+//! contacting the server, so that pricing rules can be debugged against a hand-written
+//! JSON context.
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    BidParser, DataContext, EntityResolver, EvaluationError, cli_utils,
+    commands::shared::dispatch_command, http_utils,
+};
+
+const BID_USAGE: &str = "Usage: stigctl bid <eval> [args...]";
+
+const BID_EVAL_USAGE: &str = "Usage: stigctl bid eval --expr <bid-expression> --context <ctx.json>";
+
+/// Result of evaluating a bid against a JSON context.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BidEvaluation {
+    /// Whether the ON condition was truthy
+    pub condition: bool,
+    /// The evaluated BID value, present only when the condition matched
+    pub value: Option<Value>,
+}
+
+/// Resolver for local evaluation, where no entity store is available to dereference keys.
+struct NoEntities;
+
+impl EntityResolver for NoEntities {
+    fn resolve(&self, _key: &Value) -> Result<Option<Value>, EvaluationError> {
+        Ok(None)
+    }
+}
+
+/// Handles all bid-related commands.
+///
+/// # Arguments
+/// * `args` - Command arguments (first element is the subcommand)
+/// * `client` - HTTP client for API communication
+/// * `output_format` - Output format for the evaluation result
+pub async fn handle_bid_command(
+    args: &[String],
+    client: &http_utils::StigmergyClient,
+    output_format: cli_utils::OutputFormat,
+) {
+    dispatch_command!("bid", BID_USAGE, args, client, output_format, {
+        "eval" => handle_bid_eval,
+    });
+}
+
+/// Handles local evaluation of a bid expression against a JSON context file.
+async fn handle_bid_eval(
+    args: &[String],
+    _client: &http_utils::StigmergyClient,
+    output_format: cli_utils::OutputFormat,
+) {
+    let mut expr = None;
+    let mut context_path = None;
+    let mut rest = args[1..].iter();
+    while let Some(flag) = rest.next() {
+        let slot = match flag.as_str() {
+            "--expr" => &mut expr,
+            "--context" => &mut context_path,
+            _ => cli_utils::exit_with_usage_error(
+                &format!("Unknown bid eval argument '{}'", flag),
+                BID_EVAL_USAGE,
+            ),
+        };
+        match rest.next() {
+            Some(value) => *slot = Some(value.as_str()),
+            None => cli_utils::exit_with_usage_error(
+                &format!("{} requires a value", flag),
+                BID_EVAL_USAGE,
+            ),
+        }
+    }
+    let (Some(expr), Some(context_path)) = (expr, context_path) else {
+        cli_utils::exit_with_usage_error("bid eval requires --expr and --context", BID_EVAL_USAGE);
+    };
+
+    let content = std::fs::read_to_string(context_path).unwrap_or_else(|e| {
+        cli_utils::exit_with_error(&format!("Failed to read file {}: {}", context_path, e))
+    });
+    let context: Value = serde_json::from_str(&content).unwrap_or_else(|e| {
+        cli_utils::exit_with_error(&format!("Invalid JSON in {}: {}", context_path, e))
+    });
+
+    let evaluation =
+        evaluate_bid(expr, &context).unwrap_or_else(|e| cli_utils::exit_with_error(&e));
+
+    if output_format != cli_utils::OutputFormat::Plain {
+        cli_utils::print_formatted_or_exit(&evaluation, output_format, "bid evaluation");
+    } else {
+        println!("{}", plain_output(&evaluation));
+    }
+}
+
+/// Renders an evaluation as the `condition:`/`value:` lines of the plain output format.
+fn plain_output(evaluation: &BidEvaluation) -> String {
+    let value = match &evaluation.value {
+        Some(value) => value.to_string(),
+        None => "none".to_string(),
+    };
+    format!("condition: {}\nvalue: {}", evaluation.condition, value)
+}
+
+/// Returns an integral float as an integer, so `score * 2` reports `40` rather than `40.0`.
+///
+/// The evaluator's arithmetic always produces floats; only values that an `i64` holds
+/// exactly are converted.
+fn integral_as_integer(value: Value) -> Value {
+    match value.as_f64() {
+        Some(float) if value.is_f64() && float.fract() == 0.0 && float.abs() < 2f64.powi(53) => {
+            Value::from(float as i64)
+        }
+        _ => value,
+    }
+}
+
+/// Parses `expr` and evaluates its condition and, when that matches, its value.
+fn evaluate_bid(expr: &str, context: &Value) -> Result<BidEvaluation, String> {
+    let bid = BidParser::parse(expr).map_err(|e| format!("Invalid bid expression: {}", e))?;
+    let context = DataContext::new(context, &NoEntities);
+    let condition = bid
+        .on_condition
+        .evaluate_truthy(&context)
+        .map_err(|e| format!("Failed to evaluate condition: {}", e))?;
+    let value = if condition {
+        let value = bid
+            .bid_value
+            .evaluate(&context)
+            .map_err(|e| format!("Failed to evaluate bid value: {}", e))?;
+        Some(integral_as_integer(value))
+    } else {
+        None
+    };
+    Ok(BidEvaluation { condition, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn evaluate_bid_reports_condition_and_value() {
+        let evaluation =
+            evaluate_bid("ON score > 10 BID score * 2", &json!({"score": 20})).unwrap();
+        println!(
+            "evaluate_bid_reports_condition_and_value response: {:?}",
+            evaluation
+        );
+        assert!(evaluation.condition);
+        assert_eq!(evaluation.value, Some(json!(40)));
+        assert_eq!(plain_output(&evaluation), "condition: true\nvalue: 40");
+
+        let evaluation = evaluate_bid("ON score > 10 BID score * 2", &json!({"score": 5})).unwrap();
+        assert!(!evaluation.condition);
+        assert_eq!(evaluation.value, None);
+        assert_eq!(plain_output(&evaluation), "condition: false\nvalue: none");
+
+        let evaluation = evaluate_bid("ON true BID score / 8", &json!({"score": 20})).unwrap();
+        assert_eq!(evaluation.value, Some(json!(2.5)));
+    }
+}
@@ -2,16 +2,22 @@
 //!
 //! This module handles component definition-related CLI commands including
 //! creation, listing, retrieval, updating, and deletion of component definitions.
+//!
+//! Definitions can also be moved between servers as a bundle: `export` writes every
+//! definition to a JSON array (or to JSON Lines when the file ends in `.jsonl`), and
+//! `import` upserts the definitions of such a bundle through the apply endpoint.
 
 use crate::{
-    ComponentDefinition, cli_utils,
+    ComponentDefinition,
+    apply::{ApplyRequest, ApplyResponse, Operation, OperationResult},
+    cli_utils,
     commands::shared::{dispatch_command, validate_args_count_or_exit},
     component_utils, http_utils,
 };
 use serde_json::Value;
 
 const COMPONENTDEFINITION_USAGE: &str =
-    "Usage: stigctl componentdefinition <create|list|get|update|delete> [args...]";
+    "Usage: stigctl componentdefinition <create|list|get|update|delete|export|import> [args...]";
 
 /// Handles all component definition-related commands.
 ///
@@ -30,6 +36,8 @@ pub async fn handle_componentdefinition_command(
         "get" => handle_componentdefinition_get,
         "update" => handle_componentdefinition_update,
         "delete" => handle_componentdefinition_delete,
+        "export" => handle_componentdefinition_export,
+        "import" => handle_componentdefinition_import,
     });
 }
 
@@ -177,3 +185,178 @@ async fn handle_componentdefinition_delete(
 
     println!("Deleted component definition: {}", def_id);
 }
+
+/// Handles exporting every component definition to a bundle file, or stdout.
+async fn handle_componentdefinition_export(
+    args: &[String],
+    client: &http_utils::StigmergyClient,
+    _output_format: cli_utils::OutputFormat,
+) {
+    validate_args_count_or_exit(
+        args,
+        1,
+        2,
+        "export",
+        "Usage: stigctl componentdefinition export [bundle.json|bundle.jsonl]",
+    );
+
+    let definitions = http_utils::execute_or_exit(
+        || client.get::<Vec<ComponentDefinition>>("componentdefinition"),
+        "Failed to list component definitions",
+    )
+    .await;
+
+    let file_path = args.get(1);
+    let jsonl = file_path.is_some_and(|path| path.ends_with(".jsonl"));
+    let bundle = write_bundle(&definitions, jsonl).unwrap_or_else(|e| {
+        cli_utils::exit_with_error(&format!("Failed to serialize component definitions: {}", e))
+    });
+
+    match file_path {
+        Some(file_path) => {
+            std::fs::write(file_path, bundle).unwrap_or_else(|e| {
+                cli_utils::exit_with_error(&format!("Failed to write file {}: {}", file_path, e))
+            });
+            println!(
+                "Exported {} component definitions to {}",
+                definitions.len(),
+                file_path
+            );
+        }
+        None => print!("{}", bundle),
+    }
+}
+
+/// Handles importing a bundle of component definitions via the apply endpoint.
+async fn handle_componentdefinition_import(
+    args: &[String],
+    client: &http_utils::StigmergyClient,
+    _output_format: cli_utils::OutputFormat,
+) {
+    validate_args_count_or_exit(
+        args,
+        2,
+        2,
+        "import",
+        "Usage: stigctl componentdefinition import <bundle.json|bundle.jsonl>",
+    );
+
+    let file_path = &args[1];
+    let content = std::fs::read_to_string(file_path).unwrap_or_else(|e| {
+        cli_utils::exit_with_error(&format!("Failed to read file {}: {}", file_path, e))
+    });
+    let definitions = read_bundle(&content).unwrap_or_else(|e| {
+        cli_utils::exit_with_error(&format!("Invalid bundle {}: {}", file_path, e))
+    });
+    if definitions.is_empty() {
+        println!("No component definitions found in {}", file_path);
+        return;
+    }
+
+    let components: Vec<String> = definitions
+        .iter()
+        .map(|definition| definition.component.as_str().to_string())
+        .collect();
+    let mut request = ApplyRequest::new(
+        definitions
+            .into_iter()
+            .map(|definition| Operation::UpsertComponentDefinition {
+                definition,
+                check_existing: false,
+            })
+            .collect(),
+    );
+    // Each definition succeeds or fails on its own so one bad schema does not block the rest.
+    request.atomic = false;
+
+    let response = http_utils::execute_or_exit(
+        || client.post::<ApplyRequest, ApplyResponse>("apply", &request),
+        "Failed to import component definitions",
+    )
+    .await;
+
+    let mut failures = 0;
+    for result in &response.results {
+        match result {
+            OperationResult::UpsertComponentDefinition { component, created } => {
+                let action = if *created { "created" } else { "updated" };
+                println!("{}: {}", component.as_str(), action);
+            }
+            OperationResult::Error {
+                operation_index,
+                error,
+            } => {
+                failures += 1;
+                let component = components
+                    .get(*operation_index)
+                    .map(String::as_str)
+                    .unwrap_or("<unknown>");
+                println!("{}: failed: {}", component, error);
+            }
+            other => println!("unexpected result: {:?}", other),
+        }
+    }
+    println!(
+        "Imported {} of {} component definitions",
+        components.len() - failures,
+        components.len()
+    );
+    if failures > 0 {
+        std::process::exit(1);
+    }
+}
+
+/// Serializes definitions as a pretty JSON array, or as one JSON object per line.
+fn write_bundle(
+    definitions: &[ComponentDefinition],
+    jsonl: bool,
+) -> Result<String, serde_json::Error> {
+    if jsonl {
+        let mut bundle = String::new();
+        for definition in definitions {
+            bundle.push_str(&serde_json::to_string(definition)?);
+            bundle.push('\n');
+        }
+        Ok(bundle)
+    } else {
+        Ok(serde_json::to_string_pretty(definitions)? + "\n")
+    }
+}
+
+/// Parses a bundle written by [`write_bundle`], detecting JSON array versus JSON Lines.
+fn read_bundle(content: &str) -> Result<Vec<ComponentDefinition>, serde_json::Error> {
+    if content.trim_start().starts_with('[') {
+        serde_json::from_str(content)
+    } else {
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(serde_json::from_str)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Component;
+    use serde_json::json;
+
+    #[test]
+    fn bundle_round_trips_in_both_formats() {
+        let definitions = vec![
+            ComponentDefinition::new(
+                Component::new("Health").unwrap(),
+                json!({"type": "object", "properties": {"hp": {"type": "integer"}}}),
+            ),
+            ComponentDefinition::new(Component::new("Tag").unwrap(), json!({"type": "string"})),
+        ];
+
+        for jsonl in [false, true] {
+            let bundle = write_bundle(&definitions, jsonl).unwrap();
+            println!("bundle_round_trips_in_both_formats response: {}", bundle);
+            assert_eq!(bundle.lines().count() == definitions.len(), jsonl);
+            assert_eq!(read_bundle(&bundle).unwrap(), definitions);
+        }
+    }
+}
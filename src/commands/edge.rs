@@ -43,7 +43,12 @@ async fn handle_edge_create(
     let dst = parse_entity_id_or_exit(&args[2]);
     let label = parse_entity_id_or_exit(&args[3]);
 
-    let request = CreateEdgeRequest { src, dst, label };
+    let request = CreateEdgeRequest {
+        src,
+        dst,
+        label,
+        directed: true,
+    };
 
     let response = http_utils::execute_or_exit(
         || client.post::<CreateEdgeRequest, CreateEdgeResponse>("edge", &request),
@@ -84,12 +89,10 @@ async fn handle_edge_list(
         http_utils::execute_or_exit(|| client.get::<Vec<Edge>>(&path), "Failed to list edges")
             .await;
 
-    if edges.is_empty() {
-        println!("No edges found");
-    } else if output_format == cli_utils::OutputFormat::Json
-        || output_format == cli_utils::OutputFormat::Yaml
-    {
+    if output_format != cli_utils::OutputFormat::Plain {
         cli_utils::print_formatted_or_exit(&edges, output_format, "edges");
+    } else if edges.is_empty() {
+        println!("No edges found");
     } else {
         println!("Edges:");
         for edge in edges {
@@ -124,9 +127,7 @@ async fn handle_edge_get(
     let edge =
         http_utils::execute_or_exit(|| client.get::<Edge>(&path), "Failed to get edge").await;
 
-    if output_format == cli_utils::OutputFormat::Json
-        || output_format == cli_utils::OutputFormat::Yaml
-    {
+    if output_format != cli_utils::OutputFormat::Plain {
         cli_utils::print_formatted_or_exit(&edge, output_format, "edge");
     } else {
         println!(
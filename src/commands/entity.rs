@@ -60,12 +60,10 @@ async fn handle_entity_list(
     )
     .await;
 
-    if entities.is_empty() {
-        println!("No entities found");
-    } else if output_format == cli_utils::OutputFormat::Json
-        || output_format == cli_utils::OutputFormat::Yaml
-    {
+    if output_format != cli_utils::OutputFormat::Plain {
         cli_utils::print_formatted_or_exit(&entities, output_format, "entities");
+    } else if entities.is_empty() {
+        println!("No entities found");
     } else {
         println!("Entities:");
         for entity in entities {
@@ -11,6 +11,13 @@ use handled::Handle;
 impl Handle<UserError> for EntityParseError {
     fn handle(&self) -> Option<UserError> {
         let (message, hint) = match self {
+            EntityParseError::MissingPrefix => (
+                "Entity ID is missing the 'entity:' prefix".to_string(),
+                Some(
+                    "Use format 'entity:BASE64_STRING' or just 'BASE64_STRING' (43 characters)"
+                        .to_string(),
+                ),
+            ),
             EntityParseError::InvalidPrefix => (
                 "Entity ID must start with 'entity:' prefix or be a valid base64 string"
                     .to_string(),
@@ -23,14 +30,24 @@ impl Handle<UserError> for EntityParseError {
                 "Entity ID format is invalid - expected 43-character base64 string".to_string(),
                 Some("Entity IDs must be exactly 43 characters of URL-safe base64".to_string()),
             ),
-            EntityParseError::InvalidBase64 => (
-                "Entity ID contains invalid base64 characters".to_string(),
+            EntityParseError::InvalidBase64 { position } => (
+                format!(
+                    "Entity ID contains an invalid base64 character at position {}",
+                    position
+                ),
                 Some("Use only URL-safe base64 characters (A-Z, a-z, 0-9, -, _)".to_string()),
             ),
-            EntityParseError::InvalidLength => (
-                "Entity ID must decode to exactly 32 bytes".to_string(),
+            EntityParseError::WrongLength { got } => (
+                format!("Entity ID must decode to exactly 32 bytes, got {}", got),
                 Some("Entity IDs must be exactly 43 characters when base64 encoded".to_string()),
             ),
+            EntityParseError::InvalidNamespace => (
+                "Entity namespace is empty or contains invalid characters".to_string(),
+                Some(
+                    "Use format 'NAMESPACE:BASE64_STRING' with an alphanumeric namespace"
+                        .to_string(),
+                ),
+            ),
         };
 
         Some(UserError {
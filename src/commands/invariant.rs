@@ -60,6 +60,7 @@ Example: stigctl invariant create "x > 0" invariant:AAAA..."#,
 
     let request = crate::CreateInvariantRequest {
         invariant_id,
+        name: None,
         asserts: asserts.to_string(),
     };
 
@@ -92,15 +93,16 @@ async fn handle_invariant_list(
     )
     .await;
 
-    if invariants.is_empty() {
-        println!("No invariants found");
-    } else if output_format == cli_utils::OutputFormat::Json
-        || output_format == cli_utils::OutputFormat::Yaml
-    {
+    if output_format != cli_utils::OutputFormat::Plain {
         cli_utils::print_formatted_or_exit(&invariants, output_format, "invariants");
+    } else if invariants.is_empty() {
+        println!("No invariants found");
     } else {
         for inv in invariants {
             println!("ID:        {}", inv.invariant_id);
+            if let Some(name) = &inv.name {
+                println!("Name:      {}", name);
+            }
             println!("Asserts:   {}", inv.asserts);
             println!("Created:   {}", inv.created_at);
             println!("Updated:   {}", inv.updated_at);
@@ -120,26 +122,26 @@ async fn handle_invariant_get(
         2,
         2,
         "get",
-        "Usage: stigctl invariant get <invariant-id>",
+        "Usage: stigctl invariant get <invariant-id|name>",
     );
 
-    let invariant_id_str = &args[1];
-    let invariant_id = invariant_id_str
-        .parse::<InvariantID>()
-        .unwrap_or_else(|_| cli_utils::exit_with_error("Invalid invariant ID"));
-
-    let path = format!("invariant/{}", invariant_id.base64_part());
-    let error_msg = format!("Failed to get invariant {}", invariant_id);
+    let invariant_id_or_name = &args[1];
+    let path = match invariant_id_or_name.parse::<InvariantID>() {
+        Ok(invariant_id) => format!("invariant/{}", invariant_id.base64_part()),
+        Err(_) => format!("invariant/{}", invariant_id_or_name),
+    };
+    let error_msg = format!("Failed to get invariant {}", invariant_id_or_name);
 
     let invariant =
         http_utils::execute_or_exit(|| client.get::<GetInvariantResponse>(&path), &error_msg).await;
 
-    if output_format == cli_utils::OutputFormat::Json
-        || output_format == cli_utils::OutputFormat::Yaml
-    {
+    if output_format != cli_utils::OutputFormat::Plain {
         cli_utils::print_formatted_or_exit(&invariant, output_format, "invariant");
     } else {
         println!("ID:        {}", invariant.invariant_id);
+        if let Some(name) = &invariant.name {
+            println!("Name:      {}", name);
+        }
         println!("Asserts:   {}", invariant.asserts);
         println!("Created:   {}", invariant.created_at);
         println!("Updated:   {}", invariant.updated_at);
@@ -169,6 +171,7 @@ async fn handle_invariant_update(
 
     let request = crate::UpdateInvariantRequest {
         asserts: asserts.to_string(),
+        name: None,
     };
 
     let path = format!("invariant/{}", invariant_id.base64_part());
@@ -180,14 +183,15 @@ async fn handle_invariant_update(
     )
     .await;
 
-    if output_format == cli_utils::OutputFormat::Json
-        || output_format == cli_utils::OutputFormat::Yaml
-    {
+    if output_format != cli_utils::OutputFormat::Plain {
         println!("Updated invariant:");
         cli_utils::print_formatted_or_exit(&invariant, output_format, "invariant");
     } else {
         println!("Updated invariant:");
         println!("ID:        {}", invariant.invariant_id);
+        if let Some(name) = &invariant.name {
+            println!("Name:      {}", name);
+        }
         println!("Asserts:   {}", invariant.asserts);
         println!("Created:   {}", invariant.created_at);
         println!("Updated:   {}", invariant.updated_at);
@@ -0,0 +1,41 @@
+//! # Migrate Command Handler
+//!
+//! This module handles the `stigctl migrate` command, which applies the database
+//! migrations embedded in the binary.  Unlike the other commands it talks to PostgreSQL
+//! directly rather than to the API server.
+
+use crate::{cli_utils, sql};
+
+const MIGRATE_USAGE: &str = "Usage: stigctl migrate [--database-url <url>]
+
+Applies pending database migrations.  The database URL defaults to $DATABASE_URL.
+Migrations that have already been applied are skipped.";
+
+/// Handles the migrate command.
+///
+/// # Arguments
+/// * `args` - Command arguments following `migrate`
+pub async fn handle_migrate_command(args: &[String]) {
+    let database_url = match args {
+        [] => std::env::var("DATABASE_URL").unwrap_or_else(|_| {
+            cli_utils::exit_with_usage_error(
+                "migrate requires --database-url or DATABASE_URL",
+                MIGRATE_USAGE,
+            )
+        }),
+        [flag, url] if flag == "--database-url" => url.clone(),
+        _ => cli_utils::exit_with_usage_error("Invalid migrate arguments", MIGRATE_USAGE),
+    };
+
+    let pool = sqlx::PgPool::connect(&database_url)
+        .await
+        .unwrap_or_else(|e| {
+            cli_utils::exit_with_error(&format!("Failed to connect to database: {}", e))
+        });
+
+    match sql::run_migrations(&pool).await {
+        Ok(()) => println!("Migrations completed successfully"),
+        Err(e) => cli_utils::exit_with_error(&format!("Failed to run migrations: {}", e)),
+    }
+    pool.close().await;
+}
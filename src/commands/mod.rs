@@ -6,15 +6,18 @@
 //!
 //! ## Structure
 //!
+//! - `bid` - Local bid evaluation against a JSON context (eval)
 //! - `entity` - Entity management commands (create, list, delete)
 //! - `system` - System management commands (create, list, get, update, delete)
 //! - `component_definition` - Component definition commands (create, list, get, update, delete)
 //! - `component` - Component instance commands (create, list, get, update, delete)
 //! - `invariant` - Invariant management commands (create, list, get, update, delete)
+//! - `migrate` - Applies database migrations directly against PostgreSQL
 //! - `config` - Configuration management commands (get, set)
 //! - `shared` - Shared utilities and validation functions
 
 pub mod apply;
+pub mod bid;
 pub mod component;
 pub mod component_definition;
 pub mod config;
@@ -23,14 +26,17 @@ pub mod entity;
 pub mod error_extensions;
 pub mod errors;
 pub mod invariant;
+pub mod migrate;
 pub mod shared;
 pub mod system;
 
 pub use apply::handle_apply_command;
+pub use bid::handle_bid_command;
 pub use component::handle_component_command;
 pub use component_definition::handle_componentdefinition_command;
 pub use config::handle_config_command;
 pub use edge::handle_edge_command;
 pub use entity::handle_entity_command;
 pub use invariant::handle_invariant_command;
+pub use migrate::handle_migrate_command;
 pub use system::handle_system_command;
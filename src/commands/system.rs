@@ -4,14 +4,14 @@
 //! listing, retrieval, updating, and deletion of systems.
 
 use crate::{
-    CreateSystemFromMarkdownRequest, CreateSystemResponse, System, SystemConfig, SystemListItem,
-    cli_utils,
+    CreateSystemFromMarkdownRequest, CreateSystemResponse, ParseError, System, SystemConfig,
+    SystemListItem, SystemParser, cli_utils,
     commands::shared::{dispatch_command, parse_system_name_or_exit, validate_args_count_or_exit},
     http_utils,
 };
 
 const SYSTEM_USAGE: &str =
-    "Usage: stigctl system <create|create-from-md|list|get|update|delete> [args...]";
+    "Usage: stigctl system <create|create-from-md|validate|list|get|update|delete> [args...]";
 
 /// Handles all system-related commands.
 ///
@@ -27,6 +27,7 @@ pub async fn handle_system_command(
     dispatch_command!("system", SYSTEM_USAGE, args, client, output_format, {
         "create" => handle_system_create,
         "create-from-md" => handle_system_create_from_md,
+        "validate" => handle_system_validate,
         "list" => handle_system_list,
         "get" => handle_system_get,
         "update" => handle_system_update,
@@ -99,6 +100,78 @@ async fn handle_system_create_from_md(
     cli_utils::print_formatted_or_exit(&response.system, output_format, "system");
 }
 
+/// Handles local validation of a system markdown file without contacting the server.
+async fn handle_system_validate(
+    args: &[String],
+    _client: &http_utils::StigmergyClient,
+    _output_format: cli_utils::OutputFormat,
+) {
+    validate_args_count_or_exit(
+        args,
+        2,
+        2,
+        "validate",
+        "Usage: stigctl system validate <file.md>",
+    );
+
+    let file_path = &args[1];
+    let content = std::fs::read_to_string(file_path).unwrap_or_else(|e| {
+        cli_utils::exit_with_error(&format!("Failed to read file {}: {}", file_path, e))
+    });
+
+    match SystemParser::parse(&content) {
+        Ok(config) => println!(
+            "{}: system {} is valid ({} components, {} bids)",
+            file_path,
+            config.name,
+            config.component.len(),
+            config.bid.len()
+        ),
+        Err(e) => {
+            eprint!("{}", describe_parse_error(file_path, &content, &e));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Formats a system parse error with the file line it refers to.
+///
+/// Bid errors also echo the offending line and point a caret at the column
+/// where the bid parser stopped.
+fn describe_parse_error(file_path: &str, content: &str, error: &ParseError) -> String {
+    let expression = match error {
+        ParseError::BidParseError(expression, _)
+        | ParseError::ComponentParseError(expression, _) => Some(expression.as_str()),
+        _ => None,
+    };
+    let located = expression.and_then(|expression| {
+        content.lines().enumerate().find_map(|(index, line)| {
+            line.find(expression)
+                .map(|offset| (index + 1, line, offset))
+        })
+    });
+
+    match (located, error) {
+        (Some((line_number, line, offset)), ParseError::BidParseError(_, bid_error)) => {
+            let column = offset + bid_error.position().column;
+            format!(
+                "{}:{}:{}: error: {}\n    {}\n    {}^\n",
+                file_path,
+                line_number,
+                column,
+                error,
+                line,
+                " ".repeat(column - 1)
+            )
+        }
+        (Some((line_number, line, _)), _) => format!(
+            "{}:{}: error: {}\n    {}\n",
+            file_path, line_number, error, line
+        ),
+        (None, _) => format!("{}: error: {}\n", file_path, error),
+    }
+}
+
 /// Handles system listing.
 async fn handle_system_list(
     args: &[String],
@@ -192,3 +265,32 @@ async fn handle_system_delete(
 
     println!("Deleted system: {}", system_name);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_bid_error_points_at_file_column() {
+        let content = "---\nname: broken\ndescription: A broken system\nmodel: inherit\ncolor: blue\nbid:\n- ON true BID 1 +\n---\n\nBody.\n";
+        let error = SystemParser::parse(content).unwrap_err();
+        let described = describe_parse_error("broken.md", content, &error);
+        println!(
+            "describe_bid_error_points_at_file_column response: {}",
+            described
+        );
+        assert!(
+            matches!(error, ParseError::BidParseError(ref expr, _) if expr == "ON true BID 1 +")
+        );
+        assert!(described.starts_with("broken.md:7:"));
+        assert!(described.contains("ON true BID 1 +"));
+        assert!(
+            described
+                .lines()
+                .nth(2)
+                .unwrap()
+                .trim_start()
+                .starts_with('^')
+        );
+    }
+}
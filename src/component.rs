@@ -71,12 +71,20 @@
 //! ```
 
 use axum::Router;
-use axum::extract::{Path, State};
-use axum::http::StatusCode;
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, HeaderName, StatusCode, header};
 use axum::response::Json;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::routing::get;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sqlx::postgres::PgListener;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::Infallible;
+use tokio_stream::wrappers::ReceiverStream;
+
+use crate::{PatchOperation, apply_json_patch};
 
 ///////////////////////////////////////////// Component ////////////////////////////////////////////
 
@@ -84,7 +92,8 @@ use serde_json::Value;
 ///
 /// Components represent typed data that can be attached to entities. The component
 /// type identifier must be a valid Rust type path, supporting both simple names
-/// and module-qualified paths.
+/// and module-qualified paths. Deserialization applies the same validation and
+/// reports why a name is invalid.
 ///
 /// # Examples
 ///
@@ -103,9 +112,22 @@ use serde_json::Value;
 /// assert!(Component::new("123Invalid").is_none());
 /// assert!(Component::new("").is_none());
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 pub struct Component(String);
 
+impl<'de> Deserialize<'de> for Component {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let name = String::deserialize(deserializer)?;
+        Component::validate_name(&name).map_err(|err| {
+            serde::de::Error::custom(format!("invalid component name {:?}: {}", name, err))
+        })?;
+        Ok(Component(name))
+    }
+}
+
 impl Component {
     /// Creates a new Component with the given type identifier.
     ///
@@ -147,8 +169,177 @@ impl Component {
     pub fn as_str(&self) -> &str {
         &self.0
     }
+
+    /// Checks whether a string is a valid component name, explaining why not.
+    ///
+    /// A valid name is one or more segments separated by `::`, where each
+    /// segment is non-empty, starts with an ASCII letter or underscore, and
+    /// contains only ASCII letters, digits, and underscores. `Component::new`
+    /// accepts exactly the names for which this returns `Ok`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::{Component, ComponentNameError};
+    /// assert!(Component::validate_name("std::collections::HashMap").is_ok());
+    /// assert_eq!(
+    ///     Component::validate_name("123Invalid"),
+    ///     Err(ComponentNameError::LeadingDigit { segment: 0 })
+    /// );
+    /// ```
+    pub fn validate_name(name: &str) -> Result<(), ComponentNameError> {
+        if name.is_empty() {
+            return Err(ComponentNameError::Empty);
+        }
+        for (index, segment) in name.split("::").enumerate() {
+            validate_identifier(segment, index)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the `::`-separated segments of the component name.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::Component;
+    /// let component = Component::new("ghai::Issue").unwrap();
+    /// assert_eq!(component.parts(), vec!["ghai", "Issue"]);
+    /// ```
+    pub fn parts(&self) -> Vec<&str> {
+        self.0.split("::").collect()
+    }
+
+    /// Normalizes a module prefix such as `game`, `game::` or `game::*` to `game::`.
+    ///
+    /// Returns `None` if the prefix, without its trailing `::` or `::*`, is not a valid
+    /// component path.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::Component;
+    /// assert_eq!(Component::module_prefix("game::*").as_deref(), Some("game::"));
+    /// assert_eq!(Component::module_prefix("game::ai").as_deref(), Some("game::ai::"));
+    /// assert_eq!(Component::module_prefix("*"), None);
+    /// ```
+    pub fn module_prefix(prefix: &str) -> Option<String> {
+        let path = prefix.strip_suffix('*').unwrap_or(prefix);
+        let path = path.strip_suffix("::").unwrap_or(path);
+        Component::validate_name(path).ok()?;
+        Some(format!("{}::", path))
+    }
+
+    /// Returns true if this component lies under the module `prefix`.
+    ///
+    /// The prefix is normalized with [`Component::module_prefix`], so `game::*` matches
+    /// `game::Position` and `game::ai::Goal` but neither `game` nor `gameplay::Score`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::Component;
+    /// let position = Component::new("game::Position").unwrap();
+    /// assert!(position.has_prefix("game::*"));
+    /// assert!(!position.has_prefix("ui"));
+    /// ```
+    pub fn has_prefix(&self, prefix: &str) -> bool {
+        Component::module_prefix(prefix).is_some_and(|prefix| self.0.starts_with(&prefix))
+    }
+}
+
+/// How component definition names are compared for lookups and collisions.
+///
+/// Under [`ComponentNamePolicy::CaseInsensitive`], `Position` and `position` name the
+/// same definition: looking up either finds it, and defining the other is a conflict.
+/// Component instances are stored under the name of their definition, whichever case a
+/// request uses.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ComponentNamePolicy {
+    /// Names that differ only in case are distinct components
+    #[default]
+    CaseSensitive,
+    /// Names that differ only in case refer to the same component
+    CaseInsensitive,
+}
+
+impl ComponentNamePolicy {
+    /// Returns true if `a` and `b` name the same component under this policy.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::{Component, ComponentNamePolicy};
+    /// let upper = Component::new("Position").unwrap();
+    /// let lower = Component::new("position").unwrap();
+    /// assert!(!ComponentNamePolicy::CaseSensitive.same(&upper, &lower));
+    /// assert!(ComponentNamePolicy::CaseInsensitive.same(&upper, &lower));
+    /// ```
+    pub fn same(&self, a: &Component, b: &Component) -> bool {
+        match self {
+            ComponentNamePolicy::CaseSensitive => a == b,
+            ComponentNamePolicy::CaseInsensitive => a.as_str().eq_ignore_ascii_case(b.as_str()),
+        }
+    }
 }
 
+/// Router state for endpoints that look up component definitions by name.
+#[derive(Clone)]
+pub(crate) struct NamePolicyState {
+    pub(crate) pool: sqlx::PgPool,
+    pub(crate) policy: ComponentNamePolicy,
+}
+
+impl axum::extract::FromRef<NamePolicyState> for sqlx::PgPool {
+    fn from_ref(state: &NamePolicyState) -> Self {
+        state.pool.clone()
+    }
+}
+
+/// The reason a string is not a valid component name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ComponentNameError {
+    /// The name is the empty string
+    Empty,
+    /// A `::`-separated segment is empty, as in `foo::` or `a::::b`
+    EmptySegment {
+        /// Zero-based index of the offending segment
+        segment: usize,
+    },
+    /// A segment starts with a digit
+    LeadingDigit {
+        /// Zero-based index of the offending segment
+        segment: usize,
+    },
+    /// A segment contains a character other than ASCII letters, digits, or underscores
+    InvalidCharacter {
+        /// Zero-based index of the offending segment
+        segment: usize,
+        /// The first invalid character
+        character: char,
+    },
+}
+
+impl std::fmt::Display for ComponentNameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComponentNameError::Empty => write!(f, "component name is empty"),
+            ComponentNameError::EmptySegment { segment } => {
+                write!(f, "segment {} of component name is empty", segment)
+            }
+            ComponentNameError::LeadingDigit { segment } => {
+                write!(
+                    f,
+                    "segment {} of component name starts with a digit",
+                    segment
+                )
+            }
+            ComponentNameError::InvalidCharacter { segment, character } => write!(
+                f,
+                "segment {} of component name contains invalid character '{}'",
+                segment, character
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ComponentNameError {}
+
 /// Request structure for creating a new component instance.
 ///
 /// This structure is used when attaching component data to an entity via HTTP API.
@@ -184,8 +375,153 @@ pub struct ComponentListItem {
     pub component: Component,
     /// The component data
     pub data: Value,
+    /// When the component instance was created
+    pub created_at: DateTime<Utc>,
+    /// When the component instance was last updated
+    pub updated_at: DateTime<Utc>,
+    /// Incremented on every write to the component instance
+    pub version: i64,
+}
+
+impl From<crate::sql::component::ComponentRecord> for ComponentListItem {
+    fn from(record: crate::sql::component::ComponentRecord) -> Self {
+        ComponentListItem {
+            component: record.component,
+            data: record.data,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            version: record.version,
+        }
+    }
+}
+
+/// Request body for replacing the complete set of components on an entity.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplaceComponentsRequest {
+    /// The desired component data, keyed by component name; components not listed are removed
+    pub components: BTreeMap<String, Value>,
+}
+
+/// The changes made by replacing the components on an entity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplaceComponentsResponse {
+    /// The entity whose components were replaced
+    pub entity: crate::Entity,
+    /// Components that were newly attached
+    pub created: Vec<Component>,
+    /// Components whose data changed
+    pub updated: Vec<Component>,
+    /// Components that already held the requested data
+    pub unchanged: Vec<Component>,
+    /// Components that were removed because the request did not list them
+    pub deleted: Vec<Component>,
+}
+
+/// Request body for fetching the components of several entities at once.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentQueryRequest {
+    /// The entities whose components should be returned
+    pub entities: Vec<crate::Entity>,
+    /// If present, only these component types are returned
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub components: Option<Vec<Component>>,
+}
+
+/// Default number of component instances returned per page.
+const DEFAULT_COMPONENT_PAGE_LIMIT: i64 = 100;
+
+/// Maximum number of component instances that may be requested per page.
+const MAX_COMPONENT_PAGE_LIMIT: i64 = 1000;
+
+/// Query parameters for paging through all component instances.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ListComponentsQuery {
+    /// Maximum number of items to return (default 100, at most 1000)
+    pub limit: Option<i64>,
+    /// Number of items to skip (default 0)
+    pub offset: Option<i64>,
+}
+
+/// Query parameters for listing or deleting the component instances on an entity.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntityComponentsQuery {
+    /// Restricts the request to components under this module prefix, e.g. `game::*`
+    pub prefix: Option<String>,
+}
+
+/// Query parameters for fetching a single component instance.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GetComponentQuery {
+    /// Comma-separated JSON Pointers selecting the parts of the data to return,
+    /// e.g. `/x,/position/y`; the whole instance is returned when absent
+    pub fields: Option<String>,
+}
+
+/// A page of component instances across all entities.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentPage {
+    /// Entity string and component pairs in this page
+    pub items: Vec<(String, ComponentListItem)>,
+    /// Total number of component instances
+    pub total: i64,
+    /// The limit used for this page
+    pub limit: i64,
+    /// The offset used for this page
+    pub offset: i64,
+}
+
+/// The kind of change made to a component instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentChangeKind {
+    /// The component instance was created
+    Created,
+    /// The component instance's data was replaced
+    Updated,
+    /// The component instance was deleted
+    Deleted,
+}
+
+impl ComponentChangeKind {
+    /// Returns the name used for this kind in event streams.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComponentChangeKind::Created => "created",
+            ComponentChangeKind::Updated => "updated",
+            ComponentChangeKind::Deleted => "deleted",
+        }
+    }
+}
+
+/// A committed change to a component instance, as streamed to subscribers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComponentChangeEvent {
+    /// The entity whose component changed
+    pub entity: crate::Entity,
+    /// The component type that changed
+    pub component: Component,
+    /// What happened to the component instance
+    pub kind: ComponentChangeKind,
+    /// The new component data; absent for deletions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
 }
 
+/// Query parameters selecting which component changes to stream.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ComponentEventsQuery {
+    /// Only stream changes to this entity
+    pub entity: Option<String>,
+    /// Only stream changes to this component type
+    pub component: Option<String>,
+}
+
+/// Postgres notification channel carrying component changes.
+const COMPONENT_CHANGES_CHANNEL: &str = "component_changes";
+
+/// Number of events buffered per subscriber before the stream applies backpressure.
+const COMPONENT_EVENT_BUFFER: usize = 64;
+
 /// Validates that a string is a valid Rust identifier.
 ///
 /// A valid Rust identifier must:
@@ -206,20 +542,32 @@ pub struct ComponentListItem {
 /// assert!(Component::new("foo-bar").is_none());
 /// ```
 fn is_valid_rust_identifier(s: &str) -> bool {
-    if s.is_empty() {
-        return false;
-    }
+    validate_identifier(s, 0).is_ok()
+}
 
+/// Validates a single identifier, reporting failures against `segment`.
+fn validate_identifier(s: &str, segment: usize) -> Result<(), ComponentNameError> {
     let mut chars = s.chars();
-    let first = chars.next().unwrap();
+    let first = chars
+        .next()
+        .ok_or(ComponentNameError::EmptySegment { segment })?;
 
     // First character must be a letter or underscore
+    if first.is_ascii_digit() {
+        return Err(ComponentNameError::LeadingDigit { segment });
+    }
     if !first.is_ascii_alphabetic() && first != '_' {
-        return false;
+        return Err(ComponentNameError::InvalidCharacter {
+            segment,
+            character: first,
+        });
     }
 
     // Remaining characters must be alphanumeric or underscore
-    chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    match chars.find(|c| !c.is_ascii_alphanumeric() && *c != '_') {
+        Some(character) => Err(ComponentNameError::InvalidCharacter { segment, character }),
+        None => Ok(()),
+    }
 }
 
 /// Validates that a string is a valid Rust type path.
@@ -254,12 +602,46 @@ fn is_valid_rust_type_path(s: &str) -> bool {
         .all(|segment| is_valid_rust_identifier(segment))
 }
 
+/// Applies a JSON Merge Patch (RFC 7396) to `target` in place.
+///
+/// Object members in `patch` are merged recursively, `null` members delete the
+/// corresponding key, and any non-object patch replaces `target` outright.
+///
+/// # Examples
+/// ```rust
+/// # use stigmergy::apply_merge_patch;
+/// # use serde_json::json;
+/// let mut data = json!({"x": 1, "z": 3});
+/// apply_merge_patch(&mut data, &json!({"z": null}));
+/// assert_eq!(data, json!({"x": 1}));
+/// ```
+pub fn apply_merge_patch(target: &mut Value, patch: &Value) {
+    let Value::Object(patch) = patch else {
+        *target = patch.clone();
+        return;
+    };
+    if !target.is_object() {
+        *target = Value::Object(serde_json::Map::new());
+    }
+    let Value::Object(target) = target else {
+        unreachable!("target was just made an object");
+    };
+    for (key, value) in patch {
+        if value.is_null() {
+            target.remove(key);
+        } else {
+            apply_merge_patch(target.entry(key.clone()).or_insert(Value::Null), value);
+        }
+    }
+}
+
 ////////////////////////////////////////////// Routes //////////////////////////////////////////////
 
-/// Lists all component instances for a specific entity.
+/// Lists the component instances for a specific entity, optionally under a module prefix.
 async fn get_components_for_entity(
     State(pool): State<sqlx::PgPool>,
     Path(entity_str): Path<String>,
+    Query(params): Query<EntityComponentsQuery>,
 ) -> Result<Json<Vec<ComponentListItem>>, (StatusCode, &'static str)> {
     let entity: crate::Entity = entity_str
         .parse()
@@ -272,7 +654,13 @@ async fn get_components_for_entity(
         )
     })?;
 
-    match crate::sql::component::list_for_entity(&mut tx, &entity).await {
+    let components = match &params.prefix {
+        Some(prefix) => {
+            crate::sql::component::list_for_entity_with_prefix(&mut tx, &entity, prefix).await
+        }
+        None => crate::sql::component::list_for_entity(&mut tx, &entity).await,
+    };
+    match components {
         Ok(components) => {
             tx.commit().await.map_err(|_e| {
                 (
@@ -282,10 +670,13 @@ async fn get_components_for_entity(
             })?;
             let items: Vec<ComponentListItem> = components
                 .into_iter()
-                .map(|(component, data)| ComponentListItem { component, data })
+                .map(ComponentListItem::from)
                 .collect();
             Ok(Json(items))
         }
+        Err(crate::DataStoreError::InvalidInput(_)) => {
+            Err((StatusCode::BAD_REQUEST, "invalid component prefix"))
+        }
         Err(_) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             "failed to list components",
@@ -293,10 +684,20 @@ async fn get_components_for_entity(
     }
 }
 
-/// Lists all component instances in the system.
+/// Lists one page of component instances in the system.
 async fn get_all_components(
     State(pool): State<sqlx::PgPool>,
-) -> Result<Json<Vec<(String, ComponentListItem)>>, (StatusCode, &'static str)> {
+    Query(params): Query<ListComponentsQuery>,
+) -> Result<Json<ComponentPage>, (StatusCode, &'static str)> {
+    let limit = params.limit.unwrap_or(DEFAULT_COMPONENT_PAGE_LIMIT);
+    let offset = params.offset.unwrap_or(0);
+    if !(1..=MAX_COMPONENT_PAGE_LIMIT).contains(&limit) {
+        return Err((StatusCode::BAD_REQUEST, "limit must be between 1 and 1000"));
+    }
+    if offset < 0 {
+        return Err((StatusCode::BAD_REQUEST, "offset must not be negative"));
+    }
+
     let mut tx = pool.begin().await.map_err(|_e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -304,7 +705,16 @@ async fn get_all_components(
         )
     })?;
 
-    match crate::sql::component::list_all(&mut tx).await {
+    let total = crate::sql::component::count_all(&mut tx)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to count components",
+            )
+        })?;
+
+    match crate::sql::component::list_page(&mut tx, limit, offset).await {
         Ok(components) => {
             tx.commit().await.map_err(|_e| {
                 (
@@ -314,11 +724,14 @@ async fn get_all_components(
             })?;
             let items: Vec<(String, ComponentListItem)> = components
                 .into_iter()
-                .map(|((entity, component), data)| {
-                    (entity.to_string(), ComponentListItem { component, data })
-                })
+                .map(|record| (record.entity.to_string(), ComponentListItem::from(record)))
                 .collect();
-            Ok(Json(items))
+            Ok(Json(ComponentPage {
+                items,
+                total,
+                limit,
+                offset,
+            }))
         }
         Err(_) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -327,9 +740,172 @@ async fn get_all_components(
     }
 }
 
+/// Streams committed component changes as Server-Sent Events.
+///
+/// Each subscriber listens on the `component_changes` notification channel, so
+/// only committed writes are delivered, whichever router or process made them.
+/// Events are named after their [`ComponentChangeKind`] and carry a
+/// [`ComponentChangeEvent`] as JSON.
+async fn component_events(
+    State(pool): State<sqlx::PgPool>,
+    Query(params): Query<ComponentEventsQuery>,
+) -> Result<Sse<ReceiverStream<Result<Event, Infallible>>>, (StatusCode, &'static str)> {
+    let entity = params
+        .entity
+        .map(|entity| entity.parse::<crate::Entity>())
+        .transpose()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID"))?;
+    let component = params
+        .component
+        .map(|component| Component::new(component).ok_or(()))
+        .transpose()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid component name"))?;
+
+    let mut listener = PgListener::connect_with(&pool).await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to subscribe to component changes",
+        )
+    })?;
+    listener
+        .listen(COMPONENT_CHANGES_CHANNEL)
+        .await
+        .map_err(|_e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to subscribe to component changes",
+            )
+        })?;
+
+    let (sender, receiver) = tokio::sync::mpsc::channel(COMPONENT_EVENT_BUFFER);
+    tokio::spawn(async move {
+        loop {
+            let notification = tokio::select! {
+                notification = listener.recv() => match notification {
+                    Ok(notification) => notification,
+                    Err(e) => {
+                        eprintln!("component change listener failed: {}", e);
+                        break;
+                    }
+                },
+                _ = sender.closed() => break,
+            };
+            let Ok(mut event) =
+                serde_json::from_str::<ComponentChangeEvent>(notification.payload())
+            else {
+                continue;
+            };
+            if entity.is_some_and(|entity| entity != event.entity)
+                || component
+                    .as_ref()
+                    .is_some_and(|component| *component != event.component)
+            {
+                continue;
+            }
+            // Large payloads are sent without data; read the current value instead.
+            if event.kind != ComponentChangeKind::Deleted
+                && event.data.is_none()
+                && let Ok(mut tx) = pool.begin().await
+            {
+                event.data = crate::sql::component::get(&mut tx, &event.entity, &event.component)
+                    .await
+                    .ok()
+                    .flatten();
+                let _ = tx.commit().await;
+            }
+            let Ok(sse_event) = Event::default()
+                .event(event.kind.as_str())
+                .json_data(&event)
+            else {
+                continue;
+            };
+            if sender.send(Ok(sse_event)).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    Ok(Sse::new(ReceiverStream::new(receiver)).keep_alive(KeepAlive::default()))
+}
+
+/// Lists the components of many entities in one request.
+async fn query_components(
+    State(pool): State<sqlx::PgPool>,
+    Json(request): Json<ComponentQueryRequest>,
+) -> Result<Json<HashMap<String, Vec<ComponentListItem>>>, (StatusCode, &'static str)> {
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction",
+        )
+    })?;
+
+    match crate::sql::component::list_for_entities(
+        &mut tx,
+        &request.entities,
+        request.components.as_deref(),
+    )
+    .await
+    {
+        Ok(instances) => {
+            tx.commit().await.map_err(|_e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to commit transaction",
+                )
+            })?;
+            let mut result: HashMap<String, Vec<ComponentListItem>> = request
+                .entities
+                .iter()
+                .map(|entity| (entity.to_string(), Vec::new()))
+                .collect();
+            for record in instances {
+                result
+                    .entry(record.entity.to_string())
+                    .or_default()
+                    .push(ComponentListItem::from(record));
+            }
+            Ok(Json(result))
+        }
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to query components",
+        )),
+    }
+}
+
+/// Formats a component version as an entity tag.
+fn version_etag(version: i64) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Reads the component version a writer expects from its `If-Match` header.
+///
+/// Returns `Ok(None)` when the header is absent or is the `*` wildcard.
+fn expected_version(headers: &HeaderMap) -> Result<Option<i64>, (StatusCode, String)> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+    let invalid = || {
+        (
+            StatusCode::BAD_REQUEST,
+            "invalid If-Match header".to_string(),
+        )
+    };
+    let value = value.to_str().map_err(|_| invalid())?.trim();
+    if value == "*" {
+        return Ok(None);
+    }
+    value
+        .trim_matches('"')
+        .parse::<i64>()
+        .map(Some)
+        .map_err(|_| invalid())
+}
+
 /// Creates a new component instance for an entity.
 async fn create_component_for_entity(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path(entity_str): Path<String>,
     Json(request): Json<CreateComponentRequest>,
 ) -> Result<Json<CreateComponentResponse>, (StatusCode, String)> {
@@ -344,17 +920,23 @@ async fn create_component_for_entity(
         )
     })?;
 
+    let component =
+        crate::sql::component_definition::resolve_name(&mut tx, &request.component, policy)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to retrieve component definition".to_string(),
+                )
+            })?;
+
     // Validate the component data against the schema
-    let definition = match crate::sql::component_definition::get(&mut tx, &request.component).await
-    {
+    let definition = match crate::sql::component_definition::get(&mut tx, &component).await {
         Ok(Some(def_record)) => def_record.definition,
         Ok(None) => {
             return Err((
                 StatusCode::NOT_FOUND,
-                format!(
-                    "component definition not found: {}",
-                    request.component.as_str()
-                ),
+                format!("component definition not found: {}", component.as_str()),
             ));
         }
         Err(_) => {
@@ -372,7 +954,7 @@ async fn create_component_for_entity(
         ));
     }
 
-    match crate::sql::component::create(&mut tx, &entity, &request.component, &request.data).await {
+    match crate::sql::component::create(&mut tx, &entity, &component, &request.data).await {
         Ok(()) => {
             tx.commit().await.map_err(|_e| {
                 (
@@ -382,7 +964,7 @@ async fn create_component_for_entity(
             })?;
             let response = CreateComponentResponse {
                 entity,
-                component: request.component,
+                component,
                 data: request.data,
             };
             Ok(Json(response))
@@ -402,10 +984,14 @@ async fn create_component_for_entity(
 }
 
 /// Gets a specific component instance for an entity.
+///
+/// The component's version is returned in the `ETag` header. A `fields` query
+/// parameter projects the data onto the listed JSON Pointers; see [`project_fields`].
 async fn get_component_by_id_for_entity(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path((entity_str, component_str)): Path<(String, String)>,
-) -> Result<Json<Value>, (StatusCode, &'static str)> {
+    Query(params): Query<GetComponentQuery>,
+) -> Result<([(HeaderName, String); 1], Json<Value>), (StatusCode, &'static str)> {
     let entity: crate::Entity = entity_str
         .parse()
         .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID"))?;
@@ -420,15 +1006,29 @@ async fn get_component_by_id_for_entity(
         )
     })?;
 
-    match crate::sql::component::get(&mut tx, &entity, &component).await {
-        Ok(Some(data)) => {
+    let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component definition",
+            )
+        })?;
+
+    match crate::sql::component::get_with_version(&mut tx, &entity, &component).await {
+        Ok(Some((data, version))) => {
             tx.commit().await.map_err(|_e| {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "failed to commit transaction",
                 )
             })?;
-            Ok(Json(data))
+            let data = match params.fields {
+                Some(fields) => project_fields(&data, &fields)
+                    .map_err(|_| (StatusCode::BAD_REQUEST, "invalid fields parameter"))?,
+                None => data,
+            };
+            Ok(([(header::ETAG, version_etag(version))], Json(data)))
         }
         Ok(None) => Err((StatusCode::NOT_FOUND, "component instance not found")),
         Err(_) => Err((
@@ -438,15 +1038,83 @@ async fn get_component_by_id_for_entity(
     }
 }
 
+/// Projects `data` onto the comma-separated JSON Pointers in `fields`.
+///
+/// Each selected value is copied to the same location in the result, so `/a/b` yields
+/// `{"a": {"b": ...}}`. Pointers naming absent members are skipped. A pointer that does
+/// not start with `/`, contains an invalid `~` escape, or descends into an array is an
+/// error.
+fn project_fields(data: &Value, fields: &str) -> Result<Value, String> {
+    let mut projected = Value::Object(serde_json::Map::new());
+    'fields: for pointer in fields.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        let tokens = pointer
+            .strip_prefix('/')
+            .ok_or_else(|| format!("field '{}' is not a JSON Pointer", pointer))?
+            .split('/')
+            .map(|token| unescape_pointer_token(token, pointer))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut source = data;
+        let mut target = &mut projected;
+        for (index, token) in tokens.iter().enumerate() {
+            let member = match source {
+                Value::Object(object) => match object.get(token) {
+                    Some(member) => member,
+                    None => continue 'fields,
+                },
+                Value::Array(_) => {
+                    return Err(format!("field '{}' descends into an array", pointer));
+                }
+                _ => continue 'fields,
+            };
+            let target_object = target
+                .as_object_mut()
+                .expect("projection targets mirror source objects");
+            if index + 1 == tokens.len() {
+                target_object.insert(token.clone(), member.clone());
+                continue 'fields;
+            }
+            target = target_object
+                .entry(token.clone())
+                .or_insert_with(|| Value::Object(serde_json::Map::new()));
+            source = member;
+        }
+    }
+    Ok(projected)
+}
+
+/// Decodes the `~1` and `~0` escapes of a single JSON Pointer token.
+fn unescape_pointer_token(token: &str, pointer: &str) -> Result<String, String> {
+    let mut decoded = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        if c != '~' {
+            decoded.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('0') => decoded.push('~'),
+            Some('1') => decoded.push('/'),
+            _ => return Err(format!("field '{}' has an invalid '~' escape", pointer)),
+        }
+    }
+    Ok(decoded)
+}
+
 /// Updates a specific component instance for an entity.
+///
+/// An `If-Match` header naming a version makes the write conditional: if the
+/// stored version differs the update is rejected with `409 Conflict`.
 async fn update_component_by_id_for_entity(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path((entity_str, component_str)): Path<(String, String)>,
+    headers: HeaderMap,
     Json(data): Json<Value>,
-) -> Result<Json<Value>, (StatusCode, String)> {
+) -> Result<([(HeaderName, String); 1], Json<Value>), (StatusCode, String)> {
     let entity: crate::Entity = entity_str
         .parse()
         .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID".to_string()))?;
+    let expected_version = expected_version(&headers)?;
 
     let component = Component::new(component_str).ok_or((
         StatusCode::BAD_REQUEST,
@@ -460,6 +1128,15 @@ async fn update_component_by_id_for_entity(
         )
     })?;
 
+    let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component definition".to_string(),
+            )
+        })?;
+
     // Validate the component data against the schema
     let definition = match crate::sql::component_definition::get(&mut tx, &component).await {
         Ok(Some(def_record)) => def_record.definition,
@@ -484,20 +1161,35 @@ async fn update_component_by_id_for_entity(
         ));
     }
 
-    match crate::sql::component::update(&mut tx, &entity, &component, &data).await {
-        Ok(true) => {
+    match crate::sql::component::update_versioned(
+        &mut tx,
+        &entity,
+        &component,
+        &data,
+        expected_version,
+    )
+    .await
+    {
+        Ok(Some(version)) => {
             tx.commit().await.map_err(|_e| {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "failed to commit transaction".to_string(),
                 )
             })?;
-            Ok(Json(data))
+            Ok(([(header::ETAG, version_etag(version))], Json(data)))
         }
-        Ok(false) => Err((
+        Ok(None) => Err((
             StatusCode::NOT_FOUND,
             "component instance not found".to_string(),
         )),
+        Err(crate::DataStoreError::VersionMismatch { expected, actual }) => Err((
+            StatusCode::CONFLICT,
+            format!(
+                "component version mismatch: expected {}, found {}",
+                expected, actual
+            ),
+        )),
         Err(_) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             "failed to update component instance".to_string(),
@@ -505,52 +1197,246 @@ async fn update_component_by_id_for_entity(
     }
 }
 
-/// Deletes a specific component instance for an entity.
-async fn delete_component_by_id_for_entity(
-    State(pool): State<sqlx::PgPool>,
+/// Applies a JSON Merge Patch (RFC 7396) to a specific component instance.
+///
+/// The patched data is re-validated against the component definition before
+/// it is stored. `If-Match` is honored as for a full update.
+async fn patch_component_by_id_for_entity(
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path((entity_str, component_str)): Path<(String, String)>,
-) -> Result<StatusCode, (StatusCode, &'static str)> {
+    headers: HeaderMap,
+    Json(patch): Json<Value>,
+) -> Result<([(HeaderName, String); 1], Json<Value>), (StatusCode, String)> {
     let entity: crate::Entity = entity_str
         .parse()
-        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID"))?;
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID".to_string()))?;
+    let expected_version = expected_version(&headers)?;
 
-    let component =
-        Component::new(component_str).ok_or((StatusCode::BAD_REQUEST, "invalid component name"))?;
+    let component = Component::new(component_str).ok_or((
+        StatusCode::BAD_REQUEST,
+        "invalid component name".to_string(),
+    ))?;
 
     let mut tx = pool.begin().await.map_err(|_e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
-            "failed to begin transaction",
+            "failed to begin transaction".to_string(),
         )
     })?;
 
-    match crate::sql::component::delete(&mut tx, &entity, &component).await {
-        Ok(true) => {
-            tx.commit().await.map_err(|_e| {
-                (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    "failed to commit transaction",
-                )
-            })?;
-            Ok(StatusCode::NO_CONTENT)
-        }
-        Ok(false) => Err((StatusCode::NOT_FOUND, "component instance not found")),
-        Err(_) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            "failed to delete component instance",
-        )),
-    }
-}
+    let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component definition".to_string(),
+            )
+        })?;
 
-/// Deletes all component instances for an entity.
-async fn delete_components_for_entity(
-    State(pool): State<sqlx::PgPool>,
-    Path(entity_str): Path<String>,
-) -> Result<StatusCode, (StatusCode, &'static str)> {
-    let entity: crate::Entity = entity_str
-        .parse()
+    let mut data = match crate::sql::component::get(&mut tx, &entity, &component).await {
+        Ok(Some(data)) => data,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                "component instance not found".to_string(),
+            ));
+        }
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component instance".to_string(),
+            ));
+        }
+    };
+    apply_merge_patch(&mut data, &patch);
+
+    // Validate the patched data against the schema
+    let definition = match crate::sql::component_definition::get(&mut tx, &component).await {
+        Ok(Some(def_record)) => def_record.definition,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                format!("component definition not found: {}", component.as_str()),
+            ));
+        }
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component definition".to_string(),
+            ));
+        }
+    };
+
+    if let Err(e) = definition.validate_component_data(&data) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("component data validation failed: {}", e),
+        ));
+    }
+
+    match crate::sql::component::update_versioned(
+        &mut tx,
+        &entity,
+        &component,
+        &data,
+        expected_version,
+    )
+    .await
+    {
+        Ok(Some(version)) => {
+            tx.commit().await.map_err(|_e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to commit transaction".to_string(),
+                )
+            })?;
+            Ok(([(header::ETAG, version_etag(version))], Json(data)))
+        }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            "component instance not found".to_string(),
+        )),
+        Err(crate::DataStoreError::VersionMismatch { expected, actual }) => Err((
+            StatusCode::CONFLICT,
+            format!(
+                "component version mismatch: expected {}, found {}",
+                expected, actual
+            ),
+        )),
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to update component instance".to_string(),
+        )),
+    }
+}
+
+/// Applies a JSON Patch (RFC 6902) to a specific component instance.
+///
+/// A failed patch operation returns `409 Conflict` and leaves the stored data
+/// untouched; the patched data is re-validated against the component definition.
+/// `If-Match` is honored as for a full update.
+async fn json_patch_component_by_id_for_entity(
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
+    Path((entity_str, component_str)): Path<(String, String)>,
+    headers: HeaderMap,
+    Json(patch): Json<Vec<PatchOperation>>,
+) -> Result<([(HeaderName, String); 1], Json<Value>), (StatusCode, String)> {
+    let entity: crate::Entity = entity_str
+        .parse()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID".to_string()))?;
+    let expected_version = expected_version(&headers)?;
+
+    let component = Component::new(component_str).ok_or((
+        StatusCode::BAD_REQUEST,
+        "invalid component name".to_string(),
+    ))?;
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction".to_string(),
+        )
+    })?;
+
+    let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component definition".to_string(),
+            )
+        })?;
+
+    let data = match crate::sql::component::get(&mut tx, &entity, &component).await {
+        Ok(Some(data)) => data,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                "component instance not found".to_string(),
+            ));
+        }
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component instance".to_string(),
+            ));
+        }
+    };
+    let data =
+        apply_json_patch(&data, &patch).map_err(|e| (StatusCode::CONFLICT, e.to_string()))?;
+
+    // Validate the patched data against the schema
+    let definition = match crate::sql::component_definition::get(&mut tx, &component).await {
+        Ok(Some(def_record)) => def_record.definition,
+        Ok(None) => {
+            return Err((
+                StatusCode::NOT_FOUND,
+                format!("component definition not found: {}", component.as_str()),
+            ));
+        }
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component definition".to_string(),
+            ));
+        }
+    };
+
+    if let Err(e) = definition.validate_component_data(&data) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("component data validation failed: {}", e),
+        ));
+    }
+
+    match crate::sql::component::update_versioned(
+        &mut tx,
+        &entity,
+        &component,
+        &data,
+        expected_version,
+    )
+    .await
+    {
+        Ok(Some(version)) => {
+            tx.commit().await.map_err(|_e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to commit transaction".to_string(),
+                )
+            })?;
+            Ok(([(header::ETAG, version_etag(version))], Json(data)))
+        }
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            "component instance not found".to_string(),
+        )),
+        Err(crate::DataStoreError::VersionMismatch { expected, actual }) => Err((
+            StatusCode::CONFLICT,
+            format!(
+                "component version mismatch: expected {}, found {}",
+                expected, actual
+            ),
+        )),
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to update component instance".to_string(),
+        )),
+    }
+}
+
+/// Deletes a specific component instance for an entity.
+async fn delete_component_by_id_for_entity(
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
+    Path((entity_str, component_str)): Path<(String, String)>,
+) -> Result<StatusCode, (StatusCode, &'static str)> {
+    let entity: crate::Entity = entity_str
+        .parse()
         .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID"))?;
 
+    let component =
+        Component::new(component_str).ok_or((StatusCode::BAD_REQUEST, "invalid component name"))?;
+
     let mut tx = pool.begin().await.map_err(|_e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -558,8 +1444,17 @@ async fn delete_components_for_entity(
         )
     })?;
 
-    match crate::sql::component::delete_all_for_entity(&mut tx, &entity).await {
-        Ok(_) => {
+    let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to retrieve component definition",
+            )
+        })?;
+
+    match crate::sql::component::delete(&mut tx, &entity, &component).await {
+        Ok(true) => {
             tx.commit().await.map_err(|_e| {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -568,6 +1463,53 @@ async fn delete_components_for_entity(
             })?;
             Ok(StatusCode::NO_CONTENT)
         }
+        Ok(false) => Err((StatusCode::NOT_FOUND, "component instance not found")),
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to delete component instance",
+        )),
+    }
+}
+
+/// Deletes the component instances for an entity, optionally only those under a module
+/// prefix.
+async fn delete_components_for_entity(
+    State(pool): State<sqlx::PgPool>,
+    Path(entity_str): Path<String>,
+    Query(params): Query<EntityComponentsQuery>,
+) -> Result<StatusCode, (StatusCode, &'static str)> {
+    let entity: crate::Entity = entity_str
+        .parse()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID"))?;
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction",
+        )
+    })?;
+
+    let deleted = match &params.prefix {
+        Some(prefix) => crate::sql::component::delete_by_prefix(&mut tx, &entity, prefix)
+            .await
+            .map(|_| ()),
+        None => crate::sql::component::delete_all_for_entity(&mut tx, &entity)
+            .await
+            .map(|_| ()),
+    };
+    match deleted {
+        Ok(()) => {
+            tx.commit().await.map_err(|_e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to commit transaction",
+                )
+            })?;
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Err(crate::DataStoreError::InvalidInput(_)) => {
+            Err((StatusCode::BAD_REQUEST, "invalid component prefix"))
+        }
         Err(_) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
             "failed to delete component instances",
@@ -575,33 +1517,192 @@ async fn delete_components_for_entity(
     }
 }
 
+/// Replaces the complete set of components on an entity.
+///
+/// Listed components are validated against their definitions and upserted;
+/// stored components that are not listed are deleted.  Components whose data
+/// is unchanged are not rewritten.  All changes happen in one transaction, so
+/// a single invalid component leaves the entity untouched.
+async fn replace_components_for_entity(
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
+    Path(entity_str): Path<String>,
+    Json(request): Json<ReplaceComponentsRequest>,
+) -> Result<Json<ReplaceComponentsResponse>, (StatusCode, String)> {
+    let entity: crate::Entity = entity_str
+        .parse()
+        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity ID".to_string()))?;
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction".to_string(),
+        )
+    })?;
+
+    match crate::sql::entity::exists(&mut tx, &entity).await {
+        Ok(true) => {}
+        Ok(false) => return Err((StatusCode::NOT_FOUND, "entity not found".to_string())),
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to check entity".to_string(),
+            ));
+        }
+    }
+
+    let mut response = ReplaceComponentsResponse {
+        entity,
+        created: Vec::new(),
+        updated: Vec::new(),
+        unchanged: Vec::new(),
+        deleted: Vec::new(),
+    };
+
+    let mut listed = std::collections::HashSet::new();
+    for (name, data) in &request.components {
+        let component = Component::new(name.as_str()).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("invalid component name: {}", name),
+            )
+        })?;
+        let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to retrieve component definition".to_string(),
+                )
+            })?;
+        listed.insert(component.clone());
+        let definition = match crate::sql::component_definition::get(&mut tx, &component).await {
+            Ok(Some(def_record)) => def_record.definition,
+            Ok(None) => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    format!("component definition not found: {}", name),
+                ));
+            }
+            Err(_) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to retrieve component definition".to_string(),
+                ));
+            }
+        };
+        if let Err(e) = definition.validate_component_data(data) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("component data validation failed for {}: {}", name, e),
+            ));
+        }
+        match crate::sql::component::upsert(&mut tx, &entity, &component, data).await {
+            Ok(outcome) if outcome.created() => response.created.push(component),
+            Ok(outcome) if outcome.changed() => response.updated.push(component),
+            Ok(_) => response.unchanged.push(component),
+            Err(_) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to upsert component instance".to_string(),
+                ));
+            }
+        }
+    }
+
+    let existing = crate::sql::component::list_for_entity(&mut tx, &entity)
+        .await
+        .map_err(|_| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to list components".to_string(),
+            )
+        })?;
+    for record in existing {
+        if listed.contains(&record.component) {
+            continue;
+        }
+        if crate::sql::component::delete(&mut tx, &entity, &record.component)
+            .await
+            .is_err()
+        {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to delete component instance".to_string(),
+            ));
+        }
+        response.deleted.push(record.component);
+    }
+
+    tx.commit().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to commit transaction".to_string(),
+        )
+    })?;
+    Ok(Json(response))
+}
+
 ////////////////////////////////////////////// Router //////////////////////////////////////////////
 
 /// Creates an Axum router with component instance management endpoints.
 pub fn create_component_instance_router(pool: sqlx::PgPool) -> Router {
+    create_component_instance_router_with_policy(pool, ComponentNamePolicy::default())
+}
+
+/// Creates the component instance router, resolving component names according to `policy`.
+///
+/// Under [`ComponentNamePolicy::CaseInsensitive`], a component named in a request path or
+/// body is resolved to the stored name of its definition before the instance is read or
+/// written, so `Position` and `position` address the same instance.
+pub fn create_component_instance_router_with_policy(
+    pool: sqlx::PgPool,
+    policy: ComponentNamePolicy,
+) -> Router {
     Router::new()
         .route("/component", get(get_all_components))
+        .route("/component/query", axum::routing::post(query_components))
+        .route("/component/events", get(component_events))
         .route(
             "/entity/:entity_id/component",
-            get(get_components_for_entity).delete(delete_components_for_entity),
+            get(get_components_for_entity)
+                .put(replace_components_for_entity)
+                .delete(delete_components_for_entity),
         )
         .route(
             "/entity/:entity_id/component/:component_id",
             get(get_component_by_id_for_entity)
                 .put(update_component_by_id_for_entity)
+                .patch(patch_component_by_id_for_entity)
                 .delete(delete_component_by_id_for_entity),
         )
         .route(
             "/entity/:entity_id/component",
             axum::routing::post(create_component_for_entity),
         )
-        .with_state(pool)
+        .route(
+            "/entity/:entity_id/component/:component_id/jsonpatch",
+            axum::routing::patch(json_patch_component_by_id_for_entity),
+        )
+        .with_state(NamePolicyState { pool, policy })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn component_deserialize_validates_name() {
+        let component: Component = serde_json::from_str(r#""ghai::Issue""#).unwrap();
+        assert_eq!(component.as_str(), "ghai::Issue");
+
+        let err = serde_json::from_str::<Component>(r#""ghai::9Issue""#).unwrap_err();
+        println!("component_deserialize_validates_name response: {}", err);
+        assert!(
+            err.to_string()
+                .starts_with(r#"invalid component name "ghai::9Issue": "#)
+        );
+    }
+
     #[test]
     fn valid_rust_identifier_simple() {
         assert!(is_valid_rust_identifier("foo"));
@@ -645,6 +1746,100 @@ mod tests {
         assert!(!is_valid_rust_type_path("foo-bar::baz"));
     }
 
+    #[test]
+    fn component_validate_name_errors() {
+        assert_eq!(Component::validate_name("a::b_1::C"), Ok(()));
+        assert_eq!(Component::validate_name(""), Err(ComponentNameError::Empty));
+        assert_eq!(
+            Component::validate_name("123Invalid"),
+            Err(ComponentNameError::LeadingDigit { segment: 0 })
+        );
+        assert_eq!(
+            Component::validate_name("foo::"),
+            Err(ComponentNameError::EmptySegment { segment: 1 })
+        );
+        assert_eq!(
+            Component::validate_name("foo::bar-baz"),
+            Err(ComponentNameError::InvalidCharacter {
+                segment: 1,
+                character: '-'
+            })
+        );
+        assert_eq!(
+            Component::validate_name("-foo"),
+            Err(ComponentNameError::InvalidCharacter {
+                segment: 0,
+                character: '-'
+            })
+        );
+    }
+
+    #[test]
+    fn component_validate_name_agrees_with_new() {
+        for name in [
+            "", "::", "foo::", "::foo", "123::foo", "foo-bar", "a::b", "_x",
+        ] {
+            assert_eq!(
+                Component::validate_name(name).is_ok(),
+                Component::new(name).is_some(),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn component_parts() {
+        assert_eq!(Component::new("a::b").unwrap().parts(), vec!["a", "b"]);
+        assert_eq!(
+            Component::new("Position").unwrap().parts(),
+            vec!["Position"]
+        );
+    }
+
+    #[test]
+    fn merge_patch_removes_null_members() {
+        let mut data = serde_json::json!({"x": 1, "z": 3});
+        apply_merge_patch(&mut data, &serde_json::json!({"z": null}));
+        assert_eq!(data, serde_json::json!({"x": 1}));
+    }
+
+    #[test]
+    fn merge_patch_rfc7396_examples() {
+        let cases = [
+            (r#"{"a":"b"}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"b":"c"}"#, r#"{"a":"b","b":"c"}"#),
+            (r#"{"a":"b"}"#, r#"{"a":null}"#, r#"{}"#),
+            (r#"{"a":"b","b":"c"}"#, r#"{"a":null}"#, r#"{"b":"c"}"#),
+            (r#"{"a":["b"]}"#, r#"{"a":"c"}"#, r#"{"a":"c"}"#),
+            (r#"{"a":"c"}"#, r#"{"a":["b"]}"#, r#"{"a":["b"]}"#),
+            (
+                r#"{"a":{"b":"c"}}"#,
+                r#"{"a":{"b":"d","c":null}}"#,
+                r#"{"a":{"b":"d"}}"#,
+            ),
+            (r#"{"a":[{"b":"c"}]}"#, r#"{"a":[1]}"#, r#"{"a":[1]}"#),
+            (r#"["a","b"]"#, r#"["c","d"]"#, r#"["c","d"]"#),
+            (r#"{"a":"b"}"#, r#"["c"]"#, r#"["c"]"#),
+            (r#"{"a":"foo"}"#, r#"null"#, r#"null"#),
+            (r#"{"a":"foo"}"#, r#""bar""#, r#""bar""#),
+            (r#"{"e":null}"#, r#"{"a":1}"#, r#"{"e":null,"a":1}"#),
+            (r#"[1,2]"#, r#"{"a":"b","c":null}"#, r#"{"a":"b"}"#),
+            (
+                r#"{}"#,
+                r#"{"a":{"bb":{"ccc":null}}}"#,
+                r#"{"a":{"bb":{}}}"#,
+            ),
+        ];
+        for (target, patch, expected) in cases {
+            let mut target: Value = serde_json::from_str(target).unwrap();
+            let patch: Value = serde_json::from_str(patch).unwrap();
+            let expected: Value = serde_json::from_str(expected).unwrap();
+            apply_merge_patch(&mut target, &patch);
+            assert_eq!(target, expected, "patch {}", patch);
+        }
+    }
+
     #[test]
     fn component_new_with_valid_type_paths() {
         assert!(Component::new("String").is_some());
@@ -659,4 +1854,442 @@ mod tests {
         assert!(Component::new("foo::").is_none());
         assert!(Component::new("123::foo").is_none());
     }
+
+    #[tokio::test]
+    async fn stale_if_match_update_conflicts() {
+        use axum_test::TestServer;
+        use serde_json::json;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+        let entity = crate::Entity::new([7u8; 32]);
+        let component = Component::new("Counter").unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+        crate::sql::component_definition::create(&mut tx, &def)
+            .await
+            .unwrap();
+        crate::sql::component::create(&mut tx, &entity, &component, &json!({"n": 0}))
+            .await
+            .unwrap();
+        for n in 1..3 {
+            crate::sql::component::update(&mut tx, &entity, &component, &json!({"n": n}))
+                .await
+                .unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let server = TestServer::new(create_component_instance_router(pool.clone())).unwrap();
+        let path = format!("/entity/{}/component/Counter", entity.base64_part());
+
+        let response = server.get(&path).await;
+        response.assert_status_ok();
+        let etag = response.header(header::ETAG);
+        println!("stale_if_match_update_conflicts response: {:?}", etag);
+        assert_eq!(etag, "\"3\"");
+
+        let first = server
+            .put(&path)
+            .add_header(header::IF_MATCH, etag.clone())
+            .json(&json!({"n": 10}))
+            .await;
+        first.assert_status_ok();
+        assert_eq!(first.header(header::ETAG), "\"4\"");
+
+        let second = server
+            .put(&path)
+            .add_header(header::IF_MATCH, etag)
+            .json(&json!({"n": 20}))
+            .await;
+        second.assert_status(StatusCode::CONFLICT);
+
+        let mut tx = pool.begin().await.unwrap();
+        let stored = crate::sql::component::get_with_version(&mut tx, &entity, &component)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(stored, Some((json!({"n": 10}), 4)));
+    }
+
+    #[test]
+    fn project_fields_selects_pointers() {
+        use serde_json::json;
+
+        let data = json!({"x": 1, "y": 2, "z": 3, "pos": {"a": 1, "b": 2}, "list": [1, 2]});
+        let projected = project_fields(&data, "/x,/z").unwrap();
+        println!("project_fields_selects_pointers response: {:?}", projected);
+        assert_eq!(projected, json!({"x": 1, "z": 3}));
+
+        assert_eq!(
+            project_fields(&data, "/pos/b, /missing, /list").unwrap(),
+            json!({"pos": {"b": 2}, "list": [1, 2]})
+        );
+        assert!(project_fields(&data, "x").is_err());
+        assert!(project_fields(&data, "/a~2").is_err());
+        assert!(project_fields(&data, "/list/0").is_err());
+    }
+
+    #[tokio::test]
+    async fn get_component_with_fields() {
+        use axum_test::TestServer;
+        use serde_json::json;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+        let entity = crate::Entity::new([9u8; 32]);
+        let component = Component::new("Point3").unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+        crate::sql::component_definition::create(&mut tx, &def)
+            .await
+            .unwrap();
+        crate::sql::component::create(
+            &mut tx,
+            &entity,
+            &component,
+            &json!({"x": 1, "y": 2, "z": 3}),
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let server = TestServer::new(create_component_instance_router(pool)).unwrap();
+        let path = format!("/entity/{}/component/Point3", entity.base64_part());
+
+        let response = server.get(&path).add_query_param("fields", "/x,/z").await;
+        response.assert_status_ok();
+        let data: Value = response.json();
+        println!("get_component_with_fields response: {:?}", data);
+        assert_eq!(data, json!({"x": 1, "z": 3}));
+
+        let response = server.get(&path).add_query_param("fields", "x").await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn replace_components_diffs_stored_components() {
+        use axum_test::TestServer;
+        use serde_json::json;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+        let entity = crate::Entity::new([11u8; 32]);
+        let names = ["sync::A", "sync::B", "sync::C", "sync::D"];
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        for name in names {
+            let def = crate::ComponentDefinition::new(
+                Component::new(name).unwrap(),
+                json!({"type": "integer"}),
+            );
+            crate::sql::component_definition::create(&mut tx, &def)
+                .await
+                .unwrap();
+        }
+        for name in &names[..3] {
+            let component = Component::new(*name).unwrap();
+            crate::sql::component::create(&mut tx, &entity, &component, &json!(1))
+                .await
+                .unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let server = TestServer::new(create_component_instance_router(pool.clone())).unwrap();
+        let path = format!("/entity/{}/component", entity.base64_part());
+
+        let response = server
+            .put(&path)
+            .json(&json!({"components": {"sync::A": 1, "sync::B": "two"}}))
+            .await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+
+        let response = server
+            .put(&path)
+            .json(&json!({"components": {"sync::A": 1, "sync::B": 2, "sync::D": 4}}))
+            .await;
+        response.assert_status_ok();
+        let replaced: ReplaceComponentsResponse = response.json();
+        println!(
+            "replace_components_diffs_stored_components response: {:?}",
+            replaced
+        );
+        let component = |name: &str| Component::new(name).unwrap();
+        assert_eq!(replaced.created, vec![component("sync::D")]);
+        assert_eq!(replaced.updated, vec![component("sync::B")]);
+        assert_eq!(replaced.unchanged, vec![component("sync::A")]);
+        assert_eq!(replaced.deleted, vec![component("sync::C")]);
+
+        let mut tx = pool.begin().await.unwrap();
+        let stored = crate::sql::component::list_for_entity(&mut tx, &entity)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        let stored: Vec<_> = stored
+            .into_iter()
+            .map(|record| (record.component, record.data))
+            .collect();
+        assert_eq!(
+            stored,
+            vec![
+                (component("sync::A"), json!(1)),
+                (component("sync::B"), json!(2)),
+                (component("sync::D"), json!(4)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn entity_components_by_prefix() {
+        use axum_test::TestServer;
+        use serde_json::json;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+        let entity = crate::Entity::new([13u8; 32]);
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        for name in ["game::Position", "game::Health", "ui::Panel"] {
+            let component = Component::new(name).unwrap();
+            let def =
+                crate::ComponentDefinition::new(component.clone(), json!({"type": "integer"}));
+            crate::sql::component_definition::create(&mut tx, &def)
+                .await
+                .unwrap();
+            crate::sql::component::create(&mut tx, &entity, &component, &json!(1))
+                .await
+                .unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let server = TestServer::new(create_component_instance_router(pool)).unwrap();
+        let path = format!("/entity/{}/component", entity.base64_part());
+
+        let response = server.get(&path).add_query_param("prefix", "*").await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+        let response = server.delete(&path).add_query_param("prefix", "*").await;
+        response.assert_status(StatusCode::BAD_REQUEST);
+
+        let response = server.get(&path).add_query_param("prefix", "game::*").await;
+        response.assert_status_ok();
+        let listed: Vec<ComponentListItem> = response.json();
+        println!("entity_components_by_prefix response: {:?}", listed);
+        let listed: Vec<_> = listed.into_iter().map(|item| item.component).collect();
+        assert_eq!(
+            listed,
+            vec![
+                Component::new("game::Health").unwrap(),
+                Component::new("game::Position").unwrap(),
+            ]
+        );
+
+        let response = server.delete(&path).add_query_param("prefix", "game").await;
+        response.assert_status(StatusCode::NO_CONTENT);
+        let remaining: Vec<ComponentListItem> = server.get(&path).await.json();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].component, Component::new("ui::Panel").unwrap());
+    }
+
+    #[tokio::test]
+    async fn case_insensitive_policy_resolves_names_on_every_route() {
+        use axum_test::TestServer;
+        use serde_json::json;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+        let policy = ComponentNamePolicy::CaseInsensitive;
+        let entity = crate::Entity::new([12u8; 32]);
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let router = crate::create_component_definition_router_with_policy(pool.clone(), policy)
+            .merge(create_component_instance_router_with_policy(
+                pool.clone(),
+                policy,
+            ))
+            .merge(crate::create_apply_router_with_policy(pool.clone(), policy));
+        let server = TestServer::new(router).unwrap();
+
+        let response = server
+            .post("/componentdefinition")
+            .json(&json!({"component": "Position", "schema": {"type": "integer"}}))
+            .await;
+        response.assert_status_ok();
+        let response = server
+            .post("/componentdefinition")
+            .json(&json!({"component": "position", "schema": {"type": "integer"}}))
+            .await;
+        response.assert_status(StatusCode::CONFLICT);
+
+        let response = server
+            .put("/componentdefinition/POSITION")
+            .json(&json!({"component": "position", "schema": {"type": "number"}}))
+            .await;
+        response.assert_status(StatusCode::CONFLICT);
+        let response = server
+            .put("/componentdefinition")
+            .json(&json!({"component": "position", "schema": {"type": "number"}}))
+            .await;
+        response.assert_status(StatusCode::CONFLICT);
+        let response = server
+            .put("/componentdefinition/POSITION")
+            .json(&json!({"component": "Position", "schema": {"type": "number"}}))
+            .await;
+        response.assert_status_ok();
+        let updated: crate::ComponentDefinition = response.json();
+        assert_eq!(updated.component, Component::new("Position").unwrap());
+
+        let response = server
+            .post("/apply")
+            .json(&json!({"operations": [
+                {
+                    "type": "upsert_component_definition",
+                    "definition": {"component": "position", "schema": {"type": "string"}}
+                }
+            ]}))
+            .await;
+        response.assert_status_ok();
+        let applied: crate::ApplyResponse = response.json();
+        println!(
+            "case_insensitive_policy_resolves_names_on_every_route response: {:?}",
+            applied
+        );
+        assert!(!applied.committed);
+        assert!(matches!(
+            &applied.results[0],
+            crate::OperationResult::Error { error, .. } if error.contains("differs only in case")
+        ));
+        let response = server.get("/componentdefinition/position").await;
+        let stored: crate::ComponentDefinition = response.json();
+        assert_eq!(stored.schema, json!({"type": "number"}));
+
+        let path = format!("/entity/{}/component", entity.base64_part());
+        let response = server
+            .post(&path)
+            .json(&json!({"component": "position", "data": 1.5}))
+            .await;
+        response.assert_status_ok();
+        let response = server
+            .put(&format!("{}/POSITION", path))
+            .json(&json!(2.5))
+            .await;
+        response.assert_status_ok();
+
+        let response = server
+            .post("/apply")
+            .json(&json!({"operations": [
+                {"type": "get_component", "entity": entity, "component": "position"}
+            ]}))
+            .await;
+        response.assert_status_ok();
+        let applied: crate::ApplyResponse = response.json();
+        println!(
+            "case_insensitive_policy_resolves_names_on_every_route response: {:?}",
+            applied
+        );
+        assert_eq!(
+            applied.results,
+            vec![crate::OperationResult::GetComponent {
+                entity,
+                component: Component::new("Position").unwrap(),
+                data: Some(json!(2.5)),
+            }]
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        let stored = crate::sql::component::list_for_entity(&mut tx, &entity)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].component, Component::new("Position").unwrap());
+
+        let response = server.delete(&format!("{}/position", path)).await;
+        response.assert_status(StatusCode::NO_CONTENT);
+        let response = server.delete("/componentdefinition/position").await;
+        response.assert_status(StatusCode::NO_CONTENT);
+    }
+
+    #[tokio::test]
+    async fn component_events_stream_matching_upserts() {
+        use serde_json::json;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+        let entity = crate::Entity::new([9u8; 32]);
+        let position = Component::new("Position").unwrap();
+        let health = Component::new("Health").unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        for component in [&position, &health] {
+            let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+            crate::sql::component_definition::create(&mut tx, &def)
+                .await
+                .unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = create_component_instance_router(pool.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let mut response = reqwest::get(format!(
+            "http://{}/component/events?component=Position",
+            addr
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::component::upsert(&mut tx, &entity, &health, &json!({"hp": 3}))
+            .await
+            .unwrap();
+        crate::sql::component::upsert(&mut tx, &entity, &position, &json!({"x": 1, "y": 2}))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let mut body = String::new();
+        let block = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let chunk = response.chunk().await.unwrap().unwrap();
+                body.push_str(std::str::from_utf8(&chunk).unwrap());
+                while let Some(end) = body.find("\n\n") {
+                    let block: String = body.drain(..end + 2).collect();
+                    if block.lines().any(|line| line.starts_with("data:")) {
+                        return block;
+                    }
+                }
+            }
+        })
+        .await
+        .unwrap();
+        let data = block
+            .lines()
+            .find_map(|line| line.strip_prefix("data:"))
+            .unwrap()
+            .trim();
+
+        let event: ComponentChangeEvent = serde_json::from_str(data).unwrap();
+        println!(
+            "component_events_stream_matching_upserts response: {:?}",
+            event
+        );
+        assert!(
+            block
+                .lines()
+                .any(|line| line.strip_prefix("event:").map(str::trim) == Some("created"))
+        );
+        assert_eq!(
+            event,
+            ComponentChangeEvent {
+                entity,
+                component: position,
+                kind: ComponentChangeKind::Created,
+                data: Some(json!({"x": 1, "y": 2})),
+            }
+        );
+    }
 }
@@ -10,9 +10,12 @@
 //! - **Type Safety**: Component types must follow Rust naming conventions
 //! - **Flexible Schemas**: Support for complex schemas including oneOf unions and enums
 //! - **HTTP API**: Complete REST API for component definition management
+//! - **Batch Validation**: Check candidate data against a definition without storing it
 //! - **JSON and YAML Support**: Accept both formats based on Content-Type header
 
 use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Arc, LazyLock, Mutex};
 
 use axum::Router;
 use axum::async_trait;
@@ -20,11 +23,15 @@ use axum::body::Bytes;
 use axum::extract::{FromRequest, Path, Query, Request, State};
 use axum::http::StatusCode;
 use axum::response::Json;
-use axum::routing::get;
+use axum::routing::{get, post};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{Component, ValidationError, validate_value};
+use crate::component::NamePolicyState;
+use crate::{
+    CompiledSchema, Component, ComponentNamePolicy, PatchOperation, ValidationError,
+    apply_json_patch,
+};
 
 /// A component definition that associates a component type with its JSON schema.
 ///
@@ -66,6 +73,64 @@ pub struct ComponentDefinition {
     pub schema: serde_json::Value,
 }
 
+/// Compiled schemas keyed by component name, each with the hash of its source schema.
+type SchemaCache = HashMap<String, (u64, Arc<CompiledSchema>)>;
+
+/// Compiled schemas shared by every definition, keyed by component name.
+///
+/// Definitions are loaded fresh from the database for each request, so the cache lives
+/// outside them. Each entry records a hash of the schema it was compiled from; a changed
+/// schema recompiles and replaces the entry, so at most one compilation is kept per
+/// component.
+static COMPILED_SCHEMAS: LazyLock<Mutex<SchemaCache>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Returns the compiled form of `schema`, compiling it only if `component` has no
+/// compilation of the same schema cached.
+fn compiled_schema(component: &Component, schema: &Value) -> Arc<CompiledSchema> {
+    let hash = schema_hash(schema);
+    let mut cache = COMPILED_SCHEMAS.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((cached_hash, compiled)) = cache.get(component.as_str())
+        && *cached_hash == hash
+    {
+        return Arc::clone(compiled);
+    }
+    let compiled = Arc::new(CompiledSchema::compile(schema));
+    cache.insert(
+        component.as_str().to_string(),
+        (hash, Arc::clone(&compiled)),
+    );
+    compiled
+}
+
+/// Hashes the structure of a schema without serializing it.
+fn schema_hash(schema: &Value) -> u64 {
+    fn hash_value(value: &Value, state: &mut DefaultHasher) {
+        match value {
+            Value::Null => 0u8.hash(state),
+            Value::Bool(b) => (1u8, b).hash(state),
+            Value::Number(n) => (2u8, n).hash(state),
+            Value::String(s) => (3u8, s).hash(state),
+            Value::Array(items) => {
+                (4u8, items.len()).hash(state);
+                for item in items {
+                    hash_value(item, state);
+                }
+            }
+            Value::Object(map) => {
+                (5u8, map.len()).hash(state);
+                for (key, item) in map {
+                    key.hash(state);
+                    hash_value(item, state);
+                }
+            }
+        }
+    }
+    let mut state = DefaultHasher::new();
+    hash_value(schema, &mut state);
+    state.finish()
+}
+
 impl ComponentDefinition {
     /// Creates a new component definition.
     ///
@@ -85,6 +150,42 @@ impl ComponentDefinition {
         Self { component, schema }
     }
 
+    /// Creates a component definition from a raw JSON Schema after checking the schema itself.
+    ///
+    /// Unlike [`ComponentDefinition::new`], this rejects schemas that would be stored but
+    /// never enforce anything: unknown `type` names, `$ref`s that do not resolve within the
+    /// schema, and keywords that [`crate::validate_value`] does not implement or ignores,
+    /// whether because the declared `type` never consults them (`enum` on an integer) or
+    /// because a sibling `$ref`, `oneOf`, `anyOf` or type-less `allOf` replaces them.
+    /// Annotations such as `title`, `description` and `$schema` are allowed.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::{Component, ComponentDefinition, SchemaError};
+    /// # use serde_json::json;
+    /// let component = Component::new("Position").unwrap();
+    ///
+    /// let schema = json!({
+    ///     "$schema": "https://json-schema.org/draft/2020-12/schema",
+    ///     "type": "object",
+    ///     "properties": {"x": {"$ref": "#/definitions/coord"}},
+    ///     "definitions": {"coord": {"type": "number"}}
+    /// });
+    /// assert!(ComponentDefinition::from_schema_checked(component.clone(), schema).is_ok());
+    ///
+    /// let error = ComponentDefinition::from_schema_checked(component, json!({"type": "nonsense"}))
+    ///     .unwrap_err();
+    /// assert!(matches!(error, SchemaError::Malformed { .. }));
+    /// ```
+    pub fn from_schema_checked(component: Component, schema: Value) -> Result<Self, SchemaError> {
+        check_schema_node(&schema, &schema, "")?;
+        validate_schema_structure(&schema).map_err(|e| SchemaError::Malformed {
+            path: String::new(),
+            message: e.to_string(),
+        })?;
+        Ok(Self::new(component, schema))
+    }
+
     /// Validates that the schema structure is well-formed.
     ///
     /// This method checks that the JSON schema follows the expected format and
@@ -120,6 +221,9 @@ impl ComponentDefinition {
     /// This method checks that the provided data conforms to the JSON schema
     /// defined for this component type. It performs comprehensive validation
     /// including type checking, required fields, and nested structure validation.
+    /// The schema is compiled into a [`CompiledSchema`] on first use and reused by
+    /// later calls for the same component, including from other definition values
+    /// holding an identical schema.
     ///
     /// # Arguments
     /// * `data` - The component data to validate
@@ -150,10 +254,244 @@ impl ComponentDefinition {
     /// assert!(definition.validate_component_data(&json!({})).is_err());
     /// ```
     pub fn validate_component_data(&self, data: &Value) -> Result<(), ValidationError> {
-        validate_value(data, &self.schema)
+        compiled_schema(&self.component, &self.schema).validate(data)
+    }
+
+    /// Validates component data, reporting up to `max_errors` failures at once.
+    ///
+    /// Unlike [`ComponentDefinition::validate_component_data`], validation continues past
+    /// the first bad field so that a batch import can report everything that needs fixing.
+    /// Each error's [`ValidationError::path`] locates the failing value.
+    ///
+    /// # Arguments
+    /// * `data` - The component data to validate
+    /// * `max_errors` - The most errors to gather before stopping
+    ///
+    /// # Returns
+    /// The validation errors found, empty when the data is valid.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::{Component, ComponentDefinition};
+    /// # use serde_json::json;
+    /// let component = Component::new("Position").unwrap();
+    /// let schema = json!({
+    ///     "type": "object",
+    ///     "properties": {"x": {"type": "number"}, "y": {"type": "number"}}
+    /// });
+    /// let definition = ComponentDefinition::new(component, schema);
+    ///
+    /// let errors = definition.validate_component_data_all(&json!({"x": "a", "y": "b"}), 10);
+    /// let paths: Vec<String> = errors.iter().map(|e| e.path()).collect();
+    /// assert_eq!(paths, vec!["/x", "/y"]);
+    /// ```
+    pub fn validate_component_data_all(
+        &self,
+        data: &Value,
+        max_errors: usize,
+    ) -> Vec<ValidationError> {
+        compiled_schema(&self.component, &self.schema).validate_all(data, max_errors)
+    }
+
+    /// Reports the schema changes between this definition and `new`.
+    ///
+    /// Object properties and array items are compared recursively. Changes are
+    /// reported by JSON Pointer into the data, e.g. `/position/x`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::{Component, ComponentDefinition};
+    /// # use serde_json::json;
+    /// let component = Component::new("Health").unwrap();
+    /// let old = ComponentDefinition::new(
+    ///     component.clone(),
+    ///     json!({"type": "object", "properties": {"hp": {"type": "integer"}}, "required": ["hp"]}),
+    /// );
+    ///
+    /// // Adding an optional property is backward compatible
+    /// let additive = ComponentDefinition::new(
+    ///     component.clone(),
+    ///     json!({
+    ///         "type": "object",
+    ///         "properties": {"hp": {"type": "integer"}, "armor": {"type": "integer"}},
+    ///         "required": ["hp"]
+    ///     }),
+    /// );
+    /// let diff = old.diff(&additive);
+    /// assert_eq!(diff.added_properties, vec!["/armor"]);
+    /// assert!(diff.is_backward_compatible());
+    ///
+    /// // Requiring a new property is breaking
+    /// let breaking = ComponentDefinition::new(
+    ///     component,
+    ///     json!({
+    ///         "type": "object",
+    ///         "properties": {"hp": {"type": "integer"}, "armor": {"type": "integer"}},
+    ///         "required": ["hp", "armor"]
+    ///     }),
+    /// );
+    /// let diff = old.diff(&breaking);
+    /// assert_eq!(diff.newly_required, vec!["/armor"]);
+    /// assert!(!diff.is_backward_compatible());
+    /// ```
+    pub fn diff(&self, new: &ComponentDefinition) -> SchemaDiff {
+        let mut diff = SchemaDiff::default();
+        diff_schemas(&self.schema, &new.schema, "", &mut diff);
+        diff
+    }
+}
+
+/// Differences between two versions of a component definition's schema.
+///
+/// Every entry is a JSON Pointer to the affected location in component data.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDiff {
+    /// Properties present only in the new schema
+    pub added_properties: Vec<String>,
+    /// Properties present only in the old schema
+    pub removed_properties: Vec<String>,
+    /// Properties required by the new schema but not by the old one
+    pub newly_required: Vec<String>,
+    /// Locations whose schema type changed
+    pub type_changes: Vec<SchemaTypeChange>,
+}
+
+/// A change in the type of a schema location.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaTypeChange {
+    /// JSON Pointer to the location whose type changed
+    pub path: String,
+    /// The type in the old schema
+    pub old_type: String,
+    /// The type in the new schema
+    pub new_type: String,
+}
+
+impl SchemaDiff {
+    /// Returns true if all data valid under the old schema stays valid.
+    ///
+    /// Removing a property, requiring a new property, or changing a type is
+    /// considered breaking; adding an optional property is not.
+    pub fn is_backward_compatible(&self) -> bool {
+        self.removed_properties.is_empty()
+            && self.newly_required.is_empty()
+            && self.type_changes.is_empty()
+    }
+}
+
+/// Describes the kind of a schema for diffing purposes.
+fn schema_kind(schema: &Value) -> String {
+    if let Some(schema_type) = schema.get("type").and_then(|t| t.as_str()) {
+        schema_type.to_string()
+    } else if schema.get("oneOf").is_some() {
+        "oneOf".to_string()
+    } else if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+        format!("$ref {}", reference)
+    } else {
+        "unknown".to_string()
+    }
+}
+
+fn required_set(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default()
+}
+
+fn diff_schemas(old: &Value, new: &Value, path: &str, diff: &mut SchemaDiff) {
+    let old_kind = schema_kind(old);
+    let new_kind = schema_kind(new);
+    if old_kind != new_kind {
+        diff.type_changes.push(SchemaTypeChange {
+            path: path.to_string(),
+            old_type: old_kind,
+            new_type: new_kind,
+        });
+        return;
+    }
+
+    match new_kind.as_str() {
+        "object" => {
+            let empty = serde_json::Map::new();
+            let old_properties = old
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .unwrap_or(&empty);
+            let new_properties = new
+                .get("properties")
+                .and_then(|p| p.as_object())
+                .unwrap_or(&empty);
+            let property_path =
+                |name: &str| format!("{}/{}", path, name.replace('~', "~0").replace('/', "~1"));
+
+            for (name, old_property) in old_properties {
+                match new_properties.get(name) {
+                    Some(new_property) => {
+                        diff_schemas(old_property, new_property, &property_path(name), diff)
+                    }
+                    None => diff.removed_properties.push(property_path(name)),
+                }
+            }
+            for name in new_properties.keys() {
+                if !old_properties.contains_key(name) {
+                    diff.added_properties.push(property_path(name));
+                }
+            }
+
+            let old_required = required_set(old);
+            for name in required_set(new) {
+                if !old_required.contains(&name) {
+                    diff.newly_required.push(property_path(name));
+                }
+            }
+        }
+        "array" => {
+            if let (Some(old_items), Some(new_items)) = (old.get("items"), new.get("items")) {
+                diff_schemas(old_items, new_items, &format!("{}/items", path), diff);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The most values accepted by one batch validation request.
+const MAX_VALIDATE_BATCH: usize = 1000;
+
+/// The most errors reported for any one value in a batch validation request.
+const MAX_VALIDATE_ERRORS_PER_ITEM: usize = 10;
+
+/// One schema violation found by batch validation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationFailure {
+    /// JSON Pointer to the offending location in the value; empty for the root
+    pub path: String,
+    /// What was wrong at that location
+    pub message: String,
+}
+
+impl From<&ValidationError> for ValidationFailure {
+    fn from(error: &ValidationError) -> Self {
+        ValidationFailure {
+            path: error.path(),
+            message: error.leaf().to_string(),
+        }
     }
 }
 
+/// The outcome of validating one value in a batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BatchValidationResult {
+    /// Position of the value in the request
+    pub index: usize,
+    /// Whether the value satisfies the definition's schema
+    pub valid: bool,
+    /// The violations found, empty when the value is valid
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<ValidationFailure>,
+}
+
 /// A wrapper that extracts ComponentDefinition from either JSON or YAML based on Content-Type.
 pub struct ComponentDefinitionExtractor(pub ComponentDefinition);
 
@@ -189,13 +527,231 @@ where
     }
 }
 
+/// The reason a raw JSON Schema was refused by [`ComponentDefinition::from_schema_checked`].
+///
+/// Each variant carries the JSON Pointer to the offending subschema; empty for the root.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaError {
+    /// The subschema is structurally invalid, such as an unknown `type`
+    Malformed {
+        /// JSON Pointer to the subschema
+        path: String,
+        /// What was wrong with it
+        message: String,
+    },
+    /// A `$ref` does not point at anything within the schema
+    UnresolvableRef {
+        /// JSON Pointer to the subschema holding the reference
+        path: String,
+        /// The reference as written
+        reference: String,
+    },
+    /// A keyword that validation would not enforce
+    UnsupportedKeyword {
+        /// JSON Pointer to the subschema holding the keyword
+        path: String,
+        /// The keyword
+        keyword: String,
+    },
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let location = |path: &str| {
+            if path.is_empty() {
+                "schema root".to_string()
+            } else {
+                format!("'{}'", path)
+            }
+        };
+        match self {
+            SchemaError::Malformed { path, message } => {
+                write!(f, "invalid schema at {}: {}", location(path), message)
+            }
+            SchemaError::UnresolvableRef { path, reference } => write!(
+                f,
+                "$ref '{}' at {} does not resolve within the schema",
+                reference,
+                location(path)
+            ),
+            SchemaError::UnsupportedKeyword { path, keyword } => write!(
+                f,
+                "keyword '{}' at {} is not enforced by validation",
+                keyword,
+                location(path)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+/// Keywords that validation enforces, each with the types it is enforced for.
+///
+/// An empty list means the keyword is enforced whatever the declared type.
+const ENFORCED_KEYWORDS: &[(&str, &[&str])] = &[
+    ("type", &[]),
+    ("properties", &["object"]),
+    ("required", &["object"]),
+    ("additionalProperties", &["object"]),
+    ("items", &["array"]),
+    ("prefixItems", &["array"]),
+    ("enum", &["string"]),
+    ("const", &[]),
+    ("oneOf", &[]),
+    ("anyOf", &[]),
+    ("allOf", &[]),
+    ("minItems", &["array"]),
+    ("maxItems", &["array"]),
+    ("uniqueItems", &["array"]),
+    ("minLength", &["string"]),
+    ("maxLength", &["string"]),
+    ("pattern", &["string"]),
+    ("minimum", &["integer", "number"]),
+    ("maximum", &["integer", "number"]),
+    ("exclusiveMinimum", &["integer", "number"]),
+    ("exclusiveMaximum", &["integer", "number"]),
+    ("multipleOf", &["integer", "number"]),
+    ("$ref", &[]),
+];
+
+/// Keywords that carry no constraint and may appear anywhere.
+const ANNOTATION_KEYWORDS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+    "definitions",
+    "$defs",
+];
+
+/// Checks one subschema at `path` for the problems [`SchemaError`] describes, then
+/// recurses into its subschemas.
+fn check_schema_node(schema: &Value, root: &Value, path: &str) -> Result<(), SchemaError> {
+    let malformed = |message: String| SchemaError::Malformed {
+        path: path.to_string(),
+        message,
+    };
+    let schema_obj = schema
+        .as_object()
+        .ok_or_else(|| malformed("schema must be an object".to_string()))?;
+
+    // $ref, oneOf and anyOf each replace the rest of the schema during validation, as
+    // does an allOf without a type
+    let replacing = ["$ref", "oneOf", "anyOf"]
+        .into_iter()
+        .chain((!schema_obj.contains_key("type")).then_some("allOf"))
+        .find(|keyword| schema_obj.contains_key(*keyword));
+    // Type-specific keywords are only enforced when the value has one of their types
+    let declared_types: Vec<&str> = match schema_obj.get("type") {
+        Some(Value::Array(types)) => types.iter().filter_map(Value::as_str).collect(),
+        Some(other) => other.as_str().into_iter().collect(),
+        None => Vec::new(),
+    };
+    for keyword in schema_obj.keys() {
+        if ANNOTATION_KEYWORDS.contains(&keyword.as_str()) {
+            continue;
+        }
+        let ignored = replacing.is_some_and(|replacing| replacing != keyword);
+        let enforced = ENFORCED_KEYWORDS
+            .iter()
+            .find(|(enforced, _)| enforced == keyword)
+            .is_some_and(|(_, types)| {
+                types.is_empty() || types.iter().any(|t| declared_types.contains(t))
+            });
+        if ignored || !enforced {
+            return Err(SchemaError::UnsupportedKeyword {
+                path: path.to_string(),
+                keyword: keyword.clone(),
+            });
+        }
+    }
+
+    if let Some(reference) = schema_obj.get("$ref") {
+        let reference = reference
+            .as_str()
+            .ok_or_else(|| malformed("$ref must be a string".to_string()))?;
+        let resolves = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .is_some();
+        if !resolves {
+            return Err(SchemaError::UnresolvableRef {
+                path: path.to_string(),
+                reference: reference.to_string(),
+            });
+        }
+    }
+
+    if let Some(schema_type) = schema_obj.get("type") {
+        let type_names: Vec<&Value> = match schema_type {
+            Value::Array(types) => types.iter().collect(),
+            other => vec![other],
+        };
+        for type_name in type_names {
+            match type_name.as_str() {
+                Some("null" | "boolean" | "integer" | "number" | "string" | "array" | "object") => {
+                }
+                Some(unknown) => return Err(malformed(format!("unknown type \"{}\"", unknown))),
+                None => {
+                    return Err(malformed(
+                        "type must be a string or an array of strings".to_string(),
+                    ));
+                }
+            }
+        }
+    }
+
+    let child_path = |segments: &[&str]| {
+        let mut child = path.to_string();
+        for segment in segments {
+            child.push('/');
+            child.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+        }
+        child
+    };
+    for keyword in ["properties", "definitions", "$defs"] {
+        if let Some(children) = schema_obj.get(keyword) {
+            let children = children
+                .as_object()
+                .ok_or_else(|| malformed(format!("{} must be an object", keyword)))?;
+            for (name, child) in children {
+                check_schema_node(child, root, &child_path(&[keyword, name]))?;
+            }
+        }
+    }
+    for keyword in ["prefixItems", "oneOf", "anyOf", "allOf"] {
+        if let Some(children) = schema_obj.get(keyword) {
+            let children = children
+                .as_array()
+                .ok_or_else(|| malformed(format!("{} must be an array", keyword)))?;
+            for (index, child) in children.iter().enumerate() {
+                check_schema_node(child, root, &child_path(&[keyword, &index.to_string()]))?;
+            }
+        }
+    }
+    if let Some(items) = schema_obj.get("items") {
+        check_schema_node(items, root, &child_path(&["items"]))?;
+    }
+    if let Some(additional) = schema_obj.get("additionalProperties")
+        && !additional.is_boolean()
+    {
+        check_schema_node(additional, root, &child_path(&["additionalProperties"]))?;
+    }
+    Ok(())
+}
+
 /// Validates the structure of a JSON schema to ensure it's well-formed.
 ///
 /// This function recursively validates JSON schema objects to ensure they follow
 /// the expected format and contain valid schema constructs. It supports:
 /// - Basic types (null, boolean, integer, number, string)
 /// - Complex types (array, object)
-/// - Union types via oneOf
+/// - Union types via oneOf, anyOf and allOf
+/// - Nullable types via a list of type names
 /// - Nested schemas and recursive validation
 ///
 /// # Arguments
@@ -213,18 +769,39 @@ fn validate_schema_structure(schema: &Value) -> Result<(), ValidationError> {
 
     let schema_obj = schema.as_object().unwrap();
 
-    if let Some(one_of) = schema_obj.get("oneOf") {
-        if !one_of.is_array() {
+    if let Some(definitions) = schema_obj.get("definitions") {
+        let definitions = definitions.as_object().ok_or_else(|| {
+            ValidationError::InvalidSchema("definitions must be an object".to_string())
+        })?;
+        for (name, definition) in definitions {
+            validate_schema_structure(definition).map_err(|e| {
+                ValidationError::InvalidSchema(format!("Invalid definition '{}': {}", name, e))
+            })?;
+        }
+    }
+
+    if let Some(reference) = schema_obj.get("$ref") {
+        if !reference.is_string() {
             return Err(ValidationError::InvalidSchema(
-                "oneOf must be an array".to_string(),
+                "$ref must be a string".to_string(),
             ));
         }
+        return Ok(());
+    }
+
+    for keyword in ["oneOf", "anyOf", "allOf"] {
+        let Some(sub_schemas) = schema_obj.get(keyword) else {
+            continue;
+        };
+        let sub_schemas = sub_schemas.as_array().ok_or_else(|| {
+            ValidationError::InvalidSchema(format!("{} must be an array", keyword))
+        })?;
 
-        for (i, sub_schema) in one_of.as_array().unwrap().iter().enumerate() {
+        for (i, sub_schema) in sub_schemas.iter().enumerate() {
             validate_schema_structure(sub_schema).map_err(|e| {
                 ValidationError::InvalidSchema(format!(
-                    "Invalid oneOf schema at index {}: {}",
-                    i, e
+                    "Invalid {} schema at index {}: {}",
+                    keyword, i, e
                 ))
             })?;
         }
@@ -232,50 +809,90 @@ fn validate_schema_structure(schema: &Value) -> Result<(), ValidationError> {
     }
 
     if let Some(schema_type) = schema_obj.get("type") {
-        if !schema_type.is_string() {
-            return Err(ValidationError::InvalidSchema(
-                "Schema type must be a string".to_string(),
-            ));
+        let type_names: Vec<&str> = match schema_type {
+            Value::String(type_str) => vec![type_str.as_str()],
+            Value::Array(types) => types
+                .iter()
+                .map(|t| {
+                    t.as_str().ok_or_else(|| {
+                        ValidationError::InvalidSchema(
+                            "Schema type must be a string or an array of strings".to_string(),
+                        )
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+            _ => {
+                return Err(ValidationError::InvalidSchema(
+                    "Schema type must be a string or an array of strings".to_string(),
+                ));
+            }
+        };
+        for type_str in type_names {
+            validate_typed_schema_structure(type_str, schema_obj)?;
         }
+        Ok(())
+    } else {
+        Err(ValidationError::InvalidSchema(
+            "Schema must have 'type', 'oneOf', 'anyOf', 'allOf', or '$ref'".to_string(),
+        ))
+    }
+}
 
-        let type_str = schema_type.as_str().unwrap();
-        match type_str {
-            "null" | "boolean" | "integer" | "number" | "string" => Ok(()),
-            "array" => {
-                if let Some(items) = schema_obj.get("items") {
-                    validate_schema_structure(items)
-                } else {
-                    Ok(())
+/// Validates the keywords that apply to a schema of the given `type`.
+fn validate_typed_schema_structure(
+    type_str: &str,
+    schema_obj: &serde_json::Map<String, Value>,
+) -> Result<(), ValidationError> {
+    match type_str {
+        "null" | "boolean" | "integer" | "number" | "string" => Ok(()),
+        "array" => {
+            if let Some(prefix_items) = schema_obj.get("prefixItems") {
+                let prefix_items = prefix_items.as_array().ok_or_else(|| {
+                    ValidationError::InvalidSchema("prefixItems must be an array".to_string())
+                })?;
+                for prefix_item in prefix_items {
+                    validate_schema_structure(prefix_item)?;
                 }
             }
-            "object" => {
-                if let Some(properties) = schema_obj.get("properties") {
-                    if !properties.is_object() {
-                        return Err(ValidationError::InvalidSchema(
-                            "Properties must be an object".to_string(),
-                        ));
-                    }
+            if let Some(items) = schema_obj.get("items") {
+                validate_schema_structure(items)
+            } else {
+                Ok(())
+            }
+        }
+        "object" => {
+            if let Some(properties) = schema_obj.get("properties") {
+                if !properties.is_object() {
+                    return Err(ValidationError::InvalidSchema(
+                        "Properties must be an object".to_string(),
+                    ));
+                }
 
-                    for (prop_name, prop_schema) in properties.as_object().unwrap() {
-                        validate_schema_structure(prop_schema).map_err(|e| {
-                            ValidationError::InvalidSchema(format!(
-                                "Invalid property schema '{}': {}",
-                                prop_name, e
-                            ))
-                        })?;
-                    }
+                for (prop_name, prop_schema) in properties.as_object().unwrap() {
+                    validate_schema_structure(prop_schema).map_err(|e| {
+                        ValidationError::InvalidSchema(format!(
+                            "Invalid property schema '{}': {}",
+                            prop_name, e
+                        ))
+                    })?;
                 }
-                Ok(())
             }
-            _ => Err(ValidationError::InvalidSchema(format!(
-                "Unknown schema type: {}",
-                type_str
-            ))),
+            if let Some(additional) = schema_obj.get("additionalProperties")
+                && !additional.is_boolean()
+            {
+                validate_schema_structure(additional).map_err(|e| {
+                    ValidationError::InvalidSchema(format!(
+                        "Invalid additionalProperties schema: {}",
+                        e
+                    ))
+                })?;
+            }
+            Ok(())
         }
-    } else {
-        Err(ValidationError::InvalidSchema(
-            "Schema must have either 'type' or 'oneOf'".to_string(),
-        ))
+        _ => Err(ValidationError::InvalidSchema(format!(
+            "Unknown schema type: {}",
+            type_str
+        ))),
     }
 }
 
@@ -308,7 +925,7 @@ async fn get_component_definitions(
 }
 
 async fn create_component_definition(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     ComponentDefinitionExtractor(definition): ComponentDefinitionExtractor,
 ) -> Result<Json<ComponentDefinition>, (StatusCode, &'static str)> {
     if let Err(_e) = definition.validate_schema() {
@@ -322,7 +939,7 @@ async fn create_component_definition(
         )
     })?;
 
-    match crate::sql::component_definition::create(&mut tx, &definition).await {
+    match crate::sql::component_definition::create_with_policy(&mut tx, &definition, policy).await {
         Ok(()) => {
             tx.commit().await.map_err(|_e| {
                 (
@@ -338,7 +955,7 @@ async fn create_component_definition(
 }
 
 async fn update_component_definition(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     ComponentDefinitionExtractor(definition): ComponentDefinitionExtractor,
 ) -> Result<Json<ComponentDefinition>, (StatusCode, &'static str)> {
     if let Err(_e) = definition.validate_schema() {
@@ -352,6 +969,8 @@ async fn update_component_definition(
         )
     })?;
 
+    check_definition_spelling(&mut tx, &definition.component, policy).await?;
+
     match crate::sql::component_definition::update(&mut tx, &definition).await {
         Ok(_) => {
             tx.commit().await.map_err(|_e| {
@@ -369,15 +988,31 @@ async fn update_component_definition(
     }
 }
 
+/// Returns `409 Conflict` when `component` differs only in case from the stored name of
+/// an existing definition, so a write never silently renames it.
+async fn check_definition_spelling(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    component: &Component,
+    policy: ComponentNamePolicy,
+) -> Result<(), (StatusCode, &'static str)> {
+    let stored = crate::sql::component_definition::resolve_name(tx, component, policy)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "internal server error"))?;
+    if stored != *component {
+        return Err((
+            StatusCode::CONFLICT,
+            "a component definition differing only in case already exists",
+        ));
+    }
+    Ok(())
+}
+
 async fn patch_component_definition(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Json(patch): Json<Value>,
 ) -> Result<Json<ComponentDefinition>, (StatusCode, &'static str)> {
     let component = Component::new("PatchedComponent").unwrap();
-    let definition = ComponentDefinition {
-        component: component.clone(),
-        schema: patch.clone(),
-    };
+    let definition = ComponentDefinition::new(component.clone(), patch.clone());
 
     if let Err(_e) = definition.validate_schema() {
         return Err((StatusCode::BAD_REQUEST, "invalid schema"));
@@ -390,6 +1025,12 @@ async fn patch_component_definition(
         )
     })?;
 
+    let component =
+        crate::sql::component_definition::resolve_name(&mut tx, &definition.component, policy)
+            .await
+            .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "internal server error"))?;
+    let definition = ComponentDefinition::new(component, definition.schema);
+
     match crate::sql::component_definition::update(&mut tx, &definition).await {
         Ok(_) => {
             tx.commit().await.map_err(|_e| {
@@ -438,7 +1079,7 @@ async fn delete_component_definitions(
 }
 
 async fn get_component_definition_by_id(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path(id): Path<String>,
 ) -> Result<Json<ComponentDefinition>, (StatusCode, &'static str)> {
     let component =
@@ -451,7 +1092,7 @@ async fn get_component_definition_by_id(
         )
     })?;
 
-    match crate::sql::component_definition::get(&mut tx, &component).await {
+    match crate::sql::component_definition::get_with_policy(&mut tx, &component, policy).await {
         Ok(Some(record)) => {
             tx.commit().await.map_err(|_e| {
                 (
@@ -467,7 +1108,7 @@ async fn get_component_definition_by_id(
 }
 
 async fn update_component_definition_by_id(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path(id): Path<String>,
     ComponentDefinitionExtractor(definition): ComponentDefinitionExtractor,
 ) -> Result<Json<ComponentDefinition>, (StatusCode, &'static str)> {
@@ -478,7 +1119,7 @@ async fn update_component_definition_by_id(
         return Err((StatusCode::BAD_REQUEST, "invalid schema"));
     }
 
-    if component != definition.component {
+    if !policy.same(&component, &definition.component) {
         return Err((StatusCode::BAD_REQUEST, "component name mismatch"));
     }
 
@@ -489,6 +1130,8 @@ async fn update_component_definition_by_id(
         )
     })?;
 
+    check_definition_spelling(&mut tx, &definition.component, policy).await?;
+
     match crate::sql::component_definition::update(&mut tx, &definition).await {
         Ok(_) => {
             tx.commit().await.map_err(|_e| {
@@ -504,16 +1147,13 @@ async fn update_component_definition_by_id(
 }
 
 async fn patch_component_definition_by_id(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path(id): Path<String>,
     Json(patch): Json<Value>,
 ) -> Result<Json<ComponentDefinition>, (StatusCode, &'static str)> {
     let component =
         Component::new(&id).ok_or((StatusCode::BAD_REQUEST, "invalid component name"))?;
-    let definition = ComponentDefinition {
-        component: component.clone(),
-        schema: patch.clone(),
-    };
+    let definition = ComponentDefinition::new(component.clone(), patch.clone());
 
     if let Err(_e) = definition.validate_schema() {
         return Err((StatusCode::BAD_REQUEST, "invalid schema"));
@@ -526,6 +1166,11 @@ async fn patch_component_definition_by_id(
         )
     })?;
 
+    let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "internal server error"))?;
+    let definition = ComponentDefinition::new(component, definition.schema);
+
     match crate::sql::component_definition::update(&mut tx, &definition).await {
         Ok(_) => {
             tx.commit().await.map_err(|_e| {
@@ -540,8 +1185,69 @@ async fn patch_component_definition_by_id(
     }
 }
 
+/// Applies a JSON Patch (RFC 6902) to the schema of a component definition.
+///
+/// A failed patch operation returns `409 Conflict` and leaves the stored
+/// definition untouched.
+async fn json_patch_component_definition_by_id(
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
+    Path(id): Path<String>,
+    Json(patch): Json<Vec<PatchOperation>>,
+) -> Result<Json<ComponentDefinition>, (StatusCode, String)> {
+    let component = Component::new(&id).ok_or((
+        StatusCode::BAD_REQUEST,
+        "invalid component name".to_string(),
+    ))?;
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction".to_string(),
+        )
+    })?;
+
+    let record = match crate::sql::component_definition::get_with_policy(
+        &mut tx, &component, policy,
+    )
+    .await
+    {
+        Ok(Some(record)) => record,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, "not found".to_string())),
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            ));
+        }
+    };
+
+    let schema = apply_json_patch(&record.definition.schema, &patch)
+        .map_err(|e| (StatusCode::CONFLICT, e.to_string()))?;
+    let definition = ComponentDefinition::new(record.definition.component, schema);
+
+    if let Err(e) = definition.validate_schema() {
+        return Err((StatusCode::BAD_REQUEST, format!("invalid schema: {}", e)));
+    }
+
+    match crate::sql::component_definition::update(&mut tx, &definition).await {
+        Ok(_) => {
+            tx.commit().await.map_err(|_e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to commit transaction".to_string(),
+                )
+            })?;
+            Ok(Json(definition))
+        }
+        Err(_) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "internal server error".to_string(),
+        )),
+    }
+}
+
 async fn delete_component_definition_by_id(
-    State(pool): State<sqlx::PgPool>,
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
     Path(id): Path<String>,
 ) -> Result<StatusCode, (StatusCode, &'static str)> {
     let component =
@@ -554,6 +1260,10 @@ async fn delete_component_definition_by_id(
         )
     })?;
 
+    let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+        .await
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "internal server error"))?;
+
     match crate::sql::component_definition::delete(&mut tx, &component).await {
         Ok(true) => {
             tx.commit().await.map_err(|_e| {
@@ -569,6 +1279,76 @@ async fn delete_component_definition_by_id(
     }
 }
 
+/// Validates each value against a stored definition without writing anything.
+///
+/// Returns one result per value, in request order, reporting up to
+/// `MAX_VALIDATE_ERRORS_PER_ITEM` violations for each invalid value.
+async fn validate_batch_by_id(
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
+    Path(id): Path<String>,
+    Json(values): Json<Vec<Value>>,
+) -> Result<Json<Vec<BatchValidationResult>>, (StatusCode, String)> {
+    let component = Component::new(&id).ok_or((
+        StatusCode::BAD_REQUEST,
+        "invalid component name".to_string(),
+    ))?;
+    if values.len() > MAX_VALIDATE_BATCH {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "too many values: {} exceeds the limit of {}",
+                values.len(),
+                MAX_VALIDATE_BATCH
+            ),
+        ));
+    }
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction".to_string(),
+        )
+    })?;
+    let definition = match crate::sql::component_definition::get_with_policy(
+        &mut tx, &component, policy,
+    )
+    .await
+    {
+        Ok(Some(record)) => record.definition,
+        Ok(None) => return Err((StatusCode::NOT_FOUND, "not found".to_string())),
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "internal server error".to_string(),
+            ));
+        }
+    };
+    tx.commit().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to commit transaction".to_string(),
+        )
+    })?;
+
+    let results = values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let errors: Vec<ValidationFailure> = definition
+                .validate_component_data_all(value, MAX_VALIDATE_ERRORS_PER_ITEM)
+                .iter()
+                .map(ValidationFailure::from)
+                .collect();
+            BatchValidationResult {
+                index,
+                valid: errors.is_empty(),
+                errors,
+            }
+        })
+        .collect();
+    Ok(Json(results))
+}
+
 /// Creates the HTTP router for component definition endpoints.
 ///
 /// This function sets up all the routes for managing component definitions using PostgreSQL.
@@ -579,6 +1359,29 @@ async fn delete_component_definition_by_id(
 /// # Returns
 /// An Axum Router configured with component definition routes
 pub fn create_component_definition_router(pool: sqlx::PgPool) -> Router {
+    create_component_definition_router_with_policy(pool, ComponentNamePolicy::default())
+}
+
+/// Creates the component definition router, comparing names according to `policy`.
+///
+/// Under [`ComponentNamePolicy::CaseInsensitive`], every route resolves the named
+/// definition regardless of case. `POST` and `PUT` return 409 Conflict when the body
+/// names a definition that differs only in case from an existing one.
+///
+/// # Example
+///
+/// ```no_run
+/// # use stigmergy::{ComponentNamePolicy, create_component_definition_router_with_policy};
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool) {
+/// let router =
+///     create_component_definition_router_with_policy(pool, ComponentNamePolicy::CaseInsensitive);
+/// # }
+/// ```
+pub fn create_component_definition_router_with_policy(
+    pool: sqlx::PgPool,
+    policy: ComponentNamePolicy,
+) -> Router {
     Router::new()
         .route(
             "/componentdefinition",
@@ -595,5 +1398,253 @@ pub fn create_component_definition_router(pool: sqlx::PgPool) -> Router {
                 .patch(patch_component_definition_by_id)
                 .delete(delete_component_definition_by_id),
         )
-        .with_state(pool)
+        .route(
+            "/componentdefinition/:id/jsonpatch",
+            axum::routing::patch(json_patch_component_definition_by_id),
+        )
+        .route(
+            "/componentdefinition/:id/validate",
+            post(validate_batch_by_id),
+        )
+        .with_state(NamePolicyState { pool, policy })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_test::TestServer;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn validate_batch_reports_each_failure() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let definition = ComponentDefinition::new(
+            Component::new("batch::Point").unwrap(),
+            json!({
+                "type": "object",
+                "properties": {"x": {"type": "integer"}, "y": {"type": "integer"}},
+                "required": ["x", "y"]
+            }),
+        );
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::component_definition::create(&mut tx, &definition)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let values: Vec<Value> = (0..100)
+            .map(|i| {
+                if i % 10 == 3 {
+                    json!({"x": i, "y": "bad"})
+                } else if i % 25 == 0 {
+                    json!({"x": i})
+                } else {
+                    json!({"x": i, "y": i})
+                }
+            })
+            .collect();
+
+        let server = TestServer::new(create_component_definition_router(pool.clone())).unwrap();
+        let response = server
+            .post("/componentdefinition/batch::Point/validate")
+            .json(&values)
+            .await;
+        response.assert_status_ok();
+        let results: Vec<BatchValidationResult> = response.json();
+        println!(
+            "validate_batch_reports_each_failure response: {:?}",
+            &results[..4]
+        );
+
+        assert_eq!(results.len(), 100);
+        for (i, result) in results.iter().enumerate() {
+            assert_eq!(result.index, i);
+            assert_eq!(result.valid, i % 10 != 3 && i % 25 != 0, "value {}", i);
+        }
+        assert_eq!(results[3].errors.len(), 1);
+        assert_eq!(results[3].errors[0].path, "/y");
+        assert_eq!(results[25].errors.len(), 1);
+        assert_eq!(results[25].errors[0].path, "");
+        assert!(results[25].errors[0].message.contains("y"));
+
+        let mut tx = pool.begin().await.unwrap();
+        assert_eq!(crate::sql::component::count_all(&mut tx).await.unwrap(), 0);
+        tx.commit().await.unwrap();
+
+        let response = server
+            .post("/componentdefinition/batch::Missing/validate")
+            .json(&values)
+            .await;
+        response.assert_status(StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn from_schema_checked_rejects_unknown_type() {
+        let component = Component::new("Position").unwrap();
+        let schema = json!({
+            "type": "object",
+            "properties": {"x": {"type": "nonsense"}}
+        });
+        let error = ComponentDefinition::from_schema_checked(component, schema).unwrap_err();
+        println!(
+            "from_schema_checked_rejects_unknown_type response: {}",
+            error
+        );
+        assert_eq!(
+            error,
+            SchemaError::Malformed {
+                path: "/properties/x".to_string(),
+                message: "unknown type \"nonsense\"".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn from_schema_checked_rejects_unresolvable_ref() {
+        let component = Component::new("Position").unwrap();
+        let schema = json!({
+            "type": "array",
+            "items": {"$ref": "#/definitions/Missing"},
+            "definitions": {"Point": {"type": "number"}}
+        });
+        let error = ComponentDefinition::from_schema_checked(component, schema).unwrap_err();
+        println!(
+            "from_schema_checked_rejects_unresolvable_ref response: {}",
+            error
+        );
+        assert_eq!(
+            error,
+            SchemaError::UnresolvableRef {
+                path: "/items".to_string(),
+                reference: "#/definitions/Missing".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn from_schema_checked_rejects_unenforced_keywords() {
+        let component = Component::new("Position").unwrap();
+        let unknown = json!({"type": "string", "format": "email"});
+        let error =
+            ComponentDefinition::from_schema_checked(component.clone(), unknown).unwrap_err();
+        println!(
+            "from_schema_checked_rejects_unenforced_keywords response: {}",
+            error
+        );
+        assert_eq!(
+            error,
+            SchemaError::UnsupportedKeyword {
+                path: String::new(),
+                keyword: "format".to_string(),
+            }
+        );
+
+        let beside_ref = json!({
+            "$ref": "#/definitions/Point",
+            "minimum": 0,
+            "definitions": {"Point": {"type": "number"}}
+        });
+        let error = ComponentDefinition::from_schema_checked(component, beside_ref).unwrap_err();
+        assert!(matches!(
+            error,
+            SchemaError::UnsupportedKeyword { ref keyword, .. } if keyword == "minimum"
+        ));
+    }
+
+    #[test]
+    fn compiled_schema_is_shared_across_fresh_definitions() {
+        let component = Component::new("CompiledSchemaShared").unwrap();
+        let schema = json!({"type": "string", "pattern": "^[a-z]+$"});
+        // Each request loads its own copy of the definition
+        let first = ComponentDefinition::new(component.clone(), schema.clone());
+        let second = ComponentDefinition::new(component.clone(), schema.clone());
+        assert!(first.validate_component_data(&json!("abc")).is_ok());
+        assert!(second.validate_component_data(&json!("ABC")).is_err());
+
+        let compiled = compiled_schema(&first.component, &first.schema);
+        println!(
+            "compiled_schema_is_shared_across_fresh_definitions response: {:?}",
+            compiled.compiled_patterns()
+        );
+        assert!(Arc::ptr_eq(
+            &compiled,
+            &compiled_schema(&second.component, &second.schema)
+        ));
+
+        let changed = ComponentDefinition::new(component, json!({"type": "integer"}));
+        assert!(changed.validate_component_data(&json!("abc")).is_err());
+        assert!(changed.validate_component_data(&json!(1)).is_ok());
+        assert!(!Arc::ptr_eq(
+            &compiled,
+            &compiled_schema(&changed.component, &changed.schema)
+        ));
+    }
+
+    #[test]
+    fn from_schema_checked_rejects_keywords_the_declared_type_ignores() {
+        let component = Component::new("Position").unwrap();
+        let rejected = [
+            (json!({"type": "integer", "enum": [1, 2]}), "enum"),
+            (json!({"type": "string", "minimum": 0}), "minimum"),
+            (
+                json!({
+                    "allOf": [{"type": "object"}],
+                    "properties": {"x": {"type": "number"}}
+                }),
+                "properties",
+            ),
+        ];
+        for (schema, keyword) in rejected {
+            let error =
+                ComponentDefinition::from_schema_checked(component.clone(), schema).unwrap_err();
+            println!(
+                "from_schema_checked_rejects_keywords_the_declared_type_ignores response: {}",
+                error
+            );
+            assert_eq!(
+                error,
+                SchemaError::UnsupportedKeyword {
+                    path: String::new(),
+                    keyword: keyword.to_string(),
+                }
+            );
+        }
+
+        let typed_all_of = json!({
+            "type": "object",
+            "allOf": [{"type": "object", "required": ["x"]}],
+            "properties": {"x": {"type": "number"}}
+        });
+        assert!(ComponentDefinition::from_schema_checked(component, typed_all_of).is_ok());
+    }
+
+    #[test]
+    fn from_schema_checked_accepts_enforceable_schema() {
+        let component = Component::new("Tree").unwrap();
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Tree",
+            "$ref": "#/$defs/Node",
+            "$defs": {
+                "Node": {
+                    "type": "object",
+                    "description": "A node with optional children",
+                    "properties": {
+                        "value": {"type": ["integer", "null"], "minimum": 0},
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/Node"}}
+                    },
+                    "required": ["value"],
+                    "additionalProperties": false
+                }
+            }
+        });
+        let definition =
+            ComponentDefinition::from_schema_checked(component.clone(), schema.clone()).unwrap();
+        assert_eq!(definition, ComponentDefinition::new(component, schema));
+        assert!(
+            definition
+                .validate_component_data(&json!({"value": 1, "children": [{"value": 2}]}))
+                .is_ok()
+        );
+    }
 }
@@ -10,6 +10,8 @@ use axum::response::Json;
 use axum::routing::get;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
 
 use crate::Bid;
 
@@ -44,12 +46,25 @@ impl IoSystem {
     }
 }
 
+/// The shape version written by this build; see [`Config::from_json`] for migrations.
+pub const CONFIG_VERSION: u32 = 2;
+
+fn current_config_version() -> u32 {
+    CONFIG_VERSION
+}
+
 /// Global configuration containing all I/O system configurations.
 ///
 /// This structure represents the top-level configuration for the stigmergy
 /// system, containing all configured I/O systems.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Config {
+    /// Shape version of this configuration; always [`CONFIG_VERSION`] once loaded.
+    ///
+    /// Defaults to [`CONFIG_VERSION`] when absent so requests and files that deserialize
+    /// `Config` directly keep accepting unversioned configurations.
+    #[serde(default = "current_config_version")]
+    pub version: u32,
     /// List of configured I/O systems.
     pub io_systems: Vec<IoSystem>,
 }
@@ -60,17 +75,135 @@ impl Config {
     /// # Arguments
     /// * `io_systems` - List of I/O system configurations
     pub fn new(io_systems: Vec<IoSystem>) -> Self {
-        Self { io_systems }
+        Self {
+            version: CONFIG_VERSION,
+            io_systems,
+        }
     }
 
     /// Creates an empty Config with no I/O systems.
     pub fn empty() -> Self {
-        Self {
-            io_systems: Vec::new(),
+        Self::new(Vec::new())
+    }
+
+    /// Deserializes a stored configuration, upgrading older shapes to the current one.
+    ///
+    /// Configurations without a `version` field are version 1. Each version is upgraded
+    /// one step at a time until it reaches [`CONFIG_VERSION`]:
+    /// - v1 → v2: records the version; the shape is otherwise unchanged.
+    ///
+    /// # Returns
+    /// * `Ok(Config)` - The configuration in the current shape
+    /// * `Err(String)` - If the version is newer than this build understands, or the
+    ///   upgraded value does not deserialize
+    pub fn from_json(mut value: serde_json::Value) -> Result<Config, String> {
+        let mut version = match value.get("version") {
+            None => 1,
+            Some(v) => v
+                .as_u64()
+                .and_then(|v| u32::try_from(v).ok())
+                .ok_or_else(|| format!("Invalid config version: {}", v))?,
+        };
+        if version > CONFIG_VERSION {
+            return Err(format!(
+                "Config version {} is newer than the supported version {}",
+                version, CONFIG_VERSION
+            ));
         }
+        while version < CONFIG_VERSION {
+            value = match version {
+                1 => upgrade_config_v1(value),
+                _ => unreachable!("no upgrade from config version {}", version),
+            };
+            version += 1;
+        }
+        serde_json::from_value(value).map_err(|e| format!("Failed to deserialize config: {}", e))
+    }
+}
+
+/// Upgrades a version 1 configuration to version 2.
+fn upgrade_config_v1(mut value: serde_json::Value) -> serde_json::Value {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), serde_json::Value::from(2));
+    }
+    value
+}
+
+/// How long a watched config file must stay unchanged before [`Config::watch`] reloads it.
+pub const CONFIG_WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// How often [`Config::watch`] checks the watched file for changes.
+const CONFIG_WATCH_POLL: Duration = Duration::from_millis(50);
+
+impl Config {
+    /// Loads a configuration from a JSON file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to a file holding a serialized `Config`
+    ///
+    /// # Returns
+    /// * `Ok(Config)` - The parsed configuration
+    /// * `Err(String)` - If the file cannot be read or parsed
+    pub async fn load_file(path: &Path) -> Result<Config, String> {
+        let content = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|e| format!("Failed to read config file {}: {}", path.display(), e))?;
+        let value = serde_json::from_str(&content).map_err(|e| {
+            format!(
+                "Failed to deserialize config file {}: {}",
+                path.display(),
+                e
+            )
+        })?;
+        Config::from_json(value).map_err(|e| format!("{} in {}", e, path.display()))
+    }
+
+    /// Watches a config file and invokes `callback` with the reloaded `Config` on change.
+    ///
+    /// The file's modification time and length are polled; once a change has been quiet for
+    /// [`CONFIG_WATCH_DEBOUNCE`], the file is reloaded with [`Config::load_file`]. Files that
+    /// fail to load are reported on stderr and skipped, so a half-written file never reaches
+    /// the callback. The watcher runs until the returned task is aborted.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the config file to watch
+    /// * `callback` - Called with each successfully reloaded configuration
+    pub fn watch<F>(path: impl Into<PathBuf>, mut callback: F) -> tokio::task::JoinHandle<()>
+    where
+        F: FnMut(Config) + Send + 'static,
+    {
+        let path = path.into();
+        tokio::spawn(async move {
+            let mut stamp = file_stamp(&path).await;
+            let mut changed_at: Option<Instant> = None;
+            loop {
+                tokio::time::sleep(CONFIG_WATCH_POLL).await;
+                let current = file_stamp(&path).await;
+                if current != stamp {
+                    stamp = current;
+                    changed_at = Some(Instant::now());
+                    continue;
+                }
+                if let Some(at) = changed_at
+                    && at.elapsed() >= CONFIG_WATCH_DEBOUNCE
+                {
+                    changed_at = None;
+                    match Config::load_file(&path).await {
+                        Ok(config) => callback(config),
+                        Err(e) => eprintln!("Ignoring config change: {}", e),
+                    }
+                }
+            }
+        })
     }
 }
 
+/// Returns the modification time and length of `path`, or `None` if it does not exist.
+async fn file_stamp(path: &Path) -> Option<(Option<SystemTime>, u64)> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    Some((metadata.modified().ok(), metadata.len()))
+}
+
 impl Default for Config {
     fn default() -> Self {
         Self::empty()
@@ -105,7 +238,9 @@ pub struct PostConfigResponse {
 /// Loads the latest configuration from the database.
 ///
 /// Queries the database for the configuration with the maximum version number.
-/// If no configurations exist, returns an empty configuration.
+/// If no configurations exist, returns an empty configuration. Configurations saved
+/// in an older shape are upgraded with [`Config::from_json`]. Transient database
+/// failures are retried with [`crate::sql::with_retry`].
 ///
 /// # Arguments
 /// * `pool` - PostgreSQL connection pool
@@ -114,21 +249,27 @@ pub struct PostConfigResponse {
 /// * `Ok(Config)` - The latest configuration
 /// * `Err(String)` - If a database error occurs
 pub async fn load_latest_config(pool: &PgPool) -> Result<Config, String> {
-    let result: Option<(serde_json::Value,)> = sqlx::query_as(
-        r#"
-        SELECT config_json
-        FROM configs
-        ORDER BY version DESC
-        LIMIT 1
-        "#,
-    )
-    .fetch_optional(pool)
-    .await
-    .map_err(|e| format!("Failed to load config from database: {}", e))?;
+    // A read-only transaction, so retrying after any transient failure is safe.
+    let result: Option<(serde_json::Value,)> =
+        crate::sql::with_retry(pool, &crate::sql::RetryPolicy::default(), |tx| {
+            Box::pin(async move {
+                sqlx::query_as(
+                    r#"
+                    SELECT config_json
+                    FROM configs
+                    ORDER BY version DESC
+                    LIMIT 1
+                    "#,
+                )
+                .fetch_optional(&mut **tx)
+                .await
+            })
+        })
+        .await
+        .map_err(|e| format!("Failed to load config from database: {}", e))?;
 
     match result {
-        Some((config_json,)) => serde_json::from_value(config_json)
-            .map_err(|e| format!("Failed to deserialize config: {}", e)),
+        Some((config_json,)) => Config::from_json(config_json),
         None => Ok(Config::empty()),
     }
 }
@@ -373,4 +514,80 @@ mod tests {
             deserialized.io_systems[0].endpoint
         );
     }
+
+    #[test]
+    fn from_json_upgrades_v1_config() {
+        let v1 = serde_json::json!({
+            "io_systems": [
+                {"bid": [], "endpoint": "https://example.com/io", "headers": {}}
+            ]
+        });
+        let config = Config::from_json(v1).unwrap();
+        println!("from_json_upgrades_v1_config response: {:?}", config);
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.io_systems.len(), 1);
+        assert!(config.io_systems[0].headers.is_empty());
+
+        let current = serde_json::to_value(Config::empty()).unwrap();
+        assert_eq!(Config::from_json(current).unwrap(), Config::empty());
+
+        let future = serde_json::json!({"version": CONFIG_VERSION + 1, "io_systems": []});
+        let err = Config::from_json(future).unwrap_err();
+        assert!(err.contains("newer than the supported version"));
+    }
+
+    #[test]
+    fn unversioned_config_deserializes_directly() {
+        let request: PostConfigRequest = serde_json::from_value(serde_json::json!({
+            "config": {
+                "io_systems": [
+                    {"bid": [], "endpoint": "https://example.com/io", "headers": {}}
+                ]
+            }
+        }))
+        .unwrap();
+        println!(
+            "unversioned_config_deserializes_directly response: {:?}",
+            request
+        );
+        assert_eq!(request.config.version, CONFIG_VERSION);
+        assert_eq!(request.config.io_systems.len(), 1);
+
+        let yaml: Config = serde_yml::from_str("io_systems: []\n").unwrap();
+        assert_eq!(yaml, Config::empty());
+    }
+
+    #[tokio::test]
+    async fn watch_reloads_changed_config() {
+        let path = std::env::temp_dir().join(format!(
+            "stigmergy-watch-{}-{}.json",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        std::fs::write(&path, serde_json::to_string(&Config::empty()).unwrap()).unwrap();
+
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let watcher = Config::watch(path.clone(), move |config| {
+            let _ = tx.send(config);
+        });
+        tokio::time::sleep(CONFIG_WATCH_POLL * 2).await;
+
+        let io_system = IoSystem::new(
+            vec![BidParser::parse("ON true BID 1").unwrap()],
+            "https://example.com/io".to_string(),
+            HeaderMap::new(),
+        );
+        let updated = Config::new(vec![io_system]);
+        std::fs::write(&path, serde_json::to_string(&updated).unwrap()).unwrap();
+
+        let reloaded = tokio::time::timeout(CONFIG_WATCH_DEBOUNCE * 8, rx.recv())
+            .await
+            .expect("watcher did not report the change")
+            .unwrap();
+        println!("watch_reloads_changed_config response: {:?}", reloaded);
+        assert_eq!(reloaded, updated);
+
+        watcher.abort();
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -1,7 +1,7 @@
 //! # Edge Management for Graph Representation
 //!
 //! This module implements a graph layer over stigmergy entities, representing relationships
-//! as directed, labeled edges between entities.
+//! as labeled edges between entities. Edges are directed unless created as undirected.
 //!
 //! ## Graph Semantics
 //!
@@ -11,9 +11,10 @@
 //!
 //! ## Key Properties
 //!
-//! 1. **Directed edges**: Each edge has a direction from src to dst
+//! 1. **Directed edges**: Each edge has a direction from src to dst unless created as undirected
 //! 2. **Labeled edges**: Each edge is labeled by an entity
-//! 3. **Unique constraint**: Only one edge per `(src, dst, label)` combination
+//! 3. **Unique constraint**: Only one edge per `(src, dst, label)` combination, and only one
+//!    undirected edge per label between any two entities
 //! 4. **Entity-based labels**: Labels are entities, enabling edge metadata through components
 //!
 //! ## Metadata Strategy
@@ -41,6 +42,9 @@
 //! - All edges from a vertex with a specific label
 //! - All edges to a vertex with a specific label
 //!
+//! Entities reachable within a bounded number of steps are found with a recursive query
+//! that follows directed edges forward and undirected edges in both directions.
+//!
 //! ## Example Usage
 //!
 //! ```rust
@@ -51,7 +55,7 @@
 //! let friendship = Entity::random_url_safe()?;
 //!
 //! // Create edge: alice --[friendship]--> bob
-//! let edge = Edge { src: alice, dst: bob, label: friendship };
+//! let edge = Edge { src: alice, dst: bob, label: friendship, directed: true };
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
 
@@ -67,7 +71,7 @@ use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use std::str::FromStr;
 
-/// Represents a directed, labeled edge in the graph.
+/// Represents a labeled edge in the graph.
 ///
 /// Each edge connects a source entity to a destination entity with a label entity.
 /// The label entity can have components attached to store edge metadata.
@@ -79,6 +83,9 @@ pub struct Edge {
     pub dst: Entity,
     /// The label entity of the edge.
     pub label: Entity,
+    /// Whether the edge points from src to dst (true) or joins them symmetrically (false).
+    #[serde(default = "default_directed")]
+    pub directed: bool,
 }
 
 /// Request to create a new edge.
@@ -90,6 +97,13 @@ pub struct CreateEdgeRequest {
     pub dst: Entity,
     /// The label entity of the edge.
     pub label: Entity,
+    /// Whether the edge points from src to dst (true) or joins them symmetrically (false).
+    #[serde(default = "default_directed")]
+    pub directed: bool,
+}
+
+pub(crate) fn default_directed() -> bool {
+    true
 }
 
 /// Response from creating an edge.
@@ -101,6 +115,30 @@ pub struct CreateEdgeResponse {
     pub created: bool,
 }
 
+/// Maximum depth accepted by the traversal endpoint.
+pub const MAX_TRAVERSAL_DEPTH: u32 = 32;
+
+/// Request to find the entities reachable from a starting entity.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TraverseEdgesRequest {
+    /// The entity to start from.
+    pub start: Entity,
+    /// The maximum number of edges to follow.
+    pub max_depth: u32,
+    /// If set, only edges with this label are followed.
+    #[serde(default)]
+    pub label_filter: Option<Entity>,
+}
+
+/// An entity found by a traversal, with its shortest distance from the start.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReachableEntity {
+    /// The reachable entity.
+    pub entity: Entity,
+    /// The number of edges on the shortest path from the start.
+    pub distance: u32,
+}
+
 async fn create_edge(
     State(pool): State<PgPool>,
     Json(request): Json<CreateEdgeRequest>,
@@ -109,6 +147,7 @@ async fn create_edge(
         src: request.src,
         dst: request.dst,
         label: request.label,
+        directed: request.directed,
     };
 
     let mut tx = pool.begin().await.map_err(|_e| {
@@ -147,6 +186,10 @@ async fn create_edge(
                     };
                     Ok(Json(response))
                 }
+                Err(crate::errors::DataStoreError::AlreadyExists) => Err((
+                    StatusCode::CONFLICT,
+                    "undirected edge already exists in the opposite direction",
+                )),
                 Err(_e) => Err((StatusCode::INTERNAL_SERVER_ERROR, "failed to create edge")),
             }
         }
@@ -365,6 +408,112 @@ async fn delete_edge(
     Ok(StatusCode::NO_CONTENT)
 }
 
+async fn list_edges_out(
+    State(pool): State<PgPool>,
+    Path(entity): Path<String>,
+) -> Result<Json<Vec<Edge>>, (StatusCode, &'static str)> {
+    let entity =
+        Entity::from_str(&entity).map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity"))?;
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction",
+        )
+    })?;
+
+    let edges = sql::edge::list_out(&mut tx, &entity)
+        .await
+        .map_err(|_e| (StatusCode::INTERNAL_SERVER_ERROR, "failed to list edges"))?;
+
+    tx.commit().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to commit transaction",
+        )
+    })?;
+
+    Ok(Json(edges))
+}
+
+async fn list_edges_in(
+    State(pool): State<PgPool>,
+    Path(entity): Path<String>,
+) -> Result<Json<Vec<Edge>>, (StatusCode, &'static str)> {
+    let entity =
+        Entity::from_str(&entity).map_err(|_| (StatusCode::BAD_REQUEST, "invalid entity"))?;
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction",
+        )
+    })?;
+
+    let edges = sql::edge::list_in(&mut tx, &entity)
+        .await
+        .map_err(|_e| (StatusCode::INTERNAL_SERVER_ERROR, "failed to list edges"))?;
+
+    tx.commit().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to commit transaction",
+        )
+    })?;
+
+    Ok(Json(edges))
+}
+
+async fn traverse_edges(
+    State(pool): State<PgPool>,
+    Json(request): Json<TraverseEdgesRequest>,
+) -> Result<Json<Vec<ReachableEntity>>, (StatusCode, &'static str)> {
+    if request.max_depth > MAX_TRAVERSAL_DEPTH {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "max_depth exceeds the traversal limit",
+        ));
+    }
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction",
+        )
+    })?;
+
+    let reachable = sql::edge::traverse(
+        &mut tx,
+        &request.start,
+        request.max_depth as i32,
+        request.label_filter.as_ref(),
+    )
+    .await
+    .map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to traverse edges",
+        )
+    })?;
+
+    tx.commit().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to commit transaction",
+        )
+    })?;
+
+    Ok(Json(
+        reachable
+            .into_iter()
+            .map(|(entity, distance)| ReachableEntity {
+                entity,
+                distance: distance as u32,
+            })
+            .collect(),
+    ))
+}
+
 /// Creates the HTTP router for edge management endpoints.
 pub fn create_edge_router(pool: PgPool) -> Router {
     Router::new()
@@ -373,6 +522,9 @@ pub fn create_edge_router(pool: PgPool) -> Router {
         .route("/edge/to/:dst", get(list_edges_to))
         .route("/edge/labeled/:label", get(list_edges_labeled))
         .route("/edge/from/:src/to/:dst", get(list_edges_between))
+        .route("/edge/out/:entity", get(list_edges_out))
+        .route("/edge/in/:entity", get(list_edges_in))
+        .route("/edge/traverse", post(traverse_edges))
         .route(
             "/edge/from/:src/to/:dst/labeled/:label",
             get(get_edge).delete(delete_edge),
@@ -412,7 +564,12 @@ mod tests {
         sql::entity::create(&mut tx, &label).await.unwrap();
         tx.commit().await.unwrap();
 
-        let edge = Edge { src, dst, label };
+        let edge = Edge {
+            src,
+            dst,
+            label,
+            directed: true,
+        };
 
         let mut tx = pool.begin().await.unwrap();
         let result = sql::edge::create(&mut tx, &edge).await;
@@ -440,7 +597,12 @@ mod tests {
         sql::entity::create(&mut tx, &label).await.unwrap();
         tx.commit().await.unwrap();
 
-        let edge = Edge { src, dst, label };
+        let edge = Edge {
+            src,
+            dst,
+            label,
+            directed: true,
+        };
 
         let mut tx = pool.begin().await.unwrap();
         sql::edge::create(&mut tx, &edge).await.unwrap();
@@ -454,6 +616,92 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn create_edges_with_distinct_labels() {
+        let pool = crate::sql::tests::setup_test_db().await;
+
+        let src = unique_entity("distinct_labels_src");
+        let dst = unique_entity("distinct_labels_dst");
+        let owns = unique_entity("distinct_labels_owns");
+        let follows = unique_entity("distinct_labels_follows");
+
+        let mut tx = pool.begin().await.unwrap();
+        for entity in [&src, &dst, &owns, &follows] {
+            sql::entity::create(&mut tx, entity).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        for label in [owns, follows] {
+            let edge = Edge {
+                src,
+                dst,
+                label,
+                directed: true,
+            };
+            sql::edge::create(&mut tx, &edge).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let edges = sql::edge::list_between(&mut tx, &src, &dst).await.unwrap();
+        assert_eq!(edges.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn create_undirected_edge_reverse_rejected() {
+        let pool = crate::sql::tests::setup_test_db().await;
+
+        let a = unique_entity("undirected_a");
+        let b = unique_entity("undirected_b");
+        let label = unique_entity("undirected_label");
+
+        let mut tx = pool.begin().await.unwrap();
+        for entity in [&a, &b, &label] {
+            sql::entity::create(&mut tx, entity).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let edge = Edge {
+            src: a,
+            dst: b,
+            label,
+            directed: false,
+        };
+        sql::edge::create(&mut tx, &edge).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let stored = sql::edge::get(&mut tx, &a, &b, &label).await.unwrap();
+        assert!(!stored.directed);
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let reverse = Edge {
+            src: b,
+            dst: a,
+            label,
+            directed: false,
+        };
+        let result = sql::edge::create(&mut tx, &reverse).await;
+        assert!(matches!(
+            result,
+            Err(crate::errors::DataStoreError::AlreadyExists)
+        ));
+        drop(tx);
+
+        let mut tx = pool.begin().await.unwrap();
+        let directed_reverse = Edge {
+            src: b,
+            dst: a,
+            label,
+            directed: true,
+        };
+        sql::edge::create(&mut tx, &directed_reverse).await.unwrap();
+        tx.commit().await.unwrap();
+    }
+
     #[tokio::test]
     async fn get_edge_not_found() {
         let pool = crate::sql::tests::setup_test_db().await;
@@ -484,7 +732,12 @@ mod tests {
         sql::entity::create(&mut tx, &label).await.unwrap();
         tx.commit().await.unwrap();
 
-        let edge = Edge { src, dst, label };
+        let edge = Edge {
+            src,
+            dst,
+            label,
+            directed: true,
+        };
 
         let mut tx = pool.begin().await.unwrap();
         sql::edge::create(&mut tx, &edge).await.unwrap();
@@ -544,11 +797,13 @@ mod tests {
             src: src1,
             dst: dst1,
             label: label1,
+            directed: true,
         };
         let edge2 = Edge {
             src: src2,
             dst: dst2,
             label: label2,
+            directed: true,
         };
 
         let mut tx = pool.begin().await.unwrap();
@@ -593,11 +848,13 @@ mod tests {
             src,
             dst: dst1,
             label: label1,
+            directed: true,
         };
         let edge2 = Edge {
             src,
             dst: dst2,
             label: label2,
+            directed: true,
         };
 
         let mut tx = pool.begin().await.unwrap();
@@ -633,11 +890,13 @@ mod tests {
             src: src1,
             dst,
             label: label1,
+            directed: true,
         };
         let edge2 = Edge {
             src: src2,
             dst,
             label: label2,
+            directed: true,
         };
 
         let mut tx = pool.begin().await.unwrap();
@@ -673,11 +932,13 @@ mod tests {
             src: src1,
             dst: dst1,
             label,
+            directed: true,
         };
         let edge2 = Edge {
             src: src2,
             dst: dst2,
             label,
+            directed: true,
         };
 
         let mut tx = pool.begin().await.unwrap();
@@ -711,11 +972,13 @@ mod tests {
             src,
             dst,
             label: label1,
+            directed: true,
         };
         let edge2 = Edge {
             src,
             dst,
             label: label2,
+            directed: true,
         };
 
         let mut tx = pool.begin().await.unwrap();
@@ -743,7 +1006,12 @@ mod tests {
         sql::entity::create(&mut tx, &label).await.unwrap();
         tx.commit().await.unwrap();
 
-        let edge = Edge { src, dst, label };
+        let edge = Edge {
+            src,
+            dst,
+            label,
+            directed: true,
+        };
 
         let mut tx = pool.begin().await.unwrap();
         sql::edge::create(&mut tx, &edge).await.unwrap();
@@ -777,7 +1045,12 @@ mod tests {
         let router = create_edge_router(pool.clone());
         let server = TestServer::new(router).unwrap();
 
-        let request_body = CreateEdgeRequest { src, dst, label };
+        let request_body = CreateEdgeRequest {
+            src,
+            dst,
+            label,
+            directed: true,
+        };
 
         let response = server.post("/edge").json(&request_body).await;
 
@@ -805,4 +1078,126 @@ mod tests {
         assert_eq!(second_response.edge.dst, dst);
         assert_eq!(second_response.edge.label, label);
     }
+
+    #[tokio::test]
+    async fn traverse_respects_max_depth() {
+        use axum_test::TestServer;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+
+        let root = unique_entity("traverse_root");
+        let first = unique_entity("traverse_first");
+        let second = unique_entity("traverse_second");
+        let third = unique_entity("traverse_third");
+        let label = unique_entity("traverse_label");
+
+        let mut tx = pool.begin().await.unwrap();
+        for entity in [&root, &first, &second, &third, &label] {
+            sql::entity::create(&mut tx, entity).await.unwrap();
+        }
+        for (src, dst) in [(root, first), (first, second), (second, third)] {
+            let edge = Edge {
+                src,
+                dst,
+                label,
+                directed: true,
+            };
+            sql::edge::create(&mut tx, &edge).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let router = create_edge_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let response = server
+            .post("/edge/traverse")
+            .json(&TraverseEdgesRequest {
+                start: root,
+                max_depth: 2,
+                label_filter: Some(label),
+            })
+            .await;
+
+        response.assert_status_ok();
+        let reachable: Vec<ReachableEntity> = response.json();
+        println!("traverse_respects_max_depth response: {:?}", reachable);
+        assert_eq!(
+            reachable,
+            vec![
+                ReachableEntity {
+                    entity: first,
+                    distance: 1
+                },
+                ReachableEntity {
+                    entity: second,
+                    distance: 2
+                },
+            ]
+        );
+
+        let response = server
+            .post("/edge/traverse")
+            .json(&TraverseEdgesRequest {
+                start: root,
+                max_depth: 3,
+                label_filter: Some(root),
+            })
+            .await;
+        response.assert_status_ok();
+        let reachable: Vec<ReachableEntity> = response.json();
+        assert!(reachable.is_empty());
+    }
+
+    #[tokio::test]
+    async fn list_out_and_in_include_undirected_edges() {
+        use axum_test::TestServer;
+
+        let pool = crate::sql::tests::setup_test_db().await;
+
+        let a = unique_entity("out_in_a");
+        let b = unique_entity("out_in_b");
+        let c = unique_entity("out_in_c");
+        let label = unique_entity("out_in_label");
+
+        let mut tx = pool.begin().await.unwrap();
+        for entity in [&a, &b, &c, &label] {
+            sql::entity::create(&mut tx, entity).await.unwrap();
+        }
+        let directed = Edge {
+            src: a,
+            dst: b,
+            label,
+            directed: true,
+        };
+        let undirected = Edge {
+            src: c,
+            dst: a,
+            label,
+            directed: false,
+        };
+        sql::edge::create(&mut tx, &directed).await.unwrap();
+        sql::edge::create(&mut tx, &undirected).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let router = create_edge_router(pool.clone());
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.get(&format!("/edge/out/{}", a)).await;
+        response.assert_status_ok();
+        let out: Vec<Edge> = response.json();
+        println!("list_out_and_in_include_undirected_edges out: {:?}", out);
+        assert_eq!(out.len(), 2);
+        assert!(out.contains(&directed));
+        assert!(out.contains(&undirected));
+
+        let response = server.get(&format!("/edge/in/{}", a)).await;
+        response.assert_status_ok();
+        let incoming: Vec<Edge> = response.json();
+        assert_eq!(incoming, vec![undirected]);
+
+        let response = server.get(&format!("/edge/in/{}", b)).await;
+        response.assert_status_ok();
+        let incoming: Vec<Edge> = response.json();
+        assert_eq!(incoming, vec![directed]);
+    }
 }
@@ -38,6 +38,7 @@
 //! assert_eq!(bytes, &[1u8; 32]);
 //! ```
 
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::fs::File;
 use std::io::Read;
@@ -52,6 +53,10 @@ const ENTITY_PREFIX_LEN: usize = 7;
 /// Expected length of base64 encoded 32 bytes (without padding)
 const BASE64_ENCODED_LEN: usize = 43;
 
+/// Number of leading and trailing base64 characters kept by `Entity::to_short_string`
+const SHORT_PREFIX_LEN: usize = 4;
+const SHORT_SUFFIX_LEN: usize = 3;
+
 /// Maximum number of retries when generating entities without special characters
 const MAX_GENERATION_RETRIES: usize = 1000;
 
@@ -59,9 +64,12 @@ use axum::Router;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 use axum::response::Json;
-use axum::routing::{delete, get};
+use axum::routing::{delete, get, post};
 use serde::{Deserialize, Serialize};
 
+use crate::component::NamePolicyState;
+use crate::{Component, ComponentNamePolicy};
+
 /////////////////////////////////////////////// Entity ////////////////////////////////////////////////
 
 /// A 32-byte entity identifier with URL-safe base64 string representation.
@@ -82,7 +90,10 @@ use serde::{Deserialize, Serialize};
 /// let parsed: Entity = entity_string.parse().unwrap();
 /// assert_eq!(entity, parsed);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+///
+/// Entities order by their bytes, lexicographically, which matches how PostgreSQL
+/// orders the BYTEA `entity_id` column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Entity([u8; 32]);
 
 impl Entity {
@@ -202,6 +213,30 @@ impl Entity {
     pub fn base64_part(&self) -> String {
         encode_base64_url_safe(&self.0)
     }
+
+    /// Returns an abbreviated form of the entity for logs and CLI output.
+    ///
+    /// The short form keeps the "entity:" prefix, the first four and the last
+    /// three base64 characters, joined by an ellipsis. It is display-only:
+    /// the elided characters cannot be recovered, so there is deliberately no
+    /// `from_short_string` and `FromStr` rejects the short form.
+    ///
+    /// # Examples
+    /// ```
+    /// # use stigmergy::Entity;
+    /// let entity = Entity::new([1u8; 32]);
+    /// assert_eq!(entity.to_short_string(), "entity:AQEB…AQE");
+    /// assert!(entity.to_short_string().parse::<Entity>().is_err());
+    /// ```
+    pub fn to_short_string(&self) -> String {
+        let encoded = encode_base64_url_safe(&self.0);
+        format!(
+            "{}{}…{}",
+            ENTITY_PREFIX,
+            &encoded[..SHORT_PREFIX_LEN],
+            &encoded[BASE64_ENCODED_LEN - SHORT_SUFFIX_LEN..]
+        )
+    }
 }
 
 ////////////////////////////////////// URL-Safe Base64 Encoding //////////////////////////////////////
@@ -355,26 +390,48 @@ impl Display for Entity {
 /// providing specific error types for different categories of parsing problems.
 #[derive(Debug, PartialEq, Eq)]
 pub enum EntityParseError {
-    /// The entity string does not start with the required "entity:" prefix
+    /// The entity string has no prefix and is not a bare base64 entity
+    MissingPrefix,
+    /// The entity string starts with a prefix other than "entity:"
     InvalidPrefix,
-    /// The entity string format is invalid (e.g., wrong length after prefix)
+    /// The base64 portion has a length no base64 encoding can produce
     InvalidFormat,
-    /// The base64 portion contains invalid characters or is malformed
-    InvalidBase64,
-    /// The decoded bytes are not exactly 32 bytes in length
-    InvalidLength,
+    /// The base64 portion contains an invalid character
+    InvalidBase64 {
+        /// Byte offset of the offending character within the parsed string
+        position: usize,
+    },
+    /// The base64 portion does not decode to exactly 32 bytes
+    WrongLength {
+        /// The number of bytes actually decoded
+        got: usize,
+    },
+    /// The namespace prefix is empty or contains characters other than ASCII
+    /// alphanumerics, `-` and `_`
+    InvalidNamespace,
 }
 
 impl Display for EntityParseError {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
+            EntityParseError::MissingPrefix => {
+                write!(f, "Missing entity prefix - expected \"entity:{{base64}}\"")
+            }
             EntityParseError::InvalidPrefix => write!(f, "Invalid entity prefix"),
             EntityParseError::InvalidFormat => write!(
                 f,
                 "Invalid entity format - expected 43-character base64 string"
             ),
-            EntityParseError::InvalidBase64 => write!(f, "Invalid base64 encoding"),
-            EntityParseError::InvalidLength => write!(f, "Entity must be exactly 32 bytes"),
+            EntityParseError::InvalidBase64 { position } => {
+                write!(f, "Invalid base64 character at position {}", position)
+            }
+            EntityParseError::WrongLength { got } => {
+                write!(f, "Entity must be exactly 32 bytes, got {}", got)
+            }
+            EntityParseError::InvalidNamespace => write!(
+                f,
+                "Invalid entity namespace - expected non-empty alphanumeric, '-' or '_'"
+            ),
         }
     }
 }
@@ -390,6 +447,9 @@ impl FromStr for Entity {
     /// - "entity:{base64}" - full format with prefix
     /// - "{base64}" - base64 only (43 characters of URL-safe base64)
     ///
+    /// A string without a prefix that is not valid base64 is reported as
+    /// [`EntityParseError::MissingPrefix`], since it cannot be a bare entity.
+    ///
     /// # Arguments
     /// * `s` - The string to parse
     ///
@@ -405,26 +465,48 @@ impl FromStr for Entity {
     /// assert_eq!(entity1, entity2);
     /// ```
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let base64_part = if let Some(base64) = s.strip_prefix(ENTITY_PREFIX) {
+        if let Some(base64) = s.strip_prefix(ENTITY_PREFIX) {
             // Has "entity:" prefix, use the part after it
-            base64
+            Entity::from_base64_part(base64, ENTITY_PREFIX_LEN)
         } else if s.contains(':') {
             // Has some other prefix - this is invalid
-            return Err(EntityParseError::InvalidPrefix);
+            Err(EntityParseError::InvalidPrefix)
         } else {
             // No prefix, assume it's already the base64 part
-            s
-        };
+            Entity::from_base64_part(s, 0).map_err(|err| match err {
+                EntityParseError::InvalidBase64 { .. } => EntityParseError::MissingPrefix,
+                err => err,
+            })
+        }
+    }
+}
 
-        if base64_part.len() != BASE64_ENCODED_LEN {
+impl Entity {
+    /// Decodes the base64 part of an entity string.
+    ///
+    /// `offset` is the byte offset of `base64_part` within the string being
+    /// parsed, so that [`EntityParseError::InvalidBase64`] positions refer to
+    /// the caller's input.
+    fn from_base64_part(base64_part: &str, offset: usize) -> Result<Self, EntityParseError> {
+        // No base64 encoding leaves a single character in its final quantum
+        if base64_part.len() % 4 == 1 {
             return Err(EntityParseError::InvalidFormat);
         }
 
-        let decoded =
-            decode_base64_url_safe(base64_part).map_err(|_| EntityParseError::InvalidBase64)?;
+        if let Some((index, _)) = base64_part
+            .char_indices()
+            .find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '-' && *c != '_')
+        {
+            return Err(EntityParseError::InvalidBase64 {
+                position: offset + index,
+            });
+        }
+
+        let decoded = decode_base64_url_safe(base64_part)
+            .map_err(|_| EntityParseError::InvalidBase64 { position: offset })?;
 
         if decoded.len() != 32 {
-            return Err(EntityParseError::InvalidLength);
+            return Err(EntityParseError::WrongLength { got: decoded.len() });
         }
 
         let mut bytes = [0u8; 32];
@@ -433,6 +515,31 @@ impl FromStr for Entity {
     }
 }
 
+impl TryFrom<&[u8]> for Entity {
+    type Error = EntityParseError;
+
+    /// Creates an Entity from a byte slice, such as a database BYTEA column.
+    ///
+    /// # Returns
+    /// * `Ok(Entity)` - The slice is exactly 32 bytes
+    /// * `Err(EntityParseError::WrongLength)` - The slice has any other length
+    ///
+    /// # Examples
+    /// ```
+    /// # use stigmergy::Entity;
+    /// let entity = Entity::new([7u8; 32]);
+    /// let bytes: &[u8] = entity.as_bytes();
+    /// assert_eq!(Entity::try_from(bytes).unwrap(), entity);
+    /// assert!(Entity::try_from(&bytes[..31]).is_err());
+    /// ```
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let bytes: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| EntityParseError::WrongLength { got: bytes.len() })?;
+        Ok(Entity(bytes))
+    }
+}
+
 impl Serialize for Entity {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -466,35 +573,116 @@ impl<'de> serde::de::Visitor<'de> for EntityVisitor {
         E: serde::de::Error,
     {
         // Handle both formats: base64-only and entity:base64
-        let base64_part = if value.starts_with(ENTITY_PREFIX) {
-            &value[ENTITY_PREFIX_LEN..]
-        } else {
-            value
-        };
+        value
+            .parse()
+            .map_err(|err| E::custom(format!("invalid entity {:?}: {}", value, err)))
+    }
+}
 
-        // Validate length
-        if base64_part.len() != BASE64_ENCODED_LEN {
-            return Err(serde::de::Error::custom(format!(
-                "Entity base64 must be exactly {} characters, got {}",
-                BASE64_ENCODED_LEN,
-                base64_part.len()
-            )));
+////////////////////////////////////////// NamespacedEntity //////////////////////////////////////////
+
+/// An entity identifier qualified by a namespace such as `user` or `order`.
+///
+/// Namespaced entities are displayed as "{namespace}:{base64}" and round-trip
+/// through `FromStr`, preserving the namespace. The plain `Entity` form
+/// corresponds to the `entity` namespace.
+///
+/// # Examples
+///
+/// ```
+/// # use stigmergy::{Entity, NamespacedEntity};
+/// let entity = NamespacedEntity::new("user", Entity::new([1u8; 32])).unwrap();
+/// assert_eq!(entity.to_string(), "user:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE");
+///
+/// let parsed: NamespacedEntity = "user:AQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQEBAQE".parse().unwrap();
+/// assert_eq!(parsed.namespace(), "user");
+/// assert_eq!(parsed.entity(), Entity::new([1u8; 32]));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NamespacedEntity {
+    namespace: String,
+    entity: Entity,
+}
+
+impl NamespacedEntity {
+    /// Creates a namespaced entity.
+    ///
+    /// # Returns
+    /// * `Ok(NamespacedEntity)` - The namespace is valid
+    /// * `Err(EntityParseError::InvalidNamespace)` - The namespace is empty or
+    ///   contains characters other than ASCII alphanumerics, `-` and `_`
+    pub fn new(namespace: &str, entity: Entity) -> Result<Self, EntityParseError> {
+        let valid = !namespace.is_empty()
+            && namespace
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(EntityParseError::InvalidNamespace);
         }
+        Ok(NamespacedEntity {
+            namespace: namespace.to_string(),
+            entity,
+        })
+    }
 
-        // Decode base64
-        let decoded = decode_base64_url_safe(base64_part)
-            .map_err(|e| serde::de::Error::custom(format!("Invalid base64: {}", e)))?;
+    /// Returns the namespace without the trailing ':'.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
 
-        if decoded.len() != 32 {
-            return Err(serde::de::Error::custom(format!(
-                "Decoded entity must be exactly 32 bytes, got {}",
-                decoded.len()
-            )));
+    /// Returns the underlying entity identifier.
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+}
+
+impl From<Entity> for NamespacedEntity {
+    fn from(entity: Entity) -> Self {
+        NamespacedEntity {
+            namespace: ENTITY_PREFIX[..ENTITY_PREFIX_LEN - 1].to_string(),
+            entity,
         }
+    }
+}
 
-        let mut bytes = [0u8; 32];
-        bytes.copy_from_slice(&decoded);
-        Ok(Entity(bytes))
+impl Display for NamespacedEntity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}:{}", self.namespace, self.entity.base64_part())
+    }
+}
+
+impl FromStr for NamespacedEntity {
+    type Err = EntityParseError;
+
+    /// Parses a namespaced entity from "{namespace}:{base64}".
+    ///
+    /// # Returns
+    /// * `Ok(NamespacedEntity)` - The parsed namespaced entity
+    /// * `Err(EntityParseError::MissingPrefix)` - The string has no ':' separator
+    /// * `Err(EntityParseError)` - The namespace or base64 part is invalid
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (namespace, base64_part) = s.split_once(':').ok_or(EntityParseError::MissingPrefix)?;
+        let entity = Entity::from_base64_part(base64_part, namespace.len() + 1)?;
+        NamespacedEntity::new(namespace, entity)
+    }
+}
+
+impl Serialize for NamespacedEntity {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for NamespacedEntity {
+    fn deserialize<D>(deserializer: D) -> Result<NamespacedEntity, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -586,6 +774,139 @@ async fn create_entity(
     }
 }
 
+/// Request structure for creating an entity together with its components.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateEntityWithComponentsRequest {
+    /// Optional entity to create. If None, a random entity will be generated.
+    #[serde(default)]
+    pub entity: Option<Entity>,
+    /// Component data to attach, keyed by component name.
+    #[serde(default)]
+    pub components: BTreeMap<String, serde_json::Value>,
+}
+
+/// Response structure for creating an entity together with its components.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateEntityWithComponentsResponse {
+    /// The entity that was created.
+    pub entity: Entity,
+    /// The components attached to the entity, in name order.
+    pub components: Vec<Component>,
+}
+
+/// HTTP endpoint for creating an entity and attaching components in one transaction.
+///
+/// Every component is validated against its definition before anything is
+/// committed; if the entity exists or any component is invalid, nothing is
+/// created.
+///
+/// # Request Format
+/// ```json
+/// {
+///   "entity": null,
+///   "components": {"Health": {"hp": 100}, "game::Position": {"x": 0, "y": 0}}
+/// }
+/// ```
+///
+/// # Errors
+/// Returns `StatusCode::BAD_REQUEST` for an invalid component name or data that fails validation.
+/// Returns `StatusCode::NOT_FOUND` if a component has no definition.
+/// Returns `StatusCode::CONFLICT` if the entity already exists in the data store.
+async fn create_entity_with_components(
+    State(NamePolicyState { pool, policy }): State<NamePolicyState>,
+    Json(request): Json<CreateEntityWithComponentsRequest>,
+) -> Result<Json<CreateEntityWithComponentsResponse>, (StatusCode, String)> {
+    let entity = match request.entity {
+        Some(entity) => entity,
+        None => Entity::random_url_safe().map_err(|_e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to generate random entity".to_string(),
+            )
+        })?,
+    };
+
+    let mut tx = pool.begin().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to begin transaction".to_string(),
+        )
+    })?;
+
+    match crate::sql::entity::create(&mut tx, &entity).await {
+        Ok(()) => {}
+        Err(crate::DataStoreError::AlreadyExists) => {
+            return Err((StatusCode::CONFLICT, "entity already exists".to_string()));
+        }
+        Err(_) => {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to create entity".to_string(),
+            ));
+        }
+    }
+
+    let mut components = Vec::new();
+    for (name, data) in &request.components {
+        let component = Component::new(name.as_str()).ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                format!("invalid component name: {}", name),
+            )
+        })?;
+        let component = crate::sql::component_definition::resolve_name(&mut tx, &component, policy)
+            .await
+            .map_err(|_| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to retrieve component definition".to_string(),
+                )
+            })?;
+        let definition = match crate::sql::component_definition::get(&mut tx, &component).await {
+            Ok(Some(def_record)) => def_record.definition,
+            Ok(None) => {
+                return Err((
+                    StatusCode::NOT_FOUND,
+                    format!("component definition not found: {}", name),
+                ));
+            }
+            Err(_) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to retrieve component definition".to_string(),
+                ));
+            }
+        };
+        if let Err(e) = definition.validate_component_data(data) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("component data validation failed for {}: {}", name, e),
+            ));
+        }
+        if crate::sql::component::create(&mut tx, &entity, &component, data)
+            .await
+            .is_err()
+        {
+            return Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "failed to create component instance".to_string(),
+            ));
+        }
+        components.push(component);
+    }
+
+    tx.commit().await.map_err(|_e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "failed to commit transaction".to_string(),
+        )
+    })?;
+    Ok(Json(CreateEntityWithComponentsResponse {
+        entity,
+        components,
+    }))
+}
+
 /// HTTP endpoint for deleting an entity by its base64 identifier.
 ///
 /// This endpoint accepts a DELETE request with the entity's base64 identifier
@@ -670,15 +991,18 @@ async fn list_entities(
         )
     })?;
 
-    match crate::sql::entity::list(&mut tx).await {
-        Ok(entities) => {
+    match crate::sql::entity::list(&mut tx, &crate::sql::entity::EntityListFilter::default()).await
+    {
+        Ok(records) => {
             tx.commit().await.map_err(|_e| {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
                     "failed to commit transaction",
                 )
             })?;
-            Ok(Json(entities))
+            Ok(Json(
+                records.into_iter().map(|record| record.entity).collect(),
+            ))
         }
         Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "failed to list entities")),
     }
@@ -694,6 +1018,7 @@ async fn list_entities(
 /// # Routes
 /// - `GET /entity` - List all entities
 /// - `POST /entity` - Create a new entity (optionally random)
+/// - `POST /entity/full` - Create an entity together with validated components
 /// - `DELETE /entity/{entity_id}` - Delete an entity by ID
 ///
 /// # Returns
@@ -711,10 +1036,17 @@ async fn list_entities(
 /// # }
 /// ```
 pub fn create_entity_router(pool: sqlx::PgPool) -> Router {
+    create_entity_router_with_policy(pool, ComponentNamePolicy::default())
+}
+
+/// Creates the entity router, resolving the component names given to `POST /entity/full`
+/// according to `policy`.
+pub fn create_entity_router_with_policy(pool: sqlx::PgPool, policy: ComponentNamePolicy) -> Router {
     Router::new()
         .route("/entity", get(list_entities).post(create_entity))
+        .route("/entity/full", post(create_entity_with_components))
         .route("/entity/:entity_id", delete(delete_entity))
-        .with_state(pool)
+        .with_state(NamePolicyState { pool, policy })
 }
 
 #[cfg(test)]
@@ -776,6 +1108,127 @@ mod tests {
         assert_eq!(display.len(), ENTITY_PREFIX_LEN + BASE64_ENCODED_LEN);
     }
 
+    #[test]
+    fn entity_short_string_format() {
+        assert_eq!(Entity::new([1u8; 32]).to_short_string(), "entity:AQEB…AQE");
+        assert_eq!(Entity::new([0u8; 32]).to_short_string(), "entity:AAAA…AAA");
+
+        let mut bytes = [0u8; 32];
+        bytes[0] = 0xff;
+        bytes[31] = 0xff;
+        let entity = Entity::new(bytes);
+        let full = entity.base64_part();
+        let short = entity.to_short_string();
+        assert_eq!(short, format!("entity:{}…{}", &full[..4], &full[40..]));
+        assert!(short.parse::<Entity>().is_err());
+    }
+
+    #[test]
+    fn entity_try_from_slice() {
+        let entity = Entity::new([
+            0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+            24, 25, 26, 27, 28, 29, 30, 31,
+        ]);
+        let round_trip = Entity::try_from(entity.as_bytes().as_slice()).unwrap();
+        assert_eq!(round_trip, entity);
+
+        let short = [0u8; 31];
+        assert_eq!(
+            Entity::try_from(short.as_slice()),
+            Err(EntityParseError::WrongLength { got: 31 })
+        );
+        let long = [0u8; 33];
+        assert_eq!(
+            Entity::try_from(long.as_slice()),
+            Err(EntityParseError::WrongLength { got: 33 })
+        );
+    }
+
+    #[test]
+    fn entity_deserialize_reports_reason() {
+        let entity = Entity::new([3u8; 32]);
+        let json = serde_json::to_string(&entity).unwrap();
+        assert_eq!(serde_json::from_str::<Entity>(&json).unwrap(), entity);
+
+        let err = serde_json::from_str::<Entity>(r#""user:AAAA""#).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with(r#"invalid entity "user:AAAA": Invalid entity prefix"#),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn entity_ordering_is_byte_lexicographic() {
+        let mut high_first = [0u8; 32];
+        high_first[0] = 1;
+        let mut high_last = [0u8; 32];
+        high_last[31] = 255;
+        let mut entities = vec![
+            Entity::new([255u8; 32]),
+            Entity::new(high_first),
+            Entity::new([0u8; 32]),
+            Entity::new(high_last),
+        ];
+        entities.sort();
+        assert_eq!(
+            entities,
+            vec![
+                Entity::new([0u8; 32]),
+                Entity::new(high_last),
+                Entity::new(high_first),
+                Entity::new([255u8; 32]),
+            ]
+        );
+    }
+
+    #[test]
+    fn namespaced_entity_round_trip() {
+        let entity = Entity::new([1u8; 32]);
+        let namespaced = NamespacedEntity::new("user", entity).unwrap();
+        let string = namespaced.to_string();
+        assert_eq!(string, format!("user:{}", entity.base64_part()));
+
+        let parsed: NamespacedEntity = string.parse().unwrap();
+        assert_eq!(parsed, namespaced);
+        assert_eq!(parsed.namespace(), "user");
+        assert_eq!(parsed.entity(), entity);
+
+        let json = serde_json::to_string(&namespaced).unwrap();
+        let deserialized: NamespacedEntity = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, namespaced);
+    }
+
+    #[test]
+    fn namespaced_entity_from_entity() {
+        let entity = Entity::new([2u8; 32]);
+        let namespaced = NamespacedEntity::from(entity);
+        assert_eq!(namespaced.namespace(), "entity");
+        assert_eq!(namespaced.to_string(), entity.to_string());
+    }
+
+    #[test]
+    fn namespaced_entity_rejects_invalid_namespace() {
+        let base64 = Entity::new([1u8; 32]).base64_part();
+        assert_eq!(
+            format!(":{}", base64).parse::<NamespacedEntity>(),
+            Err(EntityParseError::InvalidNamespace)
+        );
+        assert_eq!(
+            format!("us er:{}", base64).parse::<NamespacedEntity>(),
+            Err(EntityParseError::InvalidNamespace)
+        );
+        assert_eq!(
+            base64.parse::<NamespacedEntity>(),
+            Err(EntityParseError::MissingPrefix)
+        );
+        assert_eq!(
+            "user:short".parse::<NamespacedEntity>(),
+            Err(EntityParseError::InvalidFormat)
+        );
+    }
+
     #[test]
     fn entity_from_str_valid() {
         let entity_str = "entity:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
@@ -792,23 +1245,43 @@ mod tests {
     #[test]
     fn entity_from_str_invalid_length() {
         let result = Entity::from_str("entity:ABC");
-        assert_eq!(result, Err(EntityParseError::InvalidFormat));
+        assert_eq!(result, Err(EntityParseError::WrongLength { got: 2 }));
     }
 
     #[test]
     fn entity_from_str_invalid_base64() {
         // Test with correct length but invalid base64 characters
         let result = Entity::from_str("entity:AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA!AAAAAAAA");
-        assert_eq!(result, Err(EntityParseError::InvalidBase64));
+        assert_eq!(
+            result,
+            Err(EntityParseError::InvalidBase64 { position: 41 })
+        );
     }
 
     #[test]
     fn entity_from_str_wrong_length_triggers_format_error() {
-        // Test that wrong length triggers format error before base64 validation
-        let result = Entity::from_str("entity:SHORTSTRING");
+        // A length no base64 encoding produces is a format error
+        let result = Entity::from_str("entity:SHORT");
         assert_eq!(result, Err(EntityParseError::InvalidFormat));
     }
 
+    #[test]
+    fn entity_from_str_error_details() {
+        let result = Entity::from_str("AQEB...");
+        assert_eq!(result, Err(EntityParseError::MissingPrefix));
+
+        let twenty = format!("entity:{}", encode_base64_url_safe(&[1u8; 20]));
+        let result = Entity::from_str(&twenty);
+        assert_eq!(result, Err(EntityParseError::WrongLength { got: 20 }));
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Entity must be exactly 32 bytes, got 20"
+        );
+
+        let result = "user:AQ!B".parse::<NamespacedEntity>();
+        assert_eq!(result, Err(EntityParseError::InvalidBase64 { position: 7 }));
+    }
+
     #[test]
     fn entity_round_trip_display_fromstr() {
         let original_bytes = [
@@ -949,6 +1422,76 @@ mod tests {
         assert!(stored.is_some());
     }
 
+    fn default_policy_state(pool: &sqlx::PgPool) -> NamePolicyState {
+        NamePolicyState {
+            pool: pool.clone(),
+            policy: ComponentNamePolicy::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn create_entity_with_components_is_atomic() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let mut tx = pool.begin().await.unwrap();
+        for name in ["full::Health", "full::Name", "full::Tags"] {
+            let schema = match name {
+                "full::Health" => serde_json::json!({"type": "integer"}),
+                "full::Name" => serde_json::json!({"type": "string"}),
+                _ => serde_json::json!({"type": "array", "items": {"type": "string"}}),
+            };
+            let definition = crate::ComponentDefinition::new(Component::new(name).unwrap(), schema);
+            let _ = crate::sql::component_definition::create(&mut tx, &definition).await;
+        }
+        tx.commit().await.unwrap();
+
+        let entity = unique_entity("create_entity_with_components");
+        let request = CreateEntityWithComponentsRequest {
+            entity: Some(entity),
+            components: BTreeMap::from([
+                ("full::Health".to_string(), serde_json::json!(100)),
+                ("full::Name".to_string(), serde_json::json!("orc")),
+                ("full::Tags".to_string(), serde_json::json!(["hostile"])),
+            ]),
+        };
+        let response =
+            create_entity_with_components(State(default_policy_state(&pool)), Json(request))
+                .await
+                .unwrap()
+                .0;
+        println!(
+            "create_entity_with_components_is_atomic response: {:?}",
+            response
+        );
+        assert_eq!(response.entity, entity);
+        assert_eq!(response.components.len(), 3);
+
+        let mut tx = pool.begin().await.unwrap();
+        let stored = crate::sql::component::list_for_entity(&mut tx, &entity)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(stored.len(), 3);
+
+        let invalid = unique_entity("create_entity_with_bad_component");
+        let request = CreateEntityWithComponentsRequest {
+            entity: Some(invalid),
+            components: BTreeMap::from([
+                ("full::Health".to_string(), serde_json::json!(100)),
+                ("full::Name".to_string(), serde_json::json!(7)),
+            ]),
+        };
+        let (status, _) =
+            create_entity_with_components(State(default_policy_state(&pool)), Json(request))
+                .await
+                .unwrap_err();
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let mut tx = pool.begin().await.unwrap();
+        let stored = crate::sql::entity::get(&mut tx, &invalid).await.unwrap();
+        tx.commit().await.unwrap();
+        assert!(stored.is_none());
+    }
+
     #[tokio::test]
     async fn create_entity_generates_random_when_none() {
         let pool = crate::sql::tests::setup_test_db().await;
@@ -1,28 +1,98 @@
 //! Error types for stigmergy operations.
 
+use std::sync::Arc;
+
+use crate::ValidationError;
+
 /// Errors that can occur during data store operations.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Errors that originate in the database driver keep the driver error as their
+/// [`source`](std::error::Error::source).
+#[derive(Debug, Clone)]
 pub enum DataStoreError {
     /// The requested item was not found in the data store.
     NotFound,
     /// An item with the same identifier already exists.
     AlreadyExists,
+    /// The stored item's version differs from the version the caller expected.
+    VersionMismatch {
+        /// The version the caller expected.
+        expected: i64,
+        /// The version currently stored.
+        actual: i64,
+    },
+    /// Data did not satisfy its JSON schema.
+    ValidationFailed {
+        /// JSON Pointer to the offending location in the data; empty for the root.
+        path: String,
+        /// What was wrong at that location.
+        message: String,
+    },
+    /// A caller-supplied argument was malformed.
+    InvalidInput(String),
     /// JSON serialization or deserialization failed.
     SerializationError(String),
     /// An I/O operation failed (for persistent storage backends).
     IoError(String),
     /// An internal storage system error occurred.
     Internal(String),
+    /// The database reported an error not covered by a more specific variant.
+    Database(Arc<sqlx::Error>),
+}
+
+impl PartialEq for DataStoreError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::NotFound, Self::NotFound) => true,
+            (Self::AlreadyExists, Self::AlreadyExists) => true,
+            (
+                Self::VersionMismatch { expected, actual },
+                Self::VersionMismatch {
+                    expected: other_expected,
+                    actual: other_actual,
+                },
+            ) => expected == other_expected && actual == other_actual,
+            (
+                Self::ValidationFailed { path, message },
+                Self::ValidationFailed {
+                    path: other_path,
+                    message: other_message,
+                },
+            ) => path == other_path && message == other_message,
+            (Self::InvalidInput(a), Self::InvalidInput(b)) => a == b,
+            (Self::SerializationError(a), Self::SerializationError(b)) => a == b,
+            (Self::IoError(a), Self::IoError(b)) => a == b,
+            (Self::Internal(a), Self::Internal(b)) => a == b,
+            // sqlx errors are not comparable, so compare what they report.
+            (Self::Database(a), Self::Database(b)) => a.to_string() == b.to_string(),
+            _ => false,
+        }
+    }
 }
 
+impl Eq for DataStoreError {}
+
 impl std::fmt::Display for DataStoreError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::NotFound => write!(f, "Item not found in data store"),
             Self::AlreadyExists => write!(f, "Item already exists in data store"),
+            Self::VersionMismatch { expected, actual } => write!(
+                f,
+                "Version mismatch: expected {}, found {}",
+                expected, actual
+            ),
+            Self::ValidationFailed { path, message } if path.is_empty() => {
+                write!(f, "Validation failed: {}", message)
+            }
+            Self::ValidationFailed { path, message } => {
+                write!(f, "Validation failed at {}: {}", path, message)
+            }
+            Self::InvalidInput(msg) => write!(f, "Invalid input: {}", msg),
             Self::SerializationError(msg) => write!(f, "Serialization error: {}", msg),
             Self::IoError(msg) => write!(f, "IO error: {}", msg),
             Self::Internal(msg) => write!(f, "Internal error: {}", msg),
+            Self::Database(e) => write!(f, "Database error: {}", e),
         }
     }
 }
@@ -34,9 +104,51 @@ impl From<sqlx::Error> for DataStoreError {
             sqlx::Error::Database(db_err) if db_err.is_unique_violation() => {
                 DataStoreError::AlreadyExists
             }
-            _ => DataStoreError::Internal(e.to_string()),
+            _ => DataStoreError::Database(Arc::new(e)),
         }
     }
 }
 
-impl std::error::Error for DataStoreError {}
+impl From<ValidationError> for DataStoreError {
+    fn from(e: ValidationError) -> Self {
+        DataStoreError::ValidationFailed {
+            path: e.path(),
+            message: e.leaf().to_string(),
+        }
+    }
+}
+
+impl std::error::Error for DataStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Database(e) => Some(e.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn database_errors_keep_sqlx_source() {
+        let _: Box<dyn Error> = DataStoreError::NotFound.into();
+        assert!(DataStoreError::NotFound.source().is_none());
+
+        let error = DataStoreError::from(sqlx::Error::PoolTimedOut);
+        println!("database_errors_keep_sqlx_source response: {}", error);
+        assert!(matches!(error, DataStoreError::Database(_)));
+        let source = error.source().expect("database errors have a source");
+        assert!(matches!(
+            source.downcast_ref::<sqlx::Error>(),
+            Some(sqlx::Error::PoolTimedOut)
+        ));
+
+        assert_eq!(
+            DataStoreError::from(sqlx::Error::RowNotFound),
+            DataStoreError::NotFound
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! # Health and Readiness Probes
+//!
+//! This module exposes the endpoints load balancers and orchestrators use to decide whether
+//! a stigmergy daemon should receive traffic.
+//!
+//! - `GET /health` reports liveness: it answers `200 OK` whenever the process can serve
+//!   HTTP and never touches the database.
+//! - `GET /ready` reports readiness: it runs `SELECT 1` against the connection pool and
+//!   answers `503 Service Unavailable` with the error detail when the database cannot be
+//!   reached.
+
+use axum::Router;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Json;
+use axum::routing::get;
+use serde::{Deserialize, Serialize};
+
+/// Body returned by the health and readiness endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HealthStatus {
+    /// `"ok"` when the probe passed, `"unavailable"` otherwise
+    pub status: String,
+    /// Why the probe failed, if it did
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl HealthStatus {
+    fn ok() -> Self {
+        HealthStatus {
+            status: "ok".to_string(),
+            error: None,
+        }
+    }
+
+    fn unavailable(error: String) -> Self {
+        HealthStatus {
+            status: "unavailable".to_string(),
+            error: Some(error),
+        }
+    }
+}
+
+/// Reports that the process is up.
+async fn health() -> Json<HealthStatus> {
+    Json(HealthStatus::ok())
+}
+
+/// Reports whether the database can be queried.
+async fn ready(State(pool): State<sqlx::PgPool>) -> (StatusCode, Json<HealthStatus>) {
+    match sqlx::query("SELECT 1").execute(&pool).await {
+        Ok(_) => (StatusCode::OK, Json(HealthStatus::ok())),
+        Err(e) => (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(HealthStatus::unavailable(e.to_string())),
+        ),
+    }
+}
+
+////////////////////////////////////////////// Router //////////////////////////////////////////////
+
+/// Creates an Axum router with the `/health` and `/ready` probe endpoints.
+pub fn create_health_router(pool: sqlx::PgPool) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .route("/ready", get(ready))
+        .with_state(pool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum_test::TestServer;
+
+    #[tokio::test]
+    async fn ready_fails_when_database_is_unreachable() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let server = TestServer::new(create_health_router(pool.clone())).unwrap();
+
+        let response = server.get("/ready").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<HealthStatus>(), HealthStatus::ok());
+
+        pool.close().await;
+
+        let response = server.get("/ready").await;
+        response.assert_status(StatusCode::SERVICE_UNAVAILABLE);
+        let status: HealthStatus = response.json();
+        println!(
+            "ready_fails_when_database_is_unreachable response: {:?}",
+            status
+        );
+        assert_eq!(status.status, "unavailable");
+        assert!(status.error.is_some());
+
+        let response = server.get("/health").await;
+        response.assert_status_ok();
+        assert_eq!(response.json::<HealthStatus>(), HealthStatus::ok());
+    }
+}
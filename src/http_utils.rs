@@ -10,6 +10,10 @@
 //! - **JSON Handling**: Automatic serialization of request bodies and deserialization of responses
 //! - **Error Handling**: Comprehensive error handling with meaningful error messages
 //! - **URL Construction**: Automatic API URL construction with consistent versioning
+//! - **Retries and Timeouts**: Idempotent requests are retried on transient failures,
+//!   within per-request and total timeouts set by [`ClientConfig`]
+//! - **Typed Resources**: Methods such as `create_entity` and `apply` that take and return
+//!   the crate's request and response types, reporting failures as [`ClientError`]
 //!
 //! ## Usage Examples
 //!
@@ -28,12 +32,19 @@
 //! # };
 //! ```
 
-use reqwest::{Client, Response};
+use reqwest::{Client, Method, RequestBuilder, Response};
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use std::error::Error;
 use std::fmt;
+use std::time::{Duration, Instant};
 
-use crate::cli_utils;
+use crate::{
+    ApplyRequest, ApplyResponse, Component, ComponentDefinition, ComponentListItem,
+    CreateComponentRequest, CreateComponentResponse, CreateEdgeRequest, CreateEdgeResponse,
+    CreateEntityRequest, CreateEntityResponse, Entity, GetInvariantResponse,
+    IDEMPOTENCY_KEY_HEADER, System, SystemListItem, cli_utils,
+};
 
 /// HTTP error type for stigmergy client operations.
 ///
@@ -53,6 +64,111 @@ impl fmt::Display for HttpError {
 
 impl Error for HttpError {}
 
+/// Error returned by the typed resource methods of [`StigmergyClient`].
+#[derive(Debug)]
+pub enum ClientError {
+    /// The request could not be sent or the response could not be read.
+    Transport(reqwest::Error),
+    /// The server answered with a non-success status.
+    Status {
+        /// The HTTP status code
+        status: u16,
+        /// The error body returned by the server
+        message: String,
+    },
+    /// The response body did not match the expected type.
+    Decode(serde_json::Error),
+    /// The total timeout elapsed before a response was received.
+    Timeout,
+}
+
+impl ClientError {
+    /// Returns true if the server answered `404 Not Found`.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, ClientError::Status { status: 404, .. })
+    }
+
+    /// Returns true if the server answered `409 Conflict`.
+    pub fn is_conflict(&self) -> bool {
+        matches!(self, ClientError::Status { status: 409, .. })
+    }
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Transport(e) => write!(f, "request failed: {}", e),
+            ClientError::Status { status, message } => write!(f, "HTTP {}: {}", status, message),
+            ClientError::Decode(e) => write!(f, "invalid response body: {}", e),
+            ClientError::Timeout => write!(f, "request timed out"),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        ClientError::Transport(e)
+    }
+}
+
+/// Controls how [`StigmergyClient`] retries failed requests.
+///
+/// Only requests that are safe to repeat are retried: GET, HEAD, PUT and DELETE,
+/// plus POST and PATCH requests that carry an idempotency key. A request is retried
+/// when it fails to connect, times out, or receives a 5xx response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRetryPolicy {
+    /// Total number of attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each further retry
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between retries
+    pub max_backoff: Duration,
+}
+
+impl Default for HttpRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl HttpRetryPolicy {
+    /// A policy that sends every request exactly once.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+}
+
+/// Timeouts and retry behavior for [`StigmergyClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientConfig {
+    /// Limit on a single attempt, from sending the request to reading the response
+    pub request_timeout: Option<Duration>,
+    /// Limit on a whole call, including every retry and the waits between them
+    pub total_timeout: Option<Duration>,
+    /// When and how often to retry
+    pub retry: HttpRetryPolicy,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            request_timeout: Some(Duration::from_secs(30)),
+            total_timeout: Some(Duration::from_secs(60)),
+            retry: HttpRetryPolicy::default(),
+        }
+    }
+}
+
 /// HTTP client for communicating with stigmergy API services.
 ///
 /// This client provides a high-level interface for making HTTP requests to
@@ -72,6 +188,8 @@ pub struct StigmergyClient {
     client: Client,
     /// The base URL for the stigmergy API service
     base_url: String,
+    /// Timeouts and retry behavior
+    config: ClientConfig,
 }
 
 impl StigmergyClient {
@@ -87,9 +205,36 @@ impl StigmergyClient {
     /// let client = StigmergyClient::new("https://api.example.com".to_string());
     /// ```
     pub fn new(base_url: String) -> Self {
+        Self::with_config(base_url, ClientConfig::default())
+    }
+
+    /// Creates a new StigmergyClient with explicit timeouts and retry behavior.
+    ///
+    /// # Arguments
+    /// * `base_url` - The base URL of the stigmergy API service
+    /// * `config` - Timeouts and retry policy
+    ///
+    /// # Examples
+    /// ```
+    /// use std::time::Duration;
+    /// use stigmergy::http_utils::{ClientConfig, HttpRetryPolicy, StigmergyClient};
+    ///
+    /// let config = ClientConfig {
+    ///     request_timeout: Some(Duration::from_secs(5)),
+    ///     total_timeout: Some(Duration::from_secs(20)),
+    ///     retry: HttpRetryPolicy::none(),
+    /// };
+    /// let client = StigmergyClient::with_config("http://localhost:3000".to_string(), config);
+    /// ```
+    pub fn with_config(base_url: String, config: ClientConfig) -> Self {
+        let mut builder = Client::builder();
+        if let Some(timeout) = config.request_timeout {
+            builder = builder.timeout(timeout);
+        }
         Self {
-            client: Client::new(),
+            client: builder.build().expect("failed to build HTTP client"),
             base_url,
+            config,
         }
     }
 
@@ -143,7 +288,7 @@ impl StigmergyClient {
         T: DeserializeOwned,
     {
         let url = self.api_url(path);
-        let response = self.client.get(&url).send().await?;
+        let response = self.send(self.client.get(&url)).await?;
         self.handle_response(response).await
     }
 
@@ -178,7 +323,7 @@ impl StigmergyClient {
         T: DeserializeOwned,
     {
         let url = self.api_url(path);
-        let response = self.client.post(&url).json(body).send().await?;
+        let response = self.send(self.client.post(&url).json(body)).await?;
         self.handle_response(response).await
     }
 
@@ -211,7 +356,7 @@ impl StigmergyClient {
         T: DeserializeOwned,
     {
         let url = self.api_url(path);
-        let response = self.client.post(&url).send().await?;
+        let response = self.send(self.client.post(&url)).await?;
         self.handle_response(response).await
     }
 
@@ -246,7 +391,7 @@ impl StigmergyClient {
         T: DeserializeOwned,
     {
         let url = self.api_url(path);
-        let response = self.client.put(&url).json(body).send().await?;
+        let response = self.send(self.client.put(&url).json(body)).await?;
         self.handle_response(response).await
     }
 
@@ -273,7 +418,7 @@ impl StigmergyClient {
     /// ```
     pub async fn delete(&self, path: &str) -> Result<(), Box<dyn Error>> {
         let url = self.api_url(path);
-        let response = self.client.delete(&url).send().await?;
+        let response = self.send(self.client.delete(&url)).await?;
 
         if response.status().is_success() {
             Ok(())
@@ -288,6 +433,56 @@ impl StigmergyClient {
         }
     }
 
+    /// Sends a request, retrying it according to the client's policy.
+    ///
+    /// Responses are returned whatever their status; a 5xx response is returned
+    /// once the request is not retryable or the attempts are exhausted.
+    async fn send(&self, request: RequestBuilder) -> Result<Response, ClientError> {
+        let request = request.build()?;
+        let retryable = matches!(
+            *request.method(),
+            Method::GET | Method::HEAD | Method::PUT | Method::DELETE
+        ) || request.headers().contains_key(IDEMPOTENCY_KEY_HEADER);
+        let deadline = self
+            .config
+            .total_timeout
+            .map(|timeout| Instant::now() + timeout);
+        let mut backoff = self.config.retry.initial_backoff;
+        let mut attempt = 1;
+        let mut request = Some(request);
+
+        loop {
+            // Keep a copy for the next attempt; bodies that cannot be cloned are sent once.
+            let current = request.take().expect("request is present until sent");
+            request = current.try_clone();
+            let execution = self.client.execute(current);
+            let result = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline.into(), execution)
+                    .await
+                    .map_err(|_| ClientError::Timeout)?,
+                None => execution.await,
+            };
+
+            let transient = match &result {
+                Ok(response) => response.status().is_server_error(),
+                Err(e) => e.is_connect() || e.is_timeout(),
+            };
+            let out_of_time = deadline.is_some_and(|deadline| Instant::now() + backoff >= deadline);
+            if !retryable
+                || !transient
+                || request.is_none()
+                || attempt >= self.config.retry.max_attempts
+                || out_of_time
+            {
+                return Ok(result?);
+            }
+
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(self.config.retry.max_backoff);
+            attempt += 1;
+        }
+    }
+
     /// Handles HTTP response processing with automatic JSON deserialization.
     ///
     /// This internal method processes HTTP responses, deserializing successful
@@ -320,6 +515,243 @@ impl StigmergyClient {
     }
 }
 
+/// Typed methods for each stigmergy resource.
+///
+/// These build the request path and body from the crate's own types and map
+/// failures to [`ClientError`], so callers never construct URLs or JSON by hand.
+///
+/// # Examples
+/// ```no_run
+/// # use stigmergy::http_utils::{ClientError, StigmergyClient};
+/// # use stigmergy::{ApplyRequest, Operation};
+/// # async fn example() -> Result<(), ClientError> {
+/// let client = StigmergyClient::new("http://localhost:3000".to_string());
+/// let created = client.create_entity(None).await?;
+/// let response = client
+///     .apply(&ApplyRequest::new(vec![Operation::DeleteEntity {
+///         entity: created.entity.into(),
+///     }]))
+///     .await?;
+/// assert!(response.committed);
+/// # Ok(())
+/// # }
+/// ```
+impl StigmergyClient {
+    /// Creates an entity, or a random one when `entity` is `None`.
+    pub async fn create_entity(
+        &self,
+        entity: Option<Entity>,
+    ) -> Result<CreateEntityResponse, ClientError> {
+        let request = self
+            .client
+            .post(self.api_url("entity"))
+            .json(&CreateEntityRequest { entity });
+        self.send_typed(request).await
+    }
+
+    /// Lists all entities.
+    pub async fn list_entities(&self) -> Result<Vec<Entity>, ClientError> {
+        self.send_typed(self.client.get(self.api_url("entity")))
+            .await
+    }
+
+    /// Deletes an entity.
+    pub async fn delete_entity(&self, entity: &Entity) -> Result<(), ClientError> {
+        let path = format!("entity/{}", entity.base64_part());
+        self.send_empty(self.client.delete(self.api_url(&path)))
+            .await
+    }
+
+    /// Creates a component definition.
+    pub async fn create_component_definition(
+        &self,
+        definition: &ComponentDefinition,
+    ) -> Result<ComponentDefinition, ClientError> {
+        let request = self
+            .client
+            .post(self.api_url("componentdefinition"))
+            .json(definition);
+        self.send_typed(request).await
+    }
+
+    /// Gets a component definition.
+    pub async fn get_component_definition(
+        &self,
+        component: &Component,
+    ) -> Result<ComponentDefinition, ClientError> {
+        let path = format!("componentdefinition/{}", component.as_str());
+        self.send_typed(self.client.get(self.api_url(&path))).await
+    }
+
+    /// Lists all component definitions.
+    pub async fn list_component_definitions(
+        &self,
+    ) -> Result<Vec<ComponentDefinition>, ClientError> {
+        self.send_typed(self.client.get(self.api_url("componentdefinition")))
+            .await
+    }
+
+    /// Attaches a new component instance to an entity.
+    pub async fn create_component(
+        &self,
+        entity: &Entity,
+        component: &Component,
+        data: &Value,
+    ) -> Result<CreateComponentResponse, ClientError> {
+        let path = format!("entity/{}/component", entity.base64_part());
+        let request = self
+            .client
+            .post(self.api_url(&path))
+            .json(&CreateComponentRequest {
+                component: component.clone(),
+                data: data.clone(),
+            });
+        self.send_typed(request).await
+    }
+
+    /// Lists the component instances of an entity.
+    pub async fn list_components(
+        &self,
+        entity: &Entity,
+    ) -> Result<Vec<ComponentListItem>, ClientError> {
+        let path = format!("entity/{}/component", entity.base64_part());
+        self.send_typed(self.client.get(self.api_url(&path))).await
+    }
+
+    /// Gets the data of a component instance.
+    pub async fn get_component(
+        &self,
+        entity: &Entity,
+        component: &Component,
+    ) -> Result<Value, ClientError> {
+        let path = component_path(entity, component);
+        self.send_typed(self.client.get(self.api_url(&path))).await
+    }
+
+    /// Replaces the data of a component instance.
+    pub async fn update_component(
+        &self,
+        entity: &Entity,
+        component: &Component,
+        data: &Value,
+    ) -> Result<Value, ClientError> {
+        let path = component_path(entity, component);
+        self.send_typed(self.client.put(self.api_url(&path)).json(data))
+            .await
+    }
+
+    /// Deletes a component instance.
+    pub async fn delete_component(
+        &self,
+        entity: &Entity,
+        component: &Component,
+    ) -> Result<(), ClientError> {
+        let path = component_path(entity, component);
+        self.send_empty(self.client.delete(self.api_url(&path)))
+            .await
+    }
+
+    /// Creates an edge.
+    pub async fn create_edge(
+        &self,
+        request: &CreateEdgeRequest,
+    ) -> Result<CreateEdgeResponse, ClientError> {
+        self.send_typed(self.client.post(self.api_url("edge")).json(request))
+            .await
+    }
+
+    /// Lists all systems.
+    pub async fn list_systems(&self) -> Result<Vec<SystemListItem>, ClientError> {
+        self.send_typed(self.client.get(self.api_url("system")))
+            .await
+    }
+
+    /// Gets a system by name.
+    pub async fn get_system(&self, name: &str) -> Result<System, ClientError> {
+        let path = format!("system/{}", name);
+        self.send_typed(self.client.get(self.api_url(&path))).await
+    }
+
+    /// Lists all invariants.
+    pub async fn list_invariants(&self) -> Result<Vec<GetInvariantResponse>, ClientError> {
+        self.send_typed(self.client.get(self.api_url("invariant")))
+            .await
+    }
+
+    /// Gets an invariant by ID or name.
+    pub async fn get_invariant(
+        &self,
+        id_or_name: &str,
+    ) -> Result<GetInvariantResponse, ClientError> {
+        let path = format!("invariant/{}", id_or_name);
+        self.send_typed(self.client.get(self.api_url(&path))).await
+    }
+
+    /// Applies a batch of operations.
+    ///
+    /// The request is sent once; use [`StigmergyClient::apply_idempotent`] to allow retries.
+    pub async fn apply(&self, request: &ApplyRequest) -> Result<ApplyResponse, ClientError> {
+        self.send_typed(self.client.post(self.api_url("apply")).json(request))
+            .await
+    }
+
+    /// Applies a batch of operations under an idempotency key.
+    ///
+    /// The server replays the original response for a repeated key, so this call
+    /// is retried on transient failures like any idempotent request.
+    pub async fn apply_idempotent(
+        &self,
+        request: &ApplyRequest,
+        idempotency_key: &str,
+    ) -> Result<ApplyResponse, ClientError> {
+        let request = self
+            .client
+            .post(self.api_url("apply"))
+            .header(IDEMPOTENCY_KEY_HEADER, idempotency_key)
+            .json(request);
+        self.send_typed(request).await
+    }
+
+    /// Sends a request and decodes a successful JSON response.
+    async fn send_typed<T>(&self, request: RequestBuilder) -> Result<T, ClientError>
+    where
+        T: DeserializeOwned,
+    {
+        let response = Self::check_status(self.send(request).await?).await?;
+        let body = response.bytes().await?;
+        serde_json::from_slice(&body).map_err(ClientError::Decode)
+    }
+
+    /// Sends a request whose successful response carries no body.
+    async fn send_empty(&self, request: RequestBuilder) -> Result<(), ClientError> {
+        Self::check_status(self.send(request).await?).await?;
+        Ok(())
+    }
+
+    /// Converts a non-success response into [`ClientError::Status`].
+    async fn check_status(response: Response) -> Result<Response, ClientError> {
+        let status = response.status();
+        if status.is_success() {
+            Ok(response)
+        } else {
+            let message = response.text().await.unwrap_or_default();
+            Err(ClientError::Status {
+                status: status.as_u16(),
+                message,
+            })
+        }
+    }
+}
+
+/// Builds the API path of a component instance.
+fn component_path(entity: &Entity, component: &Component) -> String {
+    format!(
+        "entity/{}/component/{}",
+        entity.base64_part(),
+        component.as_str()
+    )
+}
+
 /// Executes an HTTP operation and terminates the program on error.
 ///
 /// This utility function wraps HTTP operations with automatic error handling,
@@ -360,3 +792,164 @@ where
         Err(e) => cli_utils::exit_with_error(&format!("{}: {}", context, e)),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Operation, create_apply_router, create_component_instance_router};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn typed_client_round_trip() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let router = axum::Router::new()
+            .nest("/api/v1", crate::create_entity_router(pool.clone()))
+            .nest(
+                "/api/v1",
+                crate::create_component_definition_router(pool.clone()),
+            )
+            .nest("/api/v1", create_component_instance_router(pool.clone()))
+            .nest("/api/v1", create_apply_router(pool.clone()));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let client = StigmergyClient::new(format!("http://{}", addr));
+        let position = Component::new("Position").unwrap();
+        client
+            .create_component_definition(&ComponentDefinition::new(
+                position.clone(),
+                json!({"type": "object"}),
+            ))
+            .await
+            .unwrap();
+        let created = client.create_entity(None).await.unwrap();
+        let entity = created.entity;
+
+        let response = client
+            .apply(&ApplyRequest::new(vec![Operation::UpsertComponent {
+                entity: entity.into(),
+                component: position.clone(),
+                data: json!({"x": 1}),
+            }]))
+            .await
+            .unwrap();
+        println!("typed_client_round_trip response: {:?}", response);
+        assert!(response.committed);
+
+        let data = client.get_component(&entity, &position).await.unwrap();
+        assert_eq!(data, json!({"x": 1}));
+
+        client.delete_component(&entity, &position).await.unwrap();
+        let missing = client.get_component(&entity, &position).await.unwrap_err();
+        assert!(missing.is_not_found());
+    }
+
+    /// Serves `/api/v1/flaky` answering 503 to the first `failures` hits and 200
+    /// afterwards, counting hits.
+    async fn flaky_server(
+        failures: usize,
+    ) -> (String, std::sync::Arc<std::sync::atomic::AtomicUsize>) {
+        use axum::http::StatusCode;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        let handler = move || {
+            let counter = counter.clone();
+            async move {
+                if counter.fetch_add(1, Ordering::SeqCst) < failures {
+                    (StatusCode::SERVICE_UNAVAILABLE, axum::Json(json!("busy")))
+                } else {
+                    (StatusCode::OK, axum::Json(json!("ok")))
+                }
+            }
+        };
+        let router = axum::Router::new().route(
+            "/api/v1/flaky",
+            axum::routing::get(handler.clone()).post(handler),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        (format!("http://{}", addr), hits)
+    }
+
+    #[tokio::test]
+    async fn get_retries_transient_failure() {
+        use std::sync::atomic::Ordering;
+
+        let (base_url, hits) = flaky_server(1).await;
+        let client = StigmergyClient::new(base_url);
+
+        let body: Value = client.get("flaky").await.unwrap();
+        println!("get_retries_transient_failure response: {:?}", body);
+        assert_eq!(body, json!("ok"));
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn post_without_idempotency_key_is_not_retried() {
+        use std::sync::atomic::Ordering;
+
+        let (base_url, hits) = flaky_server(2).await;
+        let client = StigmergyClient::new(base_url);
+
+        let result: Result<Value, _> = client.post("flaky", &json!({})).await;
+        println!(
+            "post_without_idempotency_key_is_not_retried response: {:?}",
+            result
+        );
+        assert!(result.is_err());
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn post_with_idempotency_key_retries_until_success() {
+        use std::sync::atomic::Ordering;
+
+        let (base_url, hits) = flaky_server(2).await;
+        let client = StigmergyClient::new(base_url);
+
+        let request = client
+            .client
+            .post(client.api_url("flaky"))
+            .header(IDEMPOTENCY_KEY_HEADER, "retry-me")
+            .json(&json!({}));
+        let response = client.send(request).await.unwrap();
+        println!(
+            "post_with_idempotency_key_retries_until_success response: {:?}",
+            response.status()
+        );
+        assert!(response.status().is_success());
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn post_with_idempotency_key_stops_after_max_attempts() {
+        use std::sync::atomic::Ordering;
+
+        let (base_url, hits) = flaky_server(5).await;
+        let client = StigmergyClient::new(base_url);
+
+        let request = client
+            .client
+            .post(client.api_url("flaky"))
+            .header(IDEMPOTENCY_KEY_HEADER, "retry-me")
+            .json(&json!({}));
+        let response = client.send(request).await.unwrap();
+        println!(
+            "post_with_idempotency_key_stops_after_max_attempts response: {:?}",
+            response.status()
+        );
+        assert_eq!(
+            response.status(),
+            axum::http::StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            hits.load(Ordering::SeqCst),
+            HttpRetryPolicy::default().max_attempts as usize
+        );
+    }
+}
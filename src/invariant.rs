@@ -10,6 +10,7 @@
 //! - **Deterministic Parsing**: String representation is deterministic and reversible
 //! - **Random Generation**: Support for cryptographically random invariant generation
 //! - **Assertion Storage**: Each invariant stores its assertion condition
+//! - **Evaluation**: Assertions are evaluated against every entity's components to find violations
 //!
 //! ## InvariantID Format
 //!
@@ -37,6 +38,7 @@
 //! assert_eq!(bytes, &[1u8; 32]);
 //! ```
 
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::fs::File;
 use std::io::Read;
@@ -49,6 +51,12 @@ use axum::response::Json;
 use axum::routing::get;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+
+use crate::{
+    BidParseError, BidParser, DataContext, DataStoreError, Entity, EntityResolver, EvaluationError,
+};
 
 ////////////////////////////////////////////// Constants ///////////////////////////////////////////////
 
@@ -450,6 +458,169 @@ impl<'de> serde::de::Visitor<'de> for InvariantIDVisitor {
     }
 }
 
+/////////////////////////////////////////// Invariant Evaluation ////////////////////////////////////////
+
+/// An entity whose components fail an invariant's assertion.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InvariantViolation {
+    /// The invariant that was violated.
+    pub invariant_id: InvariantID,
+    /// The entity that violates the invariant.
+    pub entity: Entity,
+    /// Why the assertion failed for this entity.
+    pub reason: String,
+}
+
+/// Errors that prevent invariants from being evaluated.
+#[derive(Debug, Clone)]
+pub enum InvariantEvaluationError {
+    /// The invariant's assertion is not a valid expression.
+    InvalidAssertion {
+        /// The invariant whose assertion failed to parse
+        invariant_id: InvariantID,
+        /// The parse error
+        error: BidParseError,
+    },
+    /// Loading invariants or components from the data store failed.
+    DataStore(DataStoreError),
+}
+
+impl Display for InvariantEvaluationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            InvariantEvaluationError::InvalidAssertion {
+                invariant_id,
+                error,
+            } => write!(f, "invalid assertion for {}: {}", invariant_id, error),
+            InvariantEvaluationError::DataStore(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for InvariantEvaluationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InvariantEvaluationError::InvalidAssertion { error, .. } => Some(error),
+            InvariantEvaluationError::DataStore(error) => Some(error),
+        }
+    }
+}
+
+impl From<DataStoreError> for InvariantEvaluationError {
+    fn from(error: DataStoreError) -> Self {
+        InvariantEvaluationError::DataStore(error)
+    }
+}
+
+/// A snapshot of every entity's components, keyed by component name.
+///
+/// Also serves as the resolver for `*` dereferences inside assertions.
+struct EntityDocuments {
+    documents: HashMap<Entity, Value>,
+}
+
+impl EntityDocuments {
+    async fn load(tx: &mut Transaction<'_, Postgres>) -> Result<Self, DataStoreError> {
+        let mut documents: HashMap<Entity, Value> = HashMap::new();
+        for ((entity, component), data) in crate::sql::component::list_all(tx).await? {
+            if let Value::Object(map) = documents
+                .entry(entity)
+                .or_insert_with(|| Value::Object(serde_json::Map::new()))
+            {
+                map.insert(component.as_str().to_string(), data);
+            }
+        }
+        Ok(Self { documents })
+    }
+
+    fn violations(
+        &self,
+        invariant_id: &InvariantID,
+        asserts: &str,
+    ) -> Result<Vec<InvariantViolation>, InvariantEvaluationError> {
+        let expression = BidParser::parse_expression(asserts).map_err(|error| {
+            InvariantEvaluationError::InvalidAssertion {
+                invariant_id: *invariant_id,
+                error,
+            }
+        })?;
+
+        let mut violations = Vec::new();
+        for (entity, document) in &self.documents {
+            let reason = match expression.evaluate_truthy(&DataContext::new(document, self)) {
+                Ok(true) => continue,
+                Ok(false) => format!("assertion `{}` does not hold", asserts),
+                // Entities without the components an assertion refers to are not subject to it.
+                Err(EvaluationError::UndefinedVariable { .. }) => continue,
+                Err(error) => error.to_string(),
+            };
+            violations.push(InvariantViolation {
+                invariant_id: *invariant_id,
+                entity: *entity,
+                reason,
+            });
+        }
+        violations.sort_by_key(|violation| violation.entity.to_string());
+        Ok(violations)
+    }
+}
+
+impl EntityResolver for EntityDocuments {
+    fn resolve(&self, key: &Value) -> Result<Option<Value>, EvaluationError> {
+        let Some(entity) = key.as_str().and_then(|s| s.parse::<Entity>().ok()) else {
+            return Ok(None);
+        };
+        Ok(self.documents.get(&entity).cloned())
+    }
+}
+
+/// Evaluates a single invariant assertion against every entity in the data store.
+///
+/// Each entity is presented to the assertion as an object mapping component names to
+/// component data, so `score >= 0` reads the `score` component. Entities lacking a
+/// component referenced by the assertion are skipped.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `invariant_id` - The invariant being evaluated
+/// * `asserts` - The assertion expression
+///
+/// # Returns
+/// * `Ok(Vec<InvariantViolation>)` - The entities violating the assertion
+/// * `Err(InvariantEvaluationError)` - The assertion is invalid or the data store failed
+pub async fn evaluate_invariant(
+    tx: &mut Transaction<'_, Postgres>,
+    invariant_id: &InvariantID,
+    asserts: &str,
+) -> Result<Vec<InvariantViolation>, InvariantEvaluationError> {
+    EntityDocuments::load(tx)
+        .await?
+        .violations(invariant_id, asserts)
+}
+
+/// Evaluates every registered invariant against every entity in the data store.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+///
+/// # Returns
+/// * `Ok(Vec<InvariantViolation>)` - All violations, grouped by invariant
+/// * `Err(InvariantEvaluationError)` - An assertion is invalid or the data store failed
+pub async fn evaluate_invariants(
+    tx: &mut Transaction<'_, Postgres>,
+) -> Result<Vec<InvariantViolation>, InvariantEvaluationError> {
+    let invariants = crate::sql::invariants::list(tx).await?;
+    if invariants.is_empty() {
+        return Ok(Vec::new());
+    }
+    let documents = EntityDocuments::load(tx).await?;
+    let mut violations = Vec::new();
+    for invariant in invariants {
+        violations.extend(documents.violations(&invariant.invariant_id, &invariant.asserts)?);
+    }
+    Ok(violations)
+}
+
 ////////////////////////////////////////// HTTP Request/Response Types ////////////////////////////////////
 
 /// Request structure for creating a new invariant.
@@ -457,6 +628,9 @@ impl<'de> serde::de::Visitor<'de> for InvariantIDVisitor {
 pub struct CreateInvariantRequest {
     /// Optional invariant ID. If not provided, a random one will be generated.
     pub invariant_id: Option<InvariantID>,
+    /// Optional human-readable name, unique across invariants.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// The assertion expression as a string.
     pub asserts: String,
 }
@@ -466,6 +640,9 @@ pub struct CreateInvariantRequest {
 pub struct CreateInvariantResponse {
     /// The created invariant's ID.
     pub invariant_id: InvariantID,
+    /// The invariant's name, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// The assertion expression.
     pub asserts: String,
 }
@@ -475,6 +652,9 @@ pub struct CreateInvariantResponse {
 pub struct GetInvariantResponse {
     /// The invariant's ID.
     pub invariant_id: InvariantID,
+    /// The invariant's name, if it has one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
     /// The assertion expression.
     pub asserts: String,
     /// When the invariant was created.
@@ -488,6 +668,44 @@ pub struct GetInvariantResponse {
 pub struct UpdateInvariantRequest {
     /// The new assertion expression.
     pub asserts: String,
+    /// The new name. If omitted, the existing name is kept.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Maximum length of an invariant name.
+const MAX_INVARIANT_NAME_LEN: usize = 255;
+
+/// Returns true if `name` can be used as an invariant name.
+///
+/// Names are non-empty runs of ASCII alphanumerics, `_`, and `-`, and must not parse as an
+/// `InvariantID` so that a path segment is unambiguously either an ID or a name.
+fn is_valid_invariant_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_INVARIANT_NAME_LEN
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        && InvariantID::from_str(name).is_err()
+}
+
+/// Resolves a path segment that is either an invariant ID (without prefix) or a name.
+async fn resolve_invariant_id(
+    tx: &mut Transaction<'_, Postgres>,
+    id_or_name: &str,
+) -> Result<InvariantID, (StatusCode, &'static str)> {
+    let invariant_string = format!("{}{}", INVARIANT_PREFIX, id_or_name);
+    if let Ok(invariant_id) = InvariantID::from_str(&invariant_string) {
+        return Ok(invariant_id);
+    }
+    if !is_valid_invariant_name(id_or_name) {
+        return Err((StatusCode::BAD_REQUEST, "invalid invariant id"));
+    }
+    match crate::sql::invariants::get_by_name(tx, id_or_name).await {
+        Ok(Some(record)) => Ok(record.invariant_id),
+        Ok(None) => Err((StatusCode::NOT_FOUND, "invariant not found")),
+        Err(_) => Err((StatusCode::INTERNAL_SERVER_ERROR, "failed to get invariant")),
+    }
 }
 
 ////////////////////////////////////////////// HTTP Handlers //////////////////////////////////////////////
@@ -508,6 +726,12 @@ async fn create_invariant(
             )
         })?;
 
+    if let Some(name) = &request.name
+        && !is_valid_invariant_name(name)
+    {
+        return Err((StatusCode::BAD_REQUEST, "invalid invariant name"));
+    }
+
     let mut tx = pool.begin().await.map_err(|_e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -517,6 +741,20 @@ async fn create_invariant(
 
     match crate::sql::invariants::create(&mut tx, &invariant_id, &request.asserts).await {
         Ok(()) => {
+            if let Some(name) = &request.name {
+                match crate::sql::invariants::set_name(&mut tx, &invariant_id, Some(name)).await {
+                    Ok(_) => {}
+                    Err(crate::DataStoreError::AlreadyExists) => {
+                        return Err((StatusCode::CONFLICT, "invariant name already exists"));
+                    }
+                    Err(_) => {
+                        return Err((
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "failed to create invariant",
+                        ));
+                    }
+                }
+            }
             tx.commit().await.map_err(|_e| {
                 (
                     StatusCode::INTERNAL_SERVER_ERROR,
@@ -527,6 +765,7 @@ async fn create_invariant(
                 StatusCode::CREATED,
                 Json(CreateInvariantResponse {
                     invariant_id,
+                    name: request.name,
                     asserts: request.asserts,
                 }),
             ))
@@ -541,16 +780,11 @@ async fn create_invariant(
     }
 }
 
-/// HTTP endpoint for getting a specific invariant by ID.
+/// HTTP endpoint for getting a specific invariant by ID or name.
 async fn get_invariant(
     State(pool): State<sqlx::PgPool>,
-    Path(invariant_base64): Path<String>,
+    Path(invariant_id_or_name): Path<String>,
 ) -> Result<Json<GetInvariantResponse>, (StatusCode, &'static str)> {
-    let invariant_string = format!("{}{}", INVARIANT_PREFIX, invariant_base64);
-
-    let invariant_id = InvariantID::from_str(&invariant_string)
-        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid invariant id"))?;
-
     let mut tx = pool.begin().await.map_err(|_e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -558,6 +792,8 @@ async fn get_invariant(
         )
     })?;
 
+    let invariant_id = resolve_invariant_id(&mut tx, &invariant_id_or_name).await?;
+
     match crate::sql::invariants::get(&mut tx, &invariant_id).await {
         Ok(Some(record)) => {
             tx.commit().await.map_err(|_e| {
@@ -568,6 +804,7 @@ async fn get_invariant(
             })?;
             Ok(Json(GetInvariantResponse {
                 invariant_id: record.invariant_id,
+                name: record.name,
                 asserts: record.asserts,
                 created_at: record.created_at,
                 updated_at: record.updated_at,
@@ -581,14 +818,9 @@ async fn get_invariant(
 /// HTTP endpoint for updating an existing invariant.
 async fn update_invariant(
     State(pool): State<sqlx::PgPool>,
-    Path(invariant_base64): Path<String>,
+    Path(invariant_id_or_name): Path<String>,
     Json(request): Json<UpdateInvariantRequest>,
 ) -> Result<Json<GetInvariantResponse>, (StatusCode, &'static str)> {
-    let invariant_string = format!("{}{}", INVARIANT_PREFIX, invariant_base64);
-
-    let invariant_id = InvariantID::from_str(&invariant_string)
-        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid invariant id"))?;
-
     let mut tx = pool.begin().await.map_err(|_e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -596,6 +828,26 @@ async fn update_invariant(
         )
     })?;
 
+    let invariant_id = resolve_invariant_id(&mut tx, &invariant_id_or_name).await?;
+
+    if let Some(name) = &request.name {
+        if !is_valid_invariant_name(name) {
+            return Err((StatusCode::BAD_REQUEST, "invalid invariant name"));
+        }
+        match crate::sql::invariants::set_name(&mut tx, &invariant_id, Some(name)).await {
+            Ok(_) => {}
+            Err(crate::DataStoreError::AlreadyExists) => {
+                return Err((StatusCode::CONFLICT, "invariant name already exists"));
+            }
+            Err(_) => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "failed to update invariant",
+                ));
+            }
+        }
+    }
+
     match crate::sql::invariants::update(&mut tx, &invariant_id, &request.asserts).await {
         Ok(true) => match crate::sql::invariants::get(&mut tx, &invariant_id).await {
             Ok(Some(record)) => {
@@ -607,6 +859,7 @@ async fn update_invariant(
                 })?;
                 Ok(Json(GetInvariantResponse {
                     invariant_id: record.invariant_id,
+                    name: record.name,
                     asserts: record.asserts,
                     created_at: record.created_at,
                     updated_at: record.updated_at,
@@ -629,13 +882,8 @@ async fn update_invariant(
 /// HTTP endpoint for deleting an invariant by ID.
 async fn delete_invariant(
     State(pool): State<sqlx::PgPool>,
-    Path(invariant_base64): Path<String>,
+    Path(invariant_id_or_name): Path<String>,
 ) -> Result<StatusCode, (StatusCode, &'static str)> {
-    let invariant_string = format!("{}{}", INVARIANT_PREFIX, invariant_base64);
-
-    let invariant_id = InvariantID::from_str(&invariant_string)
-        .map_err(|_| (StatusCode::BAD_REQUEST, "invalid invariant id"))?;
-
     let mut tx = pool.begin().await.map_err(|_e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
@@ -643,6 +891,8 @@ async fn delete_invariant(
         )
     })?;
 
+    let invariant_id = resolve_invariant_id(&mut tx, &invariant_id_or_name).await?;
+
     match crate::sql::invariants::delete(&mut tx, &invariant_id).await {
         Ok(true) => {
             tx.commit().await.map_err(|_e| {
@@ -684,6 +934,7 @@ async fn list_invariants(
                 .into_iter()
                 .map(|record| GetInvariantResponse {
                     invariant_id: record.invariant_id,
+                    name: record.name,
                     asserts: record.asserts,
                     created_at: record.created_at,
                     updated_at: record.updated_at,
@@ -708,9 +959,9 @@ async fn list_invariants(
 /// # Routes
 /// - `GET /invariant` - List all invariants
 /// - `POST /invariant` - Create a new invariant
-/// - `GET /invariant/{invariant_id}` - Get a specific invariant by ID
-/// - `PUT /invariant/{invariant_id}` - Update an invariant by ID
-/// - `DELETE /invariant/{invariant_id}` - Delete an invariant by ID
+/// - `GET /invariant/{invariant_id}` - Get a specific invariant by ID or name
+/// - `PUT /invariant/{invariant_id}` - Update an invariant by ID or name
+/// - `DELETE /invariant/{invariant_id}` - Delete an invariant by ID or name
 ///
 /// # Returns
 /// An Axum `Router` configured with the invariant endpoints and state.
@@ -814,4 +1065,135 @@ mod tests {
         let parsed = InvariantID::from_str(&base64_part).unwrap();
         assert_eq!(parsed, invariant);
     }
+
+    async fn insert_score(pool: &sqlx::PgPool, entity: &Entity, score: i64) {
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, entity).await.unwrap();
+        crate::sql::component::create(
+            &mut tx,
+            entity,
+            &crate::Component::new("score").unwrap(),
+            &serde_json::json!(score),
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn evaluate_flags_negative_scores() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::component_definition::create(
+            &mut tx,
+            &crate::ComponentDefinition::new(
+                crate::Component::new("score").unwrap(),
+                serde_json::json!({"type": "integer"}),
+            ),
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        let negative = Entity::new([1u8; 32]);
+        let positive = Entity::new([2u8; 32]);
+        insert_score(&pool, &negative, -5).await;
+        insert_score(&pool, &positive, 3).await;
+
+        let unrelated = Entity::new([3u8; 32]);
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &unrelated)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let invariant_id = InvariantID::new([7u8; 32]);
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::invariants::create(&mut tx, &invariant_id, "score >= 0")
+            .await
+            .unwrap();
+        let violations = evaluate_invariants(&mut tx).await.unwrap();
+        tx.commit().await.unwrap();
+
+        println!(
+            "evaluate_flags_negative_scores violations: {:?}",
+            violations
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].invariant_id, invariant_id);
+        assert_eq!(violations[0].entity, negative);
+    }
+
+    #[tokio::test]
+    async fn evaluate_rejects_invalid_assertion() {
+        let pool = crate::sql::tests::setup_test_db().await;
+        let mut tx = pool.begin().await.unwrap();
+        let result = evaluate_invariant(&mut tx, &InvariantID::new([8u8; 32]), "score >=").await;
+        assert!(matches!(
+            result,
+            Err(InvariantEvaluationError::InvalidAssertion { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn create_with_name_and_get_by_name() {
+        let pool = crate::sql::tests::setup_test_db().await;
+
+        let (status, Json(created)) = create_invariant(
+            State(pool.clone()),
+            Json(CreateInvariantRequest {
+                invariant_id: None,
+                name: Some("non_negative_score".to_string()),
+                asserts: "score >= 0".to_string(),
+            }),
+        )
+        .await
+        .unwrap();
+        println!("create_with_name_and_get_by_name response: {:?}", created);
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created.name.as_deref(), Some("non_negative_score"));
+
+        let Json(fetched) =
+            get_invariant(State(pool.clone()), Path("non_negative_score".to_string()))
+                .await
+                .unwrap();
+        assert_eq!(fetched.invariant_id, created.invariant_id);
+        assert_eq!(fetched.asserts, "score >= 0");
+
+        let Json(by_id) = get_invariant(
+            State(pool.clone()),
+            Path(created.invariant_id.base64_part()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(by_id.name.as_deref(), Some("non_negative_score"));
+
+        let duplicate = create_invariant(
+            State(pool.clone()),
+            Json(CreateInvariantRequest {
+                invariant_id: None,
+                name: Some("non_negative_score".to_string()),
+                asserts: "score > -1".to_string(),
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(duplicate.0, StatusCode::CONFLICT);
+
+        let missing = get_invariant(State(pool), Path("unknown_name".to_string()))
+            .await
+            .unwrap_err();
+        assert_eq!(missing.0, StatusCode::NOT_FOUND);
+    }
+
+    #[test]
+    fn invariant_name_validation() {
+        assert!(is_valid_invariant_name("non_negative_score"));
+        assert!(is_valid_invariant_name("score-check-2"));
+        assert!(!is_valid_invariant_name(""));
+        assert!(!is_valid_invariant_name("has space"));
+        assert!(!is_valid_invariant_name(
+            "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"
+        ));
+    }
 }
@@ -0,0 +1,349 @@
+//! # JSON Patch
+//!
+//! This module implements JSON Patch (RFC 6902), an operation-based alternative to the
+//! merge-patch semantics used by the component `PATCH` endpoints.
+//!
+//! A patch is a list of operations applied in order. Application is atomic: the patch
+//! is applied to a copy of the document, and the original is untouched if any
+//! operation fails.
+//!
+//! ## Usage Examples
+//!
+//! ```rust
+//! use stigmergy::{PatchOperation, apply_json_patch};
+//! use serde_json::json;
+//!
+//! let patch: Vec<PatchOperation> =
+//!     serde_json::from_value(json!([{"op": "replace", "path": "/x", "value": 5}])).unwrap();
+//!
+//! let patched = apply_json_patch(&json!({"x": 1}), &patch).unwrap();
+//! assert_eq!(patched, json!({"x": 5}));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A single JSON Patch operation.
+///
+/// Paths are JSON Pointers (RFC 6901).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Adds a value to an object or inserts it into an array
+    Add {
+        /// Location to add the value at
+        path: String,
+        /// The value to add
+        value: Value,
+    },
+    /// Removes the value at the target location
+    Remove {
+        /// Location of the value to remove
+        path: String,
+    },
+    /// Replaces the value at the target location
+    Replace {
+        /// Location of the value to replace
+        path: String,
+        /// The replacement value
+        value: Value,
+    },
+    /// Removes the value at `from` and adds it at `path`
+    Move {
+        /// Location of the value to move
+        from: String,
+        /// Location to move the value to
+        path: String,
+    },
+    /// Copies the value at `from` to `path`
+    Copy {
+        /// Location of the value to copy
+        from: String,
+        /// Location to copy the value to
+        path: String,
+    },
+    /// Checks that the value at the target location equals `value`
+    Test {
+        /// Location of the value to test
+        path: String,
+        /// The expected value
+        value: Value,
+    },
+}
+
+/// An error applying a JSON Patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPatchError {
+    /// Index of the operation that failed
+    pub index: usize,
+    /// Description of the failure
+    pub message: String,
+}
+
+impl std::fmt::Display for JsonPatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "patch operation {} failed: {}", self.index, self.message)
+    }
+}
+
+impl std::error::Error for JsonPatchError {}
+
+/// Applies a JSON Patch to a document, returning the patched copy.
+///
+/// # Arguments
+/// * `document` - The document to patch
+/// * `patch` - The operations to apply, in order
+///
+/// # Returns
+/// * `Ok(Value)` - The patched document
+/// * `Err(JsonPatchError)` - The first operation that failed; `document` is unchanged
+pub fn apply_json_patch(
+    document: &Value,
+    patch: &[PatchOperation],
+) -> Result<Value, JsonPatchError> {
+    let mut patched = document.clone();
+    for (index, operation) in patch.iter().enumerate() {
+        apply_operation(&mut patched, operation)
+            .map_err(|message| JsonPatchError { index, message })?;
+    }
+    Ok(patched)
+}
+
+fn apply_operation(document: &mut Value, operation: &PatchOperation) -> Result<(), String> {
+    match operation {
+        PatchOperation::Add { path, value } => add(document, path, value.clone()),
+        PatchOperation::Remove { path } => remove(document, path).map(|_| ()),
+        PatchOperation::Replace { path, value } => {
+            let target = lookup_mut(document, path)?;
+            *target = value.clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.starts_with(&format!("{}/", from)) {
+                return Err(format!(
+                    "cannot move '{}' into its own child '{}'",
+                    from, path
+                ));
+            }
+            let value = remove(document, from)?;
+            add(document, path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = lookup_mut(document, from)?.clone();
+            add(document, path, value)
+        }
+        PatchOperation::Test { path, value } => {
+            let actual = lookup_mut(document, path)?;
+            if actual == value {
+                Ok(())
+            } else {
+                Err(format!(
+                    "test failed at '{}': expected {}, found {}",
+                    path, value, actual
+                ))
+            }
+        }
+    }
+}
+
+fn lookup_mut<'a>(document: &'a mut Value, path: &str) -> Result<&'a mut Value, String> {
+    check_pointer(path)?;
+    document
+        .pointer_mut(path)
+        .ok_or_else(|| format!("path '{}' does not exist", path))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> Result<(), String> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+    let (parent, token) = split_pointer(path)?;
+    match lookup_mut(document, parent)? {
+        Value::Object(object) => {
+            object.insert(token, value);
+            Ok(())
+        }
+        Value::Array(array) => {
+            let index = if token == "-" {
+                array.len()
+            } else {
+                parse_index(&token)?
+            };
+            if index > array.len() {
+                return Err(format!("index {} is out of bounds at '{}'", index, path));
+            }
+            array.insert(index, value);
+            Ok(())
+        }
+        _ => Err(format!("parent of '{}' is not an object or array", path)),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> Result<Value, String> {
+    if path.is_empty() {
+        return Err("cannot remove the whole document".to_string());
+    }
+    let (parent, token) = split_pointer(path)?;
+    match lookup_mut(document, parent)? {
+        Value::Object(object) => object
+            .remove(&token)
+            .ok_or_else(|| format!("path '{}' does not exist", path)),
+        Value::Array(array) => {
+            let index = parse_index(&token)?;
+            if index >= array.len() {
+                return Err(format!("index {} is out of bounds at '{}'", index, path));
+            }
+            Ok(array.remove(index))
+        }
+        _ => Err(format!("path '{}' does not exist", path)),
+    }
+}
+
+fn check_pointer(path: &str) -> Result<(), String> {
+    if path.is_empty() || path.starts_with('/') {
+        Ok(())
+    } else {
+        Err(format!("invalid JSON pointer '{}'", path))
+    }
+}
+
+/// Splits a non-empty pointer into its parent pointer and unescaped final token.
+fn split_pointer(path: &str) -> Result<(&str, String), String> {
+    check_pointer(path)?;
+    let (parent, token) = path
+        .rsplit_once('/')
+        .ok_or_else(|| format!("invalid JSON pointer '{}'", path))?;
+    Ok((parent, token.replace("~1", "/").replace("~0", "~")))
+}
+
+fn parse_index(token: &str) -> Result<usize, String> {
+    if !token.bytes().all(|b| b.is_ascii_digit()) || (token.len() > 1 && token.starts_with('0')) {
+        return Err(format!("invalid array index '{}'", token));
+    }
+    token
+        .parse()
+        .map_err(|_| format!("invalid array index '{}'", token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn patch(operations: Value) -> Vec<PatchOperation> {
+        serde_json::from_value(operations).unwrap()
+    }
+
+    #[test]
+    fn replace_field() {
+        let document = json!({"x": 1, "y": 2});
+        let patched = apply_json_patch(
+            &document,
+            &patch(json!([{"op": "replace", "path": "/x", "value": 5}])),
+        )
+        .unwrap();
+        assert_eq!(patched, json!({"x": 5, "y": 2}));
+    }
+
+    #[test]
+    fn add_and_remove() {
+        let document = json!({"list": [1, 3]});
+        let patched = apply_json_patch(
+            &document,
+            &patch(json!([
+                {"op": "add", "path": "/list/1", "value": 2},
+                {"op": "add", "path": "/list/-", "value": 4},
+                {"op": "add", "path": "/name", "value": "n"},
+                {"op": "remove", "path": "/list/0"}
+            ])),
+        )
+        .unwrap();
+        assert_eq!(patched, json!({"list": [2, 3, 4], "name": "n"}));
+    }
+
+    #[test]
+    fn move_and_copy() {
+        let document = json!({"a": {"b": 1}, "c": []});
+        let patched = apply_json_patch(
+            &document,
+            &patch(json!([
+                {"op": "copy", "from": "/a/b", "path": "/c/0"},
+                {"op": "move", "from": "/a/b", "path": "/d"}
+            ])),
+        )
+        .unwrap();
+        assert_eq!(patched, json!({"a": {}, "c": [1], "d": 1}));
+    }
+
+    #[test]
+    fn escaped_pointer_tokens() {
+        let document = json!({"a/b": 1, "m~n": 2});
+        let patched = apply_json_patch(
+            &document,
+            &patch(json!([
+                {"op": "replace", "path": "/a~1b", "value": 3},
+                {"op": "remove", "path": "/m~0n"}
+            ])),
+        )
+        .unwrap();
+        assert_eq!(patched, json!({"a/b": 3}));
+    }
+
+    #[test]
+    fn invalid_path_leaves_document_unchanged() {
+        let document = json!({"x": 1});
+        let error = apply_json_patch(
+            &document,
+            &patch(json!([
+                {"op": "replace", "path": "/x", "value": 2},
+                {"op": "replace", "path": "/missing", "value": 3}
+            ])),
+        )
+        .unwrap_err();
+        assert_eq!(error.index, 1);
+        assert_eq!(document, json!({"x": 1}));
+    }
+
+    #[test]
+    fn test_operation_mismatch() {
+        let document = json!({"x": 1});
+        assert!(
+            apply_json_patch(
+                &document,
+                &patch(json!([{"op": "test", "path": "/x", "value": 1}]))
+            )
+            .is_ok()
+        );
+        let error = apply_json_patch(
+            &document,
+            &patch(json!([{"op": "test", "path": "/x", "value": 2}])),
+        )
+        .unwrap_err();
+        assert_eq!(error.index, 0);
+        assert!(error.message.contains("test failed"));
+    }
+
+    #[test]
+    fn invalid_array_indices() {
+        let document = json!({"list": [1]});
+        for path in ["/list/01", "/list/5", "/list/x", "/list/+0"] {
+            assert!(
+                apply_json_patch(
+                    &document,
+                    &patch(json!([{"op": "add", "path": path, "value": 0}]))
+                )
+                .is_err(),
+                "{}",
+                path
+            );
+        }
+        assert!(
+            apply_json_patch(
+                &document,
+                &patch(json!([{"op": "move", "from": "/list", "path": "/list/0"}]))
+            )
+            .is_err()
+        );
+    }
+}
@@ -37,6 +37,7 @@ use serde::ser::{
     SerializeTupleStruct, SerializeTupleVariant, Serializer,
 };
 use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 pub(crate) const TYPE_KEY: &str = "type";
 pub(crate) const TYPE_NULL: &str = "null";
@@ -48,9 +49,26 @@ pub(crate) const TYPE_ARRAY: &str = "array";
 pub(crate) const TYPE_OBJECT: &str = "object";
 pub(crate) const PROPERTIES_KEY: &str = "properties";
 pub(crate) const REQUIRED_KEY: &str = "required";
+pub(crate) const ADDITIONAL_PROPERTIES_KEY: &str = "additionalProperties";
 pub(crate) const ITEMS_KEY: &str = "items";
+pub(crate) const PREFIX_ITEMS_KEY: &str = "prefixItems";
 pub(crate) const ENUM_KEY: &str = "enum";
+pub(crate) const CONST_KEY: &str = "const";
 pub(crate) const ONE_OF_KEY: &str = "oneOf";
+pub(crate) const ANY_OF_KEY: &str = "anyOf";
+pub(crate) const ALL_OF_KEY: &str = "allOf";
+pub(crate) const MIN_ITEMS_KEY: &str = "minItems";
+pub(crate) const MAX_ITEMS_KEY: &str = "maxItems";
+pub(crate) const UNIQUE_ITEMS_KEY: &str = "uniqueItems";
+pub(crate) const MIN_LENGTH_KEY: &str = "minLength";
+pub(crate) const MAX_LENGTH_KEY: &str = "maxLength";
+pub(crate) const PATTERN_KEY: &str = "pattern";
+pub(crate) const MINIMUM_KEY: &str = "minimum";
+pub(crate) const MAXIMUM_KEY: &str = "maximum";
+pub(crate) const EXCLUSIVE_MINIMUM_KEY: &str = "exclusiveMinimum";
+pub(crate) const EXCLUSIVE_MAXIMUM_KEY: &str = "exclusiveMaximum";
+pub(crate) const MULTIPLE_OF_KEY: &str = "multipleOf";
+pub(crate) const REF_KEY: &str = "$ref";
 
 /// Determines the JSON schema type name for a given JSON value
 pub(crate) fn get_value_type(value: &Value) -> String {
@@ -91,7 +109,96 @@ pub struct JsonSchemaBuilder {
     schema: Value,
 }
 
+impl Default for JsonSchemaBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl JsonSchemaBuilder {
+    /// Creates a builder for an empty schema, to be filled in with the fluent methods.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use stigmergy::JsonSchemaBuilder;
+    /// use serde_json::json;
+    ///
+    /// let a = json!({"type": "string"});
+    /// let b = json!({"type": "integer"});
+    /// let schema = JsonSchemaBuilder::new().one_of(vec![a.clone(), b.clone()]).build();
+    /// assert_eq!(schema, json!({"oneOf": [a, b]}));
+    /// ```
+    pub fn new() -> Self {
+        JsonSchemaBuilder {
+            schema: Value::Object(Map::new()),
+        }
+    }
+
+    /// Requires values to match one of `schemas`.
+    pub fn one_of(self, schemas: Vec<Value>) -> Self {
+        self.with_keyword(ONE_OF_KEY, Value::Array(schemas))
+    }
+
+    /// Requires values to match at least one of `schemas`.
+    pub fn any_of(self, schemas: Vec<Value>) -> Self {
+        self.with_keyword(ANY_OF_KEY, Value::Array(schemas))
+    }
+
+    /// Requires values to match every one of `schemas`.
+    pub fn all_of(self, schemas: Vec<Value>) -> Self {
+        self.with_keyword(ALL_OF_KEY, Value::Array(schemas))
+    }
+
+    /// Allows `null` in addition to whatever the schema currently accepts.
+    ///
+    /// A `type` keyword gains `"null"` as an allowed type; schemas without one are
+    /// wrapped as `{"oneOf": [{"type": "null"}, schema]}`, the same shape used for
+    /// `Option<T>`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use stigmergy::JsonSchemaBuilder;
+    /// use serde_json::json;
+    ///
+    /// let schema = JsonSchemaBuilder::from_value(&json!("text")).unwrap().nullable().build();
+    /// assert_eq!(schema, json!({"type": ["string", "null"]}));
+    /// ```
+    pub fn nullable(mut self) -> Self {
+        let null = Value::String(TYPE_NULL.to_string());
+        match self.schema.get_mut(TYPE_KEY) {
+            Some(Value::String(type_name)) if type_name.as_str() == TYPE_NULL => {}
+            Some(type_value @ Value::String(_)) => {
+                let type_name = std::mem::take(type_value);
+                *type_value = Value::Array(vec![type_name, null]);
+            }
+            Some(Value::Array(type_names)) => {
+                if !type_names.contains(&null) {
+                    type_names.push(null);
+                }
+            }
+            _ => {
+                let schema = std::mem::take(&mut self.schema);
+                self.schema = SchemaGenerator::create_one_of_schema(vec![
+                    SchemaGenerator::create_typed_schema(TYPE_NULL),
+                    schema,
+                ]);
+            }
+        }
+        self
+    }
+
+    /// Consumes the builder and returns the schema.
+    pub fn build(self) -> Value {
+        self.schema
+    }
+
+    fn with_keyword(mut self, keyword: &str, value: Value) -> Self {
+        if let Value::Object(schema) = &mut self.schema {
+            schema.insert(keyword.to_string(), value);
+        }
+        self
+    }
+
     /// Generates a JSON schema from a JSON value.
     ///
     /// This method analyzes the structure of a JSON value and generates
@@ -392,6 +499,84 @@ impl<T: JsonSchema> JsonSchema for Option<T> {
     }
 }
 
+// Maps serialize as JSON objects keyed by string
+impl<V: JsonSchema, S> JsonSchema for HashMap<String, V, S> {
+    fn json_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "additionalProperties": V::json_schema()
+        })
+    }
+}
+
+impl<V: JsonSchema> JsonSchema for BTreeMap<String, V> {
+    fn json_schema() -> Value {
+        serde_json::json!({
+            "type": "object",
+            "additionalProperties": V::json_schema()
+        })
+    }
+}
+
+impl<T: JsonSchema, S> JsonSchema for HashSet<T, S> {
+    fn json_schema() -> Value {
+        serde_json::json!({
+            "type": "array",
+            "items": T::json_schema(),
+            "uniqueItems": true
+        })
+    }
+}
+
+impl<T: JsonSchema> JsonSchema for BTreeSet<T> {
+    fn json_schema() -> Value {
+        serde_json::json!({
+            "type": "array",
+            "items": T::json_schema(),
+            "uniqueItems": true
+        })
+    }
+}
+
+impl<Tz: chrono::TimeZone> JsonSchema for chrono::DateTime<Tz> {
+    fn json_schema() -> Value {
+        serde_json::json!({
+            "type": "string",
+            "format": "date-time"
+        })
+    }
+}
+
+// Implementations for stigmergy identifiers, constrained to their string formats
+impl JsonSchema for crate::Entity {
+    fn json_schema() -> Value {
+        // Entities serialize as bare base64 and also deserialize with the `entity:` prefix
+        serde_json::json!({
+            "type": "string",
+            "pattern": "^(entity:)?[A-Za-z0-9_-]{43}$"
+        })
+    }
+}
+
+impl JsonSchema for crate::Component {
+    fn json_schema() -> Value {
+        serde_json::json!({
+            "type": "string",
+            "pattern": "^[A-Za-z_][A-Za-z0-9_]*(::[A-Za-z_][A-Za-z0-9_]*)*$"
+        })
+    }
+}
+
+impl JsonSchema for crate::SystemName {
+    fn json_schema() -> Value {
+        // SystemName::new accepts any Unicode letter, so the classes are Unicode too
+        serde_json::json!({
+            "type": "string",
+            "pattern": r"^[\p{Alphabetic}_-][\p{Alphabetic}\p{N}_-]*(::[\p{Alphabetic}_-][\p{Alphabetic}\p{N}_-]*)*$"
+        })
+    }
+}
+
 impl serde::ser::Error for JsonSchemaError {
     fn custom<T: std::fmt::Display>(msg: T) -> Self {
         JsonSchemaError::SerdeError(msg.to_string())
@@ -148,8 +148,12 @@ mod config;
 mod edge;
 mod entity;
 mod errors;
+mod health;
 mod invariant;
+mod json_patch;
 mod json_schema;
+mod openapi;
+mod request_log;
 mod system;
 mod system_parser;
 mod test_utils;
@@ -187,33 +191,57 @@ pub mod commands;
 /// stigmergy HTTP APIs, handling requests, responses, and error conditions.
 pub mod http_utils;
 
-pub use apply::{ApplyRequest, ApplyResponse, Operation, OperationResult, create_apply_router};
+pub use apply::{
+    ApplyRequest, ApplyResponse, DEFAULT_MAX_APPLY_OPERATIONS, EdgeRef, EntityRef,
+    IDEMPOTENCY_KEY_HEADER, MAX_IDEMPOTENCY_KEY_LEN, Operation, OperationResult,
+    create_apply_router, create_apply_router_with_limit, create_apply_router_with_limit_and_policy,
+    create_apply_router_with_policy, validate_operations,
+};
 pub use bid::{
-    Bid, BidParseError, BidParser, BinaryOperator, EntityResolver, EvaluationError, Expression,
-    Position, UnaryOperator,
+    Bid, BidParseError, BidParser, BinaryOperator, DataContext, EntityResolver, EvaluationContext,
+    EvaluationError, Expression, Position, UnaryOperator, bid_ast,
 };
 pub use component::{
-    Component, ComponentListItem, CreateComponentRequest, CreateComponentResponse,
-    create_component_instance_router,
+    Component, ComponentChangeEvent, ComponentChangeKind, ComponentEventsQuery, ComponentListItem,
+    ComponentNameError, ComponentNamePolicy, ComponentPage, ComponentQueryRequest,
+    CreateComponentRequest, CreateComponentResponse, EntityComponentsQuery, GetComponentQuery,
+    ListComponentsQuery, ReplaceComponentsRequest, ReplaceComponentsResponse, apply_merge_patch,
+    create_component_instance_router, create_component_instance_router_with_policy,
+};
+pub use component_definition::{
+    BatchValidationResult, ComponentDefinition, SchemaDiff, SchemaError, SchemaTypeChange,
+    ValidationFailure, create_component_definition_router,
+    create_component_definition_router_with_policy,
 };
-pub use component_definition::{ComponentDefinition, create_component_definition_router};
 pub use config::{
-    Config, GetConfigResponse, IoSystem, PostConfigRequest, PostConfigResponse,
-    create_config_router, load_latest_config, save_config,
+    CONFIG_VERSION, CONFIG_WATCH_DEBOUNCE, Config, GetConfigResponse, IoSystem, PostConfigRequest,
+    PostConfigResponse, create_config_router, load_latest_config, save_config,
+};
+pub use edge::{
+    CreateEdgeRequest, CreateEdgeResponse, Edge, MAX_TRAVERSAL_DEPTH, ReachableEntity,
+    TraverseEdgesRequest, create_edge_router,
 };
-pub use edge::{CreateEdgeRequest, CreateEdgeResponse, Edge, create_edge_router};
 pub use entity::{
-    CreateEntityRequest, CreateEntityResponse, Entity, EntityParseError, create_entity_router,
+    CreateEntityRequest, CreateEntityResponse, CreateEntityWithComponentsRequest,
+    CreateEntityWithComponentsResponse, Entity, EntityParseError, NamespacedEntity,
+    create_entity_router, create_entity_router_with_policy,
 };
 pub use errors::DataStoreError;
+pub use health::{HealthStatus, create_health_router};
 pub use invariant::{
-    CreateInvariantRequest, CreateInvariantResponse, GetInvariantResponse, InvariantID,
-    InvariantIDParseError, UpdateInvariantRequest, create_invariant_router,
+    CreateInvariantRequest, CreateInvariantResponse, GetInvariantResponse,
+    InvariantEvaluationError, InvariantID, InvariantIDParseError, InvariantViolation,
+    UpdateInvariantRequest, create_invariant_router, evaluate_invariant, evaluate_invariants,
 };
+pub use json_patch::{JsonPatchError, PatchOperation, apply_json_patch};
 pub use json_schema::{JsonSchema, JsonSchemaBuilder};
+pub use openapi::{OPENAPI_VERSION, to_openapi};
+pub use request_log::{RESPONSE_TIME_HEADER, log_requests};
 pub use system::{
-    CreateSystemFromMarkdownRequest, CreateSystemResponse, System, SystemListItem, SystemName,
-    SystemNameParseError, create_system_router,
+    CreateSystemFromMarkdownRequest, CreateSystemResponse, ListSystemsQuery, System,
+    SystemListItem, SystemName, SystemNameParseError, create_system_router,
+};
+pub use system_parser::{
+    AccessMode, ComponentAccess, ParseError, SystemConfig, SystemParser, ValidationLimits,
 };
-pub use system_parser::{AccessMode, ComponentAccess, ParseError, SystemConfig, SystemParser};
-pub use validate::{ValidationError, validate_value};
+pub use validate::{CompiledSchema, ValidationError, validate_value};
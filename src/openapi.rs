@@ -0,0 +1,257 @@
+//! # OpenAPI Generation
+//!
+//! This module assembles component definitions into an OpenAPI 3.1 document so that a
+//! deployment can publish the shape of its component data.
+//!
+//! Each definition's schema becomes an entry under `components/schemas`, keyed by its
+//! component name with `::` replaced by `.` (OpenAPI keys may not contain colons), and
+//! receives path stubs for reading and writing that component on an entity. The
+//! component definition routes themselves are described once, independent of the
+//! definitions passed in.
+
+use serde_json::{Map, Value, json};
+
+use crate::ComponentDefinition;
+
+/// The OpenAPI version emitted by [`to_openapi`].
+pub const OPENAPI_VERSION: &str = "3.1.0";
+
+/// The prefix under which `stigmergyd` mounts its API routes.
+const API_PREFIX: &str = "/api/v1";
+
+/// Builds an OpenAPI 3.1 document describing the given component definitions.
+///
+/// # Arguments
+/// * `definitions` - The component definitions to publish
+///
+/// # Returns
+/// A JSON OpenAPI document whose `components.schemas` holds one schema per definition and
+/// whose `paths` holds CRUD stubs for component definitions and per-entity components.
+///
+/// # Examples
+/// ```rust
+/// use stigmergy::{Component, ComponentDefinition, to_openapi};
+/// use serde_json::json;
+///
+/// let definitions = vec![
+///     ComponentDefinition::new(Component::new("Health").unwrap(), json!({"type": "integer"})),
+///     ComponentDefinition::new(Component::new("ghai::Issue").unwrap(), json!({"type": "string"})),
+/// ];
+/// let document = to_openapi(&definitions);
+/// assert_eq!(document["components"]["schemas"]["Health"], json!({"type": "integer"}));
+/// assert_eq!(document["components"]["schemas"]["ghai.Issue"], json!({"type": "string"}));
+/// ```
+pub fn to_openapi(definitions: &[ComponentDefinition]) -> Value {
+    let mut schemas = Map::new();
+    let mut paths = definition_paths();
+
+    for definition in definitions {
+        let key = schema_key(definition.component.as_str());
+        let mut schema = definition.schema.clone();
+        rebase_refs(&mut schema, &format!("#/components/schemas/{}", key));
+        schemas.insert(key.clone(), schema);
+        paths.insert(
+            format!(
+                "{}/entity/{{entity_id}}/component/{}",
+                API_PREFIX,
+                definition.component.as_str()
+            ),
+            component_path(definition.component.as_str(), &key),
+        );
+    }
+
+    json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": "stigmergy",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "paths": paths,
+        "components": {
+            "schemas": schemas,
+        },
+    })
+}
+
+/// Returns the `components/schemas` key for a component name.
+fn schema_key(component: &str) -> String {
+    component.replace("::", ".")
+}
+
+/// Rewrites internal `$ref`s so they resolve once the schema is nested at `base`.
+fn rebase_refs(schema: &mut Value, base: &str) {
+    match schema {
+        Value::Object(object) => {
+            for (key, value) in object.iter_mut() {
+                match value {
+                    Value::String(reference) if key == "$ref" => {
+                        if let Some(pointer) = reference.strip_prefix('#') {
+                            *reference = format!("{}{}", base, pointer);
+                        }
+                    }
+                    _ => rebase_refs(value, base),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                rebase_refs(item, base);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Path stubs for reading and writing one component on an entity.
+fn component_path(component: &str, key: &str) -> Value {
+    let schema_ref = json!({"$ref": format!("#/components/schemas/{}", key)});
+    let body = json!({
+        "required": true,
+        "content": {"application/json": {"schema": schema_ref}},
+    });
+    json!({
+        "parameters": [entity_id_parameter()],
+        "get": {
+            "summary": format!("Get the {} component of an entity", component),
+            "responses": {
+                "200": {
+                    "description": "The component data",
+                    "content": {"application/json": {"schema": schema_ref}},
+                },
+                "404": {"description": "The entity has no such component"},
+            },
+        },
+        "put": {
+            "summary": format!("Replace the {} component of an entity", component),
+            "requestBody": body,
+            "responses": {
+                "200": {"description": "The component was updated"},
+                "400": {"description": "The data does not match the component schema"},
+            },
+        },
+        "delete": {
+            "summary": format!("Remove the {} component from an entity", component),
+            "responses": {
+                "204": {"description": "The component was removed"},
+                "404": {"description": "The entity has no such component"},
+            },
+        },
+    })
+}
+
+/// Path stubs for the component definition routes.
+fn definition_paths() -> Map<String, Value> {
+    let id_parameter = json!({
+        "name": "id",
+        "in": "path",
+        "required": true,
+        "schema": {"type": "string"},
+    });
+    let mut paths = Map::new();
+    paths.insert(
+        format!("{}/componentdefinition", API_PREFIX),
+        json!({
+            "get": {
+                "summary": "List component definitions",
+                "responses": {"200": {"description": "All component definitions"}},
+            },
+            "post": {
+                "summary": "Create a component definition",
+                "responses": {"200": {"description": "The created definition"}},
+            },
+        }),
+    );
+    paths.insert(
+        format!("{}/componentdefinition/{{id}}", API_PREFIX),
+        json!({
+            "parameters": [id_parameter],
+            "get": {
+                "summary": "Get a component definition",
+                "responses": {
+                    "200": {"description": "The component definition"},
+                    "404": {"description": "No such component definition"},
+                },
+            },
+            "put": {
+                "summary": "Replace a component definition",
+                "responses": {"200": {"description": "The updated definition"}},
+            },
+            "delete": {
+                "summary": "Delete a component definition",
+                "responses": {"204": {"description": "The definition was deleted"}},
+            },
+        }),
+    );
+    paths
+}
+
+/// The `{entity_id}` path parameter shared by per-entity routes.
+fn entity_id_parameter() -> Value {
+    json!({
+        "name": "entity_id",
+        "in": "path",
+        "required": true,
+        "schema": {"type": "string"},
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Component;
+
+    #[test]
+    fn to_openapi_publishes_schemas_by_name() {
+        let definitions = vec![
+            ComponentDefinition::new(
+                Component::new("Position").unwrap(),
+                json!({
+                    "type": "object",
+                    "properties": {"x": {"type": "number"}, "y": {"type": "number"}},
+                    "required": ["x", "y"]
+                }),
+            ),
+            ComponentDefinition::new(
+                Component::new("ghai::Tree").unwrap(),
+                json!({
+                    "type": "object",
+                    "properties": {"child": {"$ref": "#/definitions/Node"}},
+                    "definitions": {"Node": {"type": "string"}}
+                }),
+            ),
+        ];
+
+        let document = to_openapi(&definitions);
+        println!(
+            "to_openapi_publishes_schemas_by_name response: {}",
+            serde_json::to_string_pretty(&document).unwrap()
+        );
+
+        assert_eq!(document["openapi"], OPENAPI_VERSION);
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+        assert_eq!(schemas.len(), 2);
+        assert_eq!(schemas["Position"], definitions[0].schema);
+        assert_eq!(
+            schemas["ghai.Tree"]["properties"]["child"]["$ref"],
+            "#/components/schemas/ghai.Tree/definitions/Node"
+        );
+
+        let paths = document["paths"].as_object().unwrap();
+        assert!(paths.contains_key("/api/v1/componentdefinition"));
+        assert!(paths.contains_key("/api/v1/componentdefinition/{id}"));
+        let position = &paths["/api/v1/entity/{entity_id}/component/Position"];
+        assert_eq!(
+            position["get"]["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Position"
+        );
+        assert!(paths.contains_key("/api/v1/entity/{entity_id}/component/ghai::Tree"));
+
+        // Deletes answer 204 No Content, with no body to describe
+        let removed = &position["delete"]["responses"];
+        assert!(removed.get("200").is_none());
+        assert!(removed["204"].get("content").is_none());
+        let deleted = &paths["/api/v1/componentdefinition/{id}"]["delete"]["responses"];
+        assert!(deleted.get("200").is_none());
+        assert!(deleted["204"].get("content").is_none());
+    }
+}
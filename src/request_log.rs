@@ -0,0 +1,74 @@
+//! # Request Logging
+//!
+//! This module provides an Axum middleware that times every request and writes one
+//! structured log line per response:
+//!
+//! ```text
+//! method=POST path=/api/v1/apply status=200 duration_ms=12
+//! ```
+//!
+//! The measured duration is also returned to the client in the
+//! [`RESPONSE_TIME_HEADER`] header so that callers can record it alongside the
+//! operation they performed.
+
+use axum::extract::Request;
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::time::Instant;
+
+/// Header carrying the server-side handling time of a request, in milliseconds.
+pub const RESPONSE_TIME_HEADER: &str = "x-response-time-ms";
+
+/// Times a request, logs it, and reports the duration in [`RESPONSE_TIME_HEADER`].
+///
+/// Install with `router.layer(axum::middleware::from_fn(log_requests))`.
+pub async fn log_requests(request: Request, next: Next) -> Response {
+    let method = request.method().clone();
+    let path = request.uri().path().to_string();
+    let start = Instant::now();
+
+    let mut response = next.run(request).await;
+
+    let duration_ms = start.elapsed().as_millis();
+    println!(
+        "method={} path={} status={} duration_ms={}",
+        method,
+        path,
+        response.status().as_u16(),
+        duration_ms
+    );
+    response
+        .headers_mut()
+        .insert(RESPONSE_TIME_HEADER, HeaderValue::from(duration_ms as u64));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::Router;
+    use axum::routing::post;
+    use axum_test::TestServer;
+
+    #[tokio::test]
+    async fn log_requests_reports_duration() {
+        let router = Router::new()
+            .route(
+                "/slow",
+                post(|| async {
+                    tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+                    "done"
+                }),
+            )
+            .layer(axum::middleware::from_fn(log_requests));
+        let server = TestServer::new(router).unwrap();
+
+        let response = server.post("/slow").await;
+        response.assert_status_ok();
+        let duration = response.header(RESPONSE_TIME_HEADER);
+        println!("log_requests_reports_duration response: {:?}", duration);
+        let duration_ms: u64 = duration.to_str().unwrap().parse().unwrap();
+        assert!(duration_ms >= 20);
+    }
+}
@@ -34,6 +34,15 @@ pub struct ActiveEntityRecord {
 /// # Returns
 /// * `Ok(())` - Active entity record created or updated successfully
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::active_entity::upsert",
+        skip_all,
+        err,
+        fields(entity = %entity)
+    )
+)]
 pub async fn upsert(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -73,6 +82,10 @@ pub async fn upsert(
 /// * `Ok(Some(ActiveEntityRecord))` - Active entity record found
 /// * `Ok(None)` - Entity is not active
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::active_entity::get", skip_all, err, fields(entity = %entity))
+)]
 pub async fn get(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -121,6 +134,15 @@ pub async fn get(
 /// # Returns
 /// * `Ok(Vec<Entity>)` - List of active entities for the system
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::active_entity::list_for_system",
+        skip_all,
+        err,
+        fields(system_name = %system_name)
+    )
+)]
 pub async fn list_for_system(
     tx: &mut Transaction<'_, Postgres>,
     system_name: &str,
@@ -165,6 +187,15 @@ pub async fn list_for_system(
 /// * `Ok(true)` - Active entity record existed and was deleted
 /// * `Ok(false)` - Active entity record did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::active_entity::delete",
+        skip_all,
+        err,
+        fields(entity = %entity)
+    )
+)]
 pub async fn delete(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlResult<bool> {
     let entity_bytes = entity.as_bytes();
 
@@ -195,6 +226,10 @@ pub async fn delete(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlR
 /// # Returns
 /// * `Ok(Vec<Entity>)` - List of all active entities
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::active_entity::list_all", skip_all, err)
+)]
 pub async fn list_all(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<Entity>> {
     let result = sqlx::query!(
         r#"
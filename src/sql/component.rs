@@ -13,7 +13,7 @@ use crate::{Component, DataStoreError, Entity};
 pub type SqlResult<T> = Result<T, DataStoreError>;
 
 /// Represents a component instance with its metadata.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ComponentRecord {
     /// The entity this component is attached to.
     pub entity: Entity,
@@ -25,6 +25,31 @@ pub struct ComponentRecord {
     pub created_at: DateTime<Utc>,
     /// When the component instance was last updated.
     pub updated_at: DateTime<Utc>,
+    /// Incremented on every write to the component instance.
+    pub version: i64,
+}
+
+/// The outcome of upserting a component instance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpsertOutcome {
+    /// The component instance did not exist and was created.
+    Created,
+    /// The component instance existed and its data was replaced.
+    Updated,
+    /// The component instance already held identical data; nothing was written.
+    Unchanged,
+}
+
+impl UpsertOutcome {
+    /// Returns true if the component instance was created.
+    pub fn created(self) -> bool {
+        self == UpsertOutcome::Created
+    }
+
+    /// Returns true if the stored data was written.
+    pub fn changed(self) -> bool {
+        self != UpsertOutcome::Unchanged
+    }
 }
 
 /// Creates a new component instance in the database.
@@ -57,6 +82,15 @@ pub struct ComponentRecord {
 /// # Ok(())
 /// # }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::create",
+        skip_all,
+        err,
+        fields(entity = %entity, component = component.as_str())
+    )
+)]
 pub async fn create(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -107,6 +141,15 @@ pub async fn create(
 /// * `Ok(Some(Value))` - Component instance found
 /// * `Ok(None)` - Component instance not found
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::get",
+        skip_all,
+        err,
+        fields(entity = %entity, component = component.as_str())
+    )
+)]
 pub async fn get(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -137,6 +180,56 @@ pub async fn get(
     }
 }
 
+/// Retrieves a component instance together with its version.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `entity` - The entity to retrieve the component from
+/// * `component` - The component type
+///
+/// # Returns
+/// * `Ok(Some((Value, i64)))` - Component data and its current version
+/// * `Ok(None)` - Component instance not found
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::get_with_version",
+        skip_all,
+        err,
+        fields(entity = %entity, component = component.as_str())
+    )
+)]
+pub async fn get_with_version(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+    component: &Component,
+) -> SqlResult<Option<(Value, i64)>> {
+    let entity_bytes = entity.as_bytes();
+    let component_name = component.as_str();
+
+    let result = sqlx::query!(
+        r#"
+        SELECT data, version
+        FROM component_instances
+        WHERE entity_id = $1 AND component_name = $2
+        "#,
+        entity_bytes.as_slice(),
+        component_name
+    )
+    .fetch_optional(&mut **tx)
+    .await;
+
+    match result {
+        Ok(Some(row)) => Ok(row.data.map(|data| (data, row.version))),
+        Ok(None) => Ok(None),
+        Err(e) => {
+            eprintln!("Database error getting component instance version: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
 /// Updates an existing component instance in the database.
 ///
 /// # Arguments
@@ -149,36 +242,87 @@ pub async fn get(
 /// * `Ok(true)` - Component instance existed and was updated
 /// * `Ok(false)` - Component instance did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::update",
+        skip_all,
+        err,
+        fields(entity = %entity, component = component.as_str())
+    )
+)]
 pub async fn update(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
     component: &Component,
     data: &Value,
 ) -> SqlResult<bool> {
+    Ok(update_versioned(tx, entity, component, data, None)
+        .await?
+        .is_some())
+}
+
+/// Updates an existing component instance if its version matches.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `entity` - The entity the component is attached to
+/// * `component` - The component type
+/// * `data` - The new component data
+/// * `expected_version` - If present, the version the instance must currently have
+///
+/// # Returns
+/// * `Ok(Some(i64))` - Component instance was updated; contains the new version
+/// * `Ok(None)` - Component instance did not exist
+/// * `Err(DataStoreError::VersionMismatch)` - The stored version differs from `expected_version`
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::update_versioned",
+        skip_all,
+        err,
+        fields(entity = %entity, component = component.as_str())
+    )
+)]
+pub async fn update_versioned(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+    component: &Component,
+    data: &Value,
+    expected_version: Option<i64>,
+) -> SqlResult<Option<i64>> {
     let entity_bytes = entity.as_bytes();
     let component_name = component.as_str();
 
     let result = sqlx::query!(
         r#"
         UPDATE component_instances
-        SET data = $3, updated_at = CURRENT_TIMESTAMP
+        SET data = $3, updated_at = CURRENT_TIMESTAMP, version = version + 1
         WHERE entity_id = $1 AND component_name = $2
+          AND ($4::BIGINT IS NULL OR version = $4)
+        RETURNING version
         "#,
         entity_bytes.as_slice(),
         component_name,
-        data
+        data,
+        expected_version
     )
-    .execute(&mut **tx)
+    .fetch_optional(&mut **tx)
     .await;
 
     match result {
-        Ok(result) => {
-            let was_updated = result.rows_affected() > 0;
-            if was_updated {
-                super::active_entity::upsert(tx, entity, None).await?;
-            }
-            Ok(was_updated)
+        Ok(Some(row)) => {
+            super::active_entity::upsert(tx, entity, None).await?;
+            Ok(Some(row.version))
         }
+        Ok(None) => match expected_version {
+            Some(expected) => match get_with_version(tx, entity, component).await? {
+                Some((_, actual)) => Err(DataStoreError::VersionMismatch { expected, actual }),
+                None => Ok(None),
+            },
+            None => Ok(None),
+        },
         Err(e) => {
             eprintln!("Database error updating component instance: {}", e);
             Err(DataStoreError::Internal(e.to_string()))
@@ -188,6 +332,9 @@ pub async fn update(
 
 /// Upserts a component instance in the database.
 ///
+/// When the stored data is already equal to `data` the row is left untouched,
+/// so neither `updated_at` nor the entity's activity timestamp moves.
+///
 /// # Arguments
 /// * `tx` - PostgreSQL transaction
 /// * `entity` - The entity to attach the component to
@@ -195,16 +342,26 @@ pub async fn update(
 /// * `data` - The component data
 ///
 /// # Returns
-/// * `Ok(true)` - Component instance was created (didn't exist before)
-/// * `Ok(false)` - Component instance was updated (existed before)
+/// * `Ok(UpsertOutcome::Created)` - Component instance was created (didn't exist before)
+/// * `Ok(UpsertOutcome::Updated)` - Component instance was updated (existed before)
+/// * `Ok(UpsertOutcome::Unchanged)` - Component instance already held identical data
 /// * `Err(DataStoreError::NotFound)` - Entity or component definition not found
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::upsert",
+        skip_all,
+        err,
+        fields(entity = %entity, component = component.as_str())
+    )
+)]
 pub async fn upsert(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
     component: &Component,
     data: &Value,
-) -> SqlResult<bool> {
+) -> SqlResult<UpsertOutcome> {
     let entity_bytes = entity.as_bytes();
     let component_name = component.as_str();
 
@@ -212,22 +369,31 @@ pub async fn upsert(
         r#"
         INSERT INTO component_instances (entity_id, component_name, data)
         VALUES ($1, $2, $3)
-        ON CONFLICT (entity_id, component_name) 
-        DO UPDATE SET data = EXCLUDED.data, updated_at = CURRENT_TIMESTAMP
+        ON CONFLICT (entity_id, component_name)
+        DO UPDATE SET
+            data = EXCLUDED.data,
+            updated_at = CURRENT_TIMESTAMP,
+            version = component_instances.version + 1
+        WHERE component_instances.data IS DISTINCT FROM EXCLUDED.data
         RETURNING (xmax = 0) as "was_insert!"
         "#,
         entity_bytes.as_slice(),
         component_name,
         data
     )
-    .fetch_one(&mut **tx)
+    .fetch_optional(&mut **tx)
     .await;
 
     match result {
-        Ok(row) => {
+        Ok(Some(row)) => {
             super::active_entity::upsert(tx, entity, None).await?;
-            Ok(row.was_insert)
+            if row.was_insert {
+                Ok(UpsertOutcome::Created)
+            } else {
+                Ok(UpsertOutcome::Updated)
+            }
         }
+        Ok(None) => Ok(UpsertOutcome::Unchanged),
         Err(sqlx::Error::Database(db_err)) if db_err.is_foreign_key_violation() => {
             Err(DataStoreError::NotFound)
         }
@@ -249,6 +415,15 @@ pub async fn upsert(
 /// * `Ok(true)` - Component instance existed and was deleted
 /// * `Ok(false)` - Component instance did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::delete",
+        skip_all,
+        err,
+        fields(entity = %entity, component = component.as_str())
+    )
+)]
 pub async fn delete(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -277,6 +452,67 @@ pub async fn delete(
     }
 }
 
+/// Normalizes a module prefix for matching with `starts_with`.
+fn module_prefix(prefix: &str) -> SqlResult<String> {
+    Component::module_prefix(prefix).ok_or_else(|| {
+        DataStoreError::InvalidInput(format!("invalid component prefix: {}", prefix))
+    })
+}
+
+/// Deletes the component instances on an entity whose type lies under a module prefix.
+///
+/// The prefix is normalized with [`Component::module_prefix`], so `game`, `game::` and
+/// `game::*` all delete `game::Position` and `game::Health` but not `ui::Panel`.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `entity` - The entity the components are attached to
+/// * `prefix` - The module prefix to match
+///
+/// # Returns
+/// * `Ok(count)` - Number of component instances deleted
+/// * `Err(DataStoreError::InvalidInput)` - The prefix is not a valid module path
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::delete_by_prefix",
+        skip_all,
+        err,
+        fields(entity = %entity, prefix = %prefix)
+    )
+)]
+pub async fn delete_by_prefix(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+    prefix: &str,
+) -> SqlResult<u32> {
+    let entity_bytes = entity.as_bytes();
+    let prefix = module_prefix(prefix)?;
+
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM component_instances
+        WHERE entity_id = $1 AND starts_with(component_name, $2)
+        "#,
+        entity_bytes.as_slice(),
+        prefix
+    )
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(result) => Ok(result.rows_affected() as u32),
+        Err(e) => {
+            eprintln!(
+                "Database error deleting component instances by prefix: {}",
+                e
+            );
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
 /// Lists all component instances for a specific entity.
 ///
 /// # Arguments
@@ -284,17 +520,26 @@ pub async fn delete(
 /// * `entity` - The entity to list components for
 ///
 /// # Returns
-/// * `Ok(Vec<(Component, Value)>)` - List of components and their data
+/// * `Ok(Vec<ComponentRecord>)` - List of component instances ordered by component name
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::list_for_entity",
+        skip_all,
+        err,
+        fields(entity = %entity)
+    )
+)]
 pub async fn list_for_entity(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
-) -> SqlResult<Vec<(Component, Value)>> {
+) -> SqlResult<Vec<ComponentRecord>> {
     let entity_bytes = entity.as_bytes();
 
     let result = sqlx::query!(
         r#"
-        SELECT component_name, data
+        SELECT component_name, data, created_at, updated_at, version
         FROM component_instances
         WHERE entity_id = $1
         ORDER BY component_name ASC
@@ -315,7 +560,14 @@ pub async fn list_for_entity(
                     ))
                 })?;
                 if let Some(data) = row.data {
-                    components.push((component, data));
+                    components.push(ComponentRecord {
+                        entity: *entity,
+                        component,
+                        data,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                        version: row.version,
+                    });
                 }
             }
             Ok(components)
@@ -327,6 +579,214 @@ pub async fn list_for_entity(
     }
 }
 
+/// Lists the component instances on an entity whose type lies under a module prefix.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `entity` - The entity to list components for
+/// * `prefix` - The module prefix to match, normalized with [`Component::module_prefix`]
+///
+/// # Returns
+/// * `Ok(Vec<ComponentRecord>)` - Matching instances ordered by component name
+/// * `Err(DataStoreError::InvalidInput)` - The prefix is not a valid module path
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::list_for_entity_with_prefix",
+        skip_all,
+        err,
+        fields(entity = %entity, prefix = %prefix)
+    )
+)]
+pub async fn list_for_entity_with_prefix(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+    prefix: &str,
+) -> SqlResult<Vec<ComponentRecord>> {
+    let entity_bytes = entity.as_bytes();
+    let prefix = module_prefix(prefix)?;
+
+    let result = sqlx::query!(
+        r#"
+        SELECT component_name, data, created_at, updated_at, version
+        FROM component_instances
+        WHERE entity_id = $1 AND starts_with(component_name, $2)
+        ORDER BY component_name ASC
+        "#,
+        entity_bytes.as_slice(),
+        prefix
+    )
+    .fetch_all(&mut **tx)
+    .await;
+
+    match result {
+        Ok(rows) => {
+            let mut components = Vec::new();
+            for row in rows {
+                let component = Component::new(&row.component_name).ok_or_else(|| {
+                    DataStoreError::Internal(format!(
+                        "invalid component name: {}",
+                        row.component_name
+                    ))
+                })?;
+                if let Some(data) = row.data {
+                    components.push(ComponentRecord {
+                        entity: *entity,
+                        component,
+                        data,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                        version: row.version,
+                    });
+                }
+            }
+            Ok(components)
+        }
+        Err(e) => {
+            eprintln!(
+                "Database error listing component instances by prefix: {}",
+                e
+            );
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Lists the component instances of several entities in a single query.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `entities` - The entities to list components for
+/// * `components` - If present, only these component types are returned
+///
+/// # Returns
+/// * `Ok(Vec<ComponentRecord>)` - Matching instances ordered by entity and component
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::list_for_entities",
+        skip_all,
+        err,
+        fields(entities = entities.len())
+    )
+)]
+pub async fn list_for_entities(
+    tx: &mut Transaction<'_, Postgres>,
+    entities: &[Entity],
+    components: Option<&[Component]>,
+) -> SqlResult<Vec<ComponentRecord>> {
+    let entity_ids: Vec<Vec<u8>> = entities.iter().map(|e| e.as_bytes().to_vec()).collect();
+    let component_names: Option<Vec<String>> =
+        components.map(|cs| cs.iter().map(|c| c.as_str().to_string()).collect());
+
+    let result = sqlx::query!(
+        r#"
+        SELECT entity_id, component_name, data, created_at, updated_at, version
+        FROM component_instances
+        WHERE entity_id = ANY($1)
+          AND ($2::TEXT[] IS NULL OR component_name = ANY($2))
+        ORDER BY entity_id ASC, component_name ASC
+        "#,
+        entity_ids.as_slice(),
+        component_names.as_deref()
+    )
+    .fetch_all(&mut **tx)
+    .await;
+
+    match result {
+        Ok(rows) => {
+            let mut instances = Vec::new();
+            for row in rows {
+                let entity = Entity::try_from(row.entity_id.as_slice()).map_err(|_| {
+                    DataStoreError::Internal("invalid entity_id length".to_string())
+                })?;
+                let component = Component::new(&row.component_name).ok_or_else(|| {
+                    DataStoreError::Internal(format!(
+                        "invalid component name: {}",
+                        row.component_name
+                    ))
+                })?;
+                if let Some(data) = row.data {
+                    instances.push(ComponentRecord {
+                        entity,
+                        component,
+                        data,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                        version: row.version,
+                    });
+                }
+            }
+            Ok(instances)
+        }
+        Err(e) => {
+            eprintln!(
+                "Database error listing component instances for entities: {}",
+                e
+            );
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Lists all instances of a specific component type.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `component` - The component type to list instances of
+///
+/// # Returns
+/// * `Ok(Vec<(Entity, Value)>)` - List of entities and their component data
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::list_for_component",
+        skip_all,
+        err,
+        fields(component = component.as_str())
+    )
+)]
+pub async fn list_for_component(
+    tx: &mut Transaction<'_, Postgres>,
+    component: &Component,
+) -> SqlResult<Vec<(Entity, Value)>> {
+    let component_name = component.as_str();
+
+    let result = sqlx::query!(
+        r#"
+        SELECT entity_id, data
+        FROM component_instances
+        WHERE component_name = $1
+        ORDER BY entity_id ASC
+        "#,
+        component_name
+    )
+    .fetch_all(&mut **tx)
+    .await;
+
+    match result {
+        Ok(rows) => {
+            let mut instances = Vec::new();
+            for row in rows {
+                let entity_bytes: [u8; 32] = row.entity_id.try_into().map_err(|_| {
+                    DataStoreError::Internal("invalid entity_id length".to_string())
+                })?;
+                if let Some(data) = row.data {
+                    instances.push((Entity::new(entity_bytes), data));
+                }
+            }
+            Ok(instances)
+        }
+        Err(e) => {
+            eprintln!("Database error listing component instances by type: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
 /// Lists all component instances in the database.
 ///
 /// # Arguments
@@ -335,6 +795,10 @@ pub async fn list_for_entity(
 /// # Returns
 /// * `Ok(Vec<((Entity, Component), Value)>)` - List of all component instances
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::component::list_all", skip_all, err)
+)]
 pub async fn list_all(
     tx: &mut Transaction<'_, Postgres>,
 ) -> SqlResult<Vec<((Entity, Component), Value)>> {
@@ -377,6 +841,162 @@ pub async fn list_all(
     }
 }
 
+/// Lists one page of component instances in the database.
+///
+/// Instances are ordered by entity and component name so that consecutive
+/// pages do not overlap.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `limit` - Maximum number of instances to return
+/// * `offset` - Number of instances to skip
+///
+/// # Returns
+/// * `Ok(Vec<ComponentRecord>)` - The requested page of component instances
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::list_page",
+        skip_all,
+        err,
+        fields(limit = limit, offset = offset)
+    )
+)]
+pub async fn list_page(
+    tx: &mut Transaction<'_, Postgres>,
+    limit: i64,
+    offset: i64,
+) -> SqlResult<Vec<ComponentRecord>> {
+    let result = sqlx::query!(
+        r#"
+        SELECT entity_id, component_name, data, created_at, updated_at, version
+        FROM component_instances
+        WHERE data IS NOT NULL
+        ORDER BY entity_id ASC, component_name ASC
+        LIMIT $1 OFFSET $2
+        "#,
+        limit,
+        offset
+    )
+    .fetch_all(&mut **tx)
+    .await;
+
+    match result {
+        Ok(rows) => {
+            let mut components = Vec::new();
+            for row in rows {
+                let entity_bytes: [u8; 32] = row.entity_id.try_into().map_err(|_| {
+                    DataStoreError::Internal("invalid entity_id length".to_string())
+                })?;
+                let entity = Entity::new(entity_bytes);
+
+                let component = Component::new(&row.component_name).ok_or_else(|| {
+                    DataStoreError::Internal(format!(
+                        "invalid component name: {}",
+                        row.component_name
+                    ))
+                })?;
+
+                if let Some(data) = row.data {
+                    components.push(ComponentRecord {
+                        entity,
+                        component,
+                        data,
+                        created_at: row.created_at,
+                        updated_at: row.updated_at,
+                        version: row.version,
+                    });
+                }
+            }
+            Ok(components)
+        }
+        Err(e) => {
+            eprintln!("Database error listing component instance page: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Counts all component instances in the database.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+///
+/// # Returns
+/// * `Ok(i64)` - Number of component instances
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::component::count_all", skip_all, err)
+)]
+pub async fn count_all(tx: &mut Transaction<'_, Postgres>) -> SqlResult<i64> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "total!"
+        FROM component_instances
+        WHERE data IS NOT NULL
+        "#
+    )
+    .fetch_one(&mut **tx)
+    .await;
+
+    match result {
+        Ok(row) => Ok(row.total),
+        Err(e) => {
+            eprintln!("Database error counting component instances: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Counts the component instances attached to an entity.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `entity` - The entity whose components to count
+///
+/// # Returns
+/// * `Ok(i64)` - Number of component instances on the entity
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::count_for_entity",
+        skip_all,
+        err,
+        fields(entity = %entity)
+    )
+)]
+pub async fn count_for_entity(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+) -> SqlResult<i64> {
+    let entity_bytes = entity.as_bytes();
+
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "total!"
+        FROM component_instances
+        WHERE entity_id = $1 AND data IS NOT NULL
+        "#,
+        entity_bytes.as_slice()
+    )
+    .fetch_one(&mut **tx)
+    .await;
+
+    match result {
+        Ok(row) => Ok(row.total),
+        Err(e) => {
+            eprintln!(
+                "Database error counting component instances for entity: {}",
+                e
+            );
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
 /// Deletes all component instances for a specific entity.
 ///
 /// # Arguments
@@ -386,6 +1006,15 @@ pub async fn list_all(
 /// # Returns
 /// * `Ok(count)` - Number of component instances deleted
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component::delete_all_for_entity",
+        skip_all,
+        err,
+        fields(entity = %entity)
+    )
+)]
 pub async fn delete_all_for_entity(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -593,12 +1222,188 @@ mod tests {
         tx.commit().await.unwrap();
         assert_eq!(components.len(), 3);
 
-        let component_names: Vec<_> = components.iter().map(|(c, _)| c).collect();
+        let component_names: Vec<_> = components.iter().map(|record| &record.component).collect();
         assert!(component_names.contains(&&comp1));
         assert!(component_names.contains(&&comp2));
         assert!(component_names.contains(&&comp3));
     }
 
+    #[tokio::test]
+    async fn delete_and_list_by_prefix() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let entity = unique_entity("component_by_prefix");
+        let position = Component::new("game::Position").unwrap();
+        let health = Component::new("game::Health").unwrap();
+        let panel = Component::new("ui::Panel").unwrap();
+        let schema = json!({"type": "object"});
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        for comp in [&position, &health, &panel] {
+            let def = crate::ComponentDefinition::new(comp.clone(), schema.clone());
+            crate::sql::component_definition::create(&mut tx, &def)
+                .await
+                .unwrap();
+            create(&mut tx, &entity, comp, &json!({})).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let listed = list_for_entity_with_prefix(&mut tx, &entity, "game::*")
+            .await
+            .unwrap();
+        let listed: Vec<_> = listed.into_iter().map(|record| record.component).collect();
+        assert_eq!(listed, vec![health.clone(), position.clone()]);
+        assert!(matches!(
+            delete_by_prefix(&mut tx, &entity, "*").await,
+            Err(DataStoreError::InvalidInput(_))
+        ));
+
+        let deleted = delete_by_prefix(&mut tx, &entity, "game::").await.unwrap();
+        println!("delete_and_list_by_prefix response: {:?}", deleted);
+        assert_eq!(deleted, 2);
+        let remaining = list_for_entity(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].component, panel);
+    }
+
+    #[tokio::test]
+    async fn list_for_component_multiple() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let entity1 = unique_entity("component_by_type_1");
+        let entity2 = unique_entity("component_by_type_2");
+        let position = Component::new("Position").unwrap();
+        let other = Component::new("Other").unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity1).await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity2).await.unwrap();
+
+        for comp in [&position, &other] {
+            let def = crate::ComponentDefinition::new(
+                comp.clone(),
+                json!({"type": "object", "properties": {"x": {"type": "number"}}}),
+            );
+            crate::sql::component_definition::create(&mut tx, &def)
+                .await
+                .unwrap();
+        }
+
+        create(&mut tx, &entity1, &position, &json!({"x": 1}))
+            .await
+            .unwrap();
+        create(&mut tx, &entity2, &position, &json!({"x": 2}))
+            .await
+            .unwrap();
+        create(&mut tx, &entity1, &other, &json!({"x": 3}))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let instances = list_for_component(&mut tx, &position).await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(instances.len(), 2);
+        assert!(instances.contains(&(entity1, json!({"x": 1}))));
+        assert!(instances.contains(&(entity2, json!({"x": 2}))));
+    }
+
+    #[tokio::test]
+    async fn list_page_and_count() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let entity = unique_entity("component_list_page");
+        let components: Vec<Component> = ["PageA", "PageB", "PageC"]
+            .into_iter()
+            .map(|name| Component::new(name).unwrap())
+            .collect();
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        for component in &components {
+            let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+            crate::sql::component_definition::create(&mut tx, &def)
+                .await
+                .unwrap();
+            create(&mut tx, &entity, component, &json!({}))
+                .await
+                .unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let total = count_all(&mut tx).await.unwrap();
+        assert_eq!(count_for_entity(&mut tx, &entity).await.unwrap(), 3);
+        assert_eq!(
+            count_for_entity(&mut tx, &Entity::new([0xffu8; 32]))
+                .await
+                .unwrap(),
+            0
+        );
+        let first = list_page(&mut tx, 2, 0).await.unwrap();
+        let second = list_page(&mut tx, 2, 2).await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(total, 3);
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert_eq!(first[0].component, components[0]);
+        assert_eq!(first[1].component, components[1]);
+        assert_eq!(second[0].component, components[2]);
+    }
+
+    #[tokio::test]
+    async fn list_for_entities_filters() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let entity1 = unique_entity("component_bulk_1");
+        let entity2 = unique_entity("component_bulk_2");
+        let other = unique_entity("component_bulk_other");
+        let position = Component::new("BulkPosition").unwrap();
+        let health = Component::new("BulkHealth").unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        for component in [&position, &health] {
+            let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+            crate::sql::component_definition::create(&mut tx, &def)
+                .await
+                .unwrap();
+        }
+        for entity in [&entity1, &entity2, &other] {
+            crate::sql::entity::create(&mut tx, entity).await.unwrap();
+            create(&mut tx, entity, &position, &json!({"x": 1}))
+                .await
+                .unwrap();
+        }
+        create(&mut tx, &entity1, &health, &json!({"hp": 10}))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let all = list_for_entities(&mut tx, &[entity1, entity2], None)
+            .await
+            .unwrap();
+        let filtered = list_for_entities(
+            &mut tx,
+            &[entity1, entity2],
+            Some(std::slice::from_ref(&health)),
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(all.len(), 3);
+        assert!(all.iter().all(|record| record.entity != other));
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].entity, entity1);
+        assert_eq!(filtered[0].component, health);
+        assert_eq!(filtered[0].data, json!({"hp": 10}));
+    }
+
     #[tokio::test]
     async fn delete_all_for_entity_test() {
         let pool = super::super::tests::setup_test_db().await;
@@ -656,17 +1461,168 @@ mod tests {
             .await
             .unwrap();
 
-        let created_first = upsert(&mut tx, &entity, &component, &data1).await.unwrap();
-        println!("First upsert created flag: {}", created_first);
-        assert!(created_first);
+        let first = upsert(&mut tx, &entity, &component, &data1).await.unwrap();
+        println!("First upsert outcome: {:?}", first);
+        assert_eq!(first, UpsertOutcome::Created);
 
-        let created_second = upsert(&mut tx, &entity, &component, &data2).await.unwrap();
-        println!("Second upsert created flag: {}", created_second);
-        assert!(!created_second);
+        let second = upsert(&mut tx, &entity, &component, &data2).await.unwrap();
+        println!("Second upsert outcome: {:?}", second);
+        assert_eq!(second, UpsertOutcome::Updated);
 
         let retrieved = get(&mut tx, &entity, &component).await.unwrap().unwrap();
         assert_eq!(retrieved, data2);
 
         tx.commit().await.unwrap();
     }
+
+    #[tokio::test]
+    async fn update_bumps_only_updated_at() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let entity = unique_entity("update_bumps_updated_at");
+        let component = Component::new("Timestamped").unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+        crate::sql::component_definition::create(&mut tx, &def)
+            .await
+            .unwrap();
+        create(&mut tx, &entity, &component, &json!({"v": 1}))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let before = list_for_entity(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(before.len(), 1);
+        assert_eq!(before[0].created_at, before[0].updated_at);
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        assert!(
+            update(&mut tx, &entity, &component, &json!({"v": 2}))
+                .await
+                .unwrap()
+        );
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let after = list_for_entity(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+        println!("update_bumps_only_updated_at response: {:?}", after);
+        assert_eq!(after.len(), 1);
+        assert_eq!(after[0].data, json!({"v": 2}));
+        assert_eq!(after[0].created_at, before[0].created_at);
+        assert!(after[0].updated_at > before[0].updated_at);
+    }
+
+    #[tokio::test]
+    async fn upsert_identical_data_is_unchanged() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let entity = unique_entity("upsert_identical");
+        let component = Component::new("Stable").unwrap();
+        let data = json!({"a": 1, "b": [true, null]});
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+        crate::sql::component_definition::create(&mut tx, &def)
+            .await
+            .unwrap();
+        let first = upsert(&mut tx, &entity, &component, &data).await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(first, UpsertOutcome::Created);
+
+        let mut tx = pool.begin().await.unwrap();
+        let before = list_for_entity(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        let second = upsert(
+            &mut tx,
+            &entity,
+            &component,
+            &json!({"b": [true, null], "a": 1}),
+        )
+        .await
+        .unwrap();
+        tx.commit().await.unwrap();
+        println!("upsert_identical_data_is_unchanged response: {:?}", second);
+        assert_eq!(second, UpsertOutcome::Unchanged);
+        assert!(!second.created());
+        assert!(!second.changed());
+
+        let mut tx = pool.begin().await.unwrap();
+        let after = list_for_entity(&mut tx, &entity).await.unwrap();
+        tx.commit().await.unwrap();
+        assert_eq!(after, before);
+    }
+
+    #[tokio::test]
+    async fn update_versioned_rejects_stale_version() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let entity = unique_entity("update_versioned");
+        let component = Component::new("Versioned").unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        crate::sql::entity::create(&mut tx, &entity).await.unwrap();
+        let def = crate::ComponentDefinition::new(component.clone(), json!({"type": "object"}));
+        crate::sql::component_definition::create(&mut tx, &def)
+            .await
+            .unwrap();
+        create(&mut tx, &entity, &component, &json!({"v": 1}))
+            .await
+            .unwrap();
+
+        let (_, version) = get_with_version(&mut tx, &entity, &component)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(version, 1);
+
+        let updated = update_versioned(&mut tx, &entity, &component, &json!({"v": 2}), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(updated, Some(2));
+
+        let stale = update_versioned(&mut tx, &entity, &component, &json!({"v": 3}), Some(1)).await;
+        println!(
+            "update_versioned_rejects_stale_version response: {:?}",
+            stale
+        );
+        assert_eq!(
+            stale,
+            Err(DataStoreError::VersionMismatch {
+                expected: 1,
+                actual: 2
+            })
+        );
+
+        let missing = Component::new("Missing").unwrap();
+        let none = update_versioned(&mut tx, &entity, &missing, &json!({}), Some(1))
+            .await
+            .unwrap();
+        assert_eq!(none, None);
+
+        assert_eq!(
+            upsert(&mut tx, &entity, &component, &json!({"v": 4}))
+                .await
+                .unwrap(),
+            UpsertOutcome::Updated
+        );
+        let (_, version) = get_with_version(&mut tx, &entity, &component)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(version, 3);
+
+        tx.commit().await.unwrap();
+    }
 }
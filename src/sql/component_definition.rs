@@ -6,7 +6,7 @@
 use chrono::{DateTime, Utc};
 use sqlx::{Postgres, Transaction};
 
-use crate::{Component, ComponentDefinition, DataStoreError};
+use crate::{Component, ComponentDefinition, ComponentNamePolicy, DataStoreError};
 
 /// Result type for database operations.
 pub type SqlResult<T> = Result<T, DataStoreError>;
@@ -52,6 +52,15 @@ pub struct ComponentDefinitionRecord {
 /// # Ok(())
 /// # }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component_definition::create",
+        skip_all,
+        err,
+        fields(component = definition.component.as_str())
+    )
+)]
 pub async fn create(
     tx: &mut Transaction<'_, Postgres>,
     definition: &ComponentDefinition,
@@ -83,6 +92,80 @@ pub async fn create(
     }
 }
 
+/// Creates a new component definition, detecting collisions according to `policy`.
+///
+/// Under [`ComponentNamePolicy::CaseInsensitive`], creating a definition whose name
+/// differs only in case from an existing one fails with `AlreadyExists`. The table is
+/// locked against concurrent writers until the transaction ends, so two transactions
+/// cannot both insert names that differ only in case.
+///
+/// # Returns
+/// * `Ok(())` - Component definition created successfully
+/// * `Err(DataStoreError::AlreadyExists)` - A colliding component definition exists
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component_definition::create_with_policy",
+        skip_all,
+        err,
+        fields(component = definition.component.as_str(), policy = ?policy)
+    )
+)]
+pub async fn create_with_policy(
+    tx: &mut Transaction<'_, Postgres>,
+    definition: &ComponentDefinition,
+    policy: ComponentNamePolicy,
+) -> SqlResult<()> {
+    if policy == ComponentNamePolicy::CaseSensitive {
+        return create(tx, definition).await;
+    }
+
+    let component_name = definition.component.as_str();
+    let schema = serde_json::to_value(&definition.schema)
+        .map_err(|e| DataStoreError::SerializationError(e.to_string()))?;
+
+    // The lock is taken before the insert's snapshot, so the NOT EXISTS check below sees
+    // any colliding name committed by a transaction that held the lock before us.
+    sqlx::query!(
+        r#"
+        LOCK TABLE component_definitions IN SHARE ROW EXCLUSIVE MODE
+        "#
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        eprintln!("Database error locking component definitions: {}", e);
+        DataStoreError::Internal(e.to_string())
+    })?;
+
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO component_definitions (component_name, schema)
+        SELECT $1::varchar, $2::jsonb
+        WHERE NOT EXISTS (
+            SELECT 1 FROM component_definitions WHERE lower(component_name) = lower($1::varchar)
+        )
+        "#,
+        component_name,
+        schema
+    )
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(done) if done.rows_affected() == 0 => Err(DataStoreError::AlreadyExists),
+        Ok(_) => Ok(()),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            Err(DataStoreError::AlreadyExists)
+        }
+        Err(e) => {
+            eprintln!("Database error creating component definition: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
 /// Retrieves a component definition from the database.
 ///
 /// # Arguments
@@ -93,35 +176,104 @@ pub async fn create(
 /// * `Ok(Some(ComponentDefinitionRecord))` - Component definition found
 /// * `Ok(None)` - Component definition not found
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component_definition::get",
+        skip_all,
+        err,
+        fields(component = component.as_str())
+    )
+)]
 pub async fn get(
     tx: &mut Transaction<'_, Postgres>,
     component: &Component,
+) -> SqlResult<Option<ComponentDefinitionRecord>> {
+    get_with_policy(tx, component, ComponentNamePolicy::CaseSensitive).await
+}
+
+/// Retrieves a component definition, comparing names according to `policy`.
+///
+/// Under [`ComponentNamePolicy::CaseInsensitive`], a definition whose name matches
+/// `component` exactly is preferred over one that differs only in case.
+///
+/// # Returns
+/// * `Ok(Some(ComponentDefinitionRecord))` - Component definition found
+/// * `Ok(None)` - Component definition not found
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component_definition::get_with_policy",
+        skip_all,
+        err,
+        fields(component = component.as_str(), policy = ?policy)
+    )
+)]
+pub async fn get_with_policy(
+    tx: &mut Transaction<'_, Postgres>,
+    component: &Component,
+    policy: ComponentNamePolicy,
 ) -> SqlResult<Option<ComponentDefinitionRecord>> {
     let component_name = component.as_str();
 
-    let result = sqlx::query!(
-        r#"
+    let result = match policy {
+        ComponentNamePolicy::CaseSensitive => sqlx::query!(
+            r#"
         SELECT component_name, schema, created_at, updated_at
         FROM component_definitions
         WHERE component_name = $1
         "#,
-        component_name
-    )
-    .fetch_optional(&mut **tx)
-    .await;
+            component_name
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map(|row| {
+            row.map(|row| {
+                (
+                    row.component_name,
+                    row.schema,
+                    row.created_at,
+                    row.updated_at,
+                )
+            })
+        }),
+        ComponentNamePolicy::CaseInsensitive => sqlx::query!(
+            r#"
+        SELECT component_name, schema, created_at, updated_at
+        FROM component_definitions
+        WHERE lower(component_name) = lower($1)
+        ORDER BY component_name = $1 DESC, component_name
+        LIMIT 1
+        "#,
+            component_name
+        )
+        .fetch_optional(&mut **tx)
+        .await
+        .map(|row| {
+            row.map(|row| {
+                (
+                    row.component_name,
+                    row.schema,
+                    row.created_at,
+                    row.updated_at,
+                )
+            })
+        }),
+    };
 
     match result {
-        Ok(Some(row)) => {
-            let component = Component::new(&row.component_name).ok_or_else(|| {
-                DataStoreError::Internal(format!("invalid component name: {}", row.component_name))
+        Ok(Some((component_name, schema, created_at, updated_at))) => {
+            let component = Component::new(&component_name).ok_or_else(|| {
+                DataStoreError::Internal(format!("invalid component name: {}", component_name))
             })?;
 
-            let definition = ComponentDefinition::new(component, row.schema);
+            let definition = ComponentDefinition::new(component, schema);
 
             Ok(Some(ComponentDefinitionRecord {
                 definition,
-                created_at: row.created_at,
-                updated_at: row.updated_at,
+                created_at,
+                updated_at,
             }))
         }
         Ok(None) => Ok(None),
@@ -132,6 +284,39 @@ pub async fn get(
     }
 }
 
+/// Returns the stored name of the definition `component` refers to under `policy`.
+///
+/// Under [`ComponentNamePolicy::CaseInsensitive`], a definition whose name differs from
+/// `component` only in case is reported by its stored name. Otherwise, including when no
+/// definition exists, `component` is returned unchanged, so callers can resolve a name
+/// once and then use the exact-match functions in this module.
+///
+/// # Returns
+/// * `Ok(Component)` - The name to use for the definition and its instances
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component_definition::resolve_name",
+        skip_all,
+        err,
+        fields(component = component.as_str(), policy = ?policy)
+    )
+)]
+pub async fn resolve_name(
+    tx: &mut Transaction<'_, Postgres>,
+    component: &Component,
+    policy: ComponentNamePolicy,
+) -> SqlResult<Component> {
+    if policy == ComponentNamePolicy::CaseSensitive {
+        return Ok(component.clone());
+    }
+    Ok(get_with_policy(tx, component, policy)
+        .await?
+        .map(|record| record.definition.component)
+        .unwrap_or_else(|| component.clone()))
+}
+
 /// Updates an existing component definition in the database.
 ///
 /// # Arguments
@@ -142,6 +327,15 @@ pub async fn get(
 /// * `Ok(true)` - Component definition existed and was updated
 /// * `Ok(false)` - Component definition did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component_definition::update",
+        skip_all,
+        err,
+        fields(component = definition.component.as_str())
+    )
+)]
 pub async fn update(
     tx: &mut Transaction<'_, Postgres>,
     definition: &ComponentDefinition,
@@ -183,6 +377,15 @@ pub async fn update(
 /// * `Ok(true)` - Component definition existed and was deleted
 /// * `Ok(false)` - Component definition did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::component_definition::delete",
+        skip_all,
+        err,
+        fields(component = component.as_str())
+    )
+)]
 pub async fn delete(tx: &mut Transaction<'_, Postgres>, component: &Component) -> SqlResult<bool> {
     let component_name = component.as_str();
 
@@ -213,6 +416,10 @@ pub async fn delete(tx: &mut Transaction<'_, Postgres>, component: &Component) -
 /// # Returns
 /// * `Ok(Vec<ComponentDefinition>)` - List of all component definitions
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::component_definition::list", skip_all, err)
+)]
 pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<ComponentDefinition>> {
     let result = sqlx::query!(
         r#"
@@ -306,6 +513,61 @@ mod tests {
         assert!(matches!(result, Err(DataStoreError::AlreadyExists)));
     }
 
+    #[tokio::test]
+    async fn case_insensitive_policy_rejects_case_collisions() {
+        let pool = super::super::tests::setup_test_db().await;
+        let policy = ComponentNamePolicy::CaseInsensitive;
+        let upper = ComponentDefinition::new(
+            Component::new("Position").unwrap(),
+            json!({"type": "object"}),
+        );
+        let lower = ComponentDefinition::new(
+            Component::new("position").unwrap(),
+            json!({"type": "string"}),
+        );
+
+        let mut tx = pool.begin().await.unwrap();
+        create_with_policy(&mut tx, &upper, policy).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let result = create_with_policy(&mut tx, &lower, policy).await;
+        println!(
+            "case_insensitive_policy_rejects_case_collisions response: {:?}",
+            result
+        );
+        assert!(matches!(result, Err(DataStoreError::AlreadyExists)));
+        drop(tx);
+
+        let mut tx = pool.begin().await.unwrap();
+        let found = get_with_policy(&mut tx, &Component::new("POSITION").unwrap(), policy)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.definition.component, upper.component);
+        assert!(
+            get(&mut tx, &Component::new("POSITION").unwrap())
+                .await
+                .unwrap()
+                .is_none()
+        );
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        create_with_policy(&mut tx, &lower, ComponentNamePolicy::CaseSensitive)
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let exact = get_with_policy(&mut tx, &lower.component, policy)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(exact.definition.component, lower.component);
+        tx.commit().await.unwrap();
+    }
+
     #[tokio::test]
     async fn update_existing() {
         let pool = super::super::tests::setup_test_db().await;
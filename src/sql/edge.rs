@@ -14,15 +14,28 @@ use sqlx::Postgres;
 use sqlx::Transaction;
 
 /// Creates a new edge in the database.
+///
+/// Returns `DataStoreError::AlreadyExists` if the edge exists, or if the edge is undirected
+/// and an undirected edge with the same label already joins the two entities.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::edge::create",
+        skip_all,
+        err,
+        fields(src = %edge.src, dst = %edge.dst, label = %edge.label)
+    )
+)]
 pub async fn create(tx: &mut Transaction<'_, Postgres>, edge: &Edge) -> Result<(), DataStoreError> {
     let result = sqlx::query!(
         r#"
-        INSERT INTO edges (src_entity, dst_entity, label_entity)
-        VALUES ($1, $2, $3)
+        INSERT INTO edges (src_entity, dst_entity, label_entity, directed)
+        VALUES ($1, $2, $3, $4)
         "#,
         edge.src.as_bytes(),
         edge.dst.as_bytes(),
-        edge.label.as_bytes()
+        edge.label.as_bytes(),
+        edge.directed
     )
     .execute(&mut **tx)
     .await;
@@ -37,6 +50,15 @@ pub async fn create(tx: &mut Transaction<'_, Postgres>, edge: &Edge) -> Result<(
 }
 
 /// Deletes a specific edge from the database.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::edge::delete",
+        skip_all,
+        err,
+        fields(src = %src, dst = %dst, label = %label)
+    )
+)]
 pub async fn delete(
     tx: &mut Transaction<'_, Postgres>,
     src: &Entity,
@@ -62,7 +84,45 @@ pub async fn delete(
     }
 }
 
+/// Deletes every edge that references an entity as its source, destination, or label.
+///
+/// Returns the number of edges removed.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::edge::delete_all_for_entity",
+        skip_all,
+        err,
+        fields(entity = %entity)
+    )
+)]
+pub async fn delete_all_for_entity(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+) -> Result<u64, DataStoreError> {
+    let result = sqlx::query!(
+        r#"
+        DELETE FROM edges
+        WHERE src_entity = $1 OR dst_entity = $1 OR label_entity = $1
+        "#,
+        entity.as_bytes()
+    )
+    .execute(&mut **tx)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
 /// Gets a specific edge from the database.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::edge::get",
+        skip_all,
+        err,
+        fields(src = %src, dst = %dst, label = %label)
+    )
+)]
 pub async fn get(
     tx: &mut Transaction<'_, Postgres>,
     src: &Entity,
@@ -71,7 +131,7 @@ pub async fn get(
 ) -> Result<Edge, DataStoreError> {
     let row = sqlx::query!(
         r#"
-        SELECT src_entity, dst_entity, label_entity
+        SELECT src_entity, dst_entity, label_entity, directed
         FROM edges
         WHERE src_entity = $1 AND dst_entity = $2 AND label_entity = $3
         "#,
@@ -87,14 +147,19 @@ pub async fn get(
         row.src_entity.as_slice(),
         row.dst_entity.as_slice(),
         row.label_entity.as_slice(),
+        row.directed,
     )
 }
 
 /// Lists all edges in the database.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::edge::list_all", skip_all, err)
+)]
 pub async fn list_all(tx: &mut Transaction<'_, Postgres>) -> Result<Vec<Edge>, DataStoreError> {
     let rows = sqlx::query!(
         r#"
-        SELECT src_entity, dst_entity, label_entity
+        SELECT src_entity, dst_entity, label_entity, directed
         FROM edges
         ORDER BY created_at
         "#
@@ -108,19 +173,24 @@ pub async fn list_all(tx: &mut Transaction<'_, Postgres>) -> Result<Vec<Edge>, D
                 row.src_entity.as_slice(),
                 row.dst_entity.as_slice(),
                 row.label_entity.as_slice(),
+                row.directed,
             )
         })
         .collect()
 }
 
 /// Lists all edges from a specific source entity.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::edge::list_from", skip_all, err, fields(src = %src))
+)]
 pub async fn list_from(
     tx: &mut Transaction<'_, Postgres>,
     src: &Entity,
 ) -> Result<Vec<Edge>, DataStoreError> {
     let rows = sqlx::query!(
         r#"
-        SELECT src_entity, dst_entity, label_entity
+        SELECT src_entity, dst_entity, label_entity, directed
         FROM edges
         WHERE src_entity = $1
         ORDER BY created_at
@@ -136,19 +206,24 @@ pub async fn list_from(
                 row.src_entity.as_slice(),
                 row.dst_entity.as_slice(),
                 row.label_entity.as_slice(),
+                row.directed,
             )
         })
         .collect()
 }
 
 /// Lists all edges to a specific destination entity.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::edge::list_to", skip_all, err, fields(dst = %dst))
+)]
 pub async fn list_to(
     tx: &mut Transaction<'_, Postgres>,
     dst: &Entity,
 ) -> Result<Vec<Edge>, DataStoreError> {
     let rows = sqlx::query!(
         r#"
-        SELECT src_entity, dst_entity, label_entity
+        SELECT src_entity, dst_entity, label_entity, directed
         FROM edges
         WHERE dst_entity = $1
         ORDER BY created_at
@@ -164,19 +239,24 @@ pub async fn list_to(
                 row.src_entity.as_slice(),
                 row.dst_entity.as_slice(),
                 row.label_entity.as_slice(),
+                row.directed,
             )
         })
         .collect()
 }
 
 /// Lists all edges with a specific label entity.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::edge::list_labeled", skip_all, err, fields(label = %label))
+)]
 pub async fn list_labeled(
     tx: &mut Transaction<'_, Postgres>,
     label: &Entity,
 ) -> Result<Vec<Edge>, DataStoreError> {
     let rows = sqlx::query!(
         r#"
-        SELECT src_entity, dst_entity, label_entity
+        SELECT src_entity, dst_entity, label_entity, directed
         FROM edges
         WHERE label_entity = $1
         ORDER BY created_at
@@ -192,12 +272,22 @@ pub async fn list_labeled(
                 row.src_entity.as_slice(),
                 row.dst_entity.as_slice(),
                 row.label_entity.as_slice(),
+                row.directed,
             )
         })
         .collect()
 }
 
 /// Lists all edges between two specific entities.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::edge::list_between",
+        skip_all,
+        err,
+        fields(src = %src, dst = %dst)
+    )
+)]
 pub async fn list_between(
     tx: &mut Transaction<'_, Postgres>,
     src: &Entity,
@@ -205,7 +295,7 @@ pub async fn list_between(
 ) -> Result<Vec<Edge>, DataStoreError> {
     let rows = sqlx::query!(
         r#"
-        SELECT src_entity, dst_entity, label_entity
+        SELECT src_entity, dst_entity, label_entity, directed
         FROM edges
         WHERE src_entity = $1 AND dst_entity = $2
         ORDER BY created_at
@@ -222,20 +312,155 @@ pub async fn list_between(
                 row.src_entity.as_slice(),
                 row.dst_entity.as_slice(),
                 row.label_entity.as_slice(),
+                row.directed,
+            )
+        })
+        .collect()
+}
+
+/// Lists all edges leaving an entity.
+///
+/// This includes directed edges whose source is the entity and undirected edges touching it.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::edge::list_out", skip_all, err, fields(entity = %entity))
+)]
+pub async fn list_out(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+) -> Result<Vec<Edge>, DataStoreError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT src_entity, dst_entity, label_entity, directed
+        FROM edges
+        WHERE src_entity = $1 OR (NOT directed AND dst_entity = $1)
+        ORDER BY created_at
+        "#,
+        entity.as_bytes()
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            edge_from_row_bytes(
+                row.src_entity.as_slice(),
+                row.dst_entity.as_slice(),
+                row.label_entity.as_slice(),
+                row.directed,
             )
         })
         .collect()
 }
 
+/// Lists all edges arriving at an entity.
+///
+/// This includes directed edges whose destination is the entity and undirected edges touching it.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::edge::list_in", skip_all, err, fields(entity = %entity))
+)]
+pub async fn list_in(
+    tx: &mut Transaction<'_, Postgres>,
+    entity: &Entity,
+) -> Result<Vec<Edge>, DataStoreError> {
+    let rows = sqlx::query!(
+        r#"
+        SELECT src_entity, dst_entity, label_entity, directed
+        FROM edges
+        WHERE dst_entity = $1 OR (NOT directed AND src_entity = $1)
+        ORDER BY created_at
+        "#,
+        entity.as_bytes()
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            edge_from_row_bytes(
+                row.src_entity.as_slice(),
+                row.dst_entity.as_slice(),
+                row.label_entity.as_slice(),
+                row.directed,
+            )
+        })
+        .collect()
+}
+
+/// Finds every entity reachable from `start` within `max_depth` edges.
+///
+/// Directed edges are followed from src to dst; undirected edges are followed both ways.
+/// When `label` is given, only edges with that label are followed. Each entity is reported
+/// once with its shortest distance from `start`; `start` itself is not reported.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::edge::traverse",
+        skip_all,
+        err,
+        fields(start = %start, max_depth = max_depth)
+    )
+)]
+pub async fn traverse(
+    tx: &mut Transaction<'_, Postgres>,
+    start: &Entity,
+    max_depth: i32,
+    label: Option<&Entity>,
+) -> Result<Vec<(Entity, i32)>, DataStoreError> {
+    let rows = sqlx::query!(
+        r#"
+        WITH RECURSIVE steps AS (
+            SELECT src_entity AS from_entity, dst_entity AS to_entity, label_entity
+            FROM edges
+            UNION ALL
+            SELECT dst_entity, src_entity, label_entity
+            FROM edges
+            WHERE NOT directed
+        ),
+        reachable (entity_id, distance) AS (
+            SELECT $1::BYTEA, 0
+            UNION
+            SELECT steps.to_entity, reachable.distance + 1
+            FROM reachable
+            JOIN steps ON steps.from_entity = reachable.entity_id
+            WHERE reachable.distance < $2
+              AND ($3::BYTEA IS NULL OR steps.label_entity = $3)
+        )
+        SELECT entity_id AS "entity_id!", MIN(distance) AS "distance!"
+        FROM reachable
+        WHERE entity_id <> $1
+        GROUP BY entity_id
+        ORDER BY 2, 1
+        "#,
+        start.as_bytes(),
+        max_depth,
+        label.map(|label| label.as_bytes().as_slice())
+    )
+    .fetch_all(&mut **tx)
+    .await?;
+
+    rows.into_iter()
+        .map(|row| {
+            Ok((
+                entity_from_bytes(&row.entity_id, "reachable entity")?,
+                row.distance,
+            ))
+        })
+        .collect()
+}
+
 fn edge_from_row_bytes(
     src_bytes: &[u8],
     dst_bytes: &[u8],
     label_bytes: &[u8],
+    directed: bool,
 ) -> Result<Edge, DataStoreError> {
     Ok(Edge {
         src: entity_from_bytes(src_bytes, "src entity")?,
         dst: entity_from_bytes(dst_bytes, "dst entity")?,
         label: entity_from_bytes(label_bytes, "label entity")?,
+        directed,
     })
 }
 
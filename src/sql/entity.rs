@@ -22,6 +22,31 @@ pub struct EntityRecord {
     pub updated_at: DateTime<Utc>,
 }
 
+/// Selects which entities [`list`] returns.
+///
+/// The default filter lists every entity.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntityListFilter {
+    /// Only entities created after this time, or at this time if `after_entity` is set and
+    /// they sort after it.
+    pub created_after: Option<DateTime<Utc>>,
+    /// Tie-breaker for entities created exactly at `created_after`.
+    pub after_entity: Option<Entity>,
+    /// Maximum number of entities to return; `None` returns them all.
+    pub limit: Option<i64>,
+}
+
+impl EntityListFilter {
+    /// Returns a filter for the entities following `record`, keeping this filter's limit.
+    pub fn after(&self, record: &EntityRecord) -> Self {
+        EntityListFilter {
+            created_after: Some(record.created_at),
+            after_entity: Some(record.entity),
+            limit: self.limit,
+        }
+    }
+}
+
 /// Creates a new entity in the database.
 ///
 /// The `created_at` and `updated_at` timestamps are automatically set to the current time.
@@ -47,6 +72,10 @@ pub struct EntityRecord {
 /// # Ok(())
 /// # }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::entity::create", skip_all, err, fields(entity = %entity))
+)]
 pub async fn create(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlResult<()> {
     let entity_bytes = entity.as_bytes();
 
@@ -85,6 +114,15 @@ pub async fn create(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlR
 /// * `Ok(true)` - Entity was created (didn't exist before)
 /// * `Ok(false)` - Entity already exists (no-op)
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::entity::create_idempotent",
+        skip_all,
+        err,
+        fields(entity = %entity)
+    )
+)]
 pub async fn create_idempotent(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -127,6 +165,10 @@ pub async fn create_idempotent(
 /// * `Ok(Some(EntityRecord))` - Entity found
 /// * `Ok(None)` - Entity not found
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::entity::get", skip_all, err, fields(entity = %entity))
+)]
 pub async fn get(
     tx: &mut Transaction<'_, Postgres>,
     entity: &Entity,
@@ -177,6 +219,10 @@ pub async fn get(
 /// * `Ok(true)` - Entity existed and was deleted
 /// * `Ok(false)` - Entity did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::entity::delete", skip_all, err, fields(entity = %entity))
+)]
 pub async fn delete(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlResult<bool> {
     let entity_bytes = entity.as_bytes();
 
@@ -199,21 +245,62 @@ pub async fn delete(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlR
     }
 }
 
-/// Lists all entities in the database.
+/// Lists entities in `(created_at, entity)` order, optionally resuming after a checkpoint.
+///
+/// Because the order is total, paging with [`EntityListFilter::after`] visits every entity
+/// created before the first page was read exactly once, even when several share a
+/// creation time.
 ///
 /// # Arguments
 /// * `tx` - PostgreSQL transaction
+/// * `filter` - Where to start and how many entities to return
 ///
 /// # Returns
-/// * `Ok(Vec<Entity>)` - List of all entities
+/// * `Ok(Vec<EntityRecord>)` - The matching entities
 /// * `Err(DataStoreError::Internal)` - Database error
-pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<Entity>> {
+///
+/// # Examples
+/// ```no_run
+/// # use stigmergy::sql::{self, entity::EntityListFilter};
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+/// let mut tx = pool.begin().await?;
+/// let mut filter = EntityListFilter {
+///     limit: Some(100),
+///     ..EntityListFilter::default()
+/// };
+/// loop {
+///     let batch = sql::entity::list(&mut tx, &filter).await?;
+///     let Some(last) = batch.last() else { break };
+///     filter = filter.after(last);
+/// }
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::entity::list", skip_all, err)
+)]
+pub async fn list(
+    tx: &mut Transaction<'_, Postgres>,
+    filter: &EntityListFilter,
+) -> SqlResult<Vec<EntityRecord>> {
+    let after_entity = filter.after_entity.map(|entity| entity.as_bytes().to_vec());
+
     let result = sqlx::query!(
         r#"
-        SELECT entity_id
+        SELECT entity_id, created_at, updated_at
         FROM entities
-        ORDER BY created_at ASC
-        "#
+        WHERE $1::TIMESTAMPTZ IS NULL
+           OR created_at > $1
+           OR (created_at = $1 AND entity_id > $2::BYTEA)
+        ORDER BY created_at ASC, entity_id ASC
+        LIMIT $3
+        "#,
+        filter.created_after,
+        after_entity,
+        filter.limit
     )
     .fetch_all(&mut **tx)
     .await;
@@ -225,7 +312,11 @@ pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<Entity>>
                 let entity_bytes: [u8; 32] = row.entity_id.try_into().map_err(|_| {
                     DataStoreError::Internal("invalid entity_id length".to_string())
                 })?;
-                entities.push(Entity::new(entity_bytes));
+                entities.push(EntityRecord {
+                    entity: Entity::new(entity_bytes),
+                    created_at: row.created_at,
+                    updated_at: row.updated_at,
+                });
             }
             Ok(entities)
         }
@@ -248,6 +339,10 @@ pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<Entity>>
 /// * `Ok(true)` - Entity existed and was updated
 /// * `Ok(false)` - Entity did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::entity::touch", skip_all, err, fields(entity = %entity))
+)]
 pub async fn touch(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlResult<bool> {
     let entity_bytes = entity.as_bytes();
 
@@ -271,6 +366,72 @@ pub async fn touch(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlRe
     }
 }
 
+/// Checks whether an entity exists without fetching its row.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `entity` - The entity to check
+///
+/// # Returns
+/// * `Ok(true)` - Entity exists
+/// * `Ok(false)` - Entity does not exist
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::entity::exists", skip_all, err, fields(entity = %entity))
+)]
+pub async fn exists(tx: &mut Transaction<'_, Postgres>, entity: &Entity) -> SqlResult<bool> {
+    let entity_bytes = entity.as_bytes();
+
+    let result = sqlx::query!(
+        r#"
+        SELECT EXISTS(SELECT 1 FROM entities WHERE entity_id = $1) AS "exists!"
+        "#,
+        entity_bytes.as_slice()
+    )
+    .fetch_one(&mut **tx)
+    .await;
+
+    match result {
+        Ok(row) => Ok(row.exists),
+        Err(e) => {
+            eprintln!("Database error checking entity existence: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Counts all entities in the database.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+///
+/// # Returns
+/// * `Ok(i64)` - Number of entities
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::entity::count", skip_all, err)
+)]
+pub async fn count(tx: &mut Transaction<'_, Postgres>) -> SqlResult<i64> {
+    let result = sqlx::query!(
+        r#"
+        SELECT COUNT(*) AS "total!"
+        FROM entities
+        "#
+    )
+    .fetch_one(&mut **tx)
+    .await;
+
+    match result {
+        Ok(row) => Ok(row.total),
+        Err(e) => {
+            eprintln!("Database error counting entities: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -385,13 +546,61 @@ mod tests {
         tx.commit().await.unwrap();
 
         let mut tx = pool.begin().await.unwrap();
-        let entities = list(&mut tx).await.unwrap();
+        let entities: Vec<Entity> = list(&mut tx, &EntityListFilter::default())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|record| record.entity)
+            .collect();
         tx.commit().await.unwrap();
         assert!(entities.contains(&entity1));
         assert!(entities.contains(&entity2));
         assert!(entities.contains(&entity3));
     }
 
+    #[tokio::test]
+    async fn list_pages_through_ties_without_gaps() {
+        let pool = super::super::tests::setup_test_db().await;
+        let entities = [
+            unique_entity("list_pages_1"),
+            unique_entity("list_pages_2"),
+            unique_entity("list_pages_3"),
+        ];
+
+        // Created in one transaction, so all three share a creation time.
+        let mut tx = pool.begin().await.unwrap();
+        for entity in &entities {
+            create(&mut tx, entity).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let all = list(&mut tx, &EntityListFilter::default()).await.unwrap();
+        let mut filter = EntityListFilter {
+            limit: Some(1),
+            ..EntityListFilter::default()
+        };
+        let mut paged = Vec::new();
+        loop {
+            let batch = list(&mut tx, &filter).await.unwrap();
+            assert!(batch.len() <= 1);
+            let Some(last) = batch.last() else { break };
+            filter = filter.after(last);
+            paged.extend(batch.into_iter().map(|record| record.entity));
+        }
+        tx.commit().await.unwrap();
+        println!(
+            "list_pages_through_ties_without_gaps response: {} entities",
+            paged.len()
+        );
+
+        let all: Vec<Entity> = all.into_iter().map(|record| record.entity).collect();
+        assert_eq!(paged, all);
+        for entity in &entities {
+            assert_eq!(paged.iter().filter(|e| *e == entity).count(), 1);
+        }
+    }
+
     #[tokio::test]
     async fn touch_updates_timestamp() {
         let pool = super::super::tests::setup_test_db().await;
@@ -419,4 +628,18 @@ mod tests {
         assert_eq!(record_after.created_at, record_before.created_at);
         assert!(record_after.updated_at > record_before.updated_at);
     }
+
+    #[tokio::test]
+    async fn exists_and_count() {
+        let pool = super::super::tests::setup_test_db().await;
+        let entity = unique_entity("exists_and_count");
+
+        let mut tx = pool.begin().await.unwrap();
+        assert!(!exists(&mut tx, &entity).await.unwrap());
+        let before = count(&mut tx).await.unwrap();
+        create(&mut tx, &entity).await.unwrap();
+        assert!(exists(&mut tx, &entity).await.unwrap());
+        assert_eq!(count(&mut tx).await.unwrap(), before + 1);
+        tx.commit().await.unwrap();
+    }
 }
@@ -0,0 +1,142 @@
+//! Idempotency key operations for PostgreSQL database.
+//!
+//! This module records the responses returned by the apply endpoint so that
+//! requests retried with the same `Idempotency-Key` are not applied twice.
+
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+
+use crate::DataStoreError;
+
+/// Result type for database operations.
+pub type SqlResult<T> = Result<T, DataStoreError>;
+
+/// Retrieves the request and response stored for an idempotency key.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `key` - The idempotency key to look up
+///
+/// # Returns
+/// * `Ok(Some((request, response)))` - Key found
+/// * `Ok(None)` - Key has not been used
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::idempotency::get", skip_all, err)
+)]
+pub async fn get(
+    tx: &mut Transaction<'_, Postgres>,
+    key: &str,
+) -> SqlResult<Option<(Value, Value)>> {
+    let result = sqlx::query!(
+        r#"
+        SELECT request, response
+        FROM apply_idempotency
+        WHERE idempotency_key = $1
+        "#,
+        key
+    )
+    .fetch_optional(&mut **tx)
+    .await;
+
+    match result {
+        Ok(Some(row)) => Ok(Some((row.request, row.response))),
+        Ok(None) => Ok(None),
+        Err(e) => {
+            eprintln!("Database error getting idempotency key: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Records the request and response for an idempotency key.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `key` - The idempotency key
+/// * `request` - The request applied under the key
+/// * `response` - The response returned for the request
+///
+/// # Returns
+/// * `Ok(())` - Key recorded successfully
+/// * `Err(DataStoreError::AlreadyExists)` - Key already recorded
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::idempotency::create", skip_all, err)
+)]
+pub async fn create(
+    tx: &mut Transaction<'_, Postgres>,
+    key: &str,
+    request: &Value,
+    response: &Value,
+) -> SqlResult<()> {
+    let result = sqlx::query!(
+        r#"
+        INSERT INTO apply_idempotency (idempotency_key, request, response)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (idempotency_key) DO NOTHING
+        "#,
+        key,
+        request,
+        response
+    )
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(result) if result.rows_affected() == 0 => Err(DataStoreError::AlreadyExists),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            eprintln!("Database error recording idempotency key: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn create_and_get() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let mut tx = pool.begin().await.unwrap();
+        assert!(get(&mut tx, "create_and_get").await.unwrap().is_none());
+        create(
+            &mut tx,
+            "create_and_get",
+            &json!({"operations": []}),
+            &json!({"results": [], "committed": true}),
+        )
+        .await
+        .unwrap();
+        let stored = get(&mut tx, "create_and_get").await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(
+            stored,
+            Some((
+                json!({"operations": []}),
+                json!({"results": [], "committed": true})
+            ))
+        );
+    }
+
+    #[tokio::test]
+    async fn create_duplicate_fails() {
+        let pool = super::super::tests::setup_test_db().await;
+
+        let mut tx = pool.begin().await.unwrap();
+        create(&mut tx, "create_duplicate_fails", &json!({}), &json!({}))
+            .await
+            .unwrap();
+        let result = create(&mut tx, "create_duplicate_fails", &json!({}), &json!({})).await;
+        tx.commit().await.unwrap();
+
+        assert!(matches!(result, Err(DataStoreError::AlreadyExists)));
+    }
+}
@@ -16,6 +16,8 @@ pub type SqlResult<T> = Result<T, DataStoreError>;
 pub struct InvariantRecord {
     /// The invariant identifier.
     pub invariant_id: InvariantID,
+    /// The optional human-readable name of the invariant.
+    pub name: Option<String>,
     /// The assertion expression as a string.
     pub asserts: String,
     /// When the invariant was created.
@@ -50,6 +52,15 @@ pub struct InvariantRecord {
 /// # Ok(())
 /// # }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::invariants::create",
+        skip_all,
+        err,
+        fields(invariant_id = %invariant_id, asserts = %asserts)
+    )
+)]
 pub async fn create(
     tx: &mut Transaction<'_, Postgres>,
     invariant_id: &InvariantID,
@@ -90,6 +101,15 @@ pub async fn create(
 /// * `Ok(Some(InvariantRecord))` - Invariant found
 /// * `Ok(None)` - Invariant not found
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::invariants::get",
+        skip_all,
+        err,
+        fields(invariant_id = %invariant_id)
+    )
+)]
 pub async fn get(
     tx: &mut Transaction<'_, Postgres>,
     invariant_id: &InvariantID,
@@ -98,7 +118,7 @@ pub async fn get(
 
     let result = sqlx::query!(
         r#"
-        SELECT invariant_id, asserts, created_at, updated_at
+        SELECT invariant_id, name, asserts, created_at, updated_at
         FROM invariants
         WHERE invariant_id = $1
         "#,
@@ -116,6 +136,7 @@ pub async fn get(
 
             Ok(Some(InvariantRecord {
                 invariant_id: InvariantID::new(invariant_bytes),
+                name: row.name,
                 asserts: row.asserts,
                 created_at: row.created_at,
                 updated_at: row.updated_at,
@@ -129,6 +150,110 @@ pub async fn get(
     }
 }
 
+/// Retrieves an invariant from the database by its name.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `name` - The name of the invariant to retrieve
+///
+/// # Returns
+/// * `Ok(Some(InvariantRecord))` - Invariant found
+/// * `Ok(None)` - No invariant has this name
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::invariants::get_by_name", skip_all, err, fields(name = %name))
+)]
+pub async fn get_by_name(
+    tx: &mut Transaction<'_, Postgres>,
+    name: &str,
+) -> SqlResult<Option<InvariantRecord>> {
+    let result = sqlx::query!(
+        r#"
+        SELECT invariant_id, name, asserts, created_at, updated_at
+        FROM invariants
+        WHERE name = $1
+        "#,
+        name
+    )
+    .fetch_optional(&mut **tx)
+    .await;
+
+    match result {
+        Ok(Some(row)) => {
+            let invariant_bytes: [u8; 32] = row
+                .invariant_id
+                .try_into()
+                .map_err(|_| DataStoreError::Internal("invalid invariant_id length".to_string()))?;
+
+            Ok(Some(InvariantRecord {
+                invariant_id: InvariantID::new(invariant_bytes),
+                name: row.name,
+                asserts: row.asserts,
+                created_at: row.created_at,
+                updated_at: row.updated_at,
+            }))
+        }
+        Ok(None) => Ok(None),
+        Err(e) => {
+            eprintln!("Database error getting invariant by name: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
+/// Sets or clears the name of an existing invariant.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `invariant_id` - The invariant to name
+/// * `name` - The new name, or `None` to remove the name
+///
+/// # Returns
+/// * `Ok(true)` - Invariant existed and was renamed
+/// * `Ok(false)` - Invariant did not exist
+/// * `Err(DataStoreError::AlreadyExists)` - Another invariant already has this name
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::invariants::set_name",
+        skip_all,
+        err,
+        fields(invariant_id = %invariant_id)
+    )
+)]
+pub async fn set_name(
+    tx: &mut Transaction<'_, Postgres>,
+    invariant_id: &InvariantID,
+    name: Option<&str>,
+) -> SqlResult<bool> {
+    let invariant_bytes = invariant_id.as_bytes();
+
+    let result = sqlx::query!(
+        r#"
+        UPDATE invariants
+        SET name = $2, updated_at = CURRENT_TIMESTAMP
+        WHERE invariant_id = $1
+        "#,
+        invariant_bytes.as_slice(),
+        name
+    )
+    .execute(&mut **tx)
+    .await;
+
+    match result {
+        Ok(result) => Ok(result.rows_affected() > 0),
+        Err(sqlx::Error::Database(db_err)) if db_err.is_unique_violation() => {
+            Err(DataStoreError::AlreadyExists)
+        }
+        Err(e) => {
+            eprintln!("Database error naming invariant: {}", e);
+            Err(DataStoreError::Internal(e.to_string()))
+        }
+    }
+}
+
 /// Updates the assertion for an existing invariant.
 ///
 /// # Arguments
@@ -140,6 +265,15 @@ pub async fn get(
 /// * `Ok(true)` - Invariant existed and was updated
 /// * `Ok(false)` - Invariant did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::invariants::update",
+        skip_all,
+        err,
+        fields(invariant_id = %invariant_id, asserts = %asserts)
+    )
+)]
 pub async fn update(
     tx: &mut Transaction<'_, Postgres>,
     invariant_id: &InvariantID,
@@ -195,6 +329,15 @@ pub async fn update(
 /// # Ok(())
 /// # }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::invariants::upsert",
+        skip_all,
+        err,
+        fields(invariant_id = %invariant_id, asserts = %asserts)
+    )
+)]
 pub async fn upsert(
     tx: &mut Transaction<'_, Postgres>,
     invariant_id: &InvariantID,
@@ -222,6 +365,15 @@ pub async fn upsert(
 /// * `Ok(true)` - Invariant existed and was deleted
 /// * `Ok(false)` - Invariant did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::invariants::delete",
+        skip_all,
+        err,
+        fields(invariant_id = %invariant_id)
+    )
+)]
 pub async fn delete(
     tx: &mut Transaction<'_, Postgres>,
     invariant_id: &InvariantID,
@@ -255,10 +407,14 @@ pub async fn delete(
 /// # Returns
 /// * `Ok(Vec<InvariantRecord>)` - List of all invariants
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::invariants::list", skip_all, err)
+)]
 pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<InvariantRecord>> {
     let result = sqlx::query!(
         r#"
-        SELECT invariant_id, asserts, created_at, updated_at
+        SELECT invariant_id, name, asserts, created_at, updated_at
         FROM invariants
         ORDER BY created_at ASC
         "#
@@ -275,6 +431,7 @@ pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<Invariant
                 })?;
                 invariants.push(InvariantRecord {
                     invariant_id: InvariantID::new(invariant_bytes),
+                    name: row.name,
                     asserts: row.asserts,
                     created_at: row.created_at,
                     updated_at: row.updated_at,
@@ -501,4 +658,54 @@ mod tests {
         assert!(ids.contains(&invariant2));
         assert!(ids.contains(&invariant3));
     }
+
+    #[tokio::test]
+    async fn set_name_and_get_by_name() {
+        let pool = super::super::tests::setup_test_db().await;
+        let invariant_id = unique_invariant("set_name_and_get_by_name");
+
+        let mut tx = pool.begin().await.unwrap();
+        create(&mut tx, &invariant_id, "score >= 0").await.unwrap();
+        assert!(
+            set_name(&mut tx, &invariant_id, Some("non_negative_score"))
+                .await
+                .unwrap()
+        );
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let record = get_by_name(&mut tx, "non_negative_score")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.invariant_id, invariant_id);
+        assert_eq!(record.name.as_deref(), Some("non_negative_score"));
+        assert!(get_by_name(&mut tx, "missing").await.unwrap().is_none());
+
+        assert!(set_name(&mut tx, &invariant_id, None).await.unwrap());
+        assert!(
+            get_by_name(&mut tx, "non_negative_score")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        tx.commit().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn set_name_duplicate_fails() {
+        let pool = super::super::tests::setup_test_db().await;
+        let first = unique_invariant("set_name_duplicate_1");
+        let second = unique_invariant("set_name_duplicate_2");
+
+        let mut tx = pool.begin().await.unwrap();
+        create(&mut tx, &first, "x > 0").await.unwrap();
+        create(&mut tx, &second, "y > 0").await.unwrap();
+        set_name(&mut tx, &first, Some("positive")).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let result = set_name(&mut tx, &second, Some("positive")).await;
+        assert!(matches!(result, Err(DataStoreError::AlreadyExists)));
+    }
 }
@@ -24,6 +24,229 @@ pub mod edge;
 /// Active entity operations with automatic timestamp tracking.
 pub mod active_entity;
 
+/// Idempotency key operations for the apply endpoint.
+pub mod idempotency;
+
+use std::future::Future;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::time::Duration;
+
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use sqlx::{PgPool, Postgres, Transaction};
+
+/// Connection pool settings for the PostgreSQL database.
+///
+/// The defaults match those of `PgPool::connect`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbConfig {
+    /// PostgreSQL database URL.
+    pub database_url: String,
+    /// Maximum number of connections held by the pool.
+    pub max_connections: u32,
+    /// Minimum number of idle connections the pool keeps open.
+    pub min_connections: u32,
+    /// How long to wait for a connection before giving up.
+    pub acquire_timeout: Duration,
+    /// How long a connection may sit idle before it is closed, or `None` to keep it.
+    pub idle_timeout: Option<Duration>,
+    /// Number of prepared statements cached per connection.
+    pub statement_cache_capacity: usize,
+}
+
+impl DbConfig {
+    /// Creates a configuration for the given URL with default pool settings.
+    pub fn new(database_url: impl Into<String>) -> Self {
+        Self {
+            database_url: database_url.into(),
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout: Duration::from_secs(30),
+            idle_timeout: Some(Duration::from_secs(600)),
+            statement_cache_capacity: 100,
+        }
+    }
+}
+
+/// Connects to PostgreSQL with the pool settings in `opts`.
+///
+/// # Arguments
+/// * `opts` - Database URL and pool settings
+///
+/// # Returns
+/// * `Ok(PgPool)` - Connected pool
+/// * `Err(sqlx::Error)` - The URL is invalid or the database is unreachable
+///
+/// # Examples
+/// ```no_run
+/// # use stigmergy::sql::{self, DbConfig};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut opts = DbConfig::new("postgres://localhost/stigmergy");
+/// opts.max_connections = 50;
+/// let pool = sql::connect(&opts).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn connect(opts: &DbConfig) -> Result<PgPool, sqlx::Error> {
+    let connect_options = PgConnectOptions::from_str(&opts.database_url)?
+        .statement_cache_capacity(opts.statement_cache_capacity);
+    PgPoolOptions::new()
+        .max_connections(opts.max_connections)
+        .min_connections(opts.min_connections)
+        .acquire_timeout(opts.acquire_timeout)
+        .idle_timeout(opts.idle_timeout)
+        .connect_with(connect_options)
+        .await
+}
+
+/// Applies any pending migrations from the `migrations/` directory.
+///
+/// The migrations are embedded at compile time. Already-applied migrations are skipped,
+/// so running this against an up-to-date database is a no-op.
+///
+/// # Examples
+/// ```no_run
+/// # use stigmergy::sql;
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+/// sql::run_migrations(&pool).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::migrate::MigrateError> {
+    sqlx::migrate!("./migrations").run(pool).await
+}
+
+/// Controls which errors `with_retry` retries and how long it waits between attempts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each subsequent attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound on the delay between attempts.
+    pub max_backoff: Duration,
+    /// SQLSTATE codes that indicate a transient failure.
+    pub sqlstates: Vec<String>,
+}
+
+impl Default for RetryPolicy {
+    /// Retries serialization failures (`40001`) and deadlocks (`40P01`) up to three attempts.
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            sqlstates: vec!["40001".to_string(), "40P01".to_string()],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns true if `error` is transient under this policy.
+    ///
+    /// Database errors are transient if their SQLSTATE is listed in `sqlstates`. Dropped
+    /// connections and pool timeouts are always transient.
+    pub fn is_transient(&self, error: &sqlx::Error) -> bool {
+        match error {
+            sqlx::Error::Database(db_err) => db_err
+                .code()
+                .is_some_and(|code| self.sqlstates.iter().any(|s| *s == code)),
+            sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut => true,
+            _ => false,
+        }
+    }
+
+    /// Returns true if `error`, reported by COMMIT, is safe to retry under this policy.
+    ///
+    /// Only database errors with a listed SQLSTATE qualify: the server reported them, so
+    /// the transaction is known to have rolled back. A dropped connection during COMMIT
+    /// leaves the outcome unknown, since the commit may already have landed, so it is
+    /// never retried.
+    pub fn is_transient_at_commit(&self, error: &sqlx::Error) -> bool {
+        matches!(error, sqlx::Error::Database(_)) && self.is_transient(error)
+    }
+}
+
+/// The future each attempt of a [`with_retry`] closure returns.
+///
+/// It is boxed and `Send` so that handlers calling [`with_retry`] remain `Send` too.
+pub type RetryFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T, sqlx::Error>> + Send + 'a>>;
+
+/// Runs `f` in a transaction, retrying on a fresh transaction when it fails transiently.
+///
+/// The transaction is committed when `f` succeeds and rolled back when it fails. Failures to
+/// begin the transaction are retried under the same policy. Failures at commit are retried
+/// only when the server reports a listed SQLSTATE, since serialization failures are often
+/// reported at commit time; see [`RetryPolicy::is_transient_at_commit`]. Non-transient
+/// errors are returned immediately.
+///
+/// `f` may run more than once, so it must confine its effects to the transaction it is
+/// given. An error returned from an ambiguous commit means the writes may or may not have
+/// been applied; callers that cannot tolerate that must make `f` idempotent.
+///
+/// # Arguments
+/// * `pool` - PostgreSQL connection pool
+/// * `policy` - Which errors to retry and how long to back off
+/// * `f` - The work to run inside each transaction
+///
+/// # Returns
+/// * `Ok(T)` - The value returned by the first successful attempt
+/// * `Err(sqlx::Error)` - A non-transient error, or the last error once attempts run out
+///
+/// # Examples
+/// ```no_run
+/// # use stigmergy::sql::{self, RetryPolicy};
+/// # use sqlx::PgPool;
+/// # async fn example(pool: PgPool) -> Result<(), Box<dyn std::error::Error>> {
+/// let count: i64 = sql::with_retry(&pool, &RetryPolicy::default(), |tx| {
+///     Box::pin(async move {
+///         sqlx::query_scalar("SELECT COUNT(*) FROM entities")
+///             .fetch_one(&mut **tx)
+///             .await
+///     })
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn with_retry<T, F>(
+    pool: &PgPool,
+    policy: &RetryPolicy,
+    mut f: F,
+) -> Result<T, sqlx::Error>
+where
+    F: for<'a> FnMut(&'a mut Transaction<'static, Postgres>) -> RetryFuture<'a, T> + Send,
+    T: Send,
+{
+    let mut backoff = policy.initial_backoff;
+    let mut attempt = 1;
+    loop {
+        // Pairs each error with whether COMMIT reported it.
+        let result = async {
+            let mut tx = pool.begin().await.map_err(|e| (e, false))?;
+            let value = f(&mut tx).await.map_err(|e| (e, false))?;
+            tx.commit().await.map_err(|e| (e, true))?;
+            Ok::<T, (sqlx::Error, bool)>(value)
+        }
+        .await;
+
+        let retry = match &result {
+            Ok(_) => false,
+            Err((e, false)) => policy.is_transient(e),
+            Err((e, true)) => policy.is_transient_at_commit(e),
+        };
+        match result {
+            Err(_) if retry && attempt < policy.max_attempts => {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(policy.max_backoff);
+                attempt += 1;
+            }
+            result => return result.map_err(|(e, _)| e),
+        }
+    }
+}
+
 #[cfg(test)]
 /// Test utilities for PostgreSQL database operations.
 pub mod tests {
@@ -76,11 +299,122 @@ pub mod tests {
             .await
             .expect("Failed to connect to test database");
 
-        sqlx::migrate!("./migrations")
-            .run(&pool)
+        super::run_migrations(&pool)
             .await
             .expect("Failed to run migrations");
 
         pool
     }
+
+    #[tokio::test]
+    async fn connect_honors_pool_settings() {
+        let base_url = std::env::var("TEST_DATABASE_URL")
+            .unwrap_or_else(|_| "postgres://localhost/stigmergy_test".to_string());
+
+        let mut opts = super::DbConfig::new(base_url);
+        opts.max_connections = 3;
+        opts.min_connections = 1;
+        let pool = super::connect(&opts).await.unwrap();
+
+        assert_eq!(pool.options().get_max_connections(), 3);
+        assert_eq!(pool.options().get_min_connections(), 1);
+        assert!(pool.size() <= 3);
+        pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn run_migrations_is_idempotent() {
+        let pool = setup_test_db().await;
+        let applied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+
+        super::run_migrations(&pool).await.unwrap();
+
+        let reapplied: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM _sqlx_migrations")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        println!("run_migrations_is_idempotent response: {}", reapplied);
+        assert!(applied > 0);
+        assert_eq!(applied, reapplied);
+    }
+
+    /// Raises an error with the given SQLSTATE inside the transaction.
+    async fn raise_sqlstate(
+        tx: &mut sqlx::Transaction<'static, sqlx::Postgres>,
+        sqlstate: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(&format!(
+            "DO $$ BEGIN RAISE EXCEPTION 'simulated failure' USING ERRCODE = '{}'; END $$",
+            sqlstate
+        ))
+        .execute(&mut **tx)
+        .await
+        .map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn with_retry_retries_serialization_failure() {
+        let pool = setup_test_db().await;
+        let mut attempts = 0;
+
+        let result = super::with_retry(&pool, &super::RetryPolicy::default(), |tx| {
+            attempts += 1;
+            let first = attempts == 1;
+            Box::pin(async move {
+                if first {
+                    raise_sqlstate(tx, "40001").await?;
+                }
+                Ok(42)
+            })
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn with_retry_does_not_retry_constraint_violation() {
+        let pool = setup_test_db().await;
+        let mut attempts = 0;
+
+        let result = super::with_retry(&pool, &super::RetryPolicy::default(), |tx| {
+            attempts += 1;
+            Box::pin(raise_sqlstate(tx, "23505"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 1);
+    }
+
+    #[test]
+    fn dropped_connection_at_commit_is_not_retried() {
+        let policy = super::RetryPolicy::default();
+        let dropped = sqlx::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "connection reset",
+        ));
+        assert!(policy.is_transient(&dropped));
+        assert!(!policy.is_transient_at_commit(&dropped));
+        assert!(!policy.is_transient_at_commit(&sqlx::Error::PoolTimedOut));
+    }
+
+    #[tokio::test]
+    async fn with_retry_gives_up_after_max_attempts() {
+        let pool = setup_test_db().await;
+        let mut attempts = 0;
+
+        let result = super::with_retry(&pool, &super::RetryPolicy::default(), |tx| {
+            attempts += 1;
+            Box::pin(raise_sqlstate(tx, "40P01"))
+        })
+        .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts, 3);
+    }
 }
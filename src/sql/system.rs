@@ -45,6 +45,7 @@ pub struct SystemRecord {
 ///     description: "Test system".to_string(),
 ///     model: "inherit".to_string(),
 ///     color: "blue".to_string(),
+///     extends: Vec::new(),
 ///     component: Vec::new(),
 ///     bid: Vec::new(),
 ///     content: "You are a test system.".to_string(),
@@ -56,6 +57,15 @@ pub struct SystemRecord {
 /// # Ok(())
 /// # }
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::system::create",
+        skip_all,
+        err,
+        fields(system = %system.name())
+    )
+)]
 pub async fn create(tx: &mut Transaction<'_, Postgres>, system: &System) -> SqlResult<()> {
     let system_name = system.name().as_str();
     let description = &system.config.description;
@@ -101,6 +111,10 @@ pub async fn create(tx: &mut Transaction<'_, Postgres>, system: &System) -> SqlR
 /// * `Ok(Some(System))` - System found
 /// * `Ok(None)` - System not found
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::system::get", skip_all, err, fields(name = %name))
+)]
 pub async fn get(
     tx: &mut Transaction<'_, Postgres>,
     name: &SystemName,
@@ -136,6 +150,7 @@ pub async fn get(
                 description: row.description.unwrap_or_default(),
                 model: row.model,
                 color: row.color.unwrap_or_default(),
+                extends: Vec::new(),
                 component: Vec::new(),
                 bid: bids,
                 content: row.content.unwrap_or_default(),
@@ -165,6 +180,15 @@ pub async fn get(
 /// * `Ok(true)` - System existed and was updated
 /// * `Ok(false)` - System did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        name = "sql::system::update",
+        skip_all,
+        err,
+        fields(system = %system.name())
+    )
+)]
 pub async fn update(tx: &mut Transaction<'_, Postgres>, system: &System) -> SqlResult<bool> {
     let system_name = system.name().as_str();
     let description = &system.config.description;
@@ -208,6 +232,10 @@ pub async fn update(tx: &mut Transaction<'_, Postgres>, system: &System) -> SqlR
 /// * `Ok(true)` - System existed and was deleted
 /// * `Ok(false)` - System did not exist
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::system::delete", skip_all, err, fields(name = %name))
+)]
 pub async fn delete(tx: &mut Transaction<'_, Postgres>, name: &SystemName) -> SqlResult<bool> {
     let system_name = name.as_str();
 
@@ -238,6 +266,10 @@ pub async fn delete(tx: &mut Transaction<'_, Postgres>, name: &SystemName) -> Sq
 /// # Returns
 /// * `Ok(count)` - Number of systems deleted
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::system::delete_all", skip_all, err)
+)]
 pub async fn delete_all(tx: &mut Transaction<'_, Postgres>) -> SqlResult<u32> {
     let result = sqlx::query!(
         r#"
@@ -264,13 +296,50 @@ pub async fn delete_all(tx: &mut Transaction<'_, Postgres>) -> SqlResult<u32> {
 /// # Returns
 /// * `Ok(Vec<System>)` - List of all systems
 /// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::system::list", skip_all, err)
+)]
 pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<System>> {
+    list_filtered(tx, None, None, None).await
+}
+
+/// Lists the systems matching every given filter, oldest first.
+///
+/// # Arguments
+/// * `tx` - PostgreSQL transaction
+/// * `color` - If present, only systems with exactly this color
+/// * `model` - If present, only systems with exactly this model
+/// * `text` - If present, only systems whose description or content contains this text,
+///   ignoring case
+///
+/// # Returns
+/// * `Ok(Vec<System>)` - Matching systems
+/// * `Err(DataStoreError::Internal)` - Database error
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(name = "sql::system::list_filtered", skip_all, err)
+)]
+pub async fn list_filtered(
+    tx: &mut Transaction<'_, Postgres>,
+    color: Option<&str>,
+    model: Option<&str>,
+    text: Option<&str>,
+) -> SqlResult<Vec<System>> {
     let result = sqlx::query!(
         r#"
         SELECT system_name, description, model, color, content, bids, created_at, updated_at
         FROM systems
+        WHERE ($1::TEXT IS NULL OR color = $1)
+          AND ($2::TEXT IS NULL OR model = $2)
+          AND ($3::TEXT IS NULL
+               OR strpos(lower(COALESCE(description, '')), lower($3)) > 0
+               OR strpos(lower(COALESCE(content, '')), lower($3)) > 0)
         ORDER BY created_at ASC
-        "#
+        "#,
+        color,
+        model,
+        text
     )
     .fetch_all(&mut **tx)
     .await;
@@ -296,6 +365,7 @@ pub async fn list(tx: &mut Transaction<'_, Postgres>) -> SqlResult<Vec<System>>
                     description: row.description.unwrap_or_default(),
                     model: row.model,
                     color: row.color.unwrap_or_default(),
+                    extends: Vec::new(),
                     component: Vec::new(),
                     bid: bids,
                     content: row.content.unwrap_or_default(),
@@ -328,6 +398,7 @@ mod tests {
             description: format!("Test system for {}", test_name),
             model: "inherit".to_string(),
             color: "blue".to_string(),
+            extends: Vec::new(),
             component: Vec::new(),
             bid: Vec::new(),
             content: "You are a test system.".to_string(),
@@ -483,6 +554,39 @@ mod tests {
         assert!(systems.is_empty());
     }
 
+    #[tokio::test]
+    async fn list_filtered_by_color_and_text() {
+        let pool = super::super::tests::setup_test_db().await;
+        let base_id = std::process::id() as u64;
+        let mut matching = unique_system("list_filtered", base_id);
+        matching.config.description = "Applies a Discount to carts".to_string();
+        let mut other_color = unique_system("list_filtered", base_id + 1);
+        other_color.config.color = "green".to_string();
+        other_color.config.content = "Offer a discount.".to_string();
+        let unrelated = unique_system("list_filtered", base_id + 2);
+
+        let mut tx = pool.begin().await.unwrap();
+        for system in [&matching, &other_color, &unrelated] {
+            create(&mut tx, system).await.unwrap();
+        }
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let systems = list_filtered(&mut tx, Some("blue"), None, Some("discount"))
+            .await
+            .unwrap();
+        tx.commit().await.unwrap();
+        let system_names: Vec<_> = systems.iter().map(|s| s.name()).collect();
+        println!(
+            "list_filtered_by_color_and_text response: {:?}",
+            system_names
+        );
+        assert!(system_names.contains(&matching.name()));
+        assert!(!system_names.contains(&other_color.name()));
+        assert!(!system_names.contains(&unrelated.name()));
+        assert!(systems.iter().all(|s| s.config.color == "blue"));
+    }
+
     #[tokio::test]
     async fn list_multiple() {
         let pool = super::super::tests::setup_test_db().await;
@@ -3,7 +3,7 @@ use std::str::FromStr;
 use axum::Router;
 use axum::async_trait;
 use axum::body::Bytes;
-use axum::extract::{FromRequest, Path, Request, State};
+use axum::extract::{FromRequest, Path, Query, Request, State};
 use axum::http::StatusCode;
 use axum::response::Json;
 use axum::routing::{get, post};
@@ -50,6 +50,11 @@ impl std::error::Error for SystemNameParseError {}
 /// System names must be valid Rust identifiers with optional module paths (like Component).
 /// This ensures system names are URL-safe and follow consistent naming conventions.
 ///
+/// Each `::`-separated segment starts with a letter, underscore or hyphen and continues with
+/// letters, digits, underscores or hyphens. Names are case-sensitive: [`SystemName::new`]
+/// accepts `Reviewer` and `reviewer` as distinct names. Use [`SystemName::normalize`] to turn
+/// free-form text into the canonical lowercase-kebab form instead.
+///
 /// # Examples
 ///
 /// ```rust
@@ -84,6 +89,48 @@ impl SystemName {
         }
     }
 
+    /// Normalizes free-form text into a lowercase-kebab system name.
+    ///
+    /// Letters are lowercased, a hyphen is inserted where a lowercase letter or digit is
+    /// followed by an uppercase letter, and every run of other characters (spaces,
+    /// underscores, punctuation) collapses into a single hyphen. Leading and trailing
+    /// hyphens are dropped.
+    ///
+    /// # Returns
+    /// * `Some(SystemName)` - If the normalized name is valid
+    /// * `None` - If nothing usable remains or the result starts with a digit
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stigmergy::SystemName;
+    ///
+    /// assert_eq!(SystemName::normalize("My System").unwrap().as_str(), "my-system");
+    /// assert_eq!(SystemName::normalize("codeReviewer_v2").unwrap().as_str(), "code-reviewer-v2");
+    /// assert!(SystemName::normalize("  ").is_none());
+    /// ```
+    pub fn normalize(text: &str) -> Option<SystemName> {
+        let mut normalized = String::with_capacity(text.len());
+        let mut previous: Option<char> = None;
+        for c in text.chars() {
+            if c.is_alphanumeric() {
+                let boundary = c.is_uppercase()
+                    && previous.is_some_and(|p| p.is_lowercase() || p.is_numeric());
+                if boundary && !normalized.is_empty() && !normalized.ends_with('-') {
+                    normalized.push('-');
+                }
+                normalized.extend(c.to_lowercase());
+            } else if !normalized.is_empty() && !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+            previous = Some(c);
+        }
+        while normalized.ends_with('-') {
+            normalized.pop();
+        }
+        SystemName::new(normalized)
+    }
+
     /// Returns the system name as a string slice.
     pub fn as_str(&self) -> &str {
         &self.0
@@ -326,9 +373,21 @@ async fn create_system_from_markdown(
     }
 }
 
-/// Lists all systems.
+/// Query parameters for filtering the system list.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ListSystemsQuery {
+    /// Only systems with exactly this color
+    pub color: Option<String>,
+    /// Only systems with exactly this model
+    pub model: Option<String>,
+    /// Only systems whose description or content contains this text, ignoring case
+    pub q: Option<String>,
+}
+
+/// Lists all systems, filtered by the query parameters.
 async fn list_systems(
     State(pool): State<sqlx::PgPool>,
+    Query(params): Query<ListSystemsQuery>,
 ) -> Result<Json<Vec<SystemListItem>>, (StatusCode, &'static str)> {
     let mut tx = pool.begin().await.map_err(|_e| {
         (
@@ -337,7 +396,14 @@ async fn list_systems(
         )
     })?;
 
-    match crate::sql::system::list(&mut tx).await {
+    match crate::sql::system::list_filtered(
+        &mut tx,
+        params.color.as_deref(),
+        params.model.as_deref(),
+        params.q.as_deref(),
+    )
+    .await
+    {
         Ok(systems) => {
             tx.commit().await.map_err(|_e| {
                 (
@@ -626,12 +692,62 @@ mod tests {
             description: "A test system".to_string(),
             model: "inherit".to_string(),
             color: "blue".to_string(),
+            extends: Vec::new(),
             component: Vec::new(),
             bid: Vec::new(),
             content: "You are a test system.".to_string(),
         }
     }
 
+    #[test]
+    fn system_name_normalize() {
+        let cases = [
+            ("My System", Some("my-system")),
+            ("  Code   Reviewer  ", Some("code-reviewer")),
+            ("dry_principal", Some("dry-principal")),
+            ("HTTPServer", Some("httpserver")),
+            ("codeReviewer2Go", Some("code-reviewer2-go")),
+            ("already-kebab", Some("already-kebab")),
+            ("  ", None),
+            ("--", None),
+            ("2 fast", None),
+        ];
+        for (input, expected) in cases {
+            let normalized = SystemName::normalize(input);
+            println!(
+                "system_name_normalize response: {:?} -> {:?}",
+                input, normalized
+            );
+            assert_eq!(normalized.as_ref().map(SystemName::as_str), expected);
+        }
+        assert_eq!(SystemName::new("My System"), None);
+    }
+
+    #[test]
+    fn system_name_schema_matches_new() {
+        let schema = <SystemName as crate::JsonSchema>::json_schema();
+        let names = [
+            "test-system",
+            "crate::system",
+            "_private",
+            "café-système",
+            "日本::システム2",
+            "x٣",
+            "2fast",
+            "has space",
+            "trailing::",
+            "",
+        ];
+        for name in names {
+            let accepted = crate::validate_value(&serde_json::json!(name), &schema).is_ok();
+            println!(
+                "system_name_schema_matches_new response: {:?} -> {}",
+                name, accepted
+            );
+            assert_eq!(accepted, SystemName::new(name).is_some(), "{:?}", name);
+        }
+    }
+
     #[test]
     fn system_new() {
         let config = test_system_config();
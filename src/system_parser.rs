@@ -69,6 +69,45 @@ pub enum AccessMode {
     ReadWrite,
 }
 
+impl AccessMode {
+    /// Returns true if this access mode permits reading the component.
+    pub fn can_read(&self) -> bool {
+        matches!(self, AccessMode::Read | AccessMode::ReadWrite)
+    }
+
+    /// Returns true if this access mode permits writing the component.
+    pub fn can_write(&self) -> bool {
+        matches!(self, AccessMode::Write | AccessMode::ReadWrite)
+    }
+
+    /// Returns true if this access mode permits executing the component as a tool.
+    ///
+    /// Execute is orthogonal to read and write: it neither implies nor is implied by them.
+    pub fn can_execute(&self) -> bool {
+        matches!(self, AccessMode::Execute)
+    }
+
+    /// Returns true if a grant of this access mode satisfies a request for `other`.
+    ///
+    /// A mode covers another when every capability of `other` is also a capability of `self`.
+    /// Every mode covers itself, `ReadWrite` covers `Read` and `Write`, and no mode other than
+    /// `Execute` covers `Execute`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use stigmergy::AccessMode;
+    ///
+    /// assert!(AccessMode::ReadWrite.covers(&AccessMode::Read));
+    /// assert!(!AccessMode::Read.covers(&AccessMode::Write));
+    /// ```
+    pub fn covers(&self, other: &AccessMode) -> bool {
+        (!other.can_read() || self.can_read())
+            && (!other.can_write() || self.can_write())
+            && (!other.can_execute() || self.can_execute())
+    }
+}
+
 impl fmt::Display for AccessMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -278,6 +317,7 @@ mod component_serde {
 /// - `description`: Human-readable description (required)
 /// - `model`: Model specification (required)
 /// - `color`: UI color identifier (required)
+/// - `extends`: Base systems whose components and bids are inherited (optional)
 /// - `component`: List of component access specifications (optional)
 /// - `bid`: List of bid expressions (optional, parsed from bullet list format)
 /// - `content`: Markdown content after frontmatter
@@ -291,6 +331,11 @@ pub struct SystemConfig {
     pub model: String,
     /// The color theme for the system (required field)
     pub color: String,
+    /// Base systems this system inherits components and bids from (optional field)
+    ///
+    /// Inheritance is applied by [`SystemConfig::resolve`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extends: Vec<SystemName>,
     /// List of component access specifications (optional field)
     #[serde(with = "component_serde")]
     pub component: Vec<ComponentAccess>,
@@ -347,6 +392,181 @@ impl std::fmt::Display for ParseError {
 
 impl std::error::Error for ParseError {}
 
+/// The CSS named colors accepted as a system color, sorted by name, with their RGB values.
+const NAMED_COLORS: &[(&str, [u8; 3])] = &[
+    ("aliceblue", [0xf0, 0xf8, 0xff]),
+    ("antiquewhite", [0xfa, 0xeb, 0xd7]),
+    ("aqua", [0x00, 0xff, 0xff]),
+    ("aquamarine", [0x7f, 0xff, 0xd4]),
+    ("azure", [0xf0, 0xff, 0xff]),
+    ("beige", [0xf5, 0xf5, 0xdc]),
+    ("bisque", [0xff, 0xe4, 0xc4]),
+    ("black", [0x00, 0x00, 0x00]),
+    ("blanchedalmond", [0xff, 0xeb, 0xcd]),
+    ("blue", [0x00, 0x00, 0xff]),
+    ("blueviolet", [0x8a, 0x2b, 0xe2]),
+    ("brown", [0xa5, 0x2a, 0x2a]),
+    ("burlywood", [0xde, 0xb8, 0x87]),
+    ("cadetblue", [0x5f, 0x9e, 0xa0]),
+    ("chartreuse", [0x7f, 0xff, 0x00]),
+    ("chocolate", [0xd2, 0x69, 0x1e]),
+    ("coral", [0xff, 0x7f, 0x50]),
+    ("cornflowerblue", [0x64, 0x95, 0xed]),
+    ("cornsilk", [0xff, 0xf8, 0xdc]),
+    ("crimson", [0xdc, 0x14, 0x3c]),
+    ("cyan", [0x00, 0xff, 0xff]),
+    ("darkblue", [0x00, 0x00, 0x8b]),
+    ("darkcyan", [0x00, 0x8b, 0x8b]),
+    ("darkgoldenrod", [0xb8, 0x86, 0x0b]),
+    ("darkgray", [0xa9, 0xa9, 0xa9]),
+    ("darkgreen", [0x00, 0x64, 0x00]),
+    ("darkgrey", [0xa9, 0xa9, 0xa9]),
+    ("darkkhaki", [0xbd, 0xb7, 0x6b]),
+    ("darkmagenta", [0x8b, 0x00, 0x8b]),
+    ("darkolivegreen", [0x55, 0x6b, 0x2f]),
+    ("darkorange", [0xff, 0x8c, 0x00]),
+    ("darkorchid", [0x99, 0x32, 0xcc]),
+    ("darkred", [0x8b, 0x00, 0x00]),
+    ("darksalmon", [0xe9, 0x96, 0x7a]),
+    ("darkseagreen", [0x8f, 0xbc, 0x8f]),
+    ("darkslateblue", [0x48, 0x3d, 0x8b]),
+    ("darkslategray", [0x2f, 0x4f, 0x4f]),
+    ("darkslategrey", [0x2f, 0x4f, 0x4f]),
+    ("darkturquoise", [0x00, 0xce, 0xd1]),
+    ("darkviolet", [0x94, 0x00, 0xd3]),
+    ("deeppink", [0xff, 0x14, 0x93]),
+    ("deepskyblue", [0x00, 0xbf, 0xff]),
+    ("dimgray", [0x69, 0x69, 0x69]),
+    ("dimgrey", [0x69, 0x69, 0x69]),
+    ("dodgerblue", [0x1e, 0x90, 0xff]),
+    ("firebrick", [0xb2, 0x22, 0x22]),
+    ("floralwhite", [0xff, 0xfa, 0xf0]),
+    ("forestgreen", [0x22, 0x8b, 0x22]),
+    ("fuchsia", [0xff, 0x00, 0xff]),
+    ("gainsboro", [0xdc, 0xdc, 0xdc]),
+    ("ghostwhite", [0xf8, 0xf8, 0xff]),
+    ("gold", [0xff, 0xd7, 0x00]),
+    ("goldenrod", [0xda, 0xa5, 0x20]),
+    ("gray", [0x80, 0x80, 0x80]),
+    ("green", [0x00, 0x80, 0x00]),
+    ("greenyellow", [0xad, 0xff, 0x2f]),
+    ("grey", [0x80, 0x80, 0x80]),
+    ("honeydew", [0xf0, 0xff, 0xf0]),
+    ("hotpink", [0xff, 0x69, 0xb4]),
+    ("indianred", [0xcd, 0x5c, 0x5c]),
+    ("indigo", [0x4b, 0x00, 0x82]),
+    ("ivory", [0xff, 0xff, 0xf0]),
+    ("khaki", [0xf0, 0xe6, 0x8c]),
+    ("lavender", [0xe6, 0xe6, 0xfa]),
+    ("lavenderblush", [0xff, 0xf0, 0xf5]),
+    ("lawngreen", [0x7c, 0xfc, 0x00]),
+    ("lemonchiffon", [0xff, 0xfa, 0xcd]),
+    ("lightblue", [0xad, 0xd8, 0xe6]),
+    ("lightcoral", [0xf0, 0x80, 0x80]),
+    ("lightcyan", [0xe0, 0xff, 0xff]),
+    ("lightgoldenrodyellow", [0xfa, 0xfa, 0xd2]),
+    ("lightgray", [0xd3, 0xd3, 0xd3]),
+    ("lightgreen", [0x90, 0xee, 0x90]),
+    ("lightgrey", [0xd3, 0xd3, 0xd3]),
+    ("lightpink", [0xff, 0xb6, 0xc1]),
+    ("lightsalmon", [0xff, 0xa0, 0x7a]),
+    ("lightseagreen", [0x20, 0xb2, 0xaa]),
+    ("lightskyblue", [0x87, 0xce, 0xfa]),
+    ("lightslategray", [0x77, 0x88, 0x99]),
+    ("lightslategrey", [0x77, 0x88, 0x99]),
+    ("lightsteelblue", [0xb0, 0xc4, 0xde]),
+    ("lightyellow", [0xff, 0xff, 0xe0]),
+    ("lime", [0x00, 0xff, 0x00]),
+    ("limegreen", [0x32, 0xcd, 0x32]),
+    ("linen", [0xfa, 0xf0, 0xe6]),
+    ("magenta", [0xff, 0x00, 0xff]),
+    ("maroon", [0x80, 0x00, 0x00]),
+    ("mediumaquamarine", [0x66, 0xcd, 0xaa]),
+    ("mediumblue", [0x00, 0x00, 0xcd]),
+    ("mediumorchid", [0xba, 0x55, 0xd3]),
+    ("mediumpurple", [0x93, 0x70, 0xdb]),
+    ("mediumseagreen", [0x3c, 0xb3, 0x71]),
+    ("mediumslateblue", [0x7b, 0x68, 0xee]),
+    ("mediumspringgreen", [0x00, 0xfa, 0x9a]),
+    ("mediumturquoise", [0x48, 0xd1, 0xcc]),
+    ("mediumvioletred", [0xc7, 0x15, 0x85]),
+    ("midnightblue", [0x19, 0x19, 0x70]),
+    ("mintcream", [0xf5, 0xff, 0xfa]),
+    ("mistyrose", [0xff, 0xe4, 0xe1]),
+    ("moccasin", [0xff, 0xe4, 0xb5]),
+    ("navajowhite", [0xff, 0xde, 0xad]),
+    ("navy", [0x00, 0x00, 0x80]),
+    ("oldlace", [0xfd, 0xf5, 0xe6]),
+    ("olive", [0x80, 0x80, 0x00]),
+    ("olivedrab", [0x6b, 0x8e, 0x23]),
+    ("orange", [0xff, 0xa5, 0x00]),
+    ("orangered", [0xff, 0x45, 0x00]),
+    ("orchid", [0xda, 0x70, 0xd6]),
+    ("palegoldenrod", [0xee, 0xe8, 0xaa]),
+    ("palegreen", [0x98, 0xfb, 0x98]),
+    ("paleturquoise", [0xaf, 0xee, 0xee]),
+    ("palevioletred", [0xdb, 0x70, 0x93]),
+    ("papayawhip", [0xff, 0xef, 0xd5]),
+    ("peachpuff", [0xff, 0xda, 0xb9]),
+    ("peru", [0xcd, 0x85, 0x3f]),
+    ("pink", [0xff, 0xc0, 0xcb]),
+    ("plum", [0xdd, 0xa0, 0xdd]),
+    ("powderblue", [0xb0, 0xe0, 0xe6]),
+    ("purple", [0x80, 0x00, 0x80]),
+    ("rebeccapurple", [0x66, 0x33, 0x99]),
+    ("red", [0xff, 0x00, 0x00]),
+    ("rosybrown", [0xbc, 0x8f, 0x8f]),
+    ("royalblue", [0x41, 0x69, 0xe1]),
+    ("saddlebrown", [0x8b, 0x45, 0x13]),
+    ("salmon", [0xfa, 0x80, 0x72]),
+    ("sandybrown", [0xf4, 0xa4, 0x60]),
+    ("seagreen", [0x2e, 0x8b, 0x57]),
+    ("seashell", [0xff, 0xf5, 0xee]),
+    ("sienna", [0xa0, 0x52, 0x2d]),
+    ("silver", [0xc0, 0xc0, 0xc0]),
+    ("skyblue", [0x87, 0xce, 0xeb]),
+    ("slateblue", [0x6a, 0x5a, 0xcd]),
+    ("slategray", [0x70, 0x80, 0x90]),
+    ("slategrey", [0x70, 0x80, 0x90]),
+    ("snow", [0xff, 0xfa, 0xfa]),
+    ("springgreen", [0x00, 0xff, 0x7f]),
+    ("steelblue", [0x46, 0x82, 0xb4]),
+    ("tan", [0xd2, 0xb4, 0x8c]),
+    ("teal", [0x00, 0x80, 0x80]),
+    ("thistle", [0xd8, 0xbf, 0xd8]),
+    ("tomato", [0xff, 0x63, 0x47]),
+    ("turquoise", [0x40, 0xe0, 0xd0]),
+    ("violet", [0xee, 0x82, 0xee]),
+    ("wheat", [0xf5, 0xde, 0xb3]),
+    ("white", [0xff, 0xff, 0xff]),
+    ("whitesmoke", [0xf5, 0xf5, 0xf5]),
+    ("yellow", [0xff, 0xff, 0x00]),
+    ("yellowgreen", [0x9a, 0xcd, 0x32]),
+];
+
+/// Size and count ceilings enforced by [`SystemConfig::validate_with_limits`].
+///
+/// The default limits are the ones [`SystemConfig::validate`] enforces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValidationLimits {
+    /// Maximum size of the system content, in bytes
+    pub max_content_bytes: usize,
+    /// Maximum number of component access expressions
+    pub max_components: usize,
+    /// Maximum number of bid expressions
+    pub max_bids: usize,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        ValidationLimits {
+            max_content_bytes: 10 * 1024,
+            max_components: 100,
+            max_bids: 100,
+        }
+    }
+}
+
 impl SystemConfig {
     /// Validates the system configuration against business rules and constraints.
     ///
@@ -360,12 +580,39 @@ impl SystemConfig {
     /// # Validation Rules
     /// - Name: 1-100 characters, non-empty
     /// - Description: 1-500 characters, non-empty
-    /// - Color: Basic color name or hex format (#RRGGBB)
+    /// - Color: CSS named color or hex format (#RRGGBB or #RRGGBBAA)
     /// - Model: Non-empty string
     /// - Content: Maximum 10KB
     /// - Component: Maximum 100 component access expressions
     /// - Bid: Maximum 100 bid expressions
     pub fn validate(&self) -> Result<(), ParseError> {
+        self.validate_with_limits(&ValidationLimits::default())
+    }
+
+    /// Validates the system configuration like [`SystemConfig::validate`], but with the
+    /// content size and component and bid counts bounded by `limits`.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::{SystemConfig, SystemName, ValidationLimits};
+    /// let config = SystemConfig {
+    ///     name: SystemName::new("writer").unwrap(),
+    ///     description: "Writes long prompts".to_string(),
+    ///     model: "inherit".to_string(),
+    ///     color: "blue".to_string(),
+    ///     extends: Vec::new(),
+    ///     component: Vec::new(),
+    ///     bid: Vec::new(),
+    ///     content: "x".repeat(20 * 1024),
+    /// };
+    /// assert!(config.validate().is_err());
+    /// let limits = ValidationLimits {
+    ///     max_content_bytes: 50 * 1024,
+    ///     ..ValidationLimits::default()
+    /// };
+    /// assert!(config.validate_with_limits(&limits).is_ok());
+    /// ```
+    pub fn validate_with_limits(&self, limits: &ValidationLimits) -> Result<(), ParseError> {
         // Validate name length (1-100 characters)
         if self.name.as_str().is_empty() {
             return Err(ParseError::ValidationError(
@@ -390,23 +637,18 @@ impl SystemConfig {
             ));
         }
 
-        // Validate color (basic validation for common CSS colors)
-        let valid_colors = [
-            "red", "blue", "green", "yellow", "orange", "purple", "pink", "gray", "black", "white",
-        ];
-        if !valid_colors.contains(&self.color.as_str()) && !self.color.starts_with('#') {
+        // Validate color (a CSS named color or hex code)
+        if !self.color.starts_with('#') && self.color_rgba().is_none() {
             return Err(ParseError::ValidationError(format!(
-                "Invalid color: {}. Use a basic color name or hex code",
+                "Invalid color: {}. Use a CSS color name or hex code",
                 self.color
             )));
         }
 
         // Validate hex color format if it starts with #
-        if self.color.starts_with('#')
-            && (self.color.len() != 7 || !self.color[1..].chars().all(|c| c.is_ascii_hexdigit()))
-        {
+        if self.color.starts_with('#') && self.color_rgba().is_none() {
             return Err(ParseError::ValidationError(
-                "Hex color must be in format #RRGGBB".to_string(),
+                "Hex color must be in format #RRGGBB or #RRGGBBAA".to_string(),
             ));
         }
 
@@ -417,31 +659,159 @@ impl SystemConfig {
             ));
         }
 
-        // Validate content size (max 10KB)
-        if self.content.len() > 10 * 1024 {
-            return Err(ParseError::ValidationError(
-                "Content cannot exceed 10KB".to_string(),
-            ));
+        // Validate content size
+        if self.content.len() > limits.max_content_bytes {
+            let limit = if limits.max_content_bytes.is_multiple_of(1024) {
+                format!("{}KB", limits.max_content_bytes / 1024)
+            } else {
+                format!("{} bytes", limits.max_content_bytes)
+            };
+            return Err(ParseError::ValidationError(format!(
+                "Content cannot exceed {}",
+                limit
+            )));
         }
 
         // Validate bid expressions (reasonable limit on count)
-        if self.bid.len() > 100 {
-            return Err(ParseError::ValidationError(
-                "Cannot have more than 100 bid expressions".to_string(),
-            ));
+        if self.bid.len() > limits.max_bids {
+            return Err(ParseError::ValidationError(format!(
+                "Cannot have more than {} bid expressions",
+                limits.max_bids
+            )));
         }
 
         // Validate component access expressions (reasonable limit on count)
-        if self.component.len() > 100 {
-            return Err(ParseError::ValidationError(
-                "Cannot have more than 100 component access expressions".to_string(),
-            ));
+        if self.component.len() > limits.max_components {
+            return Err(ParseError::ValidationError(format!(
+                "Cannot have more than {} component access expressions",
+                limits.max_components
+            )));
         }
 
         Ok(())
     }
 }
 
+impl SystemConfig {
+    /// Returns the system color as red, green, blue and alpha channels.
+    ///
+    /// Named colors and `#RRGGBB` are fully opaque; `#RRGGBBAA` carries its own alpha.
+    /// Returns `None` if the color is neither a CSS named color nor a valid hex code.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use stigmergy::{SystemConfig, SystemName};
+    /// let mut config = SystemConfig {
+    ///     name: SystemName::new("painter").unwrap(),
+    ///     description: "Paints".to_string(),
+    ///     model: "inherit".to_string(),
+    ///     color: "#11223344".to_string(),
+    ///     extends: Vec::new(),
+    ///     component: Vec::new(),
+    ///     bid: Vec::new(),
+    ///     content: String::new(),
+    /// };
+    /// assert_eq!(config.color_rgba(), Some([0x11, 0x22, 0x33, 0x44]));
+    /// config.color = "teal".to_string();
+    /// assert_eq!(config.color_rgba(), Some([0x00, 0x80, 0x80, 0xff]));
+    /// ```
+    pub fn color_rgba(&self) -> Option<[u8; 4]> {
+        let Some(hex) = self.color.strip_prefix('#') else {
+            let index = NAMED_COLORS
+                .binary_search_by(|(name, _)| name.cmp(&self.color.as_str()))
+                .ok()?;
+            let [r, g, b] = NAMED_COLORS[index].1;
+            return Some([r, g, b, 0xff]);
+        };
+        if (hex.len() != 6 && hex.len() != 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        let alpha = if hex.len() == 8 { channel(6)? } else { 0xff };
+        Some([channel(0)?, channel(2)?, channel(4)?, alpha])
+    }
+
+    /// Returns the model to run this system with.
+    ///
+    /// A model of `"inherit"` defers to `default`; any other value is used as-is.
+    pub fn resolved_model(&self, default: &str) -> String {
+        if self.model == "inherit" {
+            default.to_string()
+        } else {
+            self.model.clone()
+        }
+    }
+
+    /// Applies `extends` inheritance, returning a configuration with no remaining bases.
+    ///
+    /// Bases are resolved recursively through `lookup` and merged in the order they are
+    /// listed, followed by this system itself:
+    /// - Components are deduplicated by component name; a later entry replaces the access
+    ///   mode of an earlier one, so the child's access wins over its bases'.
+    /// - Bids are concatenated, inherited bids first.
+    ///
+    /// All other fields come from this system.
+    ///
+    /// # Arguments
+    /// * `lookup` - Finds the configuration of a base system by name
+    ///
+    /// # Returns
+    /// * `Ok(SystemConfig)` - The merged configuration, with `extends` empty
+    /// * `Err(ParseError::ValidationError)` - A base is unknown or inheritance is cyclic
+    pub fn resolve(
+        &self,
+        lookup: impl Fn(&SystemName) -> Option<SystemConfig>,
+    ) -> Result<SystemConfig, ParseError> {
+        self.resolve_with_chain(&lookup, &mut vec![self.name.clone()])
+    }
+
+    fn resolve_with_chain(
+        &self,
+        lookup: &impl Fn(&SystemName) -> Option<SystemConfig>,
+        chain: &mut Vec<SystemName>,
+    ) -> Result<SystemConfig, ParseError> {
+        let mut component: Vec<ComponentAccess> = Vec::new();
+        let mut bid = Vec::new();
+        let mut merge = |access: &[ComponentAccess], bids: &[Bid]| {
+            for entry in access {
+                match component
+                    .iter_mut()
+                    .find(|c| c.component == entry.component)
+                {
+                    Some(existing) => existing.access = entry.access,
+                    None => component.push(entry.clone()),
+                }
+            }
+            bid.extend_from_slice(bids);
+        };
+
+        for base_name in &self.extends {
+            if chain.contains(base_name) {
+                return Err(ParseError::ValidationError(format!(
+                    "Cyclic system inheritance: {} extends {}",
+                    chain.last().unwrap_or(&self.name),
+                    base_name
+                )));
+            }
+            let base = lookup(base_name).ok_or_else(|| {
+                ParseError::ValidationError(format!("Unknown base system: {}", base_name))
+            })?;
+            chain.push(base_name.clone());
+            let base = base.resolve_with_chain(lookup, chain)?;
+            chain.pop();
+            merge(&base.component, &base.bid);
+        }
+        merge(&self.component, &self.bid);
+
+        Ok(SystemConfig {
+            extends: Vec::new(),
+            component,
+            bid,
+            ..self.clone()
+        })
+    }
+}
+
 /// Parser for system configuration files with frontmatter and markdown content.
 ///
 /// This parser handles configuration files that use Jekyll-style frontmatter format,
@@ -526,6 +896,7 @@ impl SystemParser {
             description: Self::get_required_field(&header_data, "description")?,
             model: Self::get_required_field(&header_data, "model")?,
             color: Self::get_required_field(&header_data, "color")?,
+            extends: Self::parse_extends(&header_data)?,
             component: Self::parse_component(&header_data)?,
             bid: Self::parse_bid(&header_data)?,
             content: markdown_content.trim().to_string(),
@@ -579,13 +950,13 @@ impl SystemParser {
                 let mut value = line[colon_pos + 1..].trim().to_string();
 
                 // Handle multi-line bid and component fields specially
-                if (key == "bid" || key == "component") && value.is_empty() {
+                if (key == "bid" || key == "component" || key == "extends") && value.is_empty() {
                     // Collect all the bullet list lines that follow
                     i += 1;
                     let mut field_lines = Vec::new();
                     while i < lines.len() {
                         let field_line = lines[i].trim();
-                        if field_line.is_empty() {
+                        if field_line.is_empty() || field_line.starts_with('#') {
                             i += 1;
                             continue;
                         }
@@ -625,42 +996,43 @@ impl SystemParser {
             .ok_or_else(|| ParseError::MissingRequiredField(field.to_string()))
     }
 
+    fn parse_extends(data: &HashMap<String, String>) -> Result<Vec<SystemName>, ParseError> {
+        // extends field is optional and accepts `base`, `[a, b]`, `a, b` or a bullet list
+        let Some(extends_str) = data.get("extends") else {
+            return Ok(Vec::new());
+        };
+        let extends_str = extends_str.trim();
+        let extends_str = extends_str
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .unwrap_or(extends_str);
+
+        extends_str
+            .split([',', '\n'])
+            .map(|entry| {
+                entry
+                    .trim()
+                    .strip_prefix('-')
+                    .unwrap_or(entry.trim())
+                    .trim()
+            })
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                SystemName::new(entry).ok_or_else(|| {
+                    ParseError::ValidationError(format!("Invalid base system name: {}", entry))
+                })
+            })
+            .collect()
+    }
+
     fn parse_bid(data: &HashMap<String, String>) -> Result<Vec<Bid>, ParseError> {
         // bid field is optional
         if let Some(bid_str) = data.get("bid") {
-            if bid_str.trim().is_empty() {
-                return Ok(Vec::new());
-            }
-
-            let mut bids = Vec::new();
-            for line in bid_str.lines() {
-                let line = line.trim();
-                if line.is_empty() {
-                    continue;
-                }
-
-                // Extract the bid expression from the bullet point
-                // Handle various bullet formats: "- expr", "  - expr", etc.
-                let bid_expr = if let Some(stripped) = line.strip_prefix('-') {
-                    stripped.trim()
-                } else if let Some(dash_pos) = line.find('-') {
-                    // Handle whitespace-prefixed bullets
-                    line[dash_pos + 1..].trim()
-                } else {
-                    // No bullet found, treat the whole line as a bid expression
-                    line
-                };
-
-                if !bid_expr.is_empty() {
-                    match BidParser::parse(bid_expr) {
-                        Ok(bid) => bids.push(bid),
-                        Err(err) => {
-                            return Err(ParseError::BidParseError(bid_expr.to_string(), err));
-                        }
-                    }
-                }
-            }
-            Ok(bids)
+            BidParser::parse_many(bid_str).map_err(|(index, err)| {
+                let line = bid_str.lines().nth(index).unwrap_or_default().trim();
+                let bid_expr = line.strip_prefix('-').unwrap_or(line).trim();
+                ParseError::BidParseError(bid_expr.to_string(), err)
+            })
         } else {
             Ok(Vec::new())
         }
@@ -1162,6 +1534,77 @@ Content.
         assert!(matches!(result, Err(ParseError::ValidationError(_))));
     }
 
+    #[test]
+    fn color_validation_and_rgba() {
+        let mut config = SystemConfig {
+            name: SystemName::new("colorful").unwrap(),
+            description: "System with a color".to_string(),
+            model: "inherit".to_string(),
+            color: "#11223344".to_string(),
+            extends: Vec::new(),
+            component: Vec::new(),
+            bid: Vec::new(),
+            content: String::new(),
+        };
+        assert!(config.validate().is_ok());
+        assert_eq!(config.color_rgba(), Some([0x11, 0x22, 0x33, 0x44]));
+
+        config.color = "#FF5733".to_string();
+        assert_eq!(config.color_rgba(), Some([0xff, 0x57, 0x33, 0xff]));
+
+        for name in ["teal", "cyan", "rebeccapurple"] {
+            config.color = name.to_string();
+            assert!(config.validate().is_ok(), "{} should validate", name);
+        }
+        assert_eq!(config.color_rgba(), Some([0x66, 0x33, 0x99, 0xff]));
+
+        for invalid in ["#1122", "#1122334", "#GGHHII", "notacolor"] {
+            config.color = invalid.to_string();
+            let result = config.validate();
+            println!("color_validation_and_rgba response: {:?}", result);
+            assert!(matches!(result, Err(ParseError::ValidationError(_))));
+            assert_eq!(config.color_rgba(), None);
+        }
+    }
+
+    #[test]
+    fn validate_with_custom_limits() {
+        let config = SystemConfig {
+            name: SystemName::new("big-system").unwrap(),
+            description: "System with a large prompt".to_string(),
+            model: "inherit".to_string(),
+            color: "blue".to_string(),
+            extends: Vec::new(),
+            component: Vec::new(),
+            bid: (0..300)
+                .map(|i| BidParser::parse(&format!("ON true BID {}", i)).unwrap())
+                .collect(),
+            content: "x".repeat(40 * 1024),
+        };
+
+        let result = config.validate();
+        println!("validate_with_custom_limits response: {:?}", result);
+        assert!(
+            matches!(result, Err(ParseError::ValidationError(ref msg)) if msg == "Content cannot exceed 10KB")
+        );
+
+        let limits = ValidationLimits {
+            max_content_bytes: 50 * 1024,
+            max_bids: 500,
+            ..ValidationLimits::default()
+        };
+        assert!(config.validate_with_limits(&limits).is_ok());
+
+        let limits = ValidationLimits {
+            max_bids: 200,
+            ..limits
+        };
+        assert!(matches!(
+            config.validate_with_limits(&limits),
+            Err(ParseError::ValidationError(ref msg)) if msg == "Cannot have more than 200 bid expressions"
+        ));
+    }
+
     // Comprehensive bid parsing tests
 
     #[test]
@@ -1214,6 +1657,65 @@ System content.
         }
     }
 
+    #[test]
+    fn bid_list_with_comments() {
+        let content = r#"---
+name: commented-bids
+description: System with commented bids
+model: inherit
+color: green
+bid:
+# premium users: discounted
+- ON user.premium BID base_price * 0.8
+  # large orders
+- ON order.amount > 100 BID order.amount + bonus # plus the bonus
+---
+
+System content.
+"#;
+
+        let config = SystemParser::parse(content).unwrap();
+        println!("bid_list_with_comments response: {:?}", config.bid);
+        assert_eq!(config.bid.len(), 2);
+        assert_eq!(
+            config.bid[0].to_string(),
+            "ON user.premium BID base_price * 0.8"
+        );
+        assert_eq!(
+            config.bid[1].to_string(),
+            "ON order.amount > 100 BID order.amount + bonus"
+        );
+    }
+
+    #[test]
+    fn long_bid_chains_survive_save_and_reload() {
+        let condition = (0..100)
+            .map(|i| format!("flag{}", i))
+            .collect::<Vec<_>>()
+            .join(" && ");
+        let value = (0..100)
+            .map(|i| format!("score{}", i))
+            .collect::<Vec<_>>()
+            .join(" + ");
+        let content = format!(
+            "---\nname: long-chains\ndescription: System with long bid chains\nmodel: inherit\ncolor: blue\nbid:\n- ON {} BID {}\n---\n\nSystem content.\n",
+            condition, value
+        );
+
+        let config = SystemParser::parse(&content).unwrap();
+        let saved = serde_json::to_string(&config).unwrap();
+        let reloaded: SystemConfig = serde_json::from_str(&saved).unwrap();
+        println!(
+            "long_bid_chains_survive_save_and_reload response: {}",
+            reloaded.bid[0]
+        );
+        assert_eq!(reloaded.bid[0].normalize(), config.bid[0].normalize());
+        assert_eq!(
+            reloaded.bid[0].to_string(),
+            format!("ON {} BID {}", condition, value)
+        );
+    }
+
     #[test]
     fn complex_bid_expressions_parsing() {
         let content = r#"---
@@ -1939,6 +2441,103 @@ content: |
         assert!(config.content.contains("This is the system content"));
     }
 
+    #[test]
+    fn resolve_extends_merges_components_and_bids() {
+        let base = SystemParser::parse(
+            r#"---
+name: base
+description: Base system
+model: inherit
+color: blue
+component:
+- Health: read
+- Position: read
+bid:
+- ON Health BID 1
+---
+
+Base content.
+"#,
+        )
+        .unwrap();
+        let child = SystemParser::parse(
+            r#"---
+name: healer
+description: Derived system
+model: inherit
+color: green
+extends: base
+component:
+- Health: read+write
+- Healer: read
+bid:
+- ON Healer BID 2
+---
+
+Child content.
+"#,
+        )
+        .unwrap();
+        assert_eq!(child.extends, vec![SystemName::new("base").unwrap()]);
+
+        let lookup = |name: &SystemName| (name == &base.name).then(|| base.clone());
+        let resolved = child.resolve(lookup).unwrap();
+        println!(
+            "resolve_extends_merges_components_and_bids response: {:?}",
+            resolved
+        );
+
+        let components: Vec<String> = resolved.component.iter().map(|c| c.to_string()).collect();
+        assert_eq!(
+            components,
+            vec!["Health: read+write", "Position: read", "Healer: read"]
+        );
+        let bids: Vec<String> = resolved.bid.iter().map(|b| b.to_string()).collect();
+        assert_eq!(bids.len(), 2);
+        assert!(bids[0].contains("Health"));
+        assert!(bids[1].contains("Healer"));
+        assert!(resolved.extends.is_empty());
+        assert_eq!(resolved.content, "Child content.");
+
+        let mut cyclic = base.clone();
+        cyclic.extends = vec![child.name.clone()];
+        let lookup = |name: &SystemName| (name == &cyclic.name).then(|| cyclic.clone());
+        assert!(matches!(
+            child.resolve(lookup),
+            Err(ParseError::ValidationError(_))
+        ));
+        assert!(matches!(
+            child.resolve(|_| None),
+            Err(ParseError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn resolved_model_defers_inherit_to_default() {
+        let mut config = SystemParser::parse(
+            r#"---
+name: test-system
+description: A test system
+model: inherit
+color: blue
+---
+
+Content.
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.model, "inherit");
+        let model = config.resolved_model("gpt-4");
+        println!(
+            "resolved_model_defers_inherit_to_default response: {:?}",
+            model
+        );
+        assert_eq!(model, "gpt-4");
+
+        config.model = "claude-3".to_string();
+        assert_eq!(config.resolved_model("gpt-4"), "claude-3");
+    }
+
     #[test]
     fn system_config_yaml_json_roundtrip() {
         let original = SystemConfig {
@@ -1946,6 +2545,7 @@ content: |
             description: "Testing roundtrip".to_string(),
             model: "inherit".to_string(),
             color: "green".to_string(),
+            extends: Vec::new(),
             component: vec![],
             bid: vec![],
             content: "Test content".to_string(),
@@ -1962,4 +2562,41 @@ content: |
         assert_eq!(original.bid, deserialized.bid);
         assert_eq!(original.content, deserialized.content);
     }
+
+    #[test]
+    fn access_mode_capabilities() {
+        assert!(AccessMode::Read.can_read());
+        assert!(!AccessMode::Read.can_write());
+        assert!(!AccessMode::Write.can_read());
+        assert!(AccessMode::Write.can_write());
+        assert!(AccessMode::ReadWrite.can_read());
+        assert!(AccessMode::ReadWrite.can_write());
+        assert!(!AccessMode::ReadWrite.can_execute());
+        assert!(AccessMode::Execute.can_execute());
+        assert!(!AccessMode::Execute.can_read());
+        assert!(!AccessMode::Execute.can_write());
+    }
+
+    #[test]
+    fn access_mode_covers() {
+        let all = [
+            AccessMode::Read,
+            AccessMode::Write,
+            AccessMode::Execute,
+            AccessMode::ReadWrite,
+        ];
+        for mode in &all {
+            assert!(mode.covers(mode), "{} should cover itself", mode);
+        }
+
+        assert!(AccessMode::ReadWrite.covers(&AccessMode::Read));
+        assert!(AccessMode::ReadWrite.covers(&AccessMode::Write));
+        assert!(!AccessMode::ReadWrite.covers(&AccessMode::Execute));
+        assert!(!AccessMode::Read.covers(&AccessMode::Write));
+        assert!(!AccessMode::Read.covers(&AccessMode::ReadWrite));
+        assert!(!AccessMode::Write.covers(&AccessMode::Read));
+        assert!(!AccessMode::Write.covers(&AccessMode::ReadWrite));
+        assert!(!AccessMode::Execute.covers(&AccessMode::Read));
+        assert!(!AccessMode::Execute.covers(&AccessMode::Write));
+    }
 }
@@ -33,11 +33,18 @@
 //! assert!(validate_value(&invalid_data, &schema).is_err());
 //! ```
 
-use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde_json::{Map, Number, Value};
 
 use crate::json_schema::{
-    ENUM_KEY, ITEMS_KEY, JsonSchemaBuilder, ONE_OF_KEY, PROPERTIES_KEY, REQUIRED_KEY, TYPE_ARRAY,
-    TYPE_BOOLEAN, TYPE_INTEGER, TYPE_KEY, TYPE_NULL, TYPE_NUMBER, TYPE_OBJECT, TYPE_STRING,
+    ADDITIONAL_PROPERTIES_KEY, ALL_OF_KEY, ANY_OF_KEY, CONST_KEY, ENUM_KEY, EXCLUSIVE_MAXIMUM_KEY,
+    EXCLUSIVE_MINIMUM_KEY, ITEMS_KEY, JsonSchemaBuilder, MAX_ITEMS_KEY, MAX_LENGTH_KEY,
+    MAXIMUM_KEY, MIN_ITEMS_KEY, MIN_LENGTH_KEY, MINIMUM_KEY, MULTIPLE_OF_KEY, ONE_OF_KEY,
+    PATTERN_KEY, PREFIX_ITEMS_KEY, PROPERTIES_KEY, REF_KEY, REQUIRED_KEY, TYPE_ARRAY, TYPE_BOOLEAN,
+    TYPE_INTEGER, TYPE_KEY, TYPE_NULL, TYPE_NUMBER, TYPE_OBJECT, TYPE_STRING, UNIQUE_ITEMS_KEY,
     get_value_type,
 };
 
@@ -82,6 +89,73 @@ pub enum ValidationError {
         /// The underlying validation error for the property
         source: Box<ValidationError>,
     },
+    /// The value violates a constraint keyword such as `minItems`
+    ConstraintViolation {
+        /// The schema keyword that was violated
+        keyword: String,
+        /// A description of how the value violated the constraint
+        message: String,
+    },
+}
+
+impl ValidationError {
+    /// Returns the RFC 6901 JSON Pointer to the location that failed validation.
+    ///
+    /// The pointer is built from the array indices and object properties the
+    /// validator recursed through; an error at the root yields the empty string.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use stigmergy::validate_value;
+    /// use serde_json::json;
+    ///
+    /// let schema = json!({
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "items": {
+    ///             "type": "array",
+    ///             "items": {
+    ///                 "type": "object",
+    ///                 "properties": {"price": {"type": "number"}}
+    ///             }
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// let error = validate_value(&json!({"items": [{"price": "x"}]}), &schema).unwrap_err();
+    /// assert_eq!(error.path(), "/items/0/price");
+    /// ```
+    pub fn path(&self) -> String {
+        let mut path = String::new();
+        let mut current = self;
+        loop {
+            match current {
+                ValidationError::ArrayItemError { index, source } => {
+                    path.push('/');
+                    path.push_str(&index.to_string());
+                    current = source;
+                }
+                ValidationError::ObjectPropertyError { property, source } => {
+                    path.push('/');
+                    path.push_str(&property.replace('~', "~0").replace('/', "~1"));
+                    current = source;
+                }
+                _ => return path,
+            }
+        }
+    }
+
+    /// Returns the innermost error, without the array and property context that
+    /// [`ValidationError::path`] reports.
+    pub fn leaf(&self) -> &ValidationError {
+        let mut leaf = self;
+        while let ValidationError::ArrayItemError { source, .. }
+        | ValidationError::ObjectPropertyError { source, .. } = leaf
+        {
+            leaf = source;
+        }
+        leaf
+    }
 }
 
 impl std::fmt::Display for ValidationError {
@@ -110,6 +184,9 @@ impl std::fmt::Display for ValidationError {
             ValidationError::ObjectPropertyError { property, source } => {
                 write!(f, "Object property error at '{}': {}", property, source)
             }
+            ValidationError::ConstraintViolation { keyword, message } => {
+                write!(f, "Constraint '{}' violated: {}", keyword, message)
+            }
         }
     }
 }
@@ -141,6 +218,261 @@ impl JsonSchemaBuilder {
     }
 }
 
+/// A JSON schema prepared for validating many values.
+///
+/// Compiling walks the schema once and compiles every `pattern` regex, so that
+/// [`CompiledSchema::validate`] reuses them instead of recompiling per value. Validation
+/// otherwise behaves exactly like [`validate_value`]; patterns that fail to compile are
+/// reported when a value reaches them, just as they are there.
+///
+/// # Examples
+/// ```rust
+/// use stigmergy::CompiledSchema;
+/// use serde_json::json;
+///
+/// let compiled = CompiledSchema::compile(&json!({"type": "string", "pattern": "^[a-z]+$"}));
+/// assert!(compiled.validate(&json!("abc")).is_ok());
+/// assert!(compiled.validate(&json!("ABC")).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompiledSchema {
+    schema: Value,
+    patterns: HashMap<String, Regex>,
+}
+
+impl CompiledSchema {
+    /// Compiles `schema` for repeated validation.
+    pub fn compile(schema: &Value) -> Self {
+        let mut patterns = HashMap::new();
+        collect_patterns(schema, &mut patterns);
+        Self {
+            schema: schema.clone(),
+            patterns,
+        }
+    }
+
+    /// Returns the schema this was compiled from.
+    pub fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    /// Returns the number of distinct `pattern` regexes compiled ahead of validation.
+    pub fn compiled_patterns(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Validates a JSON value against the compiled schema.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The value is valid according to the schema
+    /// * `Err(ValidationError)` - The value failed validation with specific error details
+    pub fn validate(&self, value: &Value) -> Result<(), ValidationError> {
+        let mut context = RefContext {
+            root: &self.schema,
+            active_refs: Vec::new(),
+            patterns: Some(&self.patterns),
+        };
+        validate_with_context(value, &self.schema, &mut context)
+    }
+
+    /// Validates a JSON value, gathering up to `max_errors` failures instead of stopping
+    /// at the first.
+    ///
+    /// Object properties and array items are checked independently, so each bad field is
+    /// reported with its own [`ValidationError::path`]. Missing required properties and
+    /// disallowed additional properties are reported alongside them. Schemas that combine
+    /// alternatives (`$ref`, `oneOf`, `anyOf`, `allOf`, `const`) report a single error.
+    ///
+    /// # Returns
+    /// The validation errors found, empty when the value is valid.
+    pub fn validate_all(&self, value: &Value, max_errors: usize) -> Vec<ValidationError> {
+        let mut context = RefContext {
+            root: &self.schema,
+            active_refs: Vec::new(),
+            patterns: Some(&self.patterns),
+        };
+        let mut errors = Vec::new();
+        collect_errors(value, &self.schema, &mut context, max_errors, &mut errors);
+        errors
+    }
+}
+
+/// Pushes up to `max_errors` validation errors for `value` into `errors`.
+///
+/// Plain object and array schemas are descended into so that sibling failures are all
+/// reported; anything else falls back to [`validate_with_context`].
+fn collect_errors<'a>(
+    value: &Value,
+    schema: &'a Value,
+    context: &mut RefContext<'a>,
+    max_errors: usize,
+    errors: &mut Vec<ValidationError>,
+) {
+    if errors.len() >= max_errors {
+        return;
+    }
+    let Some(schema_obj) = schema.as_object().filter(|schema_obj| {
+        [REF_KEY, ONE_OF_KEY, ANY_OF_KEY, ALL_OF_KEY, CONST_KEY]
+            .iter()
+            .all(|key| !schema_obj.contains_key(*key))
+    }) else {
+        errors.extend(validate_with_context(value, schema, context).err());
+        return;
+    };
+
+    match (schema_obj.get(TYPE_KEY).and_then(Value::as_str), value) {
+        (Some(TYPE_OBJECT), Value::Object(object)) => {
+            let properties = match schema_obj.get(PROPERTIES_KEY) {
+                None => None,
+                Some(Value::Object(properties)) => Some(properties),
+                Some(_) => {
+                    errors.extend(validate_with_context(value, schema, context).err());
+                    return;
+                }
+            };
+            for (name, prop_value) in object {
+                let prop_schema = match properties.and_then(|properties| properties.get(name)) {
+                    Some(prop_schema) => prop_schema,
+                    None => match schema_obj.get(ADDITIONAL_PROPERTIES_KEY) {
+                        None | Some(Value::Bool(true)) => continue,
+                        Some(Value::Bool(false)) => {
+                            errors.push(ValidationError::ConstraintViolation {
+                                keyword: ADDITIONAL_PROPERTIES_KEY.to_string(),
+                                message: format!("property '{}' is not allowed", name),
+                            });
+                            if errors.len() >= max_errors {
+                                return;
+                            }
+                            continue;
+                        }
+                        Some(additional) => additional,
+                    },
+                };
+                collect_nested(prop_value, prop_schema, context, max_errors, errors, |e| {
+                    ValidationError::ObjectPropertyError {
+                        property: name.clone(),
+                        source: Box::new(e),
+                    }
+                });
+                if errors.len() >= max_errors {
+                    return;
+                }
+            }
+            match schema_obj.get(REQUIRED_KEY) {
+                None => {}
+                Some(Value::Array(required)) => {
+                    for prop_name in required {
+                        let Some(prop_name) = prop_name.as_str() else {
+                            errors.push(ValidationError::InvalidSchema(
+                                "Required property names must be strings".to_string(),
+                            ));
+                            return;
+                        };
+                        if !object.contains_key(prop_name) {
+                            errors.push(ValidationError::MissingRequiredProperty {
+                                property: prop_name.to_string(),
+                            });
+                            if errors.len() >= max_errors {
+                                return;
+                            }
+                        }
+                    }
+                }
+                Some(_) => errors.push(ValidationError::InvalidSchema(
+                    "Required must be an array".to_string(),
+                )),
+            }
+        }
+        (Some(TYPE_ARRAY), Value::Array(array)) => {
+            // Check the array's own constraints without descending into its items
+            let mut shell = schema_obj.clone();
+            shell.remove(ITEMS_KEY);
+            shell.remove(PREFIX_ITEMS_KEY);
+            let mut shell_context = RefContext {
+                root: context.root,
+                active_refs: Vec::new(),
+                patterns: context.patterns,
+            };
+            if let Err(e) = validate_array(value, &shell, &mut shell_context) {
+                errors.push(e);
+                return;
+            }
+
+            let prefix = schema_obj.get(PREFIX_ITEMS_KEY).and_then(Value::as_array);
+            for (index, item) in array.iter().enumerate() {
+                let item_schema = match (prefix, schema_obj.get(ITEMS_KEY)) {
+                    (Some(prefix), _) if index < prefix.len() => &prefix[index],
+                    (_, Some(Value::Array(item_schemas))) => match item_schemas.get(index) {
+                        Some(item_schema) => item_schema,
+                        None => continue,
+                    },
+                    (_, Some(items)) => items,
+                    (_, None) => continue,
+                };
+                collect_nested(item, item_schema, context, max_errors, errors, |e| {
+                    ValidationError::ArrayItemError {
+                        index,
+                        source: Box::new(e),
+                    }
+                });
+                if errors.len() >= max_errors {
+                    return;
+                }
+            }
+        }
+        _ => errors.extend(validate_with_context(value, schema, context).err()),
+    }
+}
+
+/// Collects errors for a nested value, wrapping each with its location in the parent.
+fn collect_nested<'a>(
+    value: &Value,
+    schema: &'a Value,
+    context: &mut RefContext<'a>,
+    max_errors: usize,
+    errors: &mut Vec<ValidationError>,
+    wrap: impl Fn(ValidationError) -> ValidationError,
+) {
+    let mut nested = Vec::new();
+    collect_errors(
+        value,
+        schema,
+        context,
+        max_errors - errors.len(),
+        &mut nested,
+    );
+    errors.extend(nested.into_iter().map(wrap));
+}
+
+/// Compiles every string-valued `pattern` keyword reachable from `schema`.
+///
+/// Literal values under `const` and `enum` are not schemas and are skipped.
+fn collect_patterns(schema: &Value, patterns: &mut HashMap<String, Regex>) {
+    match schema {
+        Value::Object(object) => {
+            for (key, value) in object {
+                match (key.as_str(), value) {
+                    (CONST_KEY | ENUM_KEY, _) => {}
+                    (PATTERN_KEY, Value::String(pattern)) => {
+                        if !patterns.contains_key(pattern)
+                            && let Ok(regex) = Regex::new(pattern)
+                        {
+                            patterns.insert(pattern.clone(), regex);
+                        }
+                    }
+                    _ => collect_patterns(value, patterns),
+                }
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                collect_patterns(item, patterns);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Validates a JSON value against a JSON schema.
 ///
 /// This is the primary validation function that checks whether a JSON value
@@ -148,6 +480,11 @@ impl JsonSchemaBuilder {
 /// JSON Schema features including type validation, object properties, array
 /// items, enumerations, and oneOf unions.
 ///
+/// Internal references such as `{"$ref": "#/definitions/Node"}` are resolved
+/// against `schema` as the root document, so recursive definitions work. A
+/// reference that re-enters itself without descending into the value is
+/// reported as an invalid schema rather than recursing forever.
+///
 /// # Arguments
 /// * `value` - The JSON value to validate
 /// * `schema` - The JSON schema to validate against
@@ -169,31 +506,116 @@ impl JsonSchemaBuilder {
 /// assert!(validate_value(&invalid_value, &schema).is_err());
 /// ```
 pub fn validate_value(value: &Value, schema: &Value) -> Result<(), ValidationError> {
+    let mut context = RefContext {
+        root: schema,
+        active_refs: Vec::new(),
+        patterns: None,
+    };
+    validate_with_context(value, schema, &mut context)
+}
+
+/// Tracks the root schema and the `$ref`s currently being expanded.
+struct RefContext<'a> {
+    root: &'a Value,
+    /// Each active reference paired with the address of the value it is
+    /// being applied to; seeing the same pair twice means a cycle.
+    active_refs: Vec<(&'a str, *const Value)>,
+    /// Regexes compiled ahead of time by [`CompiledSchema`], keyed by pattern.
+    patterns: Option<&'a HashMap<String, Regex>>,
+}
+
+fn validate_with_context<'a>(
+    value: &Value,
+    schema: &'a Value,
+    context: &mut RefContext<'a>,
+) -> Result<(), ValidationError> {
     let schema_obj = schema
         .as_object()
         .ok_or_else(|| ValidationError::InvalidSchema("Schema must be an object".to_string()))?;
 
+    // References replace the schema they appear in
+    if let Some(reference) = schema_obj.get(REF_KEY) {
+        return validate_ref(value, reference, context);
+    }
+
     // Check for oneOf first
     if let Some(one_of_schemas) = schema_obj.get(ONE_OF_KEY) {
-        return validate_one_of(value, one_of_schemas);
+        return validate_one_of(value, one_of_schemas, context);
     }
 
-    // Then check for regular type-based validation
-    let schema_type = schema_obj
-        .get(TYPE_KEY)
-        .and_then(|v| v.as_str())
-        .ok_or_else(|| {
-            ValidationError::InvalidSchema("Schema must have a type field".to_string())
+    // anyOf accepts the first matching schema, just like oneOf above
+    if let Some(any_of_schemas) = schema_obj.get(ANY_OF_KEY) {
+        return validate_one_of(value, any_of_schemas, context);
+    }
+
+    if let Some(all_of_schemas) = schema_obj.get(ALL_OF_KEY) {
+        let schemas_array = all_of_schemas.as_array().ok_or_else(|| {
+            ValidationError::InvalidSchema(format!("{} must be an array", ALL_OF_KEY))
         })?;
+        for schema in schemas_array {
+            validate_with_context(value, schema, context)?;
+        }
+        if !schema_obj.contains_key(TYPE_KEY) {
+            return Ok(());
+        }
+    }
+
+    // A const pins the value exactly, e.g. the tag of an internally tagged enum
+    if let Some(expected) = schema_obj.get(CONST_KEY)
+        && value != expected
+    {
+        return Err(ValidationError::ConstraintViolation {
+            keyword: CONST_KEY.to_string(),
+            message: format!("value {} does not equal {}", value, expected),
+        });
+    }
 
+    // Then check for regular type-based validation
+    match schema_obj.get(TYPE_KEY) {
+        Some(Value::String(schema_type)) => validate_typed(value, schema_type, schema_obj, context),
+        // A list of types accepts a value matching any one of them
+        Some(Value::Array(schema_types)) => {
+            let mut mismatch = None;
+            for schema_type in schema_types {
+                let schema_type = schema_type.as_str().ok_or_else(|| {
+                    ValidationError::InvalidSchema("Schema types must be strings".to_string())
+                })?;
+                match validate_typed(value, schema_type, schema_obj, context) {
+                    Ok(()) => return Ok(()),
+                    Err(ValidationError::TypeMismatch { .. }) => {}
+                    Err(e) => mismatch = mismatch.or(Some(e)),
+                }
+            }
+            Err(mismatch.unwrap_or_else(|| ValidationError::TypeMismatch {
+                expected: schema_types
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" or "),
+                actual: get_value_type(value),
+            }))
+        }
+        _ => Err(ValidationError::InvalidSchema(
+            "Schema must have a type field".to_string(),
+        )),
+    }
+}
+
+/// Validates `value` against the keywords of a single schema type.
+fn validate_typed<'a>(
+    value: &Value,
+    schema_type: &str,
+    schema_obj: &'a Map<String, Value>,
+    context: &mut RefContext<'a>,
+) -> Result<(), ValidationError> {
     match schema_type {
         TYPE_NULL => validate_null(value),
         TYPE_BOOLEAN => validate_boolean(value),
-        TYPE_INTEGER => validate_integer(value),
-        TYPE_NUMBER => validate_number(value),
-        TYPE_STRING => validate_string(value, schema_obj),
-        TYPE_ARRAY => validate_array(value, schema_obj),
-        TYPE_OBJECT => validate_object(value, schema_obj),
+        TYPE_INTEGER => validate_integer(value, schema_obj),
+        TYPE_NUMBER => validate_number(value, schema_obj),
+        TYPE_STRING => validate_string(value, schema_obj, context),
+        TYPE_ARRAY => validate_array(value, schema_obj, context),
+        TYPE_OBJECT => validate_object(value, schema_obj, context),
         _ => Err(ValidationError::InvalidSchema(format!(
             "Unknown schema type: {}",
             schema_type
@@ -201,7 +623,44 @@ pub fn validate_value(value: &Value, schema: &Value) -> Result<(), ValidationErr
     }
 }
 
-fn validate_one_of(value: &Value, one_of_schemas: &Value) -> Result<(), ValidationError> {
+fn validate_ref<'a>(
+    value: &Value,
+    reference: &'a Value,
+    context: &mut RefContext<'a>,
+) -> Result<(), ValidationError> {
+    let reference = reference
+        .as_str()
+        .ok_or_else(|| ValidationError::InvalidSchema(format!("{} must be a string", REF_KEY)))?;
+    let pointer = reference.strip_prefix('#').ok_or_else(|| {
+        ValidationError::InvalidSchema(format!(
+            "Unsupported {} '{}': only internal references are supported",
+            REF_KEY, reference
+        ))
+    })?;
+    let root = context.root;
+    let target = root.pointer(pointer).ok_or_else(|| {
+        ValidationError::InvalidSchema(format!("Unresolvable {} '{}'", REF_KEY, reference))
+    })?;
+
+    let key = (reference, value as *const Value);
+    if context.active_refs.contains(&key) {
+        return Err(ValidationError::InvalidSchema(format!(
+            "Cyclic {} '{}'",
+            REF_KEY, reference
+        )));
+    }
+
+    context.active_refs.push(key);
+    let result = validate_with_context(value, target, context);
+    context.active_refs.pop();
+    result
+}
+
+fn validate_one_of<'a>(
+    value: &Value,
+    one_of_schemas: &'a Value,
+    context: &mut RefContext<'a>,
+) -> Result<(), ValidationError> {
     let schemas_array = one_of_schemas
         .as_array()
         .ok_or_else(|| ValidationError::InvalidSchema("oneOf must be an array".to_string()))?;
@@ -209,7 +668,7 @@ fn validate_one_of(value: &Value, one_of_schemas: &Value) -> Result<(), Validati
     let mut validation_errors = Vec::new();
 
     for schema in schemas_array {
-        match validate_value(value, schema) {
+        match validate_with_context(value, schema, context) {
             Ok(()) => return Ok(()),
             Err(e) => validation_errors.push(e),
         }
@@ -241,9 +700,9 @@ fn validate_boolean(value: &Value) -> Result<(), ValidationError> {
     }
 }
 
-fn validate_integer(value: &Value) -> Result<(), ValidationError> {
+fn validate_integer(value: &Value, schema: &Map<String, Value>) -> Result<(), ValidationError> {
     match value {
-        Value::Number(n) if n.is_i64() || n.is_u64() => Ok(()),
+        Value::Number(n) if n.is_i64() || n.is_u64() => validate_numeric_bounds(n, schema),
         _ => Err(ValidationError::TypeMismatch {
             expected: TYPE_INTEGER.to_string(),
             actual: get_value_type(value),
@@ -251,9 +710,9 @@ fn validate_integer(value: &Value) -> Result<(), ValidationError> {
     }
 }
 
-fn validate_number(value: &Value) -> Result<(), ValidationError> {
+fn validate_number(value: &Value, schema: &Map<String, Value>) -> Result<(), ValidationError> {
     match value {
-        Value::Number(_) => Ok(()),
+        Value::Number(n) => validate_numeric_bounds(n, schema),
         _ => Err(ValidationError::TypeMismatch {
             expected: TYPE_NUMBER.to_string(),
             actual: get_value_type(value),
@@ -261,7 +720,111 @@ fn validate_number(value: &Value) -> Result<(), ValidationError> {
     }
 }
 
-fn validate_string(value: &Value, schema: &Map<String, Value>) -> Result<(), ValidationError> {
+fn validate_numeric_bounds(
+    number: &Number,
+    schema: &Map<String, Value>,
+) -> Result<(), ValidationError> {
+    // Each bound is violated when the comparison of the value against it
+    // yields one of the listed orderings.
+    let bounds: [(&str, &[Ordering], &str); 4] = [
+        (MINIMUM_KEY, &[Ordering::Less], "at least"),
+        (MAXIMUM_KEY, &[Ordering::Greater], "at most"),
+        (
+            EXCLUSIVE_MINIMUM_KEY,
+            &[Ordering::Less, Ordering::Equal],
+            "greater than",
+        ),
+        (
+            EXCLUSIVE_MAXIMUM_KEY,
+            &[Ordering::Greater, Ordering::Equal],
+            "less than",
+        ),
+    ];
+
+    for (keyword, violations, expectation) in bounds {
+        if let Some(bound) = get_number(schema, keyword)?
+            && violations.contains(&compare_numbers(number, bound))
+        {
+            return Err(ValidationError::ConstraintViolation {
+                keyword: keyword.to_string(),
+                message: format!("{} is not {} {}", number, expectation, bound),
+            });
+        }
+    }
+
+    if let Some(multiple_of) = get_number(schema, MULTIPLE_OF_KEY)? {
+        let is_multiple = match (number_as_i128(number), number_as_i128(multiple_of)) {
+            (_, Some(0)) => None,
+            (Some(value), Some(divisor)) if divisor > 0 => Some(value % divisor == 0),
+            _ => {
+                let divisor = multiple_of.as_f64().unwrap_or(0.0);
+                if divisor > 0.0 {
+                    let quotient = number.as_f64().unwrap_or(0.0) / divisor;
+                    Some((quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0))
+                } else {
+                    None
+                }
+            }
+        };
+        match is_multiple {
+            Some(true) => {}
+            Some(false) => {
+                return Err(ValidationError::ConstraintViolation {
+                    keyword: MULTIPLE_OF_KEY.to_string(),
+                    message: format!("{} is not a multiple of {}", number, multiple_of),
+                });
+            }
+            None => {
+                return Err(ValidationError::InvalidSchema(format!(
+                    "{} must be greater than 0",
+                    MULTIPLE_OF_KEY
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn get_number<'a>(
+    schema: &'a Map<String, Value>,
+    key: &str,
+) -> Result<Option<&'a Number>, ValidationError> {
+    match schema.get(key) {
+        Some(Value::Number(n)) => Ok(Some(n)),
+        Some(_) => Err(ValidationError::InvalidSchema(format!(
+            "{} must be a number",
+            key
+        ))),
+        None => Ok(None),
+    }
+}
+
+fn number_as_i128(number: &Number) -> Option<i128> {
+    number
+        .as_i64()
+        .map(i128::from)
+        .or_else(|| number.as_u64().map(i128::from))
+}
+
+/// Compares two JSON numbers exactly when both are integers, falling back to
+/// floating point comparison otherwise.
+fn compare_numbers(a: &Number, b: &Number) -> Ordering {
+    match (number_as_i128(a), number_as_i128(b)) {
+        (Some(a), Some(b)) => a.cmp(&b),
+        _ => {
+            let a = a.as_f64().unwrap_or(f64::NAN);
+            let b = b.as_f64().unwrap_or(f64::NAN);
+            a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+        }
+    }
+}
+
+fn validate_string(
+    value: &Value,
+    schema: &Map<String, Value>,
+    context: &RefContext<'_>,
+) -> Result<(), ValidationError> {
     let string_value = match value {
         Value::String(s) => s,
         _ => {
@@ -272,6 +835,54 @@ fn validate_string(value: &Value, schema: &Map<String, Value>) -> Result<(), Val
         }
     };
 
+    let length = string_value.chars().count();
+
+    if let Some(min_length) = get_usize(schema, MIN_LENGTH_KEY)?
+        && length < min_length
+    {
+        return Err(ValidationError::ConstraintViolation {
+            keyword: MIN_LENGTH_KEY.to_string(),
+            message: format!(
+                "string has length {}, expected at least {}",
+                length, min_length
+            ),
+        });
+    }
+
+    if let Some(max_length) = get_usize(schema, MAX_LENGTH_KEY)?
+        && length > max_length
+    {
+        return Err(ValidationError::ConstraintViolation {
+            keyword: MAX_LENGTH_KEY.to_string(),
+            message: format!(
+                "string has length {}, expected at most {}",
+                length, max_length
+            ),
+        });
+    }
+
+    if let Some(pattern) = schema.get(PATTERN_KEY) {
+        let pattern = pattern.as_str().ok_or_else(|| {
+            ValidationError::InvalidSchema(format!("{} must be a string", PATTERN_KEY))
+        })?;
+        let compiled;
+        let regex = match context.patterns.and_then(|patterns| patterns.get(pattern)) {
+            Some(regex) => regex,
+            None => {
+                compiled = Regex::new(pattern).map_err(|e| {
+                    ValidationError::InvalidSchema(format!("invalid pattern '{}': {}", pattern, e))
+                })?;
+                &compiled
+            }
+        };
+        if !regex.is_match(string_value) {
+            return Err(ValidationError::ConstraintViolation {
+                keyword: PATTERN_KEY.to_string(),
+                message: format!("'{}' does not match pattern '{}'", string_value, pattern),
+            });
+        }
+    }
+
     if let Some(enum_values) = schema.get(ENUM_KEY) {
         validate_enum(string_value, enum_values)?;
     }
@@ -305,7 +916,11 @@ fn validate_enum(value: &str, enum_values: &Value) -> Result<(), ValidationError
     }
 }
 
-fn validate_array(value: &Value, schema: &Map<String, Value>) -> Result<(), ValidationError> {
+fn validate_array<'a>(
+    value: &Value,
+    schema: &'a Map<String, Value>,
+    context: &mut RefContext<'a>,
+) -> Result<(), ValidationError> {
     let array = match value {
         Value::Array(arr) => arr,
         _ => {
@@ -316,12 +931,71 @@ fn validate_array(value: &Value, schema: &Map<String, Value>) -> Result<(), Vali
         }
     };
 
+    if let Some(min_items) = get_usize(schema, MIN_ITEMS_KEY)?
+        && array.len() < min_items
+    {
+        return Err(ValidationError::ConstraintViolation {
+            keyword: MIN_ITEMS_KEY.to_string(),
+            message: format!(
+                "array has {} items, expected at least {}",
+                array.len(),
+                min_items
+            ),
+        });
+    }
+
+    if let Some(max_items) = get_usize(schema, MAX_ITEMS_KEY)?
+        && array.len() > max_items
+    {
+        return Err(ValidationError::ConstraintViolation {
+            keyword: MAX_ITEMS_KEY.to_string(),
+            message: format!(
+                "array has {} items, expected at most {}",
+                array.len(),
+                max_items
+            ),
+        });
+    }
+
+    if let Some(unique_items) = schema.get(UNIQUE_ITEMS_KEY) {
+        let unique_items = unique_items.as_bool().ok_or_else(|| {
+            ValidationError::InvalidSchema(format!("{} must be a boolean", UNIQUE_ITEMS_KEY))
+        })?;
+        if unique_items {
+            for (index, item) in array.iter().enumerate() {
+                if let Some(first) = array[..index].iter().position(|other| other == item) {
+                    return Err(ValidationError::ConstraintViolation {
+                        keyword: UNIQUE_ITEMS_KEY.to_string(),
+                        message: format!("items at index {} and {} are equal", first, index),
+                    });
+                }
+            }
+        }
+    }
+
+    // prefixItems types elements by position; items then covers the remainder
+    let mut prefix_len = 0;
+    if let Some(prefix_items) = schema.get(PREFIX_ITEMS_KEY) {
+        let prefix_schemas = prefix_items.as_array().ok_or_else(|| {
+            ValidationError::InvalidSchema(format!("{} must be an array", PREFIX_ITEMS_KEY))
+        })?;
+        prefix_len = prefix_schemas.len();
+        for (index, (item, item_schema)) in array.iter().zip(prefix_schemas).enumerate() {
+            validate_with_context(item, item_schema, context).map_err(|e| {
+                ValidationError::ArrayItemError {
+                    index,
+                    source: Box::new(e),
+                }
+            })?;
+        }
+    }
+
     if let Some(items_schema) = schema.get(ITEMS_KEY) {
         match items_schema {
             Value::Array(item_schemas) => {
                 for (index, item) in array.iter().enumerate() {
                     if let Some(item_schema) = item_schemas.get(index) {
-                        validate_value(item, item_schema).map_err(|e| {
+                        validate_with_context(item, item_schema, context).map_err(|e| {
                             ValidationError::ArrayItemError {
                                 index,
                                 source: Box::new(e),
@@ -331,10 +1005,12 @@ fn validate_array(value: &Value, schema: &Map<String, Value>) -> Result<(), Vali
                 }
             }
             schema => {
-                for (index, item) in array.iter().enumerate() {
-                    validate_value(item, schema).map_err(|e| ValidationError::ArrayItemError {
-                        index,
-                        source: Box::new(e),
+                for (index, item) in array.iter().enumerate().skip(prefix_len) {
+                    validate_with_context(item, schema, context).map_err(|e| {
+                        ValidationError::ArrayItemError {
+                            index,
+                            source: Box::new(e),
+                        }
                     })?;
                 }
             }
@@ -344,7 +1020,20 @@ fn validate_array(value: &Value, schema: &Map<String, Value>) -> Result<(), Vali
     Ok(())
 }
 
-fn validate_object(value: &Value, schema: &Map<String, Value>) -> Result<(), ValidationError> {
+fn get_usize(schema: &Map<String, Value>, key: &str) -> Result<Option<usize>, ValidationError> {
+    match schema.get(key) {
+        Some(value) => value.as_u64().map(|n| Some(n as usize)).ok_or_else(|| {
+            ValidationError::InvalidSchema(format!("{} must be a non-negative integer", key))
+        }),
+        None => Ok(None),
+    }
+}
+
+fn validate_object<'a>(
+    value: &Value,
+    schema: &'a Map<String, Value>,
+    context: &mut RefContext<'a>,
+) -> Result<(), ValidationError> {
     let object = match value {
         Value::Object(obj) => obj,
         _ => {
@@ -362,7 +1051,7 @@ fn validate_object(value: &Value, schema: &Map<String, Value>) -> Result<(), Val
 
         for (prop_name, prop_schema) in properties_obj {
             if let Some(prop_value) = object.get(prop_name) {
-                validate_value(prop_value, prop_schema).map_err(|e| {
+                validate_with_context(prop_value, prop_schema, context).map_err(|e| {
                     ValidationError::ObjectPropertyError {
                         property: prop_name.clone(),
                         source: Box::new(e),
@@ -372,6 +1061,32 @@ fn validate_object(value: &Value, schema: &Map<String, Value>) -> Result<(), Val
         }
     }
 
+    if let Some(additional) = schema.get(ADDITIONAL_PROPERTIES_KEY) {
+        let declared = schema.get(PROPERTIES_KEY).and_then(Value::as_object);
+        let extra = object
+            .iter()
+            .filter(|(name, _)| !declared.is_some_and(|declared| declared.contains_key(*name)));
+        for (prop_name, prop_value) in extra {
+            match additional {
+                Value::Bool(true) => {}
+                Value::Bool(false) => {
+                    return Err(ValidationError::ConstraintViolation {
+                        keyword: ADDITIONAL_PROPERTIES_KEY.to_string(),
+                        message: format!("property '{}' is not allowed", prop_name),
+                    });
+                }
+                additional => {
+                    validate_with_context(prop_value, additional, context).map_err(|e| {
+                        ValidationError::ObjectPropertyError {
+                            property: prop_name.clone(),
+                            source: Box::new(e),
+                        }
+                    })?;
+                }
+            }
+        }
+    }
+
     if let Some(required) = schema.get(REQUIRED_KEY) {
         let required_array = required.as_array().ok_or_else(|| {
             ValidationError::InvalidSchema("Required must be an array".to_string())
@@ -473,6 +1188,158 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn validate_numeric_minimum_maximum() {
+        let schema = json!({"type": "integer", "minimum": 0, "maximum": 10});
+
+        assert!(validate_value(&json!(0), &schema).is_ok());
+        assert!(validate_value(&json!(5), &schema).is_ok());
+        assert!(validate_value(&json!(10), &schema).is_ok());
+
+        let error = validate_value(&json!(-1), &schema).unwrap_err();
+        assert!(matches!(
+            &error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "minimum"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Constraint 'minimum' violated: -1 is not at least 0"
+        );
+
+        let error = validate_value(&json!(11), &schema).unwrap_err();
+        assert!(matches!(
+            error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "maximum"
+        ));
+
+        let schema = json!({"type": "number", "minimum": 0.5, "maximum": 1.5});
+        assert!(validate_value(&json!(1), &schema).is_ok());
+        assert!(validate_value(&json!(0.4), &schema).is_err());
+        assert!(validate_value(&json!(2), &schema).is_err());
+    }
+
+    #[test]
+    fn validate_numeric_exclusive_bounds() {
+        let schema = json!({"type": "integer", "exclusiveMaximum": 5});
+        assert!(validate_value(&json!(4), &schema).is_ok());
+        let error = validate_value(&json!(5), &schema).unwrap_err();
+        assert!(matches!(
+            error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "exclusiveMaximum"
+        ));
+
+        let schema = json!({"type": "number", "exclusiveMinimum": 0});
+        assert!(validate_value(&json!(0.1), &schema).is_ok());
+        assert!(validate_value(&json!(0), &schema).is_err());
+        assert!(validate_value(&json!(0.0), &schema).is_err());
+    }
+
+    #[test]
+    fn validate_numeric_multiple_of() {
+        let schema = json!({"type": "integer", "multipleOf": 2});
+        assert!(validate_value(&json!(8), &schema).is_ok());
+        let error = validate_value(&json!(7), &schema).unwrap_err();
+        assert!(matches!(
+            error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "multipleOf"
+        ));
+
+        let schema = json!({"type": "number", "multipleOf": 0.1});
+        assert!(validate_value(&json!(0.3), &schema).is_ok());
+        assert!(validate_value(&json!(0.35), &schema).is_err());
+
+        let schema = json!({"type": "number", "multipleOf": 0});
+        assert!(matches!(
+            validate_value(&json!(1), &schema).unwrap_err(),
+            ValidationError::InvalidSchema(_)
+        ));
+    }
+
+    #[test]
+    fn validate_numeric_large_integers() {
+        let schema = json!({"type": "integer", "maximum": u64::MAX});
+        assert!(validate_value(&json!(u64::MAX), &schema).is_ok());
+
+        let schema = json!({"type": "integer", "maximum": i64::MAX});
+        assert!(validate_value(&json!(u64::MAX), &schema).is_err());
+    }
+
+    #[test]
+    fn validate_prefix_items() {
+        let schema = json!({
+            "type": "array",
+            "prefixItems": [{"type": "string"}, {"type": "number"}],
+            "items": {"type": "boolean"}
+        });
+
+        assert!(validate_value(&json!(["x", 1.5]), &schema).is_ok());
+        assert!(validate_value(&json!(["x", 1.5, true, false]), &schema).is_ok());
+        let error = validate_value(&json!(["x", "y"]), &schema).unwrap_err();
+        println!("validate_prefix_items response: {}", error);
+        assert_eq!(error.path(), "/1");
+        let error = validate_value(&json!(["x", 1.5, "z"]), &schema).unwrap_err();
+        assert_eq!(error.path(), "/2");
+    }
+
+    #[test]
+    fn builder_combinators() {
+        let a = json!({"type": "string"});
+        let b = json!({"type": "integer"});
+
+        let one_of = JsonSchemaBuilder::new()
+            .one_of(vec![a.clone(), b.clone()])
+            .build();
+        println!("builder_combinators response: {}", one_of);
+        assert_eq!(one_of, json!({"oneOf": [a.clone(), b.clone()]}));
+
+        let any_of = JsonSchemaBuilder::new()
+            .any_of(vec![a.clone(), b.clone()])
+            .build();
+        assert!(validate_value(&json!(3), &any_of).is_ok());
+        assert!(validate_value(&json!(true), &any_of).is_err());
+
+        let all_of = JsonSchemaBuilder::new()
+            .all_of(vec![
+                json!({"type": "integer", "minimum": 0}),
+                json!({"type": "integer", "maximum": 10}),
+            ])
+            .build();
+        assert!(validate_value(&json!(5), &all_of).is_ok());
+        assert!(validate_value(&json!(11), &all_of).is_err());
+
+        let nullable = JsonSchemaBuilder::from_value(&json!("text"))
+            .unwrap()
+            .nullable()
+            .build();
+        assert_eq!(nullable, json!({"type": ["string", "null"]}));
+        assert!(validate_value(&json!(null), &nullable).is_ok());
+        assert!(validate_value(&json!("x"), &nullable).is_ok());
+        assert!(validate_value(&json!(1), &nullable).is_err());
+
+        let nullable_union = JsonSchemaBuilder::new()
+            .one_of(vec![a.clone(), b.clone()])
+            .nullable()
+            .build();
+        assert_eq!(
+            nullable_union,
+            json!({"oneOf": [{"type": "null"}, {"oneOf": [a, b]}]})
+        );
+        assert!(validate_value(&json!(null), &nullable_union).is_ok());
+    }
+
+    #[test]
+    fn validate_const() {
+        let schema = json!({"type": "string", "const": "Circle"});
+
+        assert!(validate_value(&json!("Circle"), &schema).is_ok());
+        let error = validate_value(&json!("Square"), &schema).unwrap_err();
+        println!("validate_const response: {}", error);
+        assert!(matches!(
+            error,
+            ValidationError::ConstraintViolation { ref keyword, .. } if keyword == "const"
+        ));
+    }
+
     #[test]
     fn validate_string_success() {
         let schema = JsonSchemaBuilder::from_value(&json!("hello")).unwrap();
@@ -491,6 +1358,138 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn validate_string_length() {
+        let schema = json!({"type": "string", "minLength": 3, "maxLength": 5});
+
+        assert!(validate_value(&json!("abc"), &schema).is_ok());
+        assert!(validate_value(&json!("héllo"), &schema).is_ok());
+
+        let error = validate_value(&json!("ab"), &schema).unwrap_err();
+        assert!(matches!(
+            &error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "minLength"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Constraint 'minLength' violated: string has length 2, expected at least 3"
+        );
+
+        let error = validate_value(&json!("abcdef"), &schema).unwrap_err();
+        assert!(matches!(
+            error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "maxLength"
+        ));
+    }
+
+    #[test]
+    fn validate_string_pattern() {
+        let schema = json!({"type": "string", "pattern": "^[0-9]+$"});
+
+        assert!(validate_value(&json!("123"), &schema).is_ok());
+
+        let error = validate_value(&json!("abc"), &schema).unwrap_err();
+        assert!(matches!(
+            &error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "pattern"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Constraint 'pattern' violated: 'abc' does not match pattern '^[0-9]+$'"
+        );
+    }
+
+    #[test]
+    fn validate_string_invalid_pattern() {
+        let schema = json!({"type": "string", "pattern": "[unclosed"});
+
+        let result = validate_value(&json!("abc"), &schema);
+        assert!(matches!(
+            result.unwrap_err(),
+            ValidationError::InvalidSchema(_)
+        ));
+    }
+
+    #[test]
+    fn compiled_schema_matches_validate_value() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "code": {"type": "string", "pattern": "^[A-Z]{3}$"},
+                "codes": {"type": "array", "items": {"type": "string", "pattern": "^[A-Z]{3}$"}},
+                "tag": {"type": "string", "const": {"pattern": "[unclosed"}}
+            },
+            "required": ["code"]
+        });
+        let compiled = CompiledSchema::compile(&schema);
+        println!(
+            "compiled_schema_matches_validate_value response: {:?}",
+            compiled.compiled_patterns()
+        );
+        assert_eq!(compiled.compiled_patterns(), 1);
+
+        for i in 0..10_000 {
+            let code = if i % 2 == 0 { "ABC" } else { "abc" };
+            let value = json!({"code": code, "codes": ["XYZ", code]});
+            assert_eq!(
+                compiled.validate(&value).is_ok(),
+                validate_value(&value, &schema).is_ok()
+            );
+        }
+        let error = compiled
+            .validate(&json!({"code": "ABC", "codes": ["XYZ", "abc"]}))
+            .unwrap_err();
+        assert_eq!(error.path(), "/codes/1");
+
+        let invalid = CompiledSchema::compile(&json!({"type": "string", "pattern": "[unclosed"}));
+        assert!(matches!(
+            invalid.validate(&json!("abc")),
+            Err(ValidationError::InvalidSchema(_))
+        ));
+    }
+
+    #[test]
+    fn compiled_schema_validate_all_gathers_errors() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "a": {"type": "integer"},
+                "b": {"type": "integer"},
+                "c": {"type": "string"},
+                "name": {"type": "string"},
+                "tags": {"type": "array", "items": {"type": "string"}}
+            },
+            "required": ["a", "name"],
+            "additionalProperties": false
+        });
+        let compiled = CompiledSchema::compile(&schema);
+        let value = json!({"a": "x", "b": "y", "c": 1, "tags": ["ok", 2], "extra": true});
+
+        let errors = compiled.validate_all(&value, 10);
+        println!(
+            "compiled_schema_validate_all_gathers_errors response: {:?}",
+            errors
+        );
+        let paths: Vec<String> = errors.iter().map(ValidationError::path).collect();
+        assert_eq!(paths, vec!["/a", "/b", "/c", "", "/tags/1", ""]);
+        assert!(matches!(
+            errors[3],
+            ValidationError::ConstraintViolation { .. }
+        ));
+        assert!(matches!(
+            errors[5],
+            ValidationError::MissingRequiredProperty { .. }
+        ));
+
+        assert_eq!(compiled.validate_all(&value, 2).len(), 2);
+        assert!(
+            compiled
+                .validate_all(&json!({"a": 1, "name": "n"}), 10)
+                .is_empty()
+        );
+        assert_eq!(compiled.validate_all(&json!("nope"), 10).len(), 1);
+    }
+
     #[test]
     fn validate_array_homogeneous_success() {
         let schema = JsonSchemaBuilder::from_value(&json!([1, 2, 3])).unwrap();
@@ -527,6 +1526,60 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn validate_array_min_max_items() {
+        let schema =
+            json!({"type": "array", "items": {"type": "integer"}, "minItems": 3, "maxItems": 4});
+
+        assert!(validate_value(&json!([1, 2, 3]), &schema).is_ok());
+        assert!(validate_value(&json!([1, 2, 3, 4]), &schema).is_ok());
+
+        let result = validate_value(&json!([1, 2]), &schema);
+        assert!(matches!(
+            result.unwrap_err(),
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "minItems"
+        ));
+
+        let result = validate_value(&json!([1, 2, 3, 4, 5]), &schema);
+        assert!(matches!(
+            result.unwrap_err(),
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "maxItems"
+        ));
+    }
+
+    #[test]
+    fn validate_array_unique_items() {
+        let schema = json!({"type": "array", "uniqueItems": true});
+
+        assert!(validate_value(&json!([1, "1", {"a": 1}, {"a": 2}]), &schema).is_ok());
+
+        let result = validate_value(&json!([{"a": 1}, 2, {"a": 1}]), &schema);
+        let error = result.unwrap_err();
+        assert!(matches!(
+            &error,
+            ValidationError::ConstraintViolation { keyword, .. } if keyword == "uniqueItems"
+        ));
+        assert!(error.to_string().contains("index 0 and 2"));
+
+        let schema = json!({"type": "array", "uniqueItems": false});
+        assert!(validate_value(&json!([1, 1]), &schema).is_ok());
+    }
+
+    #[test]
+    fn validate_array_invalid_constraints() {
+        let result = validate_value(&json!([1]), &json!({"type": "array", "minItems": -1}));
+        assert!(matches!(
+            result.unwrap_err(),
+            ValidationError::InvalidSchema(_)
+        ));
+
+        let result = validate_value(&json!([1]), &json!({"type": "array", "uniqueItems": "yes"}));
+        assert!(matches!(
+            result.unwrap_err(),
+            ValidationError::InvalidSchema(_)
+        ));
+    }
+
     #[test]
     fn validate_object_success() {
         let schema = JsonSchemaBuilder::from_value(&json!({
@@ -755,6 +1808,112 @@ mod tests {
         }
     }
 
+    #[test]
+    fn validation_error_path() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"price": {"type": "number"}}
+                    }
+                }
+            }
+        });
+
+        let error = validate_value(&json!({"items": [{"price": "x"}]}), &schema).unwrap_err();
+        assert_eq!(error.path(), "/items/0/price");
+
+        let error = validate_value(&json!("x"), &json!({"type": "number"})).unwrap_err();
+        assert_eq!(error.path(), "");
+    }
+
+    #[test]
+    fn validation_error_path_escapes_property_names() {
+        let schema = json!({
+            "type": "object",
+            "properties": {"a/b~c": {"type": "number"}}
+        });
+
+        let error = validate_value(&json!({"a/b~c": "x"}), &schema).unwrap_err();
+        assert_eq!(error.path(), "/a~1b~0c");
+    }
+
+    fn tree_node_schema() -> Value {
+        json!({
+            "$ref": "#/definitions/TreeNode",
+            "definitions": {
+                "TreeNode": {
+                    "type": "object",
+                    "properties": {
+                        "value": {"type": "integer"},
+                        "children": {
+                            "type": "array",
+                            "items": {"$ref": "#/definitions/TreeNode"}
+                        }
+                    },
+                    "required": ["value", "children"]
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn validate_ref_recursive_definition() {
+        let schema = tree_node_schema();
+        let tree = json!({
+            "value": 1,
+            "children": [
+                {"value": 2, "children": []},
+                {"value": 3, "children": [{"value": 4, "children": []}]}
+            ]
+        });
+        assert!(validate_value(&tree, &schema).is_ok());
+
+        let malformed = json!({
+            "value": 1,
+            "children": [
+                {"value": 3, "children": [{"value": "four", "children": []}]}
+            ]
+        });
+        let error = validate_value(&malformed, &schema).unwrap_err();
+        assert_eq!(error.path(), "/children/0/children/0/value");
+    }
+
+    #[test]
+    fn validate_ref_cycle_detected() {
+        let schema = json!({
+            "$ref": "#/definitions/A",
+            "definitions": {
+                "A": {"$ref": "#/definitions/B"},
+                "B": {"$ref": "#/definitions/A"}
+            }
+        });
+
+        let result = validate_value(&json!(1), &schema);
+        assert!(matches!(
+            result.unwrap_err(),
+            ValidationError::InvalidSchema(_)
+        ));
+    }
+
+    #[test]
+    fn validate_ref_unresolvable() {
+        let schema = json!({"$ref": "#/definitions/Missing"});
+        assert!(matches!(
+            validate_value(&json!(1), &schema).unwrap_err(),
+            ValidationError::InvalidSchema(_)
+        ));
+
+        let schema = json!({"$ref": "https://example.com/schema.json"});
+        assert!(matches!(
+            validate_value(&json!(1), &schema).unwrap_err(),
+            ValidationError::InvalidSchema(_)
+        ));
+    }
+
     #[test]
     fn validate_one_of_success_first_schema() {
         let schema = json!({
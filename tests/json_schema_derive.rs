@@ -1,5 +1,7 @@
+use serde::Serialize;
 use serde_json::json;
-use stigmergy::JsonSchema;
+use std::collections::{BTreeSet, HashMap};
+use stigmergy::{Component, Entity, JsonSchema, SystemName, validate_value};
 use stigmergy_derive::JsonSchema as JsonSchemaDerive;
 
 // Test structs with derive macro
@@ -52,6 +54,65 @@ enum TestPoint {
     ThreeD(f64, f64, f64),
 }
 
+/// A stocked item.
+///
+/// Prices are per unit.
+#[derive(JsonSchemaDerive)]
+#[allow(dead_code)]
+struct TestDocumented {
+    /// Display name of the item
+    name: String,
+    count: u32,
+}
+
+/// How a shipment travels.
+#[derive(JsonSchemaDerive)]
+#[allow(dead_code)]
+enum TestDocumentedMode {
+    Air,
+    Sea,
+}
+
+#[derive(JsonSchemaDerive)]
+#[allow(dead_code)]
+struct TestAssignment {
+    entity: Entity,
+    component: Component,
+    system: SystemName,
+}
+
+#[derive(JsonSchemaDerive)]
+#[allow(dead_code)]
+struct TestWrapper<T> {
+    inner: T,
+    items: Vec<T>,
+}
+
+#[derive(JsonSchemaDerive)]
+#[schema(deny_unknown_fields)]
+#[allow(dead_code)]
+struct TestStrict {
+    x: f64,
+    y: f64,
+}
+
+#[derive(JsonSchemaDerive)]
+#[allow(dead_code)]
+struct TestInventory {
+    prices: HashMap<String, f64>,
+    tags: BTreeSet<String>,
+    restocked_at: chrono::DateTime<chrono::Utc>,
+}
+
+#[derive(JsonSchemaDerive, Serialize)]
+#[serde(tag = "kind")]
+#[allow(dead_code)]
+enum TestTaggedShape {
+    Circle { radius: f64 },
+    Square { side: f64 },
+    Empty,
+}
+
 #[test]
 fn unit_enum() {
     let schema = TestStatus::json_schema();
@@ -119,8 +180,24 @@ fn tuple_variant_enum() {
         serde_json::to_string_pretty(&schema).unwrap()
     );
 
-    // Just check that it's not empty for now
-    assert!(schema.is_object());
+    assert_eq!(
+        schema["oneOf"][0],
+        json!({
+            "type": "object",
+            "properties": {
+                "TwoD": {
+                    "type": "array",
+                    "prefixItems": [{"type": "number"}, {"type": "number"}],
+                    "minItems": 2,
+                    "maxItems": 2
+                }
+            },
+            "required": ["TwoD"]
+        })
+    );
+    assert!(validate_value(&json!({"TwoD": [1.0, 2.0]}), &schema).is_ok());
+    assert!(validate_value(&json!({"TwoD": [1.0]}), &schema).is_err());
+    assert!(validate_value(&json!({"ThreeD": [1.0, 2.0, "z"]}), &schema).is_err());
 }
 
 #[test]
@@ -175,3 +252,176 @@ fn nested_struct_with_enum() {
     assert!(enum_values.contains(&json!("Inactive")));
     assert!(enum_values.contains(&json!("Pending")));
 }
+
+#[test]
+fn internally_tagged_enum() {
+    let schema = TestTaggedShape::json_schema();
+
+    println!(
+        "Tagged shape schema: {}",
+        serde_json::to_string_pretty(&schema).unwrap()
+    );
+
+    let branches = schema["oneOf"].as_array().unwrap();
+    assert_eq!(branches.len(), 3);
+    for (branch, name) in branches.iter().zip(["Circle", "Square", "Empty"]) {
+        assert_eq!(branch["properties"]["kind"]["const"], name);
+        assert_eq!(branch["required"][0], "kind");
+    }
+    assert_eq!(
+        branches[0],
+        json!({
+            "type": "object",
+            "properties": {
+                "kind": {"type": "string", "const": "Circle"},
+                "radius": {"type": "number"}
+            },
+            "required": ["kind", "radius"]
+        })
+    );
+
+    for shape in [
+        TestTaggedShape::Circle { radius: 1.5 },
+        TestTaggedShape::Square { side: 2.0 },
+        TestTaggedShape::Empty,
+    ] {
+        let value = serde_json::to_value(&shape).unwrap();
+        assert!(validate_value(&value, &schema).is_ok(), "{}", value);
+    }
+    assert!(validate_value(&json!({"kind": "Triangle", "side": 2.0}), &schema).is_err());
+}
+
+#[test]
+fn collection_and_chrono_fields() {
+    let schema = TestInventory::json_schema();
+
+    println!(
+        "Inventory schema: {}",
+        serde_json::to_string_pretty(&schema).unwrap()
+    );
+
+    assert_eq!(
+        schema["properties"],
+        json!({
+            "prices": {
+                "type": "object",
+                "additionalProperties": {"type": "number"}
+            },
+            "tags": {
+                "type": "array",
+                "items": {"type": "string"},
+                "uniqueItems": true
+            },
+            "restocked_at": {"type": "string", "format": "date-time"}
+        })
+    );
+
+    let valid = json!({
+        "prices": {"apple": 1.25, "pear": 2.0},
+        "tags": ["fruit"],
+        "restocked_at": "2025-01-01T00:00:00Z"
+    });
+    assert!(validate_value(&valid, &schema).is_ok());
+    let invalid = json!({
+        "prices": {"apple": "cheap"},
+        "tags": ["fruit", "fruit"],
+        "restocked_at": "2025-01-01T00:00:00Z"
+    });
+    assert!(validate_value(&invalid, &schema).is_err());
+}
+
+#[test]
+fn doc_comments_become_descriptions() {
+    let schema = TestDocumented::json_schema();
+
+    println!(
+        "Documented schema: {}",
+        serde_json::to_string_pretty(&schema).unwrap()
+    );
+
+    assert_eq!(
+        schema["description"],
+        "A stocked item.\n\nPrices are per unit."
+    );
+    assert_eq!(
+        schema["properties"]["name"],
+        json!({"type": "string", "description": "Display name of the item"})
+    );
+    assert_eq!(schema["properties"]["count"], json!({"type": "integer"}));
+
+    let schema = TestDocumentedMode::json_schema();
+    assert_eq!(schema["description"], "How a shipment travels.");
+    assert_eq!(schema["enum"], json!(["Air", "Sea"]));
+}
+
+#[test]
+fn deny_unknown_fields_rejects_extra_keys() {
+    let schema = TestStrict::json_schema();
+
+    println!(
+        "Strict schema: {}",
+        serde_json::to_string_pretty(&schema).unwrap()
+    );
+
+    assert_eq!(schema["additionalProperties"], json!(false));
+    assert!(validate_value(&json!({"x": 1.0, "y": 2.0}), &schema).is_ok());
+    assert!(validate_value(&json!({"x": 1.0, "y": 2.0, "z": 3.0}), &schema).is_err());
+    assert!(
+        TestPerson::json_schema()
+            .get("additionalProperties")
+            .is_none()
+    );
+}
+
+#[test]
+fn generic_struct() {
+    let schema = TestWrapper::<f64>::json_schema();
+
+    println!(
+        "Wrapper schema: {}",
+        serde_json::to_string_pretty(&schema).unwrap()
+    );
+
+    assert_eq!(schema["properties"]["inner"], json!({"type": "number"}));
+    assert_eq!(
+        schema["properties"]["items"],
+        json!({"type": "array", "items": {"type": "number"}})
+    );
+}
+
+#[test]
+fn identifier_fields() {
+    let schema = TestAssignment::json_schema();
+
+    println!(
+        "Assignment schema: {}",
+        serde_json::to_string_pretty(&schema).unwrap()
+    );
+
+    assert_eq!(schema["properties"]["entity"]["type"], "string");
+    assert!(schema["properties"]["entity"]["pattern"].is_string());
+
+    let entity = Entity::new([7u8; 32]);
+    let valid = json!({
+        "entity": entity.to_string(),
+        "component": "ghai::Issue",
+        "system": "code-reviewer"
+    });
+    assert!(validate_value(&valid, &schema).is_ok());
+    let bare = json!({
+        "entity": serde_json::to_value(entity).unwrap(),
+        "component": "Position",
+        "system": "reviewer"
+    });
+    assert!(validate_value(&bare, &schema).is_ok());
+
+    for (field, bad) in [
+        ("entity", "entity:short"),
+        ("component", "9Lives"),
+        ("system", "has space"),
+    ] {
+        let mut invalid = valid.clone();
+        invalid[field] = json!(bad);
+        assert!(validate_value(&invalid, &schema).is_err(), "{}", field);
+    }
+}